@@ -10,8 +10,15 @@
 
 pub use crate::traits;
 
+pub mod adaptive;
+
 pub mod classify;
 
+pub mod control;
+
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod dynamics;
+
 pub mod estimate;
 
 pub mod fir;
@@ -22,6 +29,8 @@ pub mod ops;
 
 pub mod rank;
 
+pub mod spectral;
+
 pub mod util;
 
 pub mod wavelet;
@@ -0,0 +1,179 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Adaptive FIR filters; online system identification and cancellation.
+//!
+//! Adaptive filters adjust their FIR weights sample by sample so that the
+//! filtered input tracks a desired signal. Each filter consumes
+//! `(input, desired)` pairs (like [`ops::Add`](super::ops::add::Add)) and emits
+//! an [`Output`] holding the filtered value and the a-priori error
+//! `desired − value` that drove the weight update.
+//!
+//! Typical uses are echo cancellation (input: far-end signal, desired:
+//! microphone signal, error: echo-free signal), noise cancellation (input:
+//! noise reference, desired: noisy signal) and system identification (the
+//! weights converge to the impulse response of the unknown system).
+//!
+//! # When to use which filter
+//!
+//! | Filter                                | Update rule                                   |
+//! | ------------------------------------- | --------------------------------------------- |
+//! | `lms::Lms`                            | `w += μ·e·x`                                  |
+//! | `nlms::Nlms`                          | `w += μ·e·x / (ε + xᵀx)`                      |
+//! | `leaky_lms::LeakyLms`                 | `w = (1 − μ·γ)·w + μ·e·x`                     |
+//! | `sign_error_lms::SignErrorLms`        | `w += μ·sign(e)·x`                            |
+//! | `rls::Rls`                            | `w += k·e` with recursive least-squares gain  |
+//! | `apa::Apa`                            | `w += μ·X·(XᵀX + ε·I)⁻¹·e` over `K` vectors   |
+//!
+//! - **`Lms`** is the cheapest option, but its convergence speed and stability
+//!   bound depend on the input power. Use it when the input level is known.
+//! - **`Nlms`** normalizes the step by the input energy in the tap window, so a
+//!   single step size `0 < μ < 2` works regardless of input level. It is the
+//!   usual default for echo cancellation.
+//! - **`LeakyLms`** pulls the weights towards zero, which bounds them when the
+//!   input is not persistently exciting, at the cost of a small bias.
+//! - **`SignErrorLms`** only uses the sign of the error, which makes it robust
+//!   to impulsive errors and cheap in fixed-point arithmetic.
+//! - **`Rls`** converges an order of magnitude faster than the LMS family on
+//!   coloured input, at O(N²) cost per sample.
+//! - **`Apa`** (affine projection) generalizes NLMS to the last `K` input
+//!   vectors, which speeds up convergence on coloured input at O(K²·N) cost per
+//!   sample; a middle ground between `Nlms` and `Rls`.
+//!
+//! **`hum::HumCanceller`** is the exception to the `(input, desired)` interface:
+//! it synthesizes its own reference, a sine and cosine per mains harmonic from a
+//! frequency-tracking NCO, and consumes plain samples. Use it instead of a fixed
+//! notch when the hum frequency drifts with the grid.
+//!
+//! # Weight ordering
+//!
+//! As with [`Convolve`](super::fir::convolve::Convolve), weight `w[k]` pairs
+//! with tap `x[n−k]`, so `w[0]` multiplies the newest input sample.
+//!
+//! # See also
+//!
+//! - [`super::fir::convolve`]: fixed-coefficient FIR filtering, e.g. for applying
+//!   identified weights.
+
+use num_traits::Num;
+
+use crate::storage::RingBuffer;
+
+pub mod apa;
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod hum;
+pub mod leaky_lms;
+pub mod lms;
+pub mod nlms;
+pub mod rls;
+pub mod sign_error_lms;
+
+/// Output of an adaptive filter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Output<T> {
+    /// The filtered input, computed with the weights before the update.
+    pub value: T,
+    /// The a-priori error `desired − value`.
+    pub error: T,
+}
+
+/// The state of an LMS-family adaptive filter.
+///
+/// Holds the weight storage `W`, which must implement
+/// [`AsSlice<T>`](crate::storage::AsSlice), and the tap ring-buffer `R`,
+/// which must implement [`RingBuffer<T>`] on relevant impls.
+#[derive(Clone, Debug)]
+pub struct State<W, R> {
+    /// The adaptive weights, `w[0]` pairing with the newest tap.
+    pub weights: W,
+    /// The filter's taps (i.e. buffered input).
+    pub taps: R,
+}
+
+/// Returns `Σ w[k]·x[n−k]`.
+fn predict<T, R>(weights: &[T], taps: &R) -> T
+where
+    T: Clone + Num,
+    R: RingBuffer<T>,
+{
+    // Taps iterate oldest->newest, so the weights are walked in reverse.
+    let skip = weights.len().saturating_sub(taps.len());
+    taps.iter()
+        .zip(weights.iter().rev().skip(skip))
+        .fold(T::zero(), |sum, (tap, weight)| {
+            sum + tap.clone() * weight.clone()
+        })
+}
+
+/// Returns `Σ x[n−k]²`.
+fn energy<T, R>(taps: &R) -> T
+where
+    T: Clone + Num,
+    R: RingBuffer<T>,
+{
+    taps.iter()
+        .fold(T::zero(), |sum, tap| sum + tap.clone() * tap.clone())
+}
+
+/// Adds `scale·x[n−k]` to every weight `w[k]`.
+fn update<T, R>(weights: &mut [T], taps: &R, scale: &T)
+where
+    T: Clone + Num,
+    R: RingBuffer<T>,
+{
+    let skip = weights.len().saturating_sub(taps.len());
+    for (weight, tap) in weights.iter_mut().rev().skip(skip).zip(taps.iter()) {
+        *weight = weight.clone() + scale.clone() * tap.clone();
+    }
+}
+
+fn assert_parts(weights: usize, taps: usize, name: &str) {
+    assert!(weights > 0, "{name}: weight count must be > 0");
+    assert_eq!(
+        weights, taps,
+        "{name}: weight count ({weights}) must equal taps capacity ({taps})"
+    );
+}
+
+fn assert_weights(expected: usize, actual: usize, name: &str) {
+    assert_eq!(
+        expected, actual,
+        "{name}: expected {expected} weights, got {actual}"
+    );
+}
+
+#[cfg(feature = "alloc")]
+fn zero_filled_heap_ring<T>(len: usize) -> circular_buffer::HeapCircularBuffer<T>
+where
+    T: Num,
+{
+    let mut taps = circular_buffer::HeapCircularBuffer::with_capacity(len);
+    for _ in 0..len {
+        let _ = taps.push_back(T::zero());
+    }
+    taps
+}
+
+#[cfg(test)]
+mod test_util {
+    use alloc::vec::Vec;
+
+    use crate::filters::fir::convolve::{Config as ConvolveConfig, ConvolveArray};
+    use crate::traits::{Filter, WithConfig};
+    use crate::util::test_fixtures::white_noise;
+
+    /// The unknown system to identify.
+    pub(super) const PLANT: [f64; 4] = [0.5, -0.3, 0.2, 0.1];
+
+    /// `(input, desired)` pairs with `desired` the input filtered by [`PLANT`].
+    pub(super) fn identification_pairs(len: usize) -> Vec<(f64, f64)> {
+        let mut plant = ConvolveArray::with_config(ConvolveConfig {
+            coefficients: PLANT,
+        });
+        white_noise(len, 0x1234_5678)
+            .into_iter()
+            .map(|x| (x, plant.filter(x)))
+            .collect()
+    }
+}
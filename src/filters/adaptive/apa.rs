@@ -0,0 +1,497 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Affine projection adaptive filter.
+
+use core::marker::PhantomData;
+
+use circular_buffer::{CircularBuffer, FixedCircularBuffer};
+use num_traits::float::FloatCore;
+
+use crate::math::matrix::{Matrix, Vector};
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{assert_weights, Output};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The APA filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The normalized step size `μ`.
+    ///
+    /// The filter converges for `0 < μ < 2`; `μ = 1` gives the fastest
+    /// convergence on noise-free input.
+    pub step_size: T,
+    /// The regularization `ε > 0` added to the diagonal of the `K×K` input
+    /// correlation matrix, which keeps it invertible while the taps are
+    /// (nearly) silent or linearly dependent.
+    pub regularization: T,
+}
+
+/// The APA filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T, W, R, const K: usize> {
+    /// The adaptive weights, `w[0]` pairing with the newest tap.
+    pub weights: W,
+    /// The filter's taps (i.e. buffered input), holding the `N + K − 1` most
+    /// recent inputs spanned by the last `K` input vectors.
+    pub taps: R,
+    /// The `K` most recent desired samples, newest first.
+    pub desired: [T; K],
+}
+
+/// An affine projection adaptive FIR filter with projection order `K`,
+/// generic over sample type `T`, weight storage `W` and tap storage `R`.
+///
+/// For every `(input, desired)` pair the filter pushes `input` into its taps
+/// and forms the `N×K` matrix `X = [x[n], …, x[n−K+1]]` of the last `K` input
+/// vectors, along with the errors `e = d − Xᵀ·w` of the last `K` desired
+/// samples, then updates
+///
+/// ```text
+/// w = w + μ·X·(XᵀX + ε·I)⁻¹·e
+/// ```
+///
+/// The output `value` is `x[n]ᵀ·w` and `error` is `e[0]`, both computed with the
+/// weights before the update.
+///
+/// Projecting onto the last `K` input vectors instead of only the newest one
+/// decorrelates coloured input, so APA converges markedly faster than
+/// [`Nlms`](super::nlms::Nlms) on speech-like or otherwise coloured signals, at
+/// `K` times the cost. With `K = 1` it reduces to NLMS.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`ApaArray<T, N, K, L>`] — stack-allocated, `no_std`-friendly, with `L = N + K − 1`.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`ApaVec<T, K>`] — heap-allocated, requires the `alloc` feature."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `ApaVec<T, K>` — heap-allocated, requires the `alloc` feature."
+)]
+/// - [`ApaRefMut<'a, T, W, K>`] — borrows a caller-owned tap buffer.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(K²·N + K³); the `K×K` correlation matrix of the
+///   last `K` input vectors and its inverse.
+/// - **Space:** O(N + K); N weights, a circular tap buffer of `N + K − 1`
+///   elements and `K` desired samples.
+#[derive(Clone, Debug)]
+pub struct Apa<T, W, R, const K: usize> {
+    config: Config<T>,
+    state: State<T, W, R, K>,
+    _pd: PhantomData<T>,
+}
+
+/// An APA filter with `N` weights and projection order `K`, backed by a
+/// const-generic weight array and a [`FixedCircularBuffer`] tap buffer.
+///
+/// `L` is the tap buffer's capacity and must equal `N + K − 1`.
+pub type ApaArray<T, const N: usize, const K: usize, const L: usize> =
+    Apa<T, [T; N], FixedCircularBuffer<T, L>, K>;
+
+/// An APA filter backed by heap-allocated weights and a
+/// [`HeapCircularBuffer`] tap buffer.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type ApaVec<T, const K: usize> = Apa<T, alloc::vec::Vec<T>, HeapCircularBuffer<T>, K>;
+
+/// An APA filter that borrows a [`CircularBuffer`] tap buffer.
+///
+/// Construct via [`Apa::from_parts`], passing a `&mut CircularBuffer<T>` for
+/// the tap buffer.
+pub type ApaRefMut<'a, T, W, const K: usize> = Apa<T, W, &'a mut CircularBuffer<T>, K>;
+
+impl<T, W, R, const K: usize> Apa<T, W, R, K>
+where
+    T: FloatCore,
+    W: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    /// Creates an [`Apa`] filter from an already-constructed `config`, initial
+    /// `weights` and `taps` ring-buffer.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-padded cold-start behavior, pre-fill `taps` with `N + K − 1` zeros.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is zero, if `weights` is empty, if `taps.capacity()` does
+    /// not equal `N + K − 1` for `N = weights.len()`, or if the step size or
+    /// regularization are not positive.
+    pub fn from_parts(config: Config<T>, weights: W, taps: R) -> Self {
+        let n = weights.len();
+        assert!(K > 0, "Apa: projection order must be > 0");
+        assert!(n > 0, "Apa: weight count must be > 0");
+        assert_eq!(
+            taps.capacity(),
+            n + K - 1,
+            "Apa: taps capacity must equal N + K − 1 = {}",
+            n + K - 1
+        );
+        assert!(config.step_size > T::zero(), "Apa: step size must be > 0");
+        assert!(
+            config.regularization > T::zero(),
+            "Apa: regularization must be > 0"
+        );
+        Self {
+            config,
+            state: State {
+                weights,
+                taps,
+                desired: [T::zero(); K],
+            },
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, W, R, const K: usize> Apa<T, W, R, K>
+where
+    W: AsSlice<T>,
+{
+    /// Returns the current weights, `w[0]` pairing with the newest input sample.
+    pub fn weights(&self) -> &[T] {
+        self.state.weights.as_slice()
+    }
+
+    /// Overwrites the current weights, e.g. to restore a checkpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights.len()` does not equal the number of weights.
+    pub fn set_weights(&mut self, weights: &[T])
+    where
+        T: Clone,
+    {
+        let own = self.state.weights.as_mut_slice();
+        assert_weights(own.len(), weights.len(), "Apa");
+        own.clone_from_slice(weights);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const K: usize> ApaVec<T, K>
+where
+    T: FloatCore,
+{
+    /// Creates a heap-backed APA filter with `len` zero weights and
+    /// zero-filled taps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` or `K` is zero, or if the step size or regularization
+    /// are not positive.
+    #[must_use]
+    pub fn new(config: Config<T>, len: usize) -> Self {
+        let weights = alloc::vec![T::zero(); len];
+        let taps = super::zero_filled_heap_ring(len + K.saturating_sub(1));
+        Self::from_parts(config, weights, taps)
+    }
+}
+
+impl<T, W, R, const K: usize> ConfigTrait for Apa<T, W, R, K> {
+    type Config = Config<T>;
+}
+
+impl<T, W, R, const K: usize> StateTrait for Apa<T, W, R, K> {
+    type State = State<T, W, R, K>;
+}
+
+impl<T, const N: usize, const K: usize, const L: usize> WithConfig for ApaArray<T, N, K, L>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// Creates an [`ApaArray`] with zero weights and zero-filled taps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` or `K` is zero, if `L` does not equal `N + K − 1`, or if
+    /// the step size or regularization are not positive.
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(config, [T::zero(); N], zero_filled_fixed_ring::<T, L>())
+    }
+}
+
+impl<T, W, R, const K: usize> ConfigRef for Apa<T, W, R, K> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, W, R, const K: usize> ConfigClone for Apa<T, W, R, K>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, W, R, const K: usize> StateMut for Apa<T, W, R, K> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, W, R, const K: usize> HasGuts for Apa<T, W, R, K> {
+    type Guts = (Config<T>, State<T, W, R, K>);
+}
+
+impl<T, W, R, const K: usize> FromGuts for Apa<T, W, R, K> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self {
+            config,
+            state,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, W, R, const K: usize> IntoGuts for Apa<T, W, R, K> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize, const K: usize, const L: usize> Reset for ApaArray<T, N, K, L>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize, const K: usize, const L: usize> ResetMut for ApaArray<T, N, K, L> where
+    Self: Reset
+{
+}
+
+impl<T, W, R, const K: usize> Filter<(T, T)> for Apa<T, W, R, K>
+where
+    T: FloatCore,
+    W: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    type Output = Output<T>;
+
+    fn filter(&mut self, (input, desired): (T, T)) -> Self::Output {
+        let State {
+            weights,
+            taps,
+            desired: history,
+        } = &mut self.state;
+        taps.push_back(input);
+        history.rotate_right(1);
+        history[0] = desired;
+
+        let weights = weights.as_mut_slice();
+        // x[j] = x[n−j]; taps count from the oldest element.
+        let tap = |j: usize| {
+            taps.len()
+                .checked_sub(j + 1)
+                .and_then(|index| taps.get(index))
+                .map_or_else(T::zero, Clone::clone)
+        };
+        // The i-th input vector's k-th element is x[n−i−k].
+        let dot = |i: usize, j: usize| {
+            (0..weights.len()).fold(T::zero(), |sum, k| sum + tap(i + k) * tap(j + k))
+        };
+
+        let errors: [T; K] = core::array::from_fn(|i| {
+            let value = weights
+                .iter()
+                .enumerate()
+                .fold(T::zero(), |sum, (k, &weight)| sum + weight * tap(i + k));
+            history[i] - value
+        });
+        let error = errors[0];
+        let value = desired - error;
+
+        let mut correlation = Matrix::<T, K, K>::zeros();
+        for i in 0..K {
+            for j in 0..=i {
+                let entry = dot(i, j);
+                correlation[(i, j)] = entry;
+                correlation[(j, i)] = entry;
+            }
+            correlation[(i, i)] = correlation[(i, i)] + self.config.regularization;
+        }
+        if let Some(inverse) = correlation.inverse() {
+            let scale = (inverse * Vector::from_column(errors)).column();
+            let step_size = self.config.step_size;
+            for (k, weight) in weights.iter_mut().enumerate() {
+                let update = scale
+                    .iter()
+                    .enumerate()
+                    .fold(T::zero(), |sum, (i, &a)| sum + a * tap(i + k));
+                *weight = *weight + step_size * update;
+            }
+        }
+
+        Output { value, error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::super::nlms::{Config as NlmsConfig, NlmsArray};
+    use super::super::test_util::{identification_pairs, PLANT};
+    use super::{ApaArray, ApaRefMut, Config, Filter, Reset, WithConfig};
+
+    #[cfg(feature = "alloc")]
+    use super::ApaVec;
+
+    use circular_buffer::FixedCircularBuffer;
+
+    use crate::filters::fir::convolve::{Config as ConvolveConfig, ConvolveArray};
+    use crate::util::test_fixtures::white_noise;
+
+    fn config() -> Config<f64> {
+        Config {
+            step_size: 0.5,
+            regularization: 1e-6,
+        }
+    }
+
+    fn squared_error(weights: &[f64]) -> f64 {
+        weights
+            .iter()
+            .zip(PLANT)
+            .map(|(w, p)| (w - p) * (w - p))
+            .sum()
+    }
+
+    /// `(input, desired)` pairs with strongly coloured (AR(1)) input and
+    /// `desired` the input filtered by [`PLANT`].
+    fn coloured_pairs(len: usize) -> Vec<(f64, f64)> {
+        let mut plant = ConvolveArray::with_config(ConvolveConfig {
+            coefficients: PLANT,
+        });
+        let mut previous = 0.0;
+        white_noise(len, 0x9e37_79b9)
+            .into_iter()
+            .map(|noise| {
+                previous = 0.95 * previous + noise;
+                (previous, plant.filter(previous))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identifies_unknown_system() {
+        let mut filter = ApaArray::<f64, 4, 3, 6>::with_config(config());
+        let mut last_error = 1.0;
+        for pair in identification_pairs(2000) {
+            last_error = filter.filter(pair).error;
+        }
+        assert_abs_diff_eq!(filter.weights(), PLANT.as_slice(), epsilon = 1e-6);
+        assert_abs_diff_eq!(last_error, 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn first_order_matches_nlms() {
+        let mut apa = ApaArray::<f64, 4, 1, 4>::with_config(config());
+        let mut nlms = NlmsArray::<f64, 4>::with_config(NlmsConfig {
+            step_size: 0.5,
+            regularization: 1e-6,
+        });
+        for pair in identification_pairs(200) {
+            let expected = nlms.filter(pair);
+            let actual = apa.filter(pair);
+            assert_abs_diff_eq!(actual.value, expected.value, epsilon = 1e-12);
+            assert_abs_diff_eq!(actual.error, expected.error, epsilon = 1e-12);
+        }
+        assert_abs_diff_eq!(apa.weights(), nlms.weights(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn converges_faster_than_nlms_on_coloured_input() {
+        let mut apa = ApaArray::<f64, 4, 4, 7>::with_config(config());
+        let mut nlms = NlmsArray::<f64, 4>::with_config(NlmsConfig {
+            step_size: 0.5,
+            regularization: 1e-6,
+        });
+        for pair in coloured_pairs(200) {
+            let _ = apa.filter(pair);
+            let _ = nlms.filter(pair);
+        }
+        assert!(squared_error(apa.weights()) < 1e-3 * squared_error(nlms.weights()));
+    }
+
+    #[test]
+    fn silent_input_keeps_weights_finite() {
+        let mut filter = ApaArray::<f64, 4, 2, 5>::with_config(config());
+        for _ in 0..10 {
+            let output = filter.filter((0.0, 1.0));
+            assert_abs_diff_eq!(output.error, 1.0);
+        }
+        assert_eq!(filter.weights(), [0.0; 4].as_slice());
+    }
+
+    #[test]
+    fn reset_clears_weights_and_history() {
+        let mut filter = ApaArray::<f64, 4, 2, 5>::with_config(config());
+        for pair in identification_pairs(100) {
+            let _ = filter.filter(pair);
+        }
+        let mut filter = filter.reset();
+        assert_eq!(filter.weights(), [0.0; 4].as_slice());
+        let output = filter.filter((1.0, 1.0));
+        assert_abs_diff_eq!(output.value, 0.0);
+    }
+
+    #[test]
+    fn ref_mut_matches_array() {
+        let mut array = ApaArray::<f64, 4, 2, 5>::with_config(config());
+        let mut buffer = FixedCircularBuffer::<f64, 5>::from([0.0; 5]);
+        let mut ref_mut: ApaRefMut<'_, f64, [f64; 4], 2> =
+            ApaRefMut::from_parts(config(), [0.0; 4], &mut buffer);
+        for pair in identification_pairs(200) {
+            assert_eq!(array.filter(pair), ref_mut.filter(pair));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let mut array = ApaArray::<f64, 4, 2, 5>::with_config(config());
+        let mut vec = ApaVec::<f64, 2>::new(config(), 4);
+        for pair in identification_pairs(200) {
+            assert_eq!(array.filter(pair), vec.filter(pair));
+        }
+        assert_eq!(array.weights(), vec.weights());
+    }
+
+    #[test]
+    #[should_panic(expected = "Apa: taps capacity must equal N + K − 1 = 5")]
+    fn array_rejects_mismatched_tap_capacity() {
+        let _ = ApaArray::<f64, 4, 2, 4>::with_config(config());
+    }
+}
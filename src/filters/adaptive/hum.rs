@@ -0,0 +1,419 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Frequency-tracking mains-hum canceller.
+
+use num_traits::Float;
+
+use crate::math::db_to_amplitude;
+use crate::math::phase::sin_cos;
+use crate::sources::oscillator::nco::Nco;
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The hum canceller's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The sample rate in Hz.
+    pub sample_rate: T,
+    /// The nominal hum fundamental in Hz, e.g. `50` or `60`.
+    pub fundamental: T,
+    /// The largest deviation in Hz the tracked fundamental may take from
+    /// `fundamental`; `0` pins the notches to the nominal frequency.
+    pub max_deviation: T,
+    /// The −3 dB width in Hz of each notch.
+    ///
+    /// Wider notches converge and track faster, but remove more of the signal
+    /// around each harmonic.
+    pub bandwidth: T,
+    /// The attenuation in dB (`>= 0`) at the center of each notch once converged.
+    ///
+    /// `T::infinity()` cancels the hum completely; finite values leave a
+    /// residual `depth_db` below the hum, e.g. to keep a hint of it audible.
+    pub depth_db: T,
+    /// The frequency-locked loop gain (`0..1`); `0` disables tracking.
+    ///
+    /// Each sample, the fundamental is corrected by `tracking` times the rotation
+    /// rate of the estimated hum phasor. Keep it well below `1`, e.g. `0.001`,
+    /// to average out the noise of the rotation measurement.
+    pub tracking: T,
+}
+
+/// The hum canceller's state.
+#[derive(Clone, Debug)]
+pub struct State<T, const K: usize> {
+    /// The oscillator running at the tracked fundamental.
+    pub nco: Nco<T>,
+    /// The tracked fundamental in Hz.
+    ///
+    /// Kept at full precision, as the NCO is tuned in `f32`.
+    pub frequency: T,
+    /// The in-phase and quadrature weights `[w_cos, w_sin]` of each harmonic.
+    pub weights: [[T; 2]; K],
+}
+
+/// A mains-hum canceller for the fundamental and the first `K − 1` overtones.
+///
+/// An [`Nco`] generates the reference `cos(k·θ)` and `sin(k·θ)` of every harmonic
+/// `k = 1..=K`. An LMS adaptive notch per harmonic fits the weights `[w_cos, w_sin]`
+/// to the hum in the input, and the weighted references are subtracted from it
+/// (Widrow's adaptive noise canceller with a synthetic reference):
+///
+/// ```text
+/// hum  = Σₖ w_cos[k]·cos(k·θ) + w_sin[k]·sin(k·θ)
+/// e    = x − hum
+/// w   += μ·e·[cos(k·θ), sin(k·θ)]          with μ = 2π·bandwidth / sample_rate
+/// y    = x − (1 − 10^(−depth_db / 20))·hum
+/// ```
+///
+/// If the grid frequency drifts, the fundamental's weight phasor rotates at the
+/// frequency error. A frequency-locked loop feeds that rotation back into the
+/// NCO, so the notches follow the hum within `fundamental ± max_deviation`. The
+/// loop can only lock onto hum within roughly one notch bandwidth of the current
+/// frequency.
+///
+/// Harmonics at or above the Nyquist frequency alias; choose `K` so that
+/// `K·(fundamental + max_deviation) < sample_rate / 2`.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(K); one phase-to-sine/cosine conversion and two
+///   weight updates per harmonic.
+/// - **Space:** O(K); two weights per harmonic.
+#[derive(Clone, Debug)]
+pub struct HumCanceller<T, const K: usize> {
+    config: Config<T>,
+    state: State<T, K>,
+}
+
+impl<T, const K: usize> HumCanceller<T, K>
+where
+    T: Float,
+{
+    /// Returns the tracked fundamental in Hz.
+    pub fn frequency(&self) -> T {
+        self.state.frequency
+    }
+
+    /// Returns the estimated hum amplitude of `harmonic` (1 = fundamental).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `harmonic` is zero or greater than `K`.
+    pub fn amplitude(&self, harmonic: usize) -> T {
+        let [w_cos, w_sin] = self.state.weights[harmonic - 1];
+        w_cos.hypot(w_sin)
+    }
+
+    /// Clamps `frequency` to the tracking range and retunes the NCO.
+    fn retune(&mut self, frequency: T) {
+        let nominal = self.config.fundamental;
+        let deviation = self.config.max_deviation;
+        let frequency = frequency.max(nominal - deviation).min(nominal + deviation);
+        self.state.frequency = frequency;
+        let sample_rate = self.config.sample_rate.to_f32().unwrap_or(f32::NAN);
+        let frequency = frequency.to_f32().unwrap_or(f32::NAN);
+        self.state.nco.set_frequency(frequency, sample_rate);
+    }
+}
+
+impl<T, const K: usize> ConfigTrait for HumCanceller<T, K> {
+    type Config = Config<T>;
+}
+
+impl<T, const K: usize> StateTrait for HumCanceller<T, K> {
+    type State = State<T, K>;
+}
+
+impl<T, const K: usize> WithConfig for HumCanceller<T, K>
+where
+    T: Float,
+{
+    type Output = Self;
+
+    /// Creates a hum canceller at the nominal fundamental with zero weights.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K` is zero, if the sample rate or fundamental is not finite
+    /// and positive, if the bandwidth is not positive, or if the maximum
+    /// deviation or depth is negative.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(K > 0, "HumCanceller: harmonic count must be > 0");
+        assert!(
+            config.sample_rate.is_finite() && config.sample_rate > T::zero(),
+            "HumCanceller: sample rate must be finite and positive"
+        );
+        assert!(
+            config.fundamental.is_finite() && config.fundamental > T::zero(),
+            "HumCanceller: fundamental must be finite and positive"
+        );
+        assert!(
+            config.max_deviation >= T::zero(),
+            "HumCanceller: max deviation must be >= 0"
+        );
+        assert!(
+            config.bandwidth > T::zero(),
+            "HumCanceller: bandwidth must be > 0"
+        );
+        assert!(
+            config.depth_db >= T::zero(),
+            "HumCanceller: depth must be >= 0 dB"
+        );
+        let mut canceller = Self {
+            state: State {
+                nco: Nco::from_phase_step(0),
+                frequency: config.fundamental,
+                weights: [[T::zero(); 2]; K],
+            },
+            config,
+        };
+        canceller.retune(canceller.config.fundamental);
+        canceller
+    }
+}
+
+impl<T, const K: usize> ConfigRef for HumCanceller<T, K> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, const K: usize> ConfigClone for HumCanceller<T, K>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, const K: usize> StateMut for HumCanceller<T, K> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, const K: usize> HasGuts for HumCanceller<T, K> {
+    type Guts = (Config<T>, State<T, K>);
+}
+
+impl<T, const K: usize> FromGuts for HumCanceller<T, K> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, const K: usize> IntoGuts for HumCanceller<T, K> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const K: usize> Reset for HumCanceller<T, K>
+where
+    T: Float,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const K: usize> ResetMut for HumCanceller<T, K> where Self: Reset {}
+
+impl<T, const K: usize> Filter<T> for HumCanceller<T, K>
+where
+    T: Float,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let two = T::one() + T::one();
+        let tau = two * T::from(core::f64::consts::PI).unwrap_or_else(T::nan);
+        let step_size = tau * self.config.bandwidth / self.config.sample_rate;
+
+        let phase = self.state.nco.phase();
+        let mut references = [[T::zero(); 2]; K];
+        let mut hum = T::zero();
+        for (harmonic, (reference, weights)) in
+            (1_u32..).zip(references.iter_mut().zip(&self.state.weights))
+        {
+            let (sin, cos) = sin_cos(phase.wrapping_mul(harmonic));
+            *reference = [
+                T::from(cos).unwrap_or_else(T::zero),
+                T::from(sin).unwrap_or_else(T::zero),
+            ];
+            hum = hum + weights[0] * reference[0] + weights[1] * reference[1];
+        }
+        let error = input - hum;
+
+        let [old_cos, old_sin] = self.state.weights[0];
+        for (weights, reference) in self.state.weights.iter_mut().zip(&references) {
+            weights[0] = weights[0] + step_size * error * reference[0];
+            weights[1] = weights[1] + step_size * error * reference[1];
+        }
+
+        if self.config.tracking > T::zero() {
+            // The hum phasor `w_cos − j·w_sin` rotates at the frequency error.
+            let [new_cos, new_sin] = self.state.weights[0];
+            let cross = old_sin * new_cos - old_cos * new_sin;
+            let energy = old_cos.hypot(old_sin) * new_cos.hypot(new_sin);
+            if energy > T::epsilon() {
+                let rotation = cross / energy;
+                let correction = self.config.tracking * rotation * self.config.sample_rate / tau;
+                self.retune(self.frequency() + correction);
+            }
+        }
+        self.state.nco.step();
+
+        let residual = db_to_amplitude(-self.config.depth_db);
+        input - (T::one() - residual) * hum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 1000.0;
+
+    fn config() -> Config<f64> {
+        Config {
+            sample_rate: SAMPLE_RATE,
+            fundamental: 50.0,
+            max_deviation: 2.0,
+            bandwidth: 2.0,
+            depth_db: f64::INFINITY,
+            tracking: 0.0,
+        }
+    }
+
+    /// Hum with three harmonics at `frequency` Hz, sample `n`.
+    fn hum(frequency: f64, n: u32) -> f64 {
+        let phase = 2.0 * core::f64::consts::PI * frequency * f64::from(n) / SAMPLE_RATE;
+        0.8 * (phase + 0.3).sin() + 0.3 * (2.0 * phase - 1.0).cos() + 0.2 * (3.0 * phase).sin()
+    }
+
+    /// Returns the RMS of `len` outputs after `settle` samples.
+    fn residual_rms<const K: usize>(
+        canceller: &mut HumCanceller<f64, K>,
+        signal: impl Fn(u32) -> f64,
+        settle: u32,
+        len: u32,
+    ) -> f64 {
+        let mut power = 0.0;
+        for n in 0..(settle + len) {
+            let output = canceller.filter(signal(n));
+            if n >= settle {
+                power += output * output;
+            }
+        }
+        (power / f64::from(len)).sqrt()
+    }
+
+    #[test]
+    fn cancels_all_harmonics_at_nominal_frequency() {
+        let mut canceller = HumCanceller::<f64, 3>::with_config(config());
+        let rms = residual_rms(&mut canceller, |n| hum(50.0, n), 5000, 1000);
+        // The NCO's sine approximation bounds the achievable cancellation.
+        assert!(rms < 1e-3, "residual RMS {rms}");
+        assert_abs_diff_eq!(canceller.amplitude(1), 0.8, epsilon = 1e-3);
+        assert_abs_diff_eq!(canceller.amplitude(2), 0.3, epsilon = 1e-3);
+        assert_abs_diff_eq!(canceller.amplitude(3), 0.2, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn missing_harmonics_leave_hum() {
+        let mut canceller = HumCanceller::<f64, 1>::with_config(config());
+        let rms = residual_rms(&mut canceller, |n| hum(50.0, n), 5000, 1000);
+        // The uncancelled overtones: √((0.3² + 0.2²) / 2).
+        assert_abs_diff_eq!(rms, 0.255, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn finite_depth_leaves_residual() {
+        let mut canceller = HumCanceller::<f64, 1>::with_config(Config {
+            depth_db: 20.0,
+            ..config()
+        });
+        let tone = |n: u32| (2.0 * core::f64::consts::PI * 50.0 * f64::from(n) / SAMPLE_RATE).sin();
+        let rms = residual_rms(&mut canceller, tone, 5000, 1000);
+        assert_abs_diff_eq!(rms, 0.1 * core::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn tracks_drifting_fundamental() {
+        let mut canceller = HumCanceller::<f64, 3>::with_config(Config {
+            tracking: 0.002,
+            ..config()
+        });
+        let rms = residual_rms(&mut canceller, |n| hum(50.6, n), 20_000, 2000);
+        assert_abs_diff_eq!(canceller.frequency(), 50.6, epsilon = 0.01);
+        assert!(rms < 1e-2, "residual RMS {rms}");
+    }
+
+    #[test]
+    fn tracking_is_limited_to_deviation() {
+        let mut canceller = HumCanceller::<f64, 1>::with_config(Config {
+            tracking: 0.002,
+            max_deviation: 0.25,
+            ..config()
+        });
+        let _ = residual_rms(&mut canceller, |n| hum(50.6, n), 20_000, 0);
+        assert!(canceller.frequency() <= 50.25);
+        assert_abs_diff_eq!(canceller.frequency(), 50.25, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn passes_signal_away_from_harmonics() {
+        let mut canceller = HumCanceller::<f64, 3>::with_config(config());
+        let tone = |n: u32| (2.0 * core::f64::consts::PI * 80.0 * f64::from(n) / SAMPLE_RATE).sin();
+        let rms = residual_rms(&mut canceller, tone, 5000, 1000);
+        // Away from the notches, the gain stays within 0.3 dB of unity.
+        assert_abs_diff_eq!(rms, core::f64::consts::FRAC_1_SQRT_2, epsilon = 2e-2);
+    }
+
+    #[test]
+    #[should_panic(expected = "HumCanceller: harmonic count must be > 0")]
+    fn rejects_zero_harmonics() {
+        let _ = HumCanceller::<f64, 0>::with_config(config());
+    }
+
+    #[test]
+    #[should_panic(expected = "HumCanceller: bandwidth must be > 0")]
+    fn rejects_zero_bandwidth() {
+        let _ = HumCanceller::<f64, 1>::with_config(Config {
+            bandwidth: 0.0,
+            ..config()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "HumCanceller: depth must be >= 0 dB")]
+    fn rejects_negative_depth() {
+        let _ = HumCanceller::<f64, 1>::with_config(Config {
+            depth_db: -6.0,
+            ..config()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "HumCanceller: max deviation must be >= 0")]
+    fn rejects_negative_max_deviation() {
+        let _ = HumCanceller::<f64, 1>::with_config(Config {
+            max_deviation: -1.0,
+            ..config()
+        });
+    }
+}
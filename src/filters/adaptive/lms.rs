@@ -0,0 +1,328 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Least-mean-squares adaptive filter.
+
+use core::marker::PhantomData;
+
+use circular_buffer::{CircularBuffer, FixedCircularBuffer};
+use num_traits::Num;
+
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{assert_parts, assert_weights, predict, update, Output, State};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The LMS filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The step size `μ`.
+    ///
+    /// The filter converges in the mean for `0 < μ < 2 / (N·σ²)`, with `σ²` the
+    /// input power. Larger steps adapt faster but leave more misadjustment.
+    pub step_size: T,
+}
+
+/// A least-mean-squares adaptive FIR filter generic over sample type `T`,
+/// weight storage `W` and tap storage `R`.
+///
+/// For every `(input, desired)` pair the filter pushes `input` into its taps,
+/// computes `value = Σ w[k]·x[n−k]` and `error = desired − value`, then updates
+/// the weights with `w[k] += μ·error·x[n−k]`.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`LmsArray<T, N>`] — stack-allocated, `no_std`-friendly.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`LmsVec<T>`] — heap-allocated, requires the `alloc` feature."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `LmsVec<T>` — heap-allocated, requires the `alloc` feature."
+)]
+/// - [`LmsRefMut<'a, T, W>`] — borrows a caller-owned tap buffer.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); one dot product and one weight update over N taps.
+/// - **Space:** O(N); N weights plus a circular tap buffer of N elements.
+#[derive(Clone, Debug)]
+pub struct Lms<T, W, R> {
+    config: Config<T>,
+    state: State<W, R>,
+    _pd: PhantomData<T>,
+}
+
+/// An LMS filter backed by a const-generic weight array and a
+/// [`FixedCircularBuffer`] tap buffer.
+pub type LmsArray<T, const N: usize> = Lms<T, [T; N], FixedCircularBuffer<T, N>>;
+
+/// An LMS filter backed by heap-allocated weights and a
+/// [`HeapCircularBuffer`] tap buffer.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type LmsVec<T> = Lms<T, alloc::vec::Vec<T>, HeapCircularBuffer<T>>;
+
+/// An LMS filter that borrows a [`CircularBuffer`] tap buffer.
+///
+/// Construct via [`Lms::from_parts`], passing a `&mut CircularBuffer<T>` for
+/// the tap buffer.
+pub type LmsRefMut<'a, T, W> = Lms<T, W, &'a mut CircularBuffer<T>>;
+
+impl<T, W, R> Lms<T, W, R>
+where
+    W: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    /// Creates an [`Lms`] filter from an already-constructed `config`, initial
+    /// `weights` and `taps` ring-buffer.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-padded cold-start behavior, pre-fill `taps` with `N` zeros.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights` is empty or its length does not equal
+    /// `taps.capacity()`.
+    pub fn from_parts(config: Config<T>, weights: W, taps: R) -> Self {
+        assert_parts(weights.len(), taps.capacity(), "Lms");
+        Self {
+            config,
+            state: State { weights, taps },
+            _pd: PhantomData,
+        }
+    }
+
+    /// Returns the current weights, `w[0]` pairing with the newest input sample.
+    pub fn weights(&self) -> &[T] {
+        self.state.weights.as_slice()
+    }
+
+    /// Overwrites the current weights, e.g. to restore a checkpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights.len()` does not equal the number of taps.
+    pub fn set_weights(&mut self, weights: &[T])
+    where
+        T: Clone,
+    {
+        let own = self.state.weights.as_mut_slice();
+        assert_weights(own.len(), weights.len(), "Lms");
+        own.clone_from_slice(weights);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> LmsVec<T>
+where
+    T: Clone + Num,
+{
+    /// Creates a heap-backed LMS filter with `len` zero weights and
+    /// zero-filled taps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero.
+    #[must_use]
+    pub fn new(config: Config<T>, len: usize) -> Self {
+        let weights = alloc::vec![T::zero(); len];
+        Self::from_parts(config, weights, super::zero_filled_heap_ring(len))
+    }
+}
+
+impl<T, W, R> ConfigTrait for Lms<T, W, R> {
+    type Config = Config<T>;
+}
+
+impl<T, W, R> StateTrait for Lms<T, W, R> {
+    type State = State<W, R>;
+}
+
+impl<T, const N: usize> WithConfig for LmsArray<T, N>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    /// Creates an [`LmsArray`] with zero weights and zero-filled taps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    fn with_config(config: Self::Config) -> Self::Output {
+        let weights = core::array::from_fn(|_| T::zero());
+        Self::from_parts(config, weights, zero_filled_fixed_ring::<T, N>())
+    }
+}
+
+impl<T, W, R> ConfigRef for Lms<T, W, R> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, W, R> ConfigClone for Lms<T, W, R>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, W, R> StateMut for Lms<T, W, R> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, W, R> HasGuts for Lms<T, W, R> {
+    type Guts = (Config<T>, State<W, R>);
+}
+
+impl<T, W, R> FromGuts for Lms<T, W, R> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self {
+            config,
+            state,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, W, R> IntoGuts for Lms<T, W, R> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize> Reset for LmsArray<T, N>
+where
+    T: Num,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for LmsArray<T, N> where Self: Reset {}
+
+impl<T, W, R> Filter<(T, T)> for Lms<T, W, R>
+where
+    T: Clone + Num,
+    W: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    type Output = Output<T>;
+
+    fn filter(&mut self, (input, desired): (T, T)) -> Self::Output {
+        self.state.taps.push_back(input);
+        let weights = self.state.weights.as_mut_slice();
+        let value = predict(weights, &self.state.taps);
+        let error = desired - value.clone();
+        let scale = self.config.step_size.clone() * error.clone();
+        update(weights, &self.state.taps, &scale);
+        Output { value, error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::super::test_util::{identification_pairs, PLANT};
+    use super::{Config, Filter, LmsArray, LmsRefMut, Reset, WithConfig};
+
+    #[cfg(feature = "alloc")]
+    use super::LmsVec;
+
+    use circular_buffer::FixedCircularBuffer;
+
+    fn config() -> Config<f64> {
+        Config { step_size: 0.05 }
+    }
+
+    #[test]
+    fn identifies_unknown_system() {
+        let mut filter = LmsArray::<f64, 4>::with_config(config());
+        let mut last_error = 1.0;
+        for pair in identification_pairs(4000) {
+            last_error = filter.filter(pair).error;
+        }
+        assert_abs_diff_eq!(filter.weights(), PLANT.as_slice(), epsilon = 1e-6);
+        assert_abs_diff_eq!(last_error, 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn first_output_uses_initial_weights() {
+        let mut filter = LmsArray::<f64, 2>::with_config(config());
+        filter.set_weights(&[2.0, 0.0]);
+        let output = filter.filter((3.0, 10.0));
+        assert_abs_diff_eq!(output.value, 6.0);
+        assert_abs_diff_eq!(output.error, 4.0);
+        // w[0] += 0.05·4·3
+        assert_abs_diff_eq!(filter.weights(), [2.6, 0.0].as_slice(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn reset_clears_weights_and_taps() {
+        let mut filter = LmsArray::<f64, 4>::with_config(config());
+        for pair in identification_pairs(100) {
+            let _ = filter.filter(pair);
+        }
+        let mut filter = filter.reset();
+        assert_eq!(filter.weights(), [0.0; 4].as_slice());
+        let output = filter.filter((1.0, 1.0));
+        assert_abs_diff_eq!(output.value, 0.0);
+    }
+
+    #[test]
+    fn ref_mut_matches_array() {
+        let mut array = LmsArray::<f64, 4>::with_config(config());
+        let mut buffer = FixedCircularBuffer::<f64, 4>::from([0.0; 4]);
+        let mut ref_mut: LmsRefMut<'_, f64, [f64; 4]> =
+            LmsRefMut::from_parts(config(), [0.0; 4], &mut buffer);
+        let pairs = identification_pairs(200);
+        let expected: Vec<_> = pairs.iter().map(|&pair| array.filter(pair)).collect();
+        let actual: Vec<_> = pairs.iter().map(|&pair| ref_mut.filter(pair)).collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let mut array = LmsArray::<f64, 4>::with_config(config());
+        let mut vec = LmsVec::new(config(), 4);
+        for pair in identification_pairs(200) {
+            assert_eq!(array.filter(pair), vec.filter(pair));
+        }
+        assert_eq!(array.weights(), vec.weights());
+    }
+
+    #[test]
+    #[should_panic(expected = "Lms: expected 4 weights, got 3")]
+    fn set_weights_rejects_wrong_length() {
+        let mut filter = LmsArray::<f64, 4>::with_config(config());
+        filter.set_weights(&[0.0; 3]);
+    }
+}
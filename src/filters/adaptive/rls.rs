@@ -0,0 +1,528 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Recursive-least-squares adaptive filter.
+
+use core::marker::PhantomData;
+
+use circular_buffer::{CircularBuffer, FixedCircularBuffer};
+use num_traits::Num;
+
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{assert_parts, assert_weights, predict, Output};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The RLS filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The forgetting factor `0 < λ ≤ 1`.
+    ///
+    /// Past errors are weighted by `λ^age`, giving an effective memory of about
+    /// `1 / (1 − λ)` samples. `λ = 1` solves the growing-window least-squares
+    /// problem; values around `0.99` let the filter track slowly varying systems.
+    pub forgetting_factor: T,
+    /// The initial inverse correlation `δ > 0`, i.e. `P = δ·I` on cold start.
+    ///
+    /// Large values express little confidence in the initial (zero) weights and
+    /// give the fastest initial convergence.
+    pub initial_covariance: T,
+}
+
+/// The RLS filter's state.
+#[derive(Clone, Debug)]
+pub struct State<W, P, R> {
+    /// The adaptive weights, `w[0]` pairing with the newest tap.
+    pub weights: W,
+    /// The gain vector `k` of the most recent update.
+    pub gain: W,
+    /// The `N×N` inverse input correlation matrix `P`, row-major.
+    pub inverse_correlation: P,
+    /// The filter's taps (i.e. buffered input).
+    pub taps: R,
+}
+
+/// A recursive-least-squares adaptive FIR filter generic over sample type `T`,
+/// weight storage `W`, matrix storage `P` and tap storage `R`.
+///
+/// For every `(input, desired)` pair the filter pushes `input` into its taps
+/// (newest first: `x = [x[n], …, x[n−N+1]]`) and computes
+/// `value = wᵀx` and `error = desired − value`, then updates
+///
+/// ```text
+/// k = P·x / (λ + xᵀ·P·x)
+/// w = w + k·error
+/// P = (P − k·xᵀ·P) / λ
+/// ```
+///
+/// This minimizes the exponentially weighted sum of squared errors exactly at
+/// every step, so RLS converges in about `2·N` samples regardless of the input
+/// spectrum, where the [`Lms`](super::lms::Lms) family slows down on coloured
+/// input.
+///
+/// # Numerical stability
+///
+/// The update of `P` is the conventional (non-square-root) form. With `λ < 1`
+/// and input that stops exciting some direction, `P` grows as `λ^-n` along that
+/// direction; prefer `λ` close to one, or [`Reset`] the filter after long silences.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`RlsArray<T, N, M>`] — stack-allocated, `no_std`-friendly, with `M = N·N`.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`RlsVec<T>`] — heap-allocated, requires the `alloc` feature."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `RlsVec<T>` — heap-allocated, requires the `alloc` feature."
+)]
+/// - [`RlsRefMut<'a, T, W, P>`] — borrows a caller-owned tap buffer.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N²); one matrix-vector product and one rank-one
+///   update of the `N×N` matrix `P`.
+/// - **Space:** O(N²); the `N×N` matrix `P`, plus N weights, N gains and a
+///   circular tap buffer of N elements.
+#[derive(Clone, Debug)]
+pub struct Rls<T, W, P, R> {
+    config: Config<T>,
+    state: State<W, P, R>,
+    _pd: PhantomData<T>,
+}
+
+/// An RLS filter with `N` weights backed by const-generic arrays and a
+/// [`FixedCircularBuffer`] tap buffer.
+///
+/// `M` is the length of the flattened `N×N` matrix and must equal `N·N`.
+pub type RlsArray<T, const N: usize, const M: usize> =
+    Rls<T, [T; N], [T; M], FixedCircularBuffer<T, N>>;
+
+/// An RLS filter backed by heap-allocated weights and matrix, and a
+/// [`HeapCircularBuffer`] tap buffer.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type RlsVec<T> = Rls<T, alloc::vec::Vec<T>, alloc::vec::Vec<T>, HeapCircularBuffer<T>>;
+
+/// An RLS filter that borrows a [`CircularBuffer`] tap buffer.
+///
+/// Construct via [`Rls::from_parts`], passing a `&mut CircularBuffer<T>` for
+/// the tap buffer.
+pub type RlsRefMut<'a, T, W, P> = Rls<T, W, P, &'a mut CircularBuffer<T>>;
+
+impl<T, W, P, R> Rls<T, W, P, R>
+where
+    T: Clone + Num + PartialOrd,
+    W: AsSlice<T>,
+    P: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    /// Creates an [`Rls`] filter from an already-constructed `config`, initial
+    /// `weights`, and `gain`, `inverse_correlation` and `taps` storage.
+    ///
+    /// `gain` and `inverse_correlation` are overwritten with zeros and
+    /// `δ·I` respectively; only their lengths matter.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-padded cold-start behavior, pre-fill `taps` with `N` zeros.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights` is empty, if the lengths of `weights`, `gain` and
+    /// `taps.capacity()` differ, if `inverse_correlation` does not hold `N·N`
+    /// elements, or if the forgetting factor or initial covariance are out of range.
+    pub fn from_parts(
+        config: Config<T>,
+        weights: W,
+        mut gain: W,
+        mut inverse_correlation: P,
+        taps: R,
+    ) -> Self {
+        let n = weights.len();
+        assert_parts(n, taps.capacity(), "Rls");
+        assert_eq!(gain.len(), n, "Rls: gain length must equal weight count");
+        assert_eq!(
+            inverse_correlation.len(),
+            n * n,
+            "Rls: inverse correlation must hold N·N = {} elements",
+            n * n
+        );
+        assert!(
+            config.forgetting_factor > T::zero() && config.forgetting_factor <= T::one(),
+            "Rls: forgetting factor must be in (0, 1]"
+        );
+        assert!(
+            config.initial_covariance > T::zero(),
+            "Rls: initial covariance must be > 0"
+        );
+
+        gain.as_mut_slice().fill(T::zero());
+        for (index, entry) in inverse_correlation.as_mut_slice().iter_mut().enumerate() {
+            *entry = if index % (n + 1) == 0 {
+                config.initial_covariance.clone()
+            } else {
+                T::zero()
+            };
+        }
+
+        Self {
+            config,
+            state: State {
+                weights,
+                gain,
+                inverse_correlation,
+                taps,
+            },
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, W, P, R> Rls<T, W, P, R>
+where
+    W: AsSlice<T>,
+{
+    /// Returns the current weights, `w[0]` pairing with the newest input sample.
+    pub fn weights(&self) -> &[T] {
+        self.state.weights.as_slice()
+    }
+
+    /// Overwrites the current weights, e.g. to restore a checkpoint.
+    ///
+    /// The inverse correlation matrix is left untouched; restore it through
+    /// [`StateMut`] for an exact checkpoint.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights.len()` does not equal the number of taps.
+    pub fn set_weights(&mut self, weights: &[T])
+    where
+        T: Clone,
+    {
+        let own = self.state.weights.as_mut_slice();
+        assert_weights(own.len(), weights.len(), "Rls");
+        own.clone_from_slice(weights);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> RlsVec<T>
+where
+    T: Clone + Num + PartialOrd,
+{
+    /// Creates a heap-backed RLS filter with `len` zero weights and
+    /// zero-filled taps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero or if the forgetting factor or initial covariance
+    /// are out of range.
+    #[must_use]
+    pub fn new(config: Config<T>, len: usize) -> Self {
+        Self::from_parts(
+            config,
+            alloc::vec![T::zero(); len],
+            alloc::vec![T::zero(); len],
+            alloc::vec![T::zero(); len * len],
+            super::zero_filled_heap_ring(len),
+        )
+    }
+}
+
+impl<T, W, P, R> ConfigTrait for Rls<T, W, P, R> {
+    type Config = Config<T>;
+}
+
+impl<T, W, P, R> StateTrait for Rls<T, W, P, R> {
+    type State = State<W, P, R>;
+}
+
+impl<T, const N: usize, const M: usize> WithConfig for RlsArray<T, N, M>
+where
+    T: Clone + Num + PartialOrd,
+{
+    type Output = Self;
+
+    /// Creates an [`RlsArray`] with zero weights, zero-filled taps and
+    /// `P = δ·I`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero, if `M` does not equal `N·N`, or if the forgetting
+    /// factor or initial covariance are out of range.
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(
+            config,
+            core::array::from_fn(|_| T::zero()),
+            core::array::from_fn(|_| T::zero()),
+            core::array::from_fn(|_| T::zero()),
+            zero_filled_fixed_ring::<T, N>(),
+        )
+    }
+}
+
+impl<T, W, P, R> ConfigRef for Rls<T, W, P, R> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, W, P, R> ConfigClone for Rls<T, W, P, R>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, W, P, R> StateMut for Rls<T, W, P, R> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, W, P, R> HasGuts for Rls<T, W, P, R> {
+    type Guts = (Config<T>, State<W, P, R>);
+}
+
+impl<T, W, P, R> FromGuts for Rls<T, W, P, R> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self {
+            config,
+            state,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, W, P, R> IntoGuts for Rls<T, W, P, R> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize, const M: usize> Reset for RlsArray<T, N, M>
+where
+    T: Clone + Num + PartialOrd,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize, const M: usize> ResetMut for RlsArray<T, N, M> where Self: Reset {}
+
+impl<T, W, P, R> Filter<(T, T)> for Rls<T, W, P, R>
+where
+    T: Clone + Num,
+    W: AsSlice<T>,
+    P: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    type Output = Output<T>;
+
+    fn filter(&mut self, (input, desired): (T, T)) -> Self::Output {
+        self.state.taps.push_back(input);
+
+        let State {
+            weights,
+            gain,
+            inverse_correlation,
+            taps,
+        } = &mut self.state;
+        let weights = weights.as_mut_slice();
+        let gain = gain.as_mut_slice();
+        let matrix = inverse_correlation.as_mut_slice();
+        let n = weights.len();
+        let lambda = self.config.forgetting_factor.clone();
+
+        // x[j] = x[n−j]; taps count from the oldest element.
+        let tap = |j: usize| {
+            taps.len()
+                .checked_sub(j + 1)
+                .and_then(|index| taps.get(index))
+                .map_or_else(T::zero, Clone::clone)
+        };
+
+        // π = P·x, stored in the gain vector until normalized.
+        for (row, pi) in matrix.chunks_exact(n).zip(gain.iter_mut()) {
+            *pi = row
+                .iter()
+                .enumerate()
+                .fold(T::zero(), |sum, (j, p)| sum + p.clone() * tap(j));
+        }
+        let denominator = gain
+            .iter()
+            .enumerate()
+            .fold(lambda.clone(), |sum, (i, pi)| sum + tap(i) * pi.clone());
+        for k in gain.iter_mut() {
+            *k = k.clone() / denominator.clone();
+        }
+
+        let value = predict(weights, taps);
+        let error = desired - value.clone();
+        for (weight, k) in weights.iter_mut().zip(gain.iter()) {
+            *weight = weight.clone() + k.clone() * error.clone();
+        }
+
+        // P = (P − k·πᵀ) / λ with π = k·denominator.
+        for (row, k_i) in matrix.chunks_exact_mut(n).zip(gain.iter()) {
+            let scale = k_i.clone() * denominator.clone();
+            for (p, k_j) in row.iter_mut().zip(gain.iter()) {
+                *p = (p.clone() - scale.clone() * k_j.clone()) / lambda.clone();
+            }
+        }
+
+        Output { value, error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::super::lms::{Config as LmsConfig, LmsArray};
+    use super::super::test_util::{identification_pairs, PLANT};
+    use super::{Config, Filter, Reset, RlsArray, RlsRefMut, StateMut, WithConfig};
+
+    #[cfg(feature = "alloc")]
+    use super::RlsVec;
+
+    use circular_buffer::FixedCircularBuffer;
+
+    fn config() -> Config<f64> {
+        Config {
+            forgetting_factor: 1.0,
+            initial_covariance: 1e6,
+        }
+    }
+
+    #[test]
+    fn first_update_matches_closed_form() {
+        let mut filter = RlsArray::<f64, 1, 1>::with_config(Config {
+            forgetting_factor: 1.0,
+            initial_covariance: 1.0,
+        });
+        let output = filter.filter((2.0, 4.0));
+        assert_abs_diff_eq!(output.value, 0.0);
+        assert_abs_diff_eq!(output.error, 4.0);
+        // k = δ·x / (λ + δ·x²) = 0.4, w = k·e, P = (δ − k·δ·x) / λ
+        assert_abs_diff_eq!(filter.weights(), [1.6].as_slice(), epsilon = 1e-12);
+        let state = filter.state_mut();
+        assert_abs_diff_eq!(state.gain[0], 0.4, epsilon = 1e-12);
+        assert_abs_diff_eq!(state.inverse_correlation[0], 0.2, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn converges_within_a_few_multiples_of_n() {
+        let mut filter = RlsArray::<f64, 4, 16>::with_config(config());
+        for pair in identification_pairs(20) {
+            let _ = filter.filter(pair);
+        }
+        assert_abs_diff_eq!(filter.weights(), PLANT.as_slice(), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn converges_faster_than_lms() {
+        let mut rls = RlsArray::<f64, 4, 16>::with_config(config());
+        let mut lms = LmsArray::<f64, 4>::with_config(LmsConfig { step_size: 0.05 });
+        let squared_error = |weights: &[f64]| {
+            weights
+                .iter()
+                .zip(PLANT)
+                .map(|(w, p)| (w - p) * (w - p))
+                .sum::<f64>()
+        };
+        for pair in identification_pairs(50) {
+            let _ = rls.filter(pair);
+            let _ = lms.filter(pair);
+        }
+        assert!(squared_error(rls.weights()) < 1e-3 * squared_error(lms.weights()));
+    }
+
+    #[test]
+    fn forgetting_factor_tracks_changing_system() {
+        let mut filter = RlsArray::<f64, 4, 16>::with_config(Config {
+            forgetting_factor: 0.95,
+            initial_covariance: 1e3,
+        });
+        let pairs = identification_pairs(400);
+        for &pair in &pairs[..200] {
+            let _ = filter.filter(pair);
+        }
+        // The unknown system flips sign.
+        for &(input, desired) in &pairs[200..] {
+            let _ = filter.filter((input, -desired));
+        }
+        let flipped: Vec<f64> = PLANT.iter().map(|w| -w).collect();
+        assert_abs_diff_eq!(filter.weights(), flipped.as_slice(), epsilon = 1e-3);
+    }
+
+    #[test]
+    fn reset_restores_initial_covariance() {
+        let mut filter = RlsArray::<f64, 2, 4>::with_config(config());
+        for pair in identification_pairs(10) {
+            let _ = filter.filter(pair);
+        }
+        let mut filter = filter.reset();
+        assert_eq!(filter.weights(), [0.0; 2].as_slice());
+        assert_eq!(filter.state_mut().inverse_correlation, [1e6, 0.0, 0.0, 1e6]);
+    }
+
+    #[test]
+    fn ref_mut_matches_array() {
+        let mut array = RlsArray::<f64, 4, 16>::with_config(config());
+        let mut buffer = FixedCircularBuffer::<f64, 4>::from([0.0; 4]);
+        let mut ref_mut: RlsRefMut<'_, f64, [f64; 4], [f64; 16]> =
+            RlsRefMut::from_parts(config(), [0.0; 4], [0.0; 4], [0.0; 16], &mut buffer);
+        for pair in identification_pairs(100) {
+            assert_eq!(array.filter(pair), ref_mut.filter(pair));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let mut array = RlsArray::<f64, 4, 16>::with_config(config());
+        let mut vec = RlsVec::new(config(), 4);
+        for pair in identification_pairs(100) {
+            assert_eq!(array.filter(pair), vec.filter(pair));
+        }
+        assert_eq!(array.weights(), vec.weights());
+    }
+
+    #[test]
+    #[should_panic(expected = "Rls: inverse correlation must hold N·N = 4 elements")]
+    fn array_rejects_mismatched_matrix_size() {
+        let _ = RlsArray::<f64, 2, 3>::with_config(config());
+    }
+
+    #[test]
+    #[should_panic(expected = "Rls: forgetting factor must be in (0, 1]")]
+    fn rejects_forgetting_factor_above_one() {
+        let _ = RlsArray::<f64, 2, 4>::with_config(Config {
+            forgetting_factor: 1.5,
+            initial_covariance: 1.0,
+        });
+    }
+}
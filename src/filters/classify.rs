@@ -24,8 +24,13 @@
 //! | `schmitt::Schmitt`             | Hysteresis threshold (Schmitt trigger); two thresholds    |
 //! | `debounce::Debounce`           | Noise rejection: require a state to persist before change  |
 //! | `peaks::Peaks`                 | Local extrema detection (peaks and valleys)                |
+//! | `peak_finder::PeakFinder`      | Peaks by height, prominence, width and distance            |
 //! | `slopes::Slopes`               | Rising/falling edge detection with hysteresis              |
 //! | `zero_crossing::ZeroCrossing`  | Detect sign-change crossings through zero                  |
+//! | `change_point::*`              | Level shifts relative to the signal's own history          |
+//! | `segment::Segmenter`           | Events with onset, offset, peak and area from classes      |
+//! | `edge_timing::EdgeTiming`      | Period, frequency, pulse widths and duty cycle of edges    |
+//! | `quantizer::Quantizer`         | Multi-level hysteresis thresholds; `N` discrete levels     |
 //!
 //! - **Threshold** is the simplest classifier: compare against a single value. Use for
 //!   basic limit switches, level detection, and binary on/off states.
@@ -37,10 +42,26 @@
 //!   with transient glitches.
 //! - **Peaks** detects local maxima and minima within a sliding window. Useful for
 //!   feature extraction in sensor streams (heartbeat peaks, vibration maxima).
+//! - **`PeakFinder`** reports only the peaks that stand out by height, prominence, width
+//!   and distance, with the semantics of `SciPy`'s `find_peaks`, at the cost of a bounded
+//!   lookahead. Use it instead of `Peaks` on noisy signals; `peak_finder::find_peaks`
+//!   is its offline counterpart for slices.
 //! - **Slopes** classifies segments as rising, falling, or flat based on sustained
 //!   trends. Built on the Schmitt trigger for hysteresis in the derivative domain.
 //! - **`ZeroCrossing`** detects when a signal changes sign (positive to negative or vice
 //!   versa). Useful in AC signal analysis, phase detection, and frequency counting.
+//! - **`change_point`** detects shifts of a signal's mean rather than absolute levels,
+//!   with CUSUM, Page-Hinkley and Bayesian online change-point detectors. Use for
+//!   monitoring process signals for level shifts and drift.
+//! - **`Segmenter`** groups a classification stream, along with the original values,
+//!   into events with onset, offset, peak and area, merging events across short gaps and
+//!   dropping short ones. Use after `Schmitt` or `Debounce` for burst and activity detection.
+//! - **`EdgeTiming`** measures the time between threshold crossings, located to a fraction
+//!   of a sample and averaged over the most recent periods. Use for PWM, tachometer and
+//!   encoder signals.
+//! - **`Quantizer`** generalizes the Schmitt trigger to `N` levels, with hysteresis at each
+//!   boundary and an optional debounce time per transition. Use for battery and tank-level
+//!   indicators that must not flicker between adjacent levels.
 //!
 //! # See also
 //!
@@ -60,10 +81,20 @@ pub mod threshold;
 
 pub mod peaks;
 
+pub mod peak_finder;
+
 pub mod slopes;
 
 pub mod zero_crossing;
 
+pub mod change_point;
+
+pub mod segment;
+
+pub mod edge_timing;
+
+pub mod quantizer;
+
 /// A trait describing a classification value.
 pub trait Classification<T, const N: usize>: Sized {
     /// The available classes.
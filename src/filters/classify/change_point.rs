@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Streaming change-point detection; level shifts and drift.
+//!
+//! Unlike threshold and Schmitt classifiers, which react to absolute levels,
+//! the detectors in this module react to changes of a signal's mean relative
+//! to its own history. Each accumulates a test statistic and raises an alarm,
+//! classified as a [`Change`], once the statistic exceeds a threshold.
+//!
+//! Every detector outputs a `(class, statistic)` pair, where the class is one
+//! of the configured `[up, none, down]` outputs, as for
+//! [`Slopes`](super::slopes::Slopes). Use [`Change::classes()`] for the
+//! [`Change`] enum itself. A detector keeps alarming for as long as its
+//! statistic stays above the threshold, unless it is configured to reset on
+//! alarm, in which case it restarts from scratch after each alarm and reports
+//! each change exactly once.
+//!
+//! # When to use which detector
+//!
+//! | Detector                          | Purpose                                                 |
+//! | --------------------------------- | ------------------------------------------------------- |
+//! | `cusum::Cusum`                    | Shifts away from a known target mean (Page's CUSUM)     |
+//! | `page_hinkley::PageHinkley`       | Shifts away from the running mean; no target required   |
+//! | `bayesian::BayesianOnline`        | Probabilistic detection from the run-length posterior   |
+//!
+//! - **`Cusum`** accumulates deviations from a known in-control mean beyond a
+//!   slack `k`, and is the optimal detector of a shift of known size `2k`.
+//! - **`PageHinkley`** accumulates deviations from the mean of all samples seen
+//!   since the start (or the last reset), for signals without a known target.
+//! - **`BayesianOnline`** maintains a posterior over the time since the last
+//!   change, truncated to a fixed number of run lengths, and alarms when recent
+//!   change points become probable. It needs the `libm` or `std` feature.
+
+use num_traits::float::FloatCore;
+
+use super::Classification;
+
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod bayesian;
+
+pub mod cusum;
+
+pub mod page_hinkley;
+
+/// A change point's kind.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Change {
+    /// An upward shift of the mean.
+    Up,
+    /// No change.
+    #[default]
+    None,
+    /// A downward shift of the mean.
+    Down,
+}
+
+impl Classification<Change, 3> for Change {
+    fn classes() -> [Self; 3] {
+        [Self::Up, Self::None, Self::Down]
+    }
+}
+
+/// The direction(s) of change a detector watches for.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Direction {
+    /// Upward shifts only (one-sided).
+    Up,
+    /// Downward shifts only (one-sided).
+    Down,
+    /// Both upward and downward shifts (two-sided).
+    #[default]
+    Both,
+}
+
+impl Direction {
+    fn up(self) -> bool {
+        matches!(self, Self::Up | Self::Both)
+    }
+
+    fn down(self) -> bool {
+        matches!(self, Self::Down | Self::Both)
+    }
+}
+
+/// Returns the change and the statistic of a pair of one-sided `upper` and
+/// `lower` statistics, each compared against `threshold` if watched for.
+fn decide<T>(direction: Direction, upper: T, lower: T, threshold: T) -> (Change, T)
+where
+    T: FloatCore,
+{
+    let upper = if direction.up() { upper } else { T::zero() };
+    let lower = if direction.down() { lower } else { T::zero() };
+    let statistic = upper.max(lower);
+    let change = if statistic <= threshold {
+        Change::None
+    } else if upper >= lower {
+        Change::Up
+    } else {
+        Change::Down
+    };
+    (change, statistic)
+}
+
+/// Returns the configured `[up, none, down]` output for `change`.
+fn output<U: Clone>(outputs: &[U; 3], change: Change) -> U {
+    let index = match change {
+        Change::Up => 0,
+        Change::None => 1,
+        Change::Down => 2,
+    };
+    outputs[index].clone()
+}
+
+#[cfg(test)]
+mod test_util {
+    use alloc::vec::Vec;
+
+    use super::Change;
+    use crate::traits::Filter;
+    use crate::util::test_fixtures::hash_noise;
+
+    /// Returns the indices and kinds of all alarms of `detector` on `input`.
+    pub(super) fn alarms<D>(detector: &mut D, input: &[f64]) -> Vec<(usize, Change)>
+    where
+        D: Filter<f64, Output = (Change, f64)>,
+    {
+        input
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &input)| match detector.filter(input) {
+                (Change::None, _) => None,
+                (change, _) => Some((index, change)),
+            })
+            .collect()
+    }
+
+    /// Returns 200 noisy samples stepping from `from` to `to` at sample 100.
+    pub(super) fn step(from: f64, to: f64) -> Vec<f64> {
+        (0_u32..200)
+            .map(|n| hash_noise(n) + if n < 100 { from } else { to })
+            .collect()
+    }
+}
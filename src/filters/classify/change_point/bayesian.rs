@@ -0,0 +1,452 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Bayesian online change-point detection.
+
+use num_traits::Float;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::{output, Change};
+
+/// The Bayesian online change-point detector's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, U> {
+    /// The prior probability `H` of a change at each sample (`0 < H < 1`),
+    /// i.e. the inverse of the expected run length between changes.
+    pub hazard: T,
+    /// The prior mean `μ₀` of each segment.
+    pub prior_mean: T,
+    /// The prior variance `σ₀²` of each segment's mean (`> 0`); should cover
+    /// the range of levels the signal may shift to.
+    pub prior_variance: T,
+    /// The variance `σ²` of the signal around its segment mean (`> 0`).
+    pub noise_variance: T,
+    /// The number of samples `L` (`≥ 1`, `L + 1 < N`) after which a change
+    /// is no longer considered recent.
+    pub lag: usize,
+    /// The probability (`0 < p < 1`) of a recent change above which the
+    /// detector alarms.
+    pub threshold: T,
+    /// Whether to restart from the prior after an alarm.
+    pub reset_on_alarm: bool,
+    /// [up, none, down] outputs.
+    pub outputs: [U; 3],
+}
+
+/// The Bayesian online change-point detector's state.
+#[derive(Clone, Debug)]
+pub struct State<T, const N: usize> {
+    /// The posterior probabilities of the run lengths `0..N`, the last one
+    /// collecting all run lengths `≥ N − 1`.
+    pub probabilities: [T; N],
+    /// The posterior segment means for each run length.
+    pub means: [T; N],
+    /// The latest segment mean of the run lengths `> L`; `None` until such
+    /// a run length has been observed.
+    pub baseline: Option<T>,
+}
+
+/// A Bayesian online change-point detector with bounded memory.
+///
+/// "Bayesian Online Changepoint Detection", 2007, arXiv:0710.3742.
+///
+/// Maintains the posterior distribution of the run length `r`, the number of
+/// samples since the last change, for a piecewise-constant mean with Gaussian
+/// noise of known variance and a Gaussian prior on each segment's mean. Each
+/// sample either extends every run (weighted by its predictive likelihood
+/// and `1 − H`) or starts a new one (weighted by `H`).
+///
+/// The run lengths are truncated to `N`: the last one collects all longer
+/// runs, whose segment mean then forgets old samples like an exponential
+/// average over about `N` samples.
+///
+/// The output statistic is the posterior probability `P(r ≤ L)` of a change
+/// within the last `L` samples. The detector alarms while it exceeds the
+/// threshold, classifying the change as [`Change::Up`] or [`Change::Down`] by
+/// comparing the segment means of recent and older run lengths. No change is
+/// reported within the first `L` samples after the start or a reset, when no
+/// older run exists yet. The older runs' mean is retained when a gross outlier
+/// makes their probabilities vanish. NaN inputs are ignored.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); one predictive likelihood and update per run length.
+/// - **Space:** O(N); stores the run-length probabilities and segment means.
+#[derive(Clone, Debug)]
+pub struct BayesianOnline<T, U, const N: usize> {
+    config: Config<T, U>,
+    state: State<T, N>,
+}
+
+impl<T, U, const N: usize> BayesianOnline<T, U, N>
+where
+    T: Float,
+{
+    /// Returns the posterior probabilities of the run lengths `0..N`.
+    pub fn probabilities(&self) -> &[T; N] {
+        &self.state.probabilities
+    }
+
+    /// Returns the most probable run length.
+    pub fn run_length(&self) -> usize {
+        let probabilities = &self.state.probabilities;
+        (0..N).fold(0, |best, index| {
+            if probabilities[index] > probabilities[best] {
+                index
+            } else {
+                best
+            }
+        })
+    }
+
+    fn initial_state(config: &Config<T, U>) -> State<T, N> {
+        let mut probabilities = [T::zero(); N];
+        probabilities[0] = T::one();
+        State {
+            probabilities,
+            means: [config.prior_mean; N],
+            baseline: None,
+        }
+    }
+
+    /// Updates the run-length posterior with `input`.
+    fn update(&mut self, input: T) {
+        let Config {
+            hazard,
+            prior_variance,
+            noise_variance,
+            ..
+        } = self.config;
+        let half = T::one() / (T::one() + T::one());
+        let State {
+            probabilities,
+            means,
+            ..
+        } = &mut self.state;
+
+        // Posterior precisions of the segment mean, and predictive log-likelihoods:
+        let precision = |run: usize| {
+            prior_variance.recip() + T::from(run).unwrap_or_else(T::nan) / noise_variance
+        };
+        let log_likelihood = |run: usize| {
+            let variance = precision(run).recip() + noise_variance;
+            let error = input - means[run];
+            -half * (error * error / variance + variance.ln())
+        };
+        let max = (0..N)
+            .filter(|&run| probabilities[run] > T::zero())
+            .map(log_likelihood)
+            .fold(T::neg_infinity(), T::max);
+
+        let mut weights = [T::zero(); N];
+        let mut updated = [T::zero(); N];
+        for run in 0..N {
+            if probabilities[run] > T::zero() {
+                weights[run] = probabilities[run] * (log_likelihood(run) - max).exp();
+            }
+            let precision = precision(run);
+            updated[run] = (precision * means[run] + input / noise_variance)
+                / (precision + noise_variance.recip());
+        }
+
+        // Grow every run by one sample, merging the longest two:
+        let last = N - 1;
+        let mut total = T::zero();
+        for run in (1..N).rev() {
+            let (growth, mean) = if run == last {
+                let growth = weights[last - 1] + weights[last];
+                let mean = if growth > T::zero() {
+                    (weights[last - 1] * updated[last - 1] + weights[last] * updated[last]) / growth
+                } else {
+                    updated[last - 1]
+                };
+                (growth, mean)
+            } else {
+                (weights[run - 1], updated[run - 1])
+            };
+            probabilities[run] = growth * (T::one() - hazard);
+            means[run] = mean;
+            total = total + probabilities[run];
+        }
+
+        // ... or start a new one:
+        let evidence = weights.iter().fold(T::zero(), |sum, &weight| sum + weight);
+        probabilities[0] = evidence * hazard;
+        means[0] = self.config.prior_mean;
+        total = total + probabilities[0];
+
+        for probability in probabilities.iter_mut() {
+            *probability = *probability / total;
+        }
+    }
+
+    /// Returns the change and the probability of a recent change.
+    fn evaluate(&mut self) -> (Change, T) {
+        let lag = self.config.lag;
+        let State {
+            probabilities,
+            means,
+            baseline,
+        } = &mut self.state;
+        let moments = |runs: core::ops::Range<usize>| {
+            runs.fold((T::zero(), T::zero()), |(mass, sum), run| {
+                (
+                    mass + probabilities[run],
+                    sum + probabilities[run] * means[run],
+                )
+            })
+        };
+        let (recent_mass, recent_sum) = moments(1..lag + 1);
+        let (older_mass, older_sum) = moments(lag + 1..N);
+        let statistic = probabilities[0] + recent_mass;
+        if older_mass > T::zero() {
+            *baseline = Some(older_sum / older_mass);
+        }
+        let Some(baseline) = *baseline else {
+            return (Change::None, statistic);
+        };
+        if statistic <= self.config.threshold {
+            return (Change::None, statistic);
+        }
+        let recent = if recent_mass > T::zero() {
+            recent_sum / recent_mass
+        } else {
+            means[0]
+        };
+        let change = if recent >= baseline {
+            Change::Up
+        } else {
+            Change::Down
+        };
+        (change, statistic)
+    }
+}
+
+impl<T, U, const N: usize> ConfigTrait for BayesianOnline<T, U, N> {
+    type Config = Config<T, U>;
+}
+
+impl<T, U, const N: usize> StateTrait for BayesianOnline<T, U, N> {
+    type State = State<T, N>;
+}
+
+impl<T, U, const N: usize> WithConfig for BayesianOnline<T, U, N>
+where
+    T: Float,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `hazard` or `threshold` is not within `(0, 1)`, a variance
+    /// is not positive, or `lag` is not within `1..N − 1`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        let unit = |value: T| value > T::zero() && value < T::one();
+        assert!(
+            unit(config.hazard),
+            "BayesianOnline: hazard must be within (0, 1)"
+        );
+        assert!(
+            unit(config.threshold),
+            "BayesianOnline: threshold must be within (0, 1)"
+        );
+        assert!(
+            config.prior_variance > T::zero() && config.noise_variance > T::zero(),
+            "BayesianOnline: variances must be > 0"
+        );
+        assert!(
+            config.lag >= 1 && config.lag + 1 < N,
+            "BayesianOnline: lag must be within 1..N - 1"
+        );
+        let state = Self::initial_state(&config);
+        Self { config, state }
+    }
+}
+
+impl<T, U, const N: usize> ConfigRef for BayesianOnline<T, U, N> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, U, const N: usize> ConfigClone for BayesianOnline<T, U, N>
+where
+    Config<T, U>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, U, const N: usize> StateMut for BayesianOnline<T, U, N> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, U, const N: usize> HasGuts for BayesianOnline<T, U, N> {
+    type Guts = (Config<T, U>, State<T, N>);
+}
+
+impl<T, U, const N: usize> FromGuts for BayesianOnline<T, U, N> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, U, const N: usize> IntoGuts for BayesianOnline<T, U, N> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, U, const N: usize> Reset for BayesianOnline<T, U, N>
+where
+    T: Float,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, U, const N: usize> ResetMut for BayesianOnline<T, U, N> where Self: Reset {}
+
+impl<T, U, const N: usize> Filter<T> for BayesianOnline<T, U, N>
+where
+    T: Float,
+    U: Clone,
+{
+    type Output = (U, T);
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        if !input.is_nan() {
+            self.update(input);
+        }
+        let (change, statistic) = self.evaluate();
+        if change != Change::None && self.config.reset_on_alarm {
+            self.state = Self::initial_state(&self.config);
+        }
+        (output(&self.config.outputs, change), statistic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::super::test_util::{alarms, step};
+    use super::*;
+    use crate::filters::classify::Classification;
+    use crate::util::test_fixtures::hash_noise;
+
+    type Detector = BayesianOnline<f64, Change, 64>;
+
+    fn detector(reset_on_alarm: bool) -> Detector {
+        BayesianOnline::with_config(Config {
+            hazard: 0.01,
+            prior_mean: 0.0,
+            prior_variance: 100.0,
+            noise_variance: 0.1,
+            lag: 5,
+            threshold: 0.5,
+            reset_on_alarm,
+            outputs: Change::classes(),
+        })
+    }
+
+    #[test]
+    fn posterior_is_normalized() {
+        let mut detector = detector(false);
+        for (index, input) in step(0.0, 3.0).into_iter().enumerate() {
+            detector.filter(input);
+            let total: f64 = detector.probabilities().iter().sum();
+            assert_abs_diff_eq!(total, 1.0, epsilon = 1e-9);
+            // The run length grows until the change, and is truncated to `N`.
+            if (10..100).contains(&index) {
+                assert_eq!(detector.run_length(), (index + 1).min(63));
+            }
+        }
+        assert!(detector.run_length() < 100, "{}", detector.run_length());
+    }
+
+    #[test]
+    fn detects_upward_shift() {
+        let mut detector = detector(true);
+        let alarms = alarms(&mut detector, &step(1.0, 3.0));
+        assert_eq!(alarms.len(), 1, "{alarms:?}");
+        let (index, change) = alarms[0];
+        assert_eq!(change, Change::Up);
+        assert!((100..105).contains(&index), "alarm at {index}");
+    }
+
+    #[test]
+    fn detects_downward_shift() {
+        let mut detector = detector(true);
+        let alarms = alarms(&mut detector, &step(1.0, -1.0));
+        assert_eq!(alarms.len(), 1, "{alarms:?}");
+        let (index, change) = alarms[0];
+        assert_eq!(change, Change::Down);
+        assert!((100..105).contains(&index), "alarm at {index}");
+    }
+
+    #[test]
+    fn alarms_while_change_is_recent_without_reset() {
+        let mut detector = detector(false);
+        let alarms = alarms(&mut detector, &step(1.0, 3.0));
+        let first = alarms[0].0;
+        assert!(alarms.len() > 1 && alarms.len() <= 6, "{alarms:?}");
+        assert!(alarms.iter().all(|&(index, change)| {
+            change == Change::Up && (first..first + 6).contains(&index)
+        }));
+    }
+
+    #[test]
+    fn survives_extreme_outliers() {
+        let mut detector = detector(false);
+        for n in 0_u32..50 {
+            detector.filter(hash_noise(n));
+        }
+        let (change, statistic) = detector.filter(1e6);
+        assert!(statistic.is_finite());
+        assert_eq!(change, Change::Up);
+    }
+
+    #[test]
+    fn ignores_nan() {
+        let mut detector = detector(false);
+        detector.filter(0.0);
+        let probabilities = *detector.probabilities();
+        detector.filter(f64::NAN);
+        assert_eq!(*detector.probabilities(), probabilities);
+    }
+
+    #[test]
+    #[should_panic(expected = "lag must be within 1..N - 1")]
+    fn lag_too_long_panics() {
+        let _ = BayesianOnline::<f64, Change, 4>::with_config(Config {
+            lag: 3,
+            ..detector(false).config()
+        });
+    }
+
+    #[test]
+    fn reset() {
+        let mut detector = detector(false);
+        detector.filter(1.0);
+        detector.filter(1.0);
+        let detector = detector.reset();
+        assert_eq!(detector.run_length(), 0);
+        assert_eq!(detector.probabilities()[0], 1.0);
+    }
+}
@@ -0,0 +1,272 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Cumulative sum (CUSUM) change-point detection.
+
+use num_traits::float::FloatCore;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::{decide, output, Change, Direction};
+
+/// The CUSUM detector's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, U> {
+    /// The in-control mean `μ₀` of the signal.
+    pub target: T,
+    /// The slack `k` (`≥ 0`), typically half the shift to detect; deviations
+    /// from the target within `±k` drain the statistics.
+    pub slack: T,
+    /// The decision threshold `h` (`> 0`), typically 4 to 5 standard
+    /// deviations of the signal.
+    pub threshold: T,
+    /// The direction(s) of change to detect.
+    pub direction: Direction,
+    /// Whether to restart the statistics after an alarm.
+    pub reset_on_alarm: bool,
+    /// [up, none, down] outputs.
+    pub outputs: [U; 3],
+}
+
+/// The CUSUM detector's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The upper cumulative sum `S⁺`.
+    pub upper: T,
+    /// The lower cumulative sum `S⁻`.
+    pub lower: T,
+}
+
+/// A tabular CUSUM change-point detector (Page, 1954).
+///
+/// Accumulates the deviations of each sample from the target mean beyond the
+/// slack, separately for both directions:
+///
+/// ```text
+/// S⁺ = max(0, S⁺ + x − μ₀ − k)
+/// S⁻ = max(0, S⁻ + μ₀ − k − x)
+/// ```
+///
+/// and alarms while `S⁺` (for [`Change::Up`]) or `S⁻` (for [`Change::Down`])
+/// exceeds the threshold `h`. The output statistic is the larger of the
+/// watched sums. NaN inputs are ignored.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); two additions and comparisons per direction.
+/// - **Space:** O(1); stores the two cumulative sums.
+#[derive(Clone, Debug)]
+pub struct Cusum<T, U> {
+    config: Config<T, U>,
+    state: State<T>,
+}
+
+impl<T, U> Cusum<T, U>
+where
+    T: FloatCore,
+{
+    /// Returns the upper cumulative sum `S⁺`.
+    pub fn upper(&self) -> T {
+        self.state.upper
+    }
+
+    /// Returns the lower cumulative sum `S⁻`.
+    pub fn lower(&self) -> T {
+        self.state.lower
+    }
+}
+
+impl<T, U> ConfigTrait for Cusum<T, U> {
+    type Config = Config<T, U>;
+}
+
+impl<T, U> StateTrait for Cusum<T, U> {
+    type State = State<T>;
+}
+
+impl<T, U> WithConfig for Cusum<T, U>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `slack` is negative or `threshold` is not positive.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(config.slack >= T::zero(), "Cusum: slack must be >= 0");
+        assert!(config.threshold > T::zero(), "Cusum: threshold must be > 0");
+        let state = State {
+            upper: T::zero(),
+            lower: T::zero(),
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, U> ConfigRef for Cusum<T, U> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, U> ConfigClone for Cusum<T, U>
+where
+    Config<T, U>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, U> StateMut for Cusum<T, U> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, U> HasGuts for Cusum<T, U> {
+    type Guts = (Config<T, U>, State<T>);
+}
+
+impl<T, U> FromGuts for Cusum<T, U> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, U> IntoGuts for Cusum<T, U> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, U> Reset for Cusum<T, U>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, U> ResetMut for Cusum<T, U> where Self: Reset {}
+
+impl<T, U> Filter<T> for Cusum<T, U>
+where
+    T: FloatCore,
+    U: Clone,
+{
+    type Output = (U, T);
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let Config {
+            target,
+            slack,
+            threshold,
+            direction,
+            ..
+        } = self.config;
+        if !input.is_nan() {
+            let deviation = input - target;
+            self.state.upper = (self.state.upper + deviation - slack).max(T::zero());
+            self.state.lower = (self.state.lower - deviation - slack).max(T::zero());
+        }
+        let (change, statistic) = decide(direction, self.state.upper, self.state.lower, threshold);
+        if change != Change::None && self.config.reset_on_alarm {
+            self.state.upper = T::zero();
+            self.state.lower = T::zero();
+        }
+        (output(&self.config.outputs, change), statistic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::{alarms, step};
+    use super::*;
+    use crate::filters::classify::Classification;
+
+    fn detector(direction: Direction, reset_on_alarm: bool) -> Cusum<f64, Change> {
+        Cusum::with_config(Config {
+            target: 0.0,
+            slack: 0.5,
+            threshold: 4.0,
+            direction,
+            reset_on_alarm,
+            outputs: Change::classes(),
+        })
+    }
+
+    #[test]
+    fn computes_tabular_sums() {
+        let mut detector = detector(Direction::Both, false);
+        assert_eq!(detector.filter(1.5), (Change::None, 1.0));
+        assert_eq!(detector.filter(-0.5), (Change::None, 0.0));
+        assert_eq!((detector.upper(), detector.lower()), (0.0, 0.0));
+        assert_eq!(detector.filter(-2.5), (Change::None, 2.0));
+        assert_eq!(detector.filter(-3.0), (Change::Down, 4.5));
+    }
+
+    #[test]
+    fn detects_upward_shift() {
+        let mut detector = detector(Direction::Both, true);
+        let alarms = alarms(&mut detector, &step(0.0, 2.0));
+        let (index, change) = alarms[0];
+        assert_eq!(change, Change::Up);
+        assert!((100..110).contains(&index), "alarm at {index}");
+        assert!(alarms
+            .iter()
+            .all(|&(index, change)| index >= 100 && change == Change::Up));
+    }
+
+    #[test]
+    fn detects_downward_shift() {
+        let mut detector = detector(Direction::Both, true);
+        let alarms = alarms(&mut detector, &step(0.0, -2.0));
+        let (index, change) = alarms[0];
+        assert_eq!(change, Change::Down);
+        assert!((100..110).contains(&index), "alarm at {index}");
+    }
+
+    #[test]
+    fn one_sided_ignores_other_direction() {
+        let mut detector = detector(Direction::Up, false);
+        assert!(alarms(&mut detector, &step(0.0, -2.0)).is_empty());
+        // The ignored sum is still tracked, but not reported.
+        assert!(detector.lower() > 4.0);
+    }
+
+    #[test]
+    fn keeps_alarming_without_reset() {
+        let mut detector = detector(Direction::Both, false);
+        let alarms = alarms(&mut detector, &step(0.0, 2.0));
+        let first = alarms[0].0;
+        assert_eq!(alarms.len(), 200 - first);
+    }
+
+    #[test]
+    fn ignores_nan() {
+        let mut detector = detector(Direction::Both, false);
+        detector.filter(2.0);
+        assert_eq!(detector.filter(f64::NAN), (Change::None, 1.5));
+    }
+
+    #[test]
+    fn reset() {
+        let mut detector = detector(Direction::Both, false);
+        detector.filter(3.0);
+        let detector = detector.reset();
+        assert_eq!(detector.upper(), 0.0);
+    }
+}
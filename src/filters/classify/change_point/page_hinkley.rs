@@ -0,0 +1,297 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Page-Hinkley change-point detection.
+
+use num_traits::float::FloatCore;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::{decide, output, Change, Direction};
+
+/// The Page-Hinkley detector's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, U> {
+    /// The tolerated magnitude `δ` (`≥ 0`) of deviations from the running
+    /// mean; smaller deviations drain the statistics.
+    pub delta: T,
+    /// The decision threshold `λ` (`> 0`).
+    pub threshold: T,
+    /// The direction(s) of change to detect.
+    pub direction: Direction,
+    /// Whether to restart the running mean and statistics after an alarm.
+    pub reset_on_alarm: bool,
+    /// [up, none, down] outputs.
+    pub outputs: [U; 3],
+}
+
+/// The Page-Hinkley detector's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The number of samples since the start or the last reset.
+    pub count: T,
+    /// The running mean `x̄` of these samples.
+    pub mean: T,
+    /// The cumulative deviation `m⁺` for upward shifts.
+    pub upper: T,
+    /// The minimum of `m⁺` so far.
+    pub upper_min: T,
+    /// The cumulative deviation `m⁻` for downward shifts.
+    pub lower: T,
+    /// The maximum of `m⁻` so far.
+    pub lower_max: T,
+}
+
+/// A Page-Hinkley change-point detector (Page, 1954; Hinkley, 1971).
+///
+/// Accumulates the deviations of each sample from the running mean `x̄` of
+/// all samples since the start (or the last reset), beyond the tolerance `δ`:
+///
+/// ```text
+/// m⁺ = m⁺ + x − x̄ − δ,    PH⁺ = m⁺ − min(m⁺)
+/// m⁻ = m⁻ + x − x̄ + δ,    PH⁻ = max(m⁻) − m⁻
+/// ```
+///
+/// and alarms while `PH⁺` (for [`Change::Up`]) or `PH⁻` (for
+/// [`Change::Down`]) exceeds the threshold `λ`. The output statistic is the
+/// larger of the watched statistics. NaN inputs are ignored.
+///
+/// Since the running mean absorbs a shift over time, the statistics of a
+/// detector that does not reset on alarm eventually decay after a change.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); a running-mean update and two cumulative sums.
+/// - **Space:** O(1); stores the running mean and the cumulative sums with their extremes.
+#[derive(Clone, Debug)]
+pub struct PageHinkley<T, U> {
+    config: Config<T, U>,
+    state: State<T>,
+}
+
+impl<T, U> PageHinkley<T, U>
+where
+    T: FloatCore,
+{
+    /// Returns the running mean of the samples since the start or the last reset.
+    pub fn mean(&self) -> T {
+        self.state.mean
+    }
+
+    fn initial_state() -> State<T> {
+        State {
+            count: T::zero(),
+            mean: T::zero(),
+            upper: T::zero(),
+            upper_min: T::zero(),
+            lower: T::zero(),
+            lower_max: T::zero(),
+        }
+    }
+}
+
+impl<T, U> ConfigTrait for PageHinkley<T, U> {
+    type Config = Config<T, U>;
+}
+
+impl<T, U> StateTrait for PageHinkley<T, U> {
+    type State = State<T>;
+}
+
+impl<T, U> WithConfig for PageHinkley<T, U>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `delta` is negative or `threshold` is not positive.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(config.delta >= T::zero(), "PageHinkley: delta must be >= 0");
+        assert!(
+            config.threshold > T::zero(),
+            "PageHinkley: threshold must be > 0"
+        );
+        let state = Self::initial_state();
+        Self { config, state }
+    }
+}
+
+impl<T, U> ConfigRef for PageHinkley<T, U> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, U> ConfigClone for PageHinkley<T, U>
+where
+    Config<T, U>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, U> StateMut for PageHinkley<T, U> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, U> HasGuts for PageHinkley<T, U> {
+    type Guts = (Config<T, U>, State<T>);
+}
+
+impl<T, U> FromGuts for PageHinkley<T, U> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, U> IntoGuts for PageHinkley<T, U> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, U> Reset for PageHinkley<T, U>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, U> ResetMut for PageHinkley<T, U> where Self: Reset {}
+
+impl<T, U> Filter<T> for PageHinkley<T, U>
+where
+    T: FloatCore,
+    U: Clone,
+{
+    type Output = (U, T);
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let delta = self.config.delta;
+        let state = &mut self.state;
+        if !input.is_nan() {
+            state.count = state.count + T::one();
+            state.mean = state.mean + (input - state.mean) / state.count;
+            let deviation = input - state.mean;
+            state.upper = state.upper + deviation - delta;
+            state.upper_min = state.upper_min.min(state.upper);
+            state.lower = state.lower + deviation + delta;
+            state.lower_max = state.lower_max.max(state.lower);
+        }
+        let (change, statistic) = decide(
+            self.config.direction,
+            state.upper - state.upper_min,
+            state.lower_max - state.lower,
+            self.config.threshold,
+        );
+        if change != Change::None && self.config.reset_on_alarm {
+            self.state = Self::initial_state();
+        }
+        (output(&self.config.outputs, change), statistic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::super::test_util::{self, alarms};
+    use super::*;
+    use crate::filters::classify::Classification;
+    use crate::util::test_fixtures::hash_noise;
+
+    fn detector(direction: Direction, reset_on_alarm: bool) -> PageHinkley<f64, Change> {
+        PageHinkley::with_config(Config {
+            delta: 0.25,
+            threshold: 5.0,
+            direction,
+            reset_on_alarm,
+            outputs: Change::classes(),
+        })
+    }
+
+    /// Returns a step from `from` to `to` at sample 100, around an
+    /// arbitrary level unknown to the detector.
+    fn step(from: f64, to: f64) -> Vec<f64> {
+        test_util::step(40.0 + from, 40.0 + to)
+    }
+
+    #[test]
+    fn tracks_running_mean() {
+        let mut detector = detector(Direction::Both, false);
+        for input in [1.0, 2.0, 3.0, 6.0] {
+            detector.filter(input);
+        }
+        assert_abs_diff_eq!(detector.mean(), 3.0);
+    }
+
+    #[test]
+    fn detects_upward_shift() {
+        let mut detector = detector(Direction::Both, true);
+        let alarms = alarms(&mut detector, &step(0.0, 2.0));
+        let (index, change) = alarms[0];
+        assert_eq!(change, Change::Up);
+        assert!((100..110).contains(&index), "alarm at {index}");
+    }
+
+    #[test]
+    fn detects_downward_shift() {
+        let mut detector = detector(Direction::Both, true);
+        let alarms = alarms(&mut detector, &step(0.0, -2.0));
+        let (index, change) = alarms[0];
+        assert_eq!(change, Change::Down);
+        assert!((100..110).contains(&index), "alarm at {index}");
+        // After the reset, the detector settles on the new level.
+        assert!(alarms.iter().all(|&(index, _)| index < 120), "{alarms:?}");
+    }
+
+    #[test]
+    fn one_sided_ignores_other_direction() {
+        let mut detector = detector(Direction::Down, false);
+        assert!(alarms(&mut detector, &step(0.0, 2.0)).is_empty());
+    }
+
+    #[test]
+    fn stationary_signal_does_not_alarm() {
+        let mut detector = detector(Direction::Both, false);
+        let input: Vec<_> = (0_u32..1000).map(hash_noise).collect();
+        assert!(alarms(&mut detector, &input).is_empty());
+    }
+
+    #[test]
+    fn ignores_nan() {
+        let mut detector = detector(Direction::Both, false);
+        detector.filter(2.0);
+        let (change, statistic) = detector.filter(f64::NAN);
+        assert_eq!(change, Change::None);
+        assert_eq!(statistic, 0.0);
+        assert_eq!(detector.mean(), 2.0);
+    }
+
+    #[test]
+    fn reset() {
+        let mut detector = detector(Direction::Both, false);
+        detector.filter(3.0);
+        let detector = detector.reset();
+        assert_eq!(detector.mean(), 0.0);
+    }
+}
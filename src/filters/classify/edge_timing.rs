@@ -0,0 +1,444 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Edge timing; period, frequency, pulse width and duty cycle measurement.
+//!
+//! Measures the time between the edges of PWM, tachometer and encoder
+//! signals, with sub-sample edge interpolation and averaging over the most
+//! recent periods.
+
+use num_traits::float::FloatCore;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The edge timing filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The level at which edges are located.
+    pub threshold: T,
+    /// The hysteresis `≥ 0` around the threshold; an edge is only confirmed
+    /// once the signal has moved beyond `threshold ± hysteresis`.
+    pub hysteresis: T,
+    /// The sample rate `> 0`. Use `1` to measure times in samples and
+    /// frequencies in cycles per sample.
+    pub sample_rate: T,
+}
+
+/// A point in time between two samples.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EdgeTime<T> {
+    /// The index of the sample following the edge.
+    pub index: usize,
+    /// The edge's offset `≥ 0` before that sample, in samples.
+    pub offset: T,
+}
+
+impl<T> EdgeTime<T>
+where
+    T: FloatCore,
+{
+    /// Returns the number of samples elapsed since `earlier`.
+    fn since(self, earlier: Self) -> T {
+        let samples = T::from(self.index - earlier.index).unwrap_or_else(T::nan);
+        samples - self.offset + earlier.offset
+    }
+}
+
+/// The timing of a signal, averaged over the most recent periods.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Timing<T> {
+    /// The time from one rising edge to the next.
+    pub period: T,
+    /// The number of periods per unit time.
+    pub frequency: T,
+    /// The time from a rising edge to the following falling edge.
+    pub high: T,
+    /// The time from a falling edge to the following rising edge.
+    pub low: T,
+    /// The fraction `∈ [0, 1]` of each period spent high.
+    pub duty_cycle: T,
+}
+
+/// The edge timing filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T, const N: usize> {
+    /// The index of the next sample.
+    pub index: usize,
+    /// The index and value of the previous non-NaN sample.
+    pub previous: Option<(usize, T)>,
+    /// The confirmed level (`true` for high), once the signal has left the
+    /// hysteresis band.
+    pub level: Option<bool>,
+    /// The most recent threshold crossing away from the confirmed level.
+    pub crossing: Option<EdgeTime<T>>,
+    /// The most recent rising edge.
+    pub rise: Option<EdgeTime<T>>,
+    /// The falling edge following the most recent rising edge.
+    pub fall: Option<EdgeTime<T>>,
+    /// The most recent periods, in samples, in a ring buffer.
+    pub periods: [T; N],
+    /// The high pulse widths of these periods, in samples.
+    pub highs: [T; N],
+    /// The number of measured periods (up to `N`).
+    pub len: usize,
+    /// The ring buffer's next write position.
+    pub next: usize,
+}
+
+/// An edge timing filter, measuring the period, frequency, pulse widths and
+/// duty cycle of a signal, averaged over the `N` most recent periods.
+///
+/// Edges are located where the signal crosses the threshold, interpolated
+/// linearly between samples, and confirmed once the signal moves beyond the
+/// hysteresis band around the threshold. Periods run from one rising edge to
+/// the next. NaN inputs are skipped, but still count as samples.
+///
+/// Outputs the updated [`Timing`] on each rising edge that completes a
+/// period, and `None` otherwise.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); O(N) on rising edges, to average the periods.
+/// - **Space:** O(N); stores the `N` most recent periods and pulse widths.
+#[derive(Clone, Debug)]
+pub struct EdgeTiming<T, const N: usize> {
+    config: Config<T>,
+    state: State<T, N>,
+}
+
+impl<T, const N: usize> EdgeTiming<T, N>
+where
+    T: FloatCore,
+{
+    /// Returns the timing averaged over the most recent periods, if any.
+    pub fn timing(&self) -> Option<Timing<T>> {
+        let len = self.state.len;
+        if len == 0 {
+            return None;
+        }
+        let count = T::from(len).unwrap_or_else(T::nan);
+        let sum = |values: &[T; N]| {
+            values[..len]
+                .iter()
+                .fold(T::zero(), |sum, &value| sum + value)
+        };
+        let sample_rate = self.config.sample_rate;
+        let period = sum(&self.state.periods) / count / sample_rate;
+        let high = sum(&self.state.highs) / count / sample_rate;
+        Some(Timing {
+            period,
+            frequency: period.recip(),
+            high,
+            low: period - high,
+            duty_cycle: high / period,
+        })
+    }
+
+    /// Records a period, in samples, and its high pulse width.
+    fn push(&mut self, period: T, high: T) {
+        let state = &mut self.state;
+        state.periods[state.next] = period;
+        state.highs[state.next] = high;
+        state.next = (state.next + 1) % N;
+        state.len = (state.len + 1).min(N);
+    }
+
+    /// Handles a confirmed rising edge, returning the updated timing if it
+    /// completes a period.
+    fn rising_edge(&mut self, time: EdgeTime<T>) -> Option<Timing<T>> {
+        let rise = self.state.rise.replace(time);
+        let fall = self.state.fall.take();
+        let (rise, fall) = rise.zip(fall)?;
+        self.push(time.since(rise), fall.since(rise));
+        self.timing()
+    }
+}
+
+impl<T, const N: usize> ConfigTrait for EdgeTiming<T, N> {
+    type Config = Config<T>;
+}
+
+impl<T, const N: usize> StateTrait for EdgeTiming<T, N> {
+    type State = State<T, N>;
+}
+
+impl<T, const N: usize> WithConfig for EdgeTiming<T, N>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `N` is zero, `hysteresis` is negative or `sample_rate` is
+    /// not positive.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(N > 0, "EdgeTiming: N must be > 0");
+        assert!(
+            config.hysteresis >= T::zero(),
+            "EdgeTiming: hysteresis must be >= 0"
+        );
+        assert!(
+            config.sample_rate > T::zero(),
+            "EdgeTiming: sample_rate must be > 0"
+        );
+        let state = State {
+            index: 0,
+            previous: None,
+            level: None,
+            crossing: None,
+            rise: None,
+            fall: None,
+            periods: [T::zero(); N],
+            highs: [T::zero(); N],
+            len: 0,
+            next: 0,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, const N: usize> ConfigRef for EdgeTiming<T, N> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, const N: usize> ConfigClone for EdgeTiming<T, N>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, const N: usize> StateMut for EdgeTiming<T, N> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, const N: usize> HasGuts for EdgeTiming<T, N> {
+    type Guts = (Config<T>, State<T, N>);
+}
+
+impl<T, const N: usize> FromGuts for EdgeTiming<T, N> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, const N: usize> IntoGuts for EdgeTiming<T, N> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize> Reset for EdgeTiming<T, N>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for EdgeTiming<T, N> where Self: Reset {}
+
+impl<T, const N: usize> Filter<T> for EdgeTiming<T, N>
+where
+    T: FloatCore,
+{
+    type Output = Option<Timing<T>>;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let index = self.state.index;
+        self.state.index += 1;
+        if input.is_nan() {
+            return None;
+        }
+        let Config {
+            threshold,
+            hysteresis,
+            ..
+        } = self.config;
+
+        if let Some((previous_index, previous)) = self.state.previous.replace((index, input)) {
+            let rising = previous < threshold && input >= threshold;
+            let falling = previous >= threshold && input < threshold;
+            if rising || falling {
+                let gap = T::from(index - previous_index).unwrap_or_else(T::nan);
+                let offset = gap * (input - threshold) / (input - previous);
+                self.state.crossing = Some(EdgeTime { index, offset });
+            }
+        }
+
+        let confirmed = if input >= threshold + hysteresis {
+            Some(true)
+        } else if input < threshold - hysteresis {
+            Some(false)
+        } else {
+            None
+        };
+        let level = self.state.level;
+        let high = confirmed.filter(|&high| level != Some(high))?;
+        self.state.level = Some(high);
+        let now = EdgeTime {
+            index,
+            offset: T::zero(),
+        };
+        let crossing = self.state.crossing.take().unwrap_or(now);
+        match (high, level) {
+            // The initial level is not an edge.
+            (true, None) => {}
+            (true, Some(_)) => return self.rising_edge(crossing),
+            (false, _) => {
+                if self.state.rise.is_some() {
+                    self.state.fall = Some(crossing);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    fn filter<const N: usize>(hysteresis: f64, sample_rate: f64) -> EdgeTiming<f64, N> {
+        EdgeTiming::with_config(Config {
+            threshold: 0.5,
+            hysteresis,
+            sample_rate,
+        })
+    }
+
+    /// Returns a square wave of `period` samples, `high` of which are high.
+    fn pwm(period: usize, high: usize, periods: usize) -> Vec<f64> {
+        (0..period * periods)
+            .map(|n| if n % period < high { 1.0 } else { 0.0 })
+            .collect()
+    }
+
+    /// Returns a triangle wave between 0 and 1 with a period of `period` samples.
+    fn triangle(period: f64, samples: u32) -> Vec<f64> {
+        (0..samples)
+            .map(|n| {
+                let phase = (f64::from(n) / period).fract();
+                1.0 - (2.0 * phase - 1.0).abs()
+            })
+            .collect()
+    }
+
+    fn timings<const N: usize>(filter: &mut EdgeTiming<f64, N>, input: &[f64]) -> Vec<Timing<f64>> {
+        input
+            .iter()
+            .filter_map(|&input| filter.filter(input))
+            .collect()
+    }
+
+    #[test]
+    fn measures_pwm() {
+        let mut filter = filter::<1>(0.0, 1.0);
+        let timings = timings(&mut filter, &pwm(10, 3, 5));
+        // The first rising edge is at sample 0, which only sets the level.
+        assert_eq!(timings.len(), 3);
+        for timing in timings {
+            assert_abs_diff_eq!(timing.period, 10.0);
+            assert_abs_diff_eq!(timing.frequency, 0.1);
+            assert_abs_diff_eq!(timing.high, 3.0);
+            assert_abs_diff_eq!(timing.low, 7.0);
+            assert_abs_diff_eq!(timing.duty_cycle, 0.3);
+        }
+    }
+
+    #[test]
+    fn measures_in_seconds_and_hertz() {
+        let mut filter = filter::<4>(0.0, 1000.0);
+        let timings = timings(&mut filter, &pwm(20, 5, 4));
+        let timing = timings.last().expect("timing");
+        assert_abs_diff_eq!(timing.period, 0.02);
+        assert_abs_diff_eq!(timing.frequency, 50.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(timing.high, 0.005);
+        assert_abs_diff_eq!(timing.duty_cycle, 0.25);
+    }
+
+    #[test]
+    fn interpolates_edges() {
+        // Edges fall between samples, at varying offsets.
+        let mut filter = filter::<1>(0.1, 1.0);
+        let timings = timings(&mut filter, &triangle(12.5, 100));
+        assert!(timings.len() >= 6);
+        for timing in timings {
+            assert_abs_diff_eq!(timing.period, 12.5, epsilon = 1e-9);
+            assert_abs_diff_eq!(timing.high, 6.25, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn averages_periods() {
+        let mut filter = filter::<2>(0.0, 1.0);
+        let mut input = pwm(10, 4, 3);
+        input.extend(pwm(20, 4, 2));
+        let periods: Vec<_> = timings(&mut filter, &input)
+            .iter()
+            .map(|timing| timing.period)
+            .collect();
+        assert_eq!(periods, [10.0, 10.0, 15.0]);
+        let timing = filter.timing().expect("timing");
+        assert_abs_diff_eq!(timing.high, 4.0);
+    }
+
+    #[test]
+    fn hysteresis_rejects_chatter() {
+        let mut filter = filter::<1>(0.2, 1.0);
+        let input = [
+            0.0, 0.0, 0.6, 0.45, 0.55, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.6, 0.45, 0.55, 1.0,
+        ];
+        let timings = timings(&mut filter, &input);
+        let timing = timings[0];
+        // Edges are located at the last threshold crossing before confirmation.
+        assert_abs_diff_eq!(timing.period, 10.0);
+        assert_abs_diff_eq!(timing.high, 4.0);
+    }
+
+    #[test]
+    fn ignores_nan() {
+        let mut filter = filter::<1>(0.0, 1.0);
+        let mut input = pwm(10, 5, 4);
+        input[12] = f64::NAN;
+        let timings = timings(&mut filter, &input);
+        assert_eq!(timings.len(), 2);
+        assert_abs_diff_eq!(timings[0].period, 10.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "sample_rate")]
+    fn rejects_non_positive_sample_rate() {
+        let _ = filter::<1>(0.0, 0.0);
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter = filter::<1>(0.0, 1.0);
+        let _ = timings(&mut filter, &pwm(10, 5, 3));
+        let filter = filter.reset();
+        assert_eq!(filter.timing(), None);
+    }
+}
@@ -0,0 +1,657 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Prominence-, width- and distance-aware peak finding.
+//!
+//! Finds local maxima like `SciPy`'s `find_peaks`, and keeps only those that
+//! satisfy a minimum height, prominence, width and distance between peaks,
+//! either streaming with a bounded lookahead ([`PeakFinder`]) or offline on a
+//! slice ([`find_peaks`], with the `alloc` feature).
+
+use core::cmp::Ordering;
+
+use num_traits::float::FloatCore;
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The peak finder's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The minimum height of a peak, if any.
+    pub height: Option<T>,
+    /// The minimum prominence of a peak, if any.
+    pub prominence: Option<T>,
+    /// The minimum width of a peak in samples, if any.
+    pub width: Option<T>,
+    /// The height at which the width is measured, relative to the
+    /// prominence (`≥ 0`): `0.5` measures the width at half prominence, `1`
+    /// at the peak's lowest contour line.
+    pub rel_height: T,
+    /// The minimum distance in samples between neighbouring peaks (`≥ 1`).
+    pub distance: usize,
+}
+
+impl<T> Default for Config<T>
+where
+    T: FloatCore,
+{
+    fn default() -> Self {
+        Self {
+            height: None,
+            prominence: None,
+            width: None,
+            rel_height: T::from(0.5).unwrap_or_else(T::nan),
+            distance: 1,
+        }
+    }
+}
+
+impl<T> Config<T>
+where
+    T: FloatCore,
+{
+    fn accepts_height(&self, value: T) -> bool {
+        self.height.is_none_or(|height| value >= height)
+    }
+
+    fn accepts(&self, peak: &PeakEvent<T>) -> bool {
+        self.prominence
+            .is_none_or(|prominence| peak.prominence >= prominence)
+            && self.width.is_none_or(|width| peak.width >= width)
+    }
+}
+
+/// A peak found by [`PeakFinder`] or [`find_peaks`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PeakEvent<T> {
+    /// The peak's sample index; the middle (rounded down) of a plateau.
+    pub index: usize,
+    /// The peak's value.
+    pub value: T,
+    /// The peak's prominence: its height above the higher of the two lowest
+    /// points separating it from higher values.
+    pub prominence: T,
+    /// The peak's width in samples at `rel_height`, linearly interpolated.
+    pub width: T,
+}
+
+/// The peak finder's state.
+#[derive(Clone, Debug)]
+pub struct State<T, const N: usize> {
+    /// The ring buffer of the latest `N` samples.
+    pub samples: [T; N],
+    /// The number of samples received.
+    pub count: usize,
+    /// The index at which the current plateau started, if it followed a rise.
+    pub rise: Option<usize>,
+    /// The ring buffer of local maxima satisfying the minimum height.
+    pub peaks: [usize; N],
+    /// The position of the oldest local maximum in `peaks`.
+    pub peaks_start: usize,
+    /// The number of local maxima in `peaks`.
+    pub peaks_len: usize,
+    /// The lowest index of a local maximum that has not been evaluated yet.
+    pub next: usize,
+}
+
+/// A streaming peak finder with bounded lookahead.
+///
+/// Finds local maxima, including the middle of flat plateaus, and reports a
+/// [`PeakEvent`] for each that satisfies the configured criteria, with the
+/// semantics of `SciPy`'s `find_peaks` with a prominence window `wlen = N`
+/// (for odd `N`):
+///
+/// - its value is at least `height`,
+/// - no higher local maximum (or equally high earlier one) satisfying `height`
+///   lies within fewer than `distance` samples,
+/// - its prominence, searched within `±(N − 1) / 2` samples, is at least
+///   `prominence`,
+/// - its width at `rel_height` of the prominence is at least `width`.
+///
+/// A peak is reported `(N − 1) / 2` samples after its index, once its
+/// prominence window is complete. Unlike `SciPy`, which selects peaks by
+/// distance greedily from the highest down, a peak is suppressed by any
+/// higher neighbour, even one that is itself suppressed. Plateaus are assumed
+/// to be shorter than the lookahead, and `distance` must not exceed it.
+///
+/// NaN inputs are ignored and do not advance the sample index.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); the prominence and width searches and the distance
+///   check each walk at most the N buffered samples.
+/// - **Space:** O(N); stores the last N samples and up to N local maxima.
+#[derive(Clone, Debug)]
+pub struct PeakFinder<T, const N: usize> {
+    config: Config<T>,
+    state: State<T, N>,
+}
+
+impl<T, const N: usize> PeakFinder<T, N>
+where
+    T: FloatCore,
+{
+    /// The number of samples `L` by which peaks are reported late.
+    const LOOKAHEAD: usize = (N - 1) / 2;
+
+    fn at(&self, index: usize) -> T {
+        self.state.samples[index % N]
+    }
+
+    fn push_peak(&mut self, index: usize) {
+        let state = &mut self.state;
+        if state.peaks_len == N {
+            state.peaks_start = (state.peaks_start + 1) % N;
+            state.peaks_len -= 1;
+        }
+        state.peaks[(state.peaks_start + state.peaks_len) % N] = index;
+        state.peaks_len += 1;
+    }
+
+    fn peak(&self, position: usize) -> usize {
+        self.state.peaks[(self.state.peaks_start + position) % N]
+    }
+
+    /// Evaluates the oldest pending local maximum whose lookahead is complete.
+    fn evaluate(&mut self) -> Option<PeakEvent<T>> {
+        let distance = self.config.distance;
+        let latest = self.state.count - 1;
+        let oldest = self.state.count.saturating_sub(N);
+
+        // Drop local maxima too old to suppress any pending one:
+        while self.state.peaks_len > 0 && self.peak(0) + distance < self.state.next {
+            self.state.peaks_start = (self.state.peaks_start + 1) % N;
+            self.state.peaks_len -= 1;
+        }
+
+        let peak = (0..self.state.peaks_len)
+            .map(|position| self.peak(position))
+            .find(|&peak| peak >= self.state.next)?;
+        if peak + Self::LOOKAHEAD > latest {
+            return None;
+        }
+        self.state.next = peak + 1;
+
+        let value = self.at(peak);
+        let suppressed = (0..self.state.peaks_len)
+            .map(|position| self.peak(position))
+            .filter(|&other| other != peak && other >= oldest && other.abs_diff(peak) < distance)
+            .any(|other| match self.at(other).partial_cmp(&value) {
+                Some(Ordering::Greater) => true,
+                Some(Ordering::Equal) => other < peak,
+                _ => false,
+            });
+        if suppressed {
+            return None;
+        }
+
+        let lower = peak.saturating_sub(Self::LOOKAHEAD).max(oldest);
+        let upper = (peak + Self::LOOKAHEAD).min(latest);
+        let event = measure(|index| self.at(index), peak, lower, upper, &self.config);
+        self.config.accepts(&event).then_some(event)
+    }
+}
+
+/// Measures the peak at `peak`, searching its bases within `lower..=upper`.
+fn measure<T, F>(at: F, peak: usize, lower: usize, upper: usize, config: &Config<T>) -> PeakEvent<T>
+where
+    T: FloatCore,
+    F: Fn(usize) -> T,
+{
+    let value = at(peak);
+
+    // Walk outwards until a higher value, tracking the lowest points:
+    let (mut left_min, mut left_base) = (value, peak);
+    for index in (lower..=peak).rev() {
+        let sample = at(index);
+        if sample > value {
+            break;
+        }
+        if sample < left_min {
+            (left_min, left_base) = (sample, index);
+        }
+    }
+    let (mut right_min, mut right_base) = (value, peak);
+    for index in peak..=upper {
+        let sample = at(index);
+        if sample > value {
+            break;
+        }
+        if sample < right_min {
+            (right_min, right_base) = (sample, index);
+        }
+    }
+    let prominence = value - left_min.max(right_min);
+
+    // Walk outwards until the reference height, and interpolate the crossings:
+    let reference = value - prominence * config.rel_height;
+    let mut left = peak;
+    while left > left_base && reference < at(left) {
+        left -= 1;
+    }
+    let mut right = peak;
+    while right < right_base && reference < at(right) {
+        right += 1;
+    }
+    let mut width = T::from(right - left).unwrap_or_else(T::nan);
+    if at(left) < reference {
+        width = width - (reference - at(left)) / (at(left + 1) - at(left));
+    }
+    if at(right) < reference {
+        width = width - (reference - at(right)) / (at(right - 1) - at(right));
+    }
+
+    PeakEvent {
+        index: peak,
+        value,
+        prominence,
+        width,
+    }
+}
+
+/// Finds the peaks of `input` satisfying `config`, with the semantics of
+/// `SciPy`'s `find_peaks`.
+///
+/// Prominences are searched within `±window / 2` samples of each peak, or the
+/// whole input if `window` is `None`. Peaks closer than `distance` are
+/// removed greedily, keeping the highest first (and of equal ones, the
+/// earliest). The peaks are returned in ascending order of their index.
+#[cfg(feature = "alloc")]
+pub fn find_peaks<T>(input: &[T], config: &Config<T>, window: Option<usize>) -> Vec<PeakEvent<T>>
+where
+    T: FloatCore,
+{
+    let len = input.len();
+
+    // Local maxima, including the middle of plateaus:
+    let mut peaks = Vec::new();
+    let mut index = 1;
+    while index + 1 < len {
+        if input[index - 1] < input[index] {
+            let mut ahead = index + 1;
+            while ahead + 1 < len && input[ahead] == input[index] {
+                ahead += 1;
+            }
+            if input[ahead] < input[index] {
+                peaks.push((index + ahead - 1) / 2);
+                index = ahead;
+            }
+        }
+        index += 1;
+    }
+    peaks.retain(|&peak| config.accepts_height(input[peak]));
+
+    // Greedy selection by distance, from the highest peak down:
+    let distance = config.distance;
+    if distance > 1 {
+        let mut order: Vec<_> = (0..peaks.len()).collect();
+        order.sort_by(|&a, &b| {
+            let ordering = input[peaks[b]].partial_cmp(&input[peaks[a]]);
+            ordering.unwrap_or(Ordering::Equal).then(a.cmp(&b))
+        });
+        let mut keep = vec![true; peaks.len()];
+        for position in order {
+            if !keep[position] {
+                continue;
+            }
+            let peak = peaks[position];
+            for other in (0..position).rev() {
+                if peak - peaks[other] >= distance {
+                    break;
+                }
+                keep[other] = false;
+            }
+            for other in position + 1..peaks.len() {
+                if peaks[other] - peak >= distance {
+                    break;
+                }
+                keep[other] = false;
+            }
+        }
+        let mut keep = keep.into_iter();
+        peaks.retain(|_| keep.next().unwrap_or(false));
+    }
+
+    let half_window = window.map_or(len, |window| window / 2);
+    peaks
+        .into_iter()
+        .map(|peak| {
+            let lower = peak.saturating_sub(half_window);
+            let upper = (peak + half_window).min(len - 1);
+            measure(|index| input[index], peak, lower, upper, config)
+        })
+        .filter(|peak| config.accepts(peak))
+        .collect()
+}
+
+impl<T, const N: usize> ConfigTrait for PeakFinder<T, N> {
+    type Config = Config<T>;
+}
+
+impl<T, const N: usize> StateTrait for PeakFinder<T, N> {
+    type State = State<T, N>;
+}
+
+impl<T, const N: usize> WithConfig for PeakFinder<T, N>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `N` is less than 3, `rel_height` is negative, or `distance`
+    /// is zero or exceeds the lookahead `(N − 1) / 2`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(N >= 3, "PeakFinder: window size N must be >= 3");
+        assert!(
+            config.rel_height >= T::zero(),
+            "PeakFinder: rel_height must be >= 0"
+        );
+        assert!(
+            config.distance >= 1 && config.distance <= Self::LOOKAHEAD,
+            "PeakFinder: distance must be within 1..=(N - 1) / 2"
+        );
+        let state = State {
+            samples: [T::zero(); N],
+            count: 0,
+            rise: None,
+            peaks: [0; N],
+            peaks_start: 0,
+            peaks_len: 0,
+            next: 0,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, const N: usize> ConfigRef for PeakFinder<T, N> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, const N: usize> ConfigClone for PeakFinder<T, N>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, const N: usize> StateMut for PeakFinder<T, N> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, const N: usize> HasGuts for PeakFinder<T, N> {
+    type Guts = (Config<T>, State<T, N>);
+}
+
+impl<T, const N: usize> FromGuts for PeakFinder<T, N> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, const N: usize> IntoGuts for PeakFinder<T, N> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize> Reset for PeakFinder<T, N>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for PeakFinder<T, N> where Self: Reset {}
+
+impl<T, const N: usize> Filter<T> for PeakFinder<T, N>
+where
+    T: FloatCore,
+{
+    type Output = Option<PeakEvent<T>>;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        if input.is_nan() {
+            return None;
+        }
+        let index = self.state.count;
+        self.state.samples[index % N] = input;
+        self.state.count += 1;
+
+        if index > 0 {
+            let previous = self.at(index - 1);
+            match input.partial_cmp(&previous) {
+                Some(Ordering::Greater) => self.state.rise = Some(index),
+                Some(Ordering::Less) => {
+                    if let Some(start) = self.state.rise.take() {
+                        if self.config.accepts_height(previous) {
+                            self.push_peak((start + index - 1) / 2);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.evaluate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    #[cfg(feature = "alloc")]
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::util::test_fixtures::hash_noise;
+
+    fn stream<const N: usize>(config: Config<f64>, input: &[f64]) -> Vec<PeakEvent<f64>> {
+        let mut finder = PeakFinder::<f64, N>::with_config(config);
+        input
+            .iter()
+            .filter_map(|&input| finder.filter(input))
+            .collect()
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn finds_local_maxima_and_plateaus() {
+        let input = [0.0, 2.0, 1.0, 3.0, 3.0, 3.0, 0.0, 4.0, 4.0, 1.0, 5.0, 5.0];
+        let indices: Vec<_> = find_peaks(&input, &Config::default(), None)
+            .iter()
+            .map(|peak| peak.index)
+            .collect();
+        // The trailing plateau is not a peak, as in `SciPy`.
+        assert_eq!(indices, [1, 4, 7]);
+        let streamed: Vec<_> = stream::<5>(Config::default(), &input)
+            .iter()
+            .map(|peak| peak.index)
+            .collect();
+        assert_eq!(streamed, indices);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn matches_scipy_prominences_and_widths() {
+        // As `scipy.signal.find_peaks(x, prominence=0, width=0)`.
+        let input = [0.0, 2.0, 1.0, 3.0, 1.0, 4.0, 0.0];
+        let peaks = find_peaks(&input, &Config::default(), None);
+        let indices: Vec<_> = peaks.iter().map(|peak| peak.index).collect();
+        assert_eq!(indices, [1, 3, 5]);
+        for (peak, (prominence, width)) in
+            peaks
+                .iter()
+                .zip([(1.0, 0.75), (2.0, 1.0), (4.0, 7.0 / 6.0)])
+        {
+            assert_abs_diff_eq!(peak.prominence, prominence);
+            assert_abs_diff_eq!(peak.width, width, epsilon = 1e-12);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn interpolates_width_at_relative_height() {
+        let input = [0.0, 1.0, 2.0, 3.0, 4.0, 3.0, 2.0, 1.0, 0.0];
+        let width = |rel_height| {
+            let config = Config {
+                rel_height,
+                ..Config::default()
+            };
+            find_peaks(&input, &config, None)[0].width
+        };
+        assert_abs_diff_eq!(width(0.5), 4.0);
+        assert_abs_diff_eq!(width(1.0), 8.0);
+        assert_abs_diff_eq!(width(0.25), 2.0);
+        assert_abs_diff_eq!(width(0.125), 1.0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn filters_by_height_prominence_and_width() {
+        let input = [0.0, 2.0, 1.0, 3.0, 1.0, 4.0, 0.0];
+        let indices = |config: Config<f64>| -> Vec<_> {
+            find_peaks(&input, &config, None)
+                .iter()
+                .map(|peak| peak.index)
+                .collect()
+        };
+        let height = Config {
+            height: Some(2.5),
+            ..Config::default()
+        };
+        assert_eq!(indices(height), [3, 5]);
+        let prominence = Config {
+            prominence: Some(1.5),
+            ..Config::default()
+        };
+        assert_eq!(indices(prominence), [3, 5]);
+        let width = Config {
+            width: Some(0.9),
+            ..Config::default()
+        };
+        assert_eq!(indices(width), [3, 5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn removes_peaks_by_distance_greedily() {
+        // Peaks at 1 (value 1), 3 (value 2) and 5 (value 3): with distance 3,
+        // `SciPy` keeps 5, removes 3, and then keeps 1.
+        let input = [0.0, 1.0, 0.0, 2.0, 0.0, 3.0, 0.0, 0.0, 0.0];
+        let config = Config {
+            distance: 3,
+            ..Config::default()
+        };
+        let offline: Vec<_> = find_peaks(&input, &config, None)
+            .iter()
+            .map(|peak| peak.index)
+            .collect();
+        assert_eq!(offline, [1, 5]);
+        // The streaming finder suppresses 1 by its higher neighbour 3.
+        let streamed: Vec<_> = stream::<7>(config, &input)
+            .iter()
+            .map(|peak| peak.index)
+            .collect();
+        assert_eq!(streamed, [5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn equal_peaks_keep_the_earliest() {
+        let input = [0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0];
+        let config = Config {
+            distance: 3,
+            ..Config::default()
+        };
+        assert_eq!(find_peaks(&input, &config, None)[0].index, 1);
+        assert_eq!(stream::<7>(config, &input)[0].index, 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn streaming_matches_offline_with_window() {
+        const N: usize = 21;
+        let input: Vec<_> = (0_u32..500)
+            .map(|n| (f64::from(n) * 0.15).sin() + 0.3 * hash_noise(n))
+            .collect();
+        let config = Config {
+            prominence: Some(0.2),
+            width: Some(2.0),
+            distance: 5,
+            ..Config::default()
+        };
+        let offline = find_peaks(&input, &config, Some(N));
+        let streamed = stream::<N>(config, &input);
+        assert!(!streamed.is_empty());
+        // Peaks within the final lookahead are not reported yet.
+        let reported = offline
+            .iter()
+            .filter(|peak| peak.index + N / 2 < input.len())
+            .count();
+        assert_eq!(streamed.len(), reported);
+        for (streamed, offline) in streamed.iter().zip(&offline) {
+            assert_eq!(streamed, offline);
+        }
+    }
+
+    #[test]
+    fn reports_with_bounded_latency() {
+        let mut finder = PeakFinder::<f64, 5>::with_config(Config::default());
+        let input = [0.0, 1.0, 0.0, 0.0, 0.0];
+        let output: Vec<_> = input.iter().map(|&input| finder.filter(input)).collect();
+        assert!(output[..3].iter().all(Option::is_none));
+        assert_eq!(output[3].map(|peak| peak.index), Some(1));
+    }
+
+    #[test]
+    fn ignores_nan() {
+        let input = [0.0, 1.0, f64::NAN, 0.0, 0.0, 0.0];
+        let peaks = stream::<5>(Config::default(), &input);
+        assert_eq!(peaks.len(), 1);
+        assert_eq!(peaks[0].index, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "distance must be within 1..=(N - 1) / 2")]
+    fn distance_beyond_lookahead_panics() {
+        let _ = PeakFinder::<f64, 5>::with_config(Config {
+            distance: 3,
+            ..Config::default()
+        });
+    }
+
+    #[test]
+    fn reset() {
+        let mut finder = PeakFinder::<f64, 5>::with_config(Config::default());
+        finder.filter(0.0);
+        finder.filter(1.0);
+        let mut finder = finder.reset();
+        finder.filter(0.0);
+        assert_eq!(finder.state.count, 1);
+        assert_eq!(finder.state.rise, None);
+    }
+}
@@ -0,0 +1,386 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Multi-level hysteresis quantizer.
+//!
+//! Generalizes the Schmitt trigger to any number of discrete levels, with
+//! hysteresis and an optional debounce time at each boundary between adjacent
+//! levels, so that readings near a boundary don't
+//! flicker between levels.
+
+use core::{
+    cmp::{Ordering, PartialOrd},
+    ops::{Add, Sub},
+};
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::schmitt::Thresholds;
+
+/// The validated boundaries between the levels of a [`Quantizer`],
+/// guaranteeing that they are ascending and their hysteresis bands don't
+/// overlap.
+#[derive(Clone, Debug)]
+pub struct Boundaries<T, const B: usize> {
+    thresholds: [Thresholds<T>; B],
+}
+
+impl<T, const B: usize> Boundaries<T, B>
+where
+    T: PartialOrd,
+{
+    /// Creates new boundaries from the `[low, high]` thresholds of each one.
+    ///
+    /// Returns `None` if the `high` threshold of a boundary exceeds the `low`
+    /// threshold of the next one.
+    pub fn new(thresholds: [Thresholds<T>; B]) -> Option<Self> {
+        let ascending = thresholds
+            .windows(2)
+            .all(|pair| pair[0].high() <= pair[1].low());
+        ascending.then_some(Self { thresholds })
+    }
+
+    /// Creates new boundaries at `thresholds`, each with a hysteresis band of
+    /// `± margin`.
+    ///
+    /// Returns `None` if a margin is negative, or if the hysteresis bands are
+    /// not ascending.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn with_margins(thresholds: [T; B], margins: [T; B]) -> Option<Self>
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T>,
+    {
+        let bands = core::array::from_fn(|index| {
+            let (threshold, margin) = (thresholds[index], margins[index]);
+            (threshold - margin, threshold + margin)
+        });
+        if !bands.iter().all(|(low, high)| low <= high) {
+            return None;
+        }
+        let thresholds = bands.map(|(low, high)| Thresholds::new(low, high).expect("low <= high"));
+        Self::new(thresholds)
+    }
+
+    /// Returns the `[low, high]` thresholds of each boundary.
+    pub fn thresholds(&self) -> &[Thresholds<T>; B] {
+        &self.thresholds
+    }
+
+    /// Returns the level reached from `level` for `input`, moving up past each
+    /// boundary whose `high` threshold `input` exceeds, or otherwise down past
+    /// each boundary whose `low` threshold `input` falls below.
+    fn target(&self, level: usize, input: &T) -> usize {
+        let thresholds = &self.thresholds;
+        let mut target = level;
+        while target < B && input > thresholds[target].high() {
+            target += 1;
+        }
+        if target == level {
+            while target > 0 && input < thresholds[target - 1].low() {
+                target -= 1;
+            }
+        }
+        target
+    }
+}
+
+/// The quantizer's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, U, const B: usize, const N: usize> {
+    /// Validated boundaries between the `N = B + 1` levels.
+    pub boundaries: Boundaries<T, B>,
+    /// The number of consecutive samples a transition past each boundary must
+    /// be called for before it takes effect; `0` and `1` switch levels
+    /// immediately.
+    pub debounce: [usize; B],
+    /// Outputs, from the lowest level to the highest.
+    ///
+    /// [`Classification::classes`](super::Classification::classes) lists
+    /// three-way classes from the highest to the lowest (e.g. `[1, 0, -1]`), so
+    /// reverse them to rise with the input.
+    pub outputs: [U; N],
+}
+
+/// The quantizer's state.
+#[derive(Clone, Debug)]
+pub struct State {
+    /// The current level, once the first sample has been seen.
+    pub level: Option<usize>,
+    /// The level of the pending transition, if any.
+    pub pending: Option<usize>,
+    /// The number of consecutive samples calling for a transition in the
+    /// pending transition's direction.
+    pub count: usize,
+}
+
+/// A multi-level hysteresis quantizer.
+///
+/// Maps its input to one of `N` ascending levels separated by `B = N - 1`
+/// boundaries. The level only moves up past a boundary once the input exceeds
+/// its `high` threshold, and only moves down past it once the input falls below
+/// its `low` threshold, as for a [`Schmitt`](super::schmitt::Schmitt) trigger.
+/// The input may cross several boundaries at once.
+///
+/// With a `debounce` time, a transition only takes effect once consecutive
+/// samples have called for a transition in the same direction for that long,
+/// and then moves to the most recently called-for level. Each boundary has its
+/// own debounce time; a transition uses the one of the first boundary it
+/// crosses, adjacent to the current level. The first sample sets the initial
+/// level immediately.
+///
+/// `N` must be `B + 1`; other combinations are rejected at compile time via
+/// [`WithConfig`].
+///
+/// Outputs the current level's output. Use `[0, 1, …, B]` as outputs for the
+/// level index.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1), or O(B) when the input crosses several boundaries.
+/// - **Space:** O(1); stores the current level and the pending transition.
+#[derive(Clone, Debug)]
+pub struct Quantizer<T, U, const B: usize, const N: usize> {
+    config: Config<T, U, B, N>,
+    state: State,
+}
+
+impl<T, U, const B: usize, const N: usize> Quantizer<T, U, B, N> {
+    /// Returns the current level, once the first sample has been seen.
+    pub fn level(&self) -> Option<usize> {
+        self.state.level
+    }
+}
+
+impl<T, U, const B: usize, const N: usize> ConfigTrait for Quantizer<T, U, B, N> {
+    type Config = Config<T, U, B, N>;
+}
+
+impl<T, U, const B: usize, const N: usize> StateTrait for Quantizer<T, U, B, N> {
+    type State = State;
+}
+
+impl<T, U, const B: usize, const N: usize> WithConfig for Quantizer<T, U, B, N> {
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        const {
+            assert!(N == B + 1, "Quantizer: N must be B + 1");
+        };
+        let state = State {
+            level: None,
+            pending: None,
+            count: 0,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, U, const B: usize, const N: usize> ConfigRef for Quantizer<T, U, B, N> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, U, const B: usize, const N: usize> ConfigClone for Quantizer<T, U, B, N>
+where
+    Config<T, U, B, N>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, U, const B: usize, const N: usize> StateMut for Quantizer<T, U, B, N> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, U, const B: usize, const N: usize> HasGuts for Quantizer<T, U, B, N> {
+    type Guts = (Config<T, U, B, N>, State);
+}
+
+impl<T, U, const B: usize, const N: usize> FromGuts for Quantizer<T, U, B, N> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, U, const B: usize, const N: usize> IntoGuts for Quantizer<T, U, B, N> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, U, const B: usize, const N: usize> Reset for Quantizer<T, U, B, N> {
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, U, const B: usize, const N: usize> ResetMut for Quantizer<T, U, B, N> where Self: Reset {}
+
+impl<T, U, const B: usize, const N: usize> Filter<T> for Quantizer<T, U, B, N>
+where
+    T: PartialOrd,
+    U: Clone,
+{
+    type Output = U;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let boundaries = &self.config.boundaries;
+        let state = &mut self.state;
+        let level = match state.level {
+            None => boundaries.target(0, &input),
+            Some(level) => {
+                let target = boundaries.target(level, &input);
+                let same_direction = state
+                    .pending
+                    .is_some_and(|pending| (pending > level) == (target > level));
+                if target == level {
+                    state.pending = None;
+                    state.count = 0;
+                } else if same_direction {
+                    state.pending = Some(target);
+                    state.count += 1;
+                } else {
+                    state.pending = Some(target);
+                    state.count = 1;
+                }
+                // The first boundary crossed is adjacent to the current level.
+                let debounce = match target.cmp(&level) {
+                    Ordering::Greater => self.config.debounce[level],
+                    Ordering::Less => self.config.debounce[level - 1],
+                    Ordering::Equal => 0,
+                };
+                if state.pending.is_some() && state.count >= debounce {
+                    state.pending = None;
+                    state.count = 0;
+                    target
+                } else {
+                    level
+                }
+            }
+        };
+        state.level = Some(level);
+        self.config.outputs[level].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    /// Returns a quantizer with boundaries at 25, 50 and 75, each with a
+    /// hysteresis band of `± 5`.
+    fn quantizer(debounce: [usize; 3]) -> Quantizer<f64, usize, 3, 4> {
+        Quantizer::with_config(Config {
+            boundaries: Boundaries::with_margins([25.0, 50.0, 75.0], [5.0; 3])
+                .expect("ascending boundaries"),
+            debounce,
+            outputs: [0, 1, 2, 3],
+        })
+    }
+
+    fn levels(quantizer: &mut Quantizer<f64, usize, 3, 4>, input: &[f64]) -> Vec<usize> {
+        input.iter().map(|&input| quantizer.filter(input)).collect()
+    }
+
+    #[test]
+    fn quantizes_with_hysteresis() {
+        let mut quantizer = quantizer([0; 3]);
+        let input = [
+            10.0, 28.0, 31.0, 28.0, 21.0, 19.0, 22.0, 29.0, 56.0, 46.0, 44.0, 81.0,
+        ];
+        assert_eq!(
+            levels(&mut quantizer, &input),
+            [0, 0, 1, 1, 1, 0, 0, 0, 2, 2, 1, 3]
+        );
+    }
+
+    #[test]
+    fn first_sample_sets_level() {
+        let mut quantizer = quantizer([10; 3]);
+        assert_eq!(quantizer.level(), None);
+        assert_eq!(quantizer.filter(60.0), 2);
+        assert_eq!(quantizer.level(), Some(2));
+    }
+
+    #[test]
+    fn crosses_several_boundaries() {
+        let mut quantizer = quantizer([0; 3]);
+        assert_eq!(levels(&mut quantizer, &[90.0, 10.0, 60.0]), [3, 0, 2]);
+    }
+
+    #[test]
+    fn debounces_transitions() {
+        let mut quantizer = quantizer([3; 3]);
+        let input = [10.0, 40.0, 40.0, 10.0, 40.0, 40.0, 40.0, 40.0];
+        assert_eq!(levels(&mut quantizer, &input), [0, 0, 0, 0, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn debounce_moves_to_latest_level() {
+        let mut quantizer = quantizer([3; 3]);
+        let input = [10.0, 40.0, 60.0, 90.0, 90.0];
+        assert_eq!(levels(&mut quantizer, &input), [0, 0, 0, 3, 3]);
+    }
+
+    #[test]
+    fn debounces_each_boundary_separately() {
+        let mut rising = quantizer([3, 1, 3]);
+        // Crossing 25 takes three samples, crossing 50 only one.
+        let input = [10.0, 40.0, 40.0, 40.0, 60.0, 40.0, 10.0, 10.0];
+        assert_eq!(levels(&mut rising, &input), [0, 0, 0, 1, 2, 1, 1, 1]);
+        // Coming back down, crossing 75 takes one sample, crossing 50 three.
+        let mut falling = quantizer([1, 3, 1]);
+        let input = [90.0, 60.0, 40.0, 40.0, 40.0];
+        assert_eq!(levels(&mut falling, &input), [3, 2, 2, 2, 1]);
+    }
+
+    #[test]
+    fn works_with_classifications() {
+        use crate::filters::classify::Classification;
+
+        let mut outputs = i8::classes();
+        outputs.reverse();
+        let mut quantizer: Quantizer<f64, i8, 2, 3> = Quantizer::with_config(Config {
+            boundaries: Boundaries::with_margins([-0.5, 0.5], [0.1; 2]).expect("ascending"),
+            debounce: [0; 2],
+            outputs,
+        });
+        let output: Vec<_> = [-1.0, 0.0, 1.0]
+            .iter()
+            .map(|&input| quantizer.filter(input))
+            .collect();
+        assert_eq!(output, [-1, 0, 1]);
+    }
+
+    #[test]
+    fn rejects_overlapping_boundaries() {
+        assert!(Boundaries::with_margins([25.0, 35.0], [5.0; 2]).is_some());
+        assert!(Boundaries::with_margins([25.0, 35.0], [5.0, 6.0]).is_none());
+        assert!(Boundaries::with_margins([30.0, 25.0], [0.0; 2]).is_none());
+        assert!(Boundaries::with_margins([25.0], [-1.0]).is_none());
+    }
+
+    #[test]
+    fn reset() {
+        let mut quantizer = quantizer([0; 3]);
+        quantizer.filter(90.0);
+        let quantizer = quantizer.reset();
+        assert_eq!(quantizer.level(), None);
+    }
+}
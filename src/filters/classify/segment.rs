@@ -0,0 +1,494 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Event segmentation of classified signals.
+//!
+//! Turns a classification stream, such as the output of a `Schmitt` or
+//! `Debounce` filter, into completed events with their onset, offset, peak
+//! and area, for burst and activity detection.
+
+use core::{cmp::PartialEq, ops::Add};
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Finalize, Reset, State as StateTrait,
+    StateMut, WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The segmenter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<U> {
+    /// The class of active samples.
+    pub predicate: U,
+    /// The minimum duration of emitted events, in samples; shorter events are dropped.
+    pub min_duration: usize,
+    /// The minimum gap between events, in samples; events separated by shorter
+    /// gaps are merged.
+    pub min_gap: usize,
+    /// The maximum duration of events, in samples; longer events are split.
+    pub max_duration: Option<usize>,
+}
+
+/// A segmented event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Event<T> {
+    /// The index of the event's first sample (onset).
+    pub start: usize,
+    /// The index after the event's last sample (offset).
+    pub end: usize,
+    /// The index of the event's largest value.
+    pub peak_index: usize,
+    /// The event's largest value.
+    pub peak: T,
+    /// The sum of the event's values.
+    pub area: T,
+    /// Whether the event was split at the maximum duration.
+    pub truncated: bool,
+}
+
+impl<T> Event<T>
+where
+    T: Copy + PartialOrd + Add<Output = T>,
+{
+    fn new(index: usize, value: T) -> Self {
+        Self {
+            start: index,
+            end: index + 1,
+            peak_index: index,
+            peak: value,
+            area: value,
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, index: usize, value: T) {
+        self.end = index + 1;
+        self.area = self.area + value;
+        if value > self.peak {
+            self.peak_index = index;
+            self.peak = value;
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.end = other.end;
+        self.area = self.area + other.area;
+        if other.peak > self.peak {
+            self.peak_index = other.peak_index;
+            self.peak = other.peak;
+        }
+    }
+}
+
+impl<T> Event<T> {
+    /// Returns the event's duration, in samples.
+    pub fn duration(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// The segmenter's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The index of the next sample.
+    pub index: usize,
+    /// The current event, if any.
+    pub event: Option<Event<T>>,
+    /// The inactive samples following the current event, if it has ended.
+    pub gap: Option<Event<T>>,
+    /// The inactive samples following `gap` that would take a merged event
+    /// beyond the maximum duration.
+    pub overflow: Option<Event<T>>,
+}
+
+/// An event segmenter.
+///
+/// Consumes `(class, value)` pairs and groups consecutive samples of the
+/// configured `predicate` class into [`Event`]s, emitting each one once it is
+/// complete:
+///
+/// - Events separated by fewer than `min_gap` inactive samples are merged,
+///   including the gap's values. An event is thus emitted `min_gap` samples
+///   after its offset.
+/// - Events reaching `max_duration` are emitted right away, marked as
+///   `truncated`, and continued as a new event. A gap merged into an event is
+///   split at `max_duration` likewise, its remaining samples starting the new
+///   event.
+/// - Events shorter than `min_duration` are dropped.
+///
+/// Call [`finalize`](Finalize::finalize) at the end of the stream to retrieve
+/// the last event, if any.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); one comparison and an event update.
+/// - **Space:** O(1); stores the current event and its trailing gap.
+#[derive(Clone, Debug)]
+pub struct Segmenter<T, U> {
+    config: Config<U>,
+    state: State<T>,
+}
+
+impl<T, U> Segmenter<T, U>
+where
+    T: Copy + PartialOrd + Add<Output = T>,
+{
+    /// Removes the current event, returning it if it is long enough.
+    fn emit(&mut self) -> Option<Event<T>> {
+        self.state.gap = None;
+        self.state.overflow = None;
+        self.state
+            .event
+            .take()
+            .filter(|event| event.duration() >= self.config.min_duration)
+    }
+}
+
+impl<T, U> ConfigTrait for Segmenter<T, U> {
+    type Config = Config<U>;
+}
+
+impl<T, U> StateTrait for Segmenter<T, U> {
+    type State = State<T>;
+}
+
+impl<T, U> WithConfig for Segmenter<T, U> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `max_duration` is zero or less than `min_duration` or
+    /// `min_gap`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        if let Some(max_duration) = config.max_duration {
+            assert!(
+                max_duration >= config.min_duration.max(1),
+                "Segmenter: max_duration must be >= min_duration and > 0"
+            );
+            assert!(
+                max_duration >= config.min_gap,
+                "Segmenter: max_duration must be >= min_gap"
+            );
+        }
+        let state = State {
+            index: 0,
+            event: None,
+            gap: None,
+            overflow: None,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, U> ConfigRef for Segmenter<T, U> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, U> ConfigClone for Segmenter<T, U>
+where
+    Config<U>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, U> StateMut for Segmenter<T, U> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, U> HasGuts for Segmenter<T, U> {
+    type Guts = (Config<U>, State<T>);
+}
+
+impl<T, U> FromGuts for Segmenter<T, U> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, U> IntoGuts for Segmenter<T, U> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, U> Reset for Segmenter<T, U> {
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, U> ResetMut for Segmenter<T, U> where Self: Reset {}
+
+impl<T, U> Filter<(U, T)> for Segmenter<T, U>
+where
+    T: Copy + PartialOrd + Add<Output = T>,
+    U: PartialEq,
+{
+    type Output = Option<Event<T>>;
+
+    fn filter(&mut self, input: (U, T)) -> Self::Output {
+        let (class, value) = input;
+        let index = self.state.index;
+        self.state.index += 1;
+
+        if class != self.config.predicate {
+            let State {
+                event,
+                gap,
+                overflow,
+                ..
+            } = &mut self.state;
+            let Some(event) = event else {
+                return None;
+            };
+            let duration = event.duration() + gap.as_ref().map_or(0, Event::duration);
+            // Samples beyond `max_duration` would start the next event if merged.
+            let pending = match self.config.max_duration {
+                Some(max_duration) if duration >= max_duration => &mut *overflow,
+                _ => &mut *gap,
+            };
+            match pending {
+                Some(pending) => pending.push(index, value),
+                None => *pending = Some(Event::new(index, value)),
+            }
+            let gap_duration = gap.as_ref().map_or(0, Event::duration)
+                + overflow.as_ref().map_or(0, Event::duration);
+            if gap_duration < self.config.min_gap {
+                return None;
+            }
+            return self.emit();
+        }
+
+        let gap = self.state.gap.take();
+        let overflow = self.state.overflow.take();
+        let max_duration = self.config.max_duration;
+        let event = match &mut self.state.event {
+            Some(event) => {
+                if let Some(gap) = gap {
+                    event.merge(gap);
+                }
+                if max_duration.is_some_and(|max_duration| event.duration() >= max_duration) {
+                    // The gap filled the event: the remaining samples start the next one.
+                    event.truncated = true;
+                    let next = match overflow {
+                        Some(mut next) => {
+                            next.push(index, value);
+                            next
+                        }
+                        None => Event::new(index, value),
+                    };
+                    let emitted = self.emit();
+                    self.state.event = Some(next);
+                    return emitted;
+                }
+                event.push(index, value);
+                event
+            }
+            event @ None => event.insert(Event::new(index, value)),
+        };
+        if max_duration.is_none_or(|max_duration| event.duration() < max_duration) {
+            return None;
+        }
+        event.truncated = true;
+        self.emit()
+    }
+}
+
+impl<T, U> Finalize for Segmenter<T, U>
+where
+    T: Copy + PartialOrd + Add<Output = T>,
+{
+    type Output = Option<Event<T>>;
+
+    fn finalize(mut self) -> Self::Output {
+        self.emit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn segmenter(
+        min_duration: usize,
+        min_gap: usize,
+        max_duration: Option<usize>,
+    ) -> Segmenter<i32, bool> {
+        Segmenter::with_config(Config {
+            predicate: true,
+            min_duration,
+            min_gap,
+            max_duration,
+        })
+    }
+
+    /// Returns the events of `values`, with samples above zero being active.
+    fn events(mut segmenter: Segmenter<i32, bool>, values: &[i32]) -> Vec<Event<i32>> {
+        let mut events: Vec<_> = values
+            .iter()
+            .filter_map(|&value| segmenter.filter((value > 0, value)))
+            .collect();
+        events.extend(segmenter.clone().finalize());
+        events
+    }
+
+    fn spans(events: &[Event<i32>]) -> Vec<(usize, usize)> {
+        events
+            .iter()
+            .map(|event| (event.start, event.end))
+            .collect()
+    }
+
+    #[test]
+    fn segments_events() {
+        let values = [0, 1, 3, 2, 0, 0, 5, 0];
+        let events = events(segmenter(1, 0, None), &values);
+        assert_eq!(
+            events,
+            [
+                Event {
+                    start: 1,
+                    end: 4,
+                    peak_index: 2,
+                    peak: 3,
+                    area: 6,
+                    truncated: false,
+                },
+                Event {
+                    start: 6,
+                    end: 7,
+                    peak_index: 6,
+                    peak: 5,
+                    area: 5,
+                    truncated: false,
+                },
+            ]
+        );
+        assert_eq!(events[0].duration(), 3);
+    }
+
+    #[test]
+    fn emits_on_offset() {
+        let mut segmenter = segmenter(1, 0, None);
+        assert_eq!(segmenter.filter((true, 1)), None);
+        assert_eq!(segmenter.filter((true, 1)), None);
+        let event = segmenter.filter((false, 0)).expect("event");
+        assert_eq!((event.start, event.end), (0, 2));
+    }
+
+    #[test]
+    fn drops_short_events() {
+        let values = [1, 0, 1, 1, 0, 1, 1, 1, 0];
+        let events = events(segmenter(2, 0, None), &values);
+        assert_eq!(spans(&events), [(2, 4), (5, 8)]);
+    }
+
+    #[test]
+    fn merges_events_across_short_gaps() {
+        let values = [1, 2, -1, 4, 0, 0, 0, 1, 0];
+        let events = events(segmenter(1, 2, None), &values);
+        assert_eq!(spans(&events), [(0, 4), (7, 8)]);
+        // The gap's values are part of the merged event.
+        assert_eq!(events[0].area, 6);
+        assert_eq!(events[0].peak_index, 3);
+    }
+
+    #[test]
+    fn merges_short_events_into_long_ones() {
+        let values = [1, 0, 1, 0, 1, 0, 0, 0];
+        let events = events(segmenter(4, 2, None), &values);
+        assert_eq!(spans(&events), [(0, 5)]);
+    }
+
+    #[test]
+    fn emits_after_min_gap() {
+        let mut segmenter = segmenter(1, 3, None);
+        segmenter.filter((true, 1));
+        assert_eq!(segmenter.filter((false, 0)), None);
+        assert_eq!(segmenter.filter((false, 0)), None);
+        let event = segmenter.filter((false, 0)).expect("event");
+        assert_eq!((event.start, event.end), (0, 1));
+    }
+
+    #[test]
+    fn splits_long_events() {
+        let values = [1, 1, 1, 1, 1, 1, 1, 0];
+        let events = events(segmenter(1, 0, Some(3)), &values);
+        assert_eq!(spans(&events), [(0, 3), (3, 6), (6, 7)]);
+        let truncated: Vec<_> = events.iter().map(|event| event.truncated).collect();
+        assert_eq!(truncated, [true, true, false]);
+    }
+
+    #[test]
+    fn splits_merged_gaps_at_max_duration() {
+        let values = [1, 1, 1, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0];
+        let events = events(segmenter(1, 3, Some(5)), &values);
+        assert_eq!(spans(&events), [(0, 5), (5, 9)]);
+        let truncated: Vec<_> = events.iter().map(|event| event.truncated).collect();
+        assert_eq!(truncated, [true, false]);
+        assert_eq!(events[0].area, 3);
+    }
+
+    #[test]
+    fn starts_next_event_with_gap_beyond_max_duration() {
+        let values = [1, 1, 1, 1, -1, -2, 1, 1, 0, 0, 0];
+        let events = events(segmenter(1, 3, Some(5)), &values);
+        assert_eq!(spans(&events), [(0, 5), (5, 8)]);
+        assert_eq!(events[0].area, 3);
+        assert_eq!(events[1].area, 0);
+        assert!(events.iter().all(|event| event.duration() <= 5));
+    }
+
+    #[test]
+    fn finalize_emits_pending_event() {
+        let mut segmenter = segmenter(2, 4, None);
+        segmenter.filter((true, 1));
+        segmenter.filter((true, 1));
+        segmenter.filter((false, 0));
+        let event = segmenter.finalize().expect("event");
+        assert_eq!((event.start, event.end), (0, 2));
+    }
+
+    #[test]
+    fn finalize_drops_short_event() {
+        let mut segmenter = segmenter(2, 0, None);
+        segmenter.filter((true, 1));
+        assert_eq!(segmenter.finalize(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "max_duration")]
+    fn rejects_max_duration_below_min_duration() {
+        let _ = segmenter(3, 0, Some(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_duration must be >= min_gap")]
+    fn rejects_max_duration_below_min_gap() {
+        let _ = segmenter(1, 3, Some(2));
+    }
+
+    #[test]
+    fn reset() {
+        let mut segmenter = segmenter(1, 0, None);
+        segmenter.filter((true, 1));
+        let mut segmenter = segmenter.reset();
+        segmenter.filter((true, 1));
+        let event = segmenter.filter((false, 0)).expect("event");
+        assert_eq!(event.start, 0);
+    }
+}
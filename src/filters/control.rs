@@ -0,0 +1,33 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Feedback controllers for closed-loop control.
+//!
+//! Controllers consume `(setpoint, measurement)` pairs and emit the actuator
+//! command that drives the measurement towards the setpoint.
+//!
+//! # When to use which filter
+//!
+//! | Filter                         | Purpose                                                    |
+//! | ------------------------------ | ---------------------------------------------------------- |
+//! | `pid::Pid`                     | General-purpose PID for motor, thermal and process loops   |
+//! | `compensator::Compensator`     | Lead, lag, lead-lag and notch compensator designs          |
+//!
+//! - **`Pid`** combines proportional, integral and filtered derivative action
+//!   with setpoint weighting, output limits with anti-windup, bumpless
+//!   manual/automatic transfer and bumpless gain changes for gain scheduling.
+//! - **`Compensator`** discretizes continuous-time compensators into
+//!   [`first_order`](super::iir::first_order) and [`biquad`](super::iir::biquad)
+//!   configurations for loop shaping; [`compensator::margins`] checks the
+//!   resulting gain and phase margins against a plant model.
+//!
+//! # See also
+//!
+//! - [`super::iir::loop_filter`]: the PI loop filter of phase-locked and
+//!   timing-recovery loops, tuned from a loop bandwidth and damping factor.
+
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod compensator;
+
+pub mod pid;
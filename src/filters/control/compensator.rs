@@ -0,0 +1,642 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Lead, lag, lead-lag and notch compensator designs, and loop margins.
+//!
+//! Compensators are specified in continuous-time terms (zero and pole
+//! frequencies, a phase-lead target, a notch frequency) and discretized with
+//! the bilinear (Tustin) transform. Each design pre-warps at its frequency of
+//! interest, so the discrete response matches the continuous one exactly there.
+//!
+//! First-order designs produce [`first_order::Config`]s, second-order designs
+//! produce [`biquad::Config`]s.
+//!
+//! [`margins`] evaluates the gain and phase margins of an open loop built from
+//! any [`FrequencyResponse`]: discretized sections wrapped in [`Sampled`],
+//! plant models given as closures, and their products via [`Series`].
+
+use num_traits::Float;
+
+use crate::filters::{
+    iir::{biquad, first_order},
+    spectral::{complex_mul, Bin},
+};
+
+/// A system's complex frequency response.
+///
+/// Implemented for closures `Fn(T) -> Bin<T>`, which makes it easy to supply
+/// continuous-time plant models.
+pub trait FrequencyResponse<T> {
+    /// Returns the complex response at `freq` Hz.
+    fn response(&self, freq: T) -> Bin<T>;
+}
+
+impl<T, F> FrequencyResponse<T> for F
+where
+    F: Fn(T) -> Bin<T>,
+{
+    fn response(&self, freq: T) -> Bin<T> {
+        self(freq)
+    }
+}
+
+/// A discrete-time section's configuration, paired with its sample rate.
+#[derive(Clone, Debug)]
+pub struct Sampled<C, T> {
+    /// The section's configuration.
+    pub config: C,
+    /// The sample rate in Hz.
+    pub sample_rate: T,
+}
+
+impl<C, T> Sampled<C, T> {
+    /// Pairs `config` with the `sample_rate` it runs at.
+    pub fn new(config: C, sample_rate: T) -> Self {
+        Self {
+            config,
+            sample_rate,
+        }
+    }
+}
+
+impl<T> FrequencyResponse<T> for Sampled<first_order::Config<T>, T>
+where
+    T: Float,
+{
+    fn response(&self, freq: T) -> Bin<T> {
+        let first_order::Config { b0, b1, a1 } = self.config;
+        let z = delay(self.sample_rate, freq);
+        let numerator = Bin::new(b0 + b1 * z.re, b1 * z.im);
+        let denominator = Bin::new(T::one() + a1 * z.re, a1 * z.im);
+        complex_div(&numerator, &denominator)
+    }
+}
+
+impl<T> FrequencyResponse<T> for Sampled<biquad::Config<T>, T>
+where
+    T: Float,
+{
+    fn response(&self, freq: T) -> Bin<T> {
+        let biquad::Config { b0, b1, b2, a1, a2 } = self.config;
+        let z1 = delay(self.sample_rate, freq);
+        let z2 = complex_mul(&z1, &z1);
+        let numerator = Bin::new(b0 + b1 * z1.re + b2 * z2.re, b1 * z1.im + b2 * z2.im);
+        let denominator = Bin::new(T::one() + a1 * z1.re + a2 * z2.re, a1 * z1.im + a2 * z2.im);
+        complex_div(&numerator, &denominator)
+    }
+}
+
+/// Two systems in series; the response is the product of both responses.
+#[derive(Clone, Debug)]
+pub struct Series<A, B>(pub A, pub B);
+
+impl<T, A, B> FrequencyResponse<T> for Series<A, B>
+where
+    T: Float,
+    A: FrequencyResponse<T>,
+    B: FrequencyResponse<T>,
+{
+    fn response(&self, freq: T) -> Bin<T> {
+        complex_mul(&self.0.response(freq), &self.1.response(freq))
+    }
+}
+
+/// Returns the unit delay `e^(−jω)` at `freq` Hz.
+fn delay<T: Float>(sample_rate: T, freq: T) -> Bin<T> {
+    let pi = T::from(core::f64::consts::PI).expect("π is representable");
+    let two = T::one() + T::one();
+    Bin::from_angle(-two * pi * freq / sample_rate)
+}
+
+fn complex_div<T: Float>(lhs: &Bin<T>, rhs: &Bin<T>) -> Bin<T> {
+    let conjugate = Bin::new(rhs.re, -rhs.im);
+    let numerator = complex_mul(lhs, &conjugate);
+    let power = rhs.power();
+    Bin::new(numerator.re / power, numerator.im / power)
+}
+
+/// Compensator designer.
+///
+/// All methods take the sample rate in Hz first, followed by continuous-time
+/// frequencies in Hz. Gains are linear unless the argument name says otherwise.
+///
+/// # Panics
+///
+/// In debug builds, every method panics if `sample_rate <= 0`, or if a
+/// frequency is `<= 0` or `>= sample_rate / 2`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Compensator;
+
+impl Compensator {
+    /// Designs a lead compensator `C(s) = gain·(1 + s/ωz) / (1 + s/ωp)`, with
+    /// `zero_freq < pole_freq`.
+    ///
+    /// The DC gain is `gain`; the high-frequency gain is `gain·pole/zero`. The
+    /// phase lead peaks at `√(zero·pole)`, which is where the design pre-warps.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::control::compensator::Compensator;
+    /// let lead = Compensator::lead(1000.0, 10.0, 40.0, 1.0);
+    /// assert!(lead.a1 < 0.0);
+    /// ```
+    pub fn lead<T: Float>(
+        sample_rate: T,
+        zero_freq: T,
+        pole_freq: T,
+        gain: T,
+    ) -> first_order::Config<T> {
+        debug_assert!(zero_freq < pole_freq, "lead: zero must be below pole");
+        first_order_section(sample_rate, zero_freq, pole_freq, gain)
+    }
+
+    /// Designs a lag compensator `C(s) = gain·(1 + s/ωz) / (1 + s/ωp)`, with
+    /// `pole_freq < zero_freq`.
+    ///
+    /// The DC gain is `gain`; the high-frequency gain is `gain·pole/zero`. The
+    /// design pre-warps at `√(zero·pole)`.
+    pub fn lag<T: Float>(
+        sample_rate: T,
+        zero_freq: T,
+        pole_freq: T,
+        gain: T,
+    ) -> first_order::Config<T> {
+        debug_assert!(pole_freq < zero_freq, "lag: pole must be below zero");
+        first_order_section(sample_rate, zero_freq, pole_freq, gain)
+    }
+
+    /// Designs a lead compensator adding `phase` radians (`0 < phase < π/2`) of
+    /// phase lead at `crossover_freq`, with unit gain there.
+    ///
+    /// The zero and pole are placed symmetrically (on a log scale) around the
+    /// crossover, at `fc·√α` and `fc/√α` with `α = (1 − sin φ) / (1 + sin φ)`.
+    /// Raise the loop gain by the compensator's DC attenuation `√α` elsewhere
+    /// if the crossover is to stay put.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, also panics unless `0 < phase < π/2`.
+    pub fn lead_for_phase<T: Float>(
+        sample_rate: T,
+        crossover_freq: T,
+        phase: T,
+    ) -> first_order::Config<T> {
+        debug_assert!(
+            phase > T::zero()
+                && phase < T::from(core::f64::consts::FRAC_PI_2).expect("π/2 is representable"),
+            "lead_for_phase: phase must be in (0, π/2)"
+        );
+        let sin = phase.sin();
+        let alpha = (T::one() - sin) / (T::one() + sin);
+        let root = alpha.sqrt();
+        first_order_section(
+            sample_rate,
+            crossover_freq * root,
+            crossover_freq / root,
+            root,
+        )
+    }
+
+    /// Designs a lag compensator raising the low-frequency gain to
+    /// `low_frequency_gain` (`> 1`) while keeping unit gain at and above
+    /// `crossover_freq`.
+    ///
+    /// The zero sits a decade below the crossover, limiting the phase lag there
+    /// to a few degrees.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, also panics unless `low_frequency_gain > 1`.
+    pub fn lag_for_gain<T: Float>(
+        sample_rate: T,
+        crossover_freq: T,
+        low_frequency_gain: T,
+    ) -> first_order::Config<T> {
+        debug_assert!(
+            low_frequency_gain > T::one(),
+            "lag_for_gain: low_frequency_gain must be > 1"
+        );
+        let ten = T::from(10.0).expect("10 is representable");
+        let zero_freq = crossover_freq / ten;
+        first_order_section(
+            sample_rate,
+            zero_freq,
+            zero_freq / low_frequency_gain,
+            low_frequency_gain,
+        )
+    }
+
+    /// Designs a lead-lag compensator, the series connection of a lead section
+    /// (`lead_zero < lead_pole`) and a lag section (`lag_pole < lag_zero`), as a
+    /// single biquad.
+    ///
+    /// The DC gain is `gain`. Each section pre-warps at its own center
+    /// frequency.
+    pub fn lead_lag<T: Float>(
+        sample_rate: T,
+        lead_zero: T,
+        lead_pole: T,
+        lag_zero: T,
+        lag_pole: T,
+        gain: T,
+    ) -> biquad::Config<T> {
+        let lead = Self::lead(sample_rate, lead_zero, lead_pole, gain);
+        let lag = Self::lag(sample_rate, lag_zero, lag_pole, T::one());
+        biquad::Config {
+            b0: lead.b0 * lag.b0,
+            b1: lead.b0 * lag.b1 + lead.b1 * lag.b0,
+            b2: lead.b1 * lag.b1,
+            a1: lead.a1 + lag.a1,
+            a2: lead.a1 * lag.a1,
+        }
+    }
+
+    /// Designs a notch compensator
+    /// `C(s) = (s² + 2·ζz·ω0·s + ω0²) / (s² + 2·ζp·ω0·s + ω0²)`,
+    /// for suppressing a structural resonance at `freq`.
+    ///
+    /// `q = 1 / (2·ζp)` sets the notch width, and `depth_db` (`≥ 0`) its depth,
+    /// `ζz = ζp·10^(−depth_db/20)`; an infinite depth places the zeros on the
+    /// unit circle. The gain is unity far from the notch, and the design
+    /// pre-warps at `freq`.
+    pub fn notch<T: Float>(sample_rate: T, freq: T, q: T, depth_db: T) -> biquad::Config<T> {
+        debug_assert!(q > T::zero(), "notch: q must be positive");
+        debug_assert!(depth_db >= T::zero(), "notch: depth must be >= 0 dB");
+        let two = T::one() + T::one();
+        let pole_damping = T::one() / (two * q);
+        let zero_damping = pole_damping * crate::math::db_to_amplitude(-depth_db);
+        let (omega, k) = prewarp(sample_rate, freq);
+        let omega2 = omega * omega;
+        tustin2(
+            [T::one(), two * zero_damping * omega, omega2],
+            [T::one(), two * pole_damping * omega, omega2],
+            k,
+        )
+    }
+}
+
+/// Returns `(ω0, k)` for pre-warping at `freq`: `s = k·(z − 1)/(z + 1)`.
+fn prewarp<T: Float>(sample_rate: T, freq: T) -> (T, T) {
+    debug_assert!(sample_rate > T::zero(), "sample_rate must be positive");
+    debug_assert!(freq > T::zero(), "freq must be positive");
+    debug_assert!(
+        freq < sample_rate / (T::one() + T::one()),
+        "freq must be below Nyquist"
+    );
+    let pi = T::from(core::f64::consts::PI).expect("π is representable");
+    let two = T::one() + T::one();
+    let omega = two * pi * freq;
+    (omega, omega / (omega / (two * sample_rate)).tan())
+}
+
+/// Discretizes `gain·(1 + s/ωz) / (1 + s/ωp)`, pre-warped at `√(ωz·ωp)`.
+fn first_order_section<T: Float>(
+    sample_rate: T,
+    zero_freq: T,
+    pole_freq: T,
+    gain: T,
+) -> first_order::Config<T> {
+    debug_assert!(zero_freq > T::zero(), "zero_freq must be positive");
+    debug_assert!(pole_freq > T::zero(), "pole_freq must be positive");
+    debug_assert!(
+        zero_freq < sample_rate / (T::one() + T::one())
+            && pole_freq < sample_rate / (T::one() + T::one()),
+        "zero_freq and pole_freq must be below Nyquist"
+    );
+    let (_, k) = prewarp(sample_rate, (zero_freq * pole_freq).sqrt());
+    let pi = T::from(core::f64::consts::PI).expect("π is representable");
+    let two = T::one() + T::one();
+    let numerator_s = gain / (two * pi * zero_freq);
+    let denominator_s = T::one() / (two * pi * pole_freq);
+    let a0 = denominator_s * k + T::one();
+    first_order::Config {
+        b0: (numerator_s * k + gain) / a0,
+        b1: (gain - numerator_s * k) / a0,
+        a1: (T::one() - denominator_s * k) / a0,
+    }
+}
+
+/// Discretizes `(n2·s² + n1·s + n0) / (d2·s² + d1·s + d0)` with
+/// `s = k·(z − 1)/(z + 1)`.
+fn tustin2<T: Float>(numerator: [T; 3], denominator: [T; 3], k: T) -> biquad::Config<T> {
+    let two = T::one() + T::one();
+    let expand = |[c2, c1, c0]: [T; 3]| {
+        let c2k = c2 * k * k;
+        let c1k = c1 * k;
+        [c2k + c1k + c0, two * (c0 - c2k), c2k - c1k + c0]
+    };
+    let [b0, b1, b2] = expand(numerator);
+    let [a0, a1, a2] = expand(denominator);
+    biquad::Config {
+        b0: b0 / a0,
+        b1: b1 / a0,
+        b2: b2 / a0,
+        a1: a1 / a0,
+        a2: a2 / a0,
+    }
+}
+
+/// The stability margins of an open loop.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Margins<T> {
+    /// The gain crossover frequency in Hz, where `|L| = 1`, if any.
+    pub gain_crossover: Option<T>,
+    /// The phase margin in radians, `π + ∠L` at the gain crossover.
+    pub phase_margin: Option<T>,
+    /// The phase crossover frequency in Hz, where `∠L = −π`, if any.
+    pub phase_crossover: Option<T>,
+    /// The gain margin in dB, `−20·log10|L|` at the phase crossover.
+    pub gain_margin_db: Option<T>,
+}
+
+/// Computes the gain and phase margins of `open_loop` between `min_freq` and
+/// `max_freq` Hz.
+///
+/// The response is sampled at `points` logarithmically spaced frequencies
+/// (which must resolve the phase without jumps of `π` or more between
+/// neighbours), and the first gain and phase crossovers are refined by
+/// bisection. Margins of crossovers outside the range are `None`.
+///
+/// # Panics
+///
+/// Panics if `points < 2`, or unless `0 < min_freq < max_freq`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use signalo::filters::control::compensator::margins;
+/// # use signalo::filters::spectral::Bin;
+/// // An integrator crossing over at 10 Hz has 90° of phase margin.
+/// let integrator = |freq: f64| Bin::new(0.0, -10.0 / freq);
+/// let margins = margins(&integrator, 1.0, 100.0, 100);
+/// assert!((margins.gain_crossover.expect("gain crossover in range") - 10.0).abs() < 1e-9);
+/// assert!((margins.phase_margin.expect("gain crossover in range") - core::f64::consts::FRAC_PI_2).abs() < 1e-9);
+/// assert_eq!(margins.gain_margin_db, None);
+/// ```
+pub fn margins<T, L>(open_loop: &L, min_freq: T, max_freq: T, points: usize) -> Margins<T>
+where
+    T: Float,
+    L: FrequencyResponse<T> + ?Sized,
+{
+    assert!(points >= 2, "margins: at least two points are required");
+    assert!(
+        min_freq > T::zero() && min_freq < max_freq,
+        "margins: frequency range must satisfy 0 < min_freq < max_freq"
+    );
+    let pi = T::from(core::f64::consts::PI).expect("π is representable");
+    let steps = T::from(points - 1).expect("point count is representable");
+    let log_ratio = (max_freq / min_freq).ln();
+    let freq_at = |index: usize| {
+        let index = T::from(index).expect("point index is representable");
+        min_freq * (log_ratio * index / steps).exp()
+    };
+
+    let mut result = Margins {
+        gain_crossover: None,
+        phase_margin: None,
+        phase_crossover: None,
+        gain_margin_db: None,
+    };
+
+    let mut previous_freq = min_freq;
+    let first = open_loop.response(min_freq);
+    let mut previous_magnitude = first.magnitude();
+    let mut previous_phase = first.phase();
+    for index in 1..points {
+        let freq = freq_at(index);
+        let response = open_loop.response(freq);
+        let magnitude = response.magnitude();
+        let phase = unwrap_phase(response.phase(), previous_phase, pi);
+
+        if result.gain_crossover.is_none()
+            && (previous_magnitude - T::one()) * (magnitude - T::one()) <= T::zero()
+        {
+            let crossover = bisect(previous_freq, freq, |freq| {
+                open_loop.response(freq).magnitude() - T::one()
+            });
+            let phase = unwrap_phase(open_loop.response(crossover).phase(), previous_phase, pi);
+            result.gain_crossover = Some(crossover);
+            result.phase_margin = Some(pi + phase);
+        }
+
+        if result.phase_crossover.is_none() && (previous_phase + pi) * (phase + pi) <= T::zero() {
+            let crossover = bisect(previous_freq, freq, |freq| {
+                unwrap_phase(open_loop.response(freq).phase(), previous_phase, pi) + pi
+            });
+            let magnitude = open_loop.response(crossover).magnitude();
+            result.phase_crossover = Some(crossover);
+            result.gain_margin_db = Some(-crate::math::amplitude_to_db(magnitude));
+        }
+
+        previous_freq = freq;
+        previous_magnitude = magnitude;
+        previous_phase = phase;
+    }
+    result
+}
+
+/// Shifts `phase` by multiples of `2π` to lie within `π` of `reference`.
+fn unwrap_phase<T: Float>(phase: T, reference: T, pi: T) -> T {
+    let turn = pi + pi;
+    phase + turn * ((reference - phase) / turn).round()
+}
+
+/// Finds a root of `f` between `low` and `high` Hz by bisection in log-frequency.
+fn bisect<T, F>(mut low: T, mut high: T, f: F) -> T
+where
+    T: Float,
+    F: Fn(T) -> T,
+{
+    let low_sign = f(low) > T::zero();
+    for _ in 0..60 {
+        let middle = (low * high).sqrt();
+        if (f(middle) > T::zero()) == low_sign {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low * high).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 1000.0;
+
+    /// Returns the continuous response of `gain·(1 + s/ωz) / (1 + s/ωp)`.
+    fn analog_first_order(zero: f64, pole: f64, gain: f64, freq: f64) -> Bin<f64> {
+        let numerator = Bin::new(gain, gain * freq / zero);
+        let denominator = Bin::new(1.0, freq / pole);
+        complex_div(&numerator, &denominator)
+    }
+
+    #[test]
+    fn lead_matches_analog_at_center() {
+        let (zero, pole) = (10.0, 40.0);
+        let lead = Compensator::lead(SAMPLE_RATE, zero, pole, 2.0);
+        let sampled = Sampled::new(lead, SAMPLE_RATE);
+        let center = 20.0;
+        let digital = sampled.response(center);
+        let analog = analog_first_order(zero, pole, 2.0, center);
+        assert_abs_diff_eq!(digital.re, analog.re, epsilon = 1e-12);
+        assert_abs_diff_eq!(digital.im, analog.im, epsilon = 1e-12);
+        // Maximum phase lead is asin((p − z) / (p + z)).
+        assert_abs_diff_eq!(digital.phase(), (0.6_f64).asin(), epsilon = 1e-12);
+        // DC gain is `gain`.
+        assert_abs_diff_eq!(sampled.response(1e-9).magnitude(), 2.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn lag_adds_phase_lag() {
+        let lag = Sampled::new(Compensator::lag(SAMPLE_RATE, 10.0, 2.0, 1.0), SAMPLE_RATE);
+        let center = 20.0_f64.sqrt();
+        assert!(lag.response(center).phase() < -0.5);
+        assert_abs_diff_eq!(lag.response(1e-9).magnitude(), 1.0, epsilon = 1e-9);
+        // Nyquist maps to s = ∞, where the gain is pole/zero.
+        assert_abs_diff_eq!(lag.response(SAMPLE_RATE / 2.0).re, 0.2, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn lead_for_phase_hits_target() {
+        let phase = 50.0_f64.to_radians();
+        let lead = Compensator::lead_for_phase(SAMPLE_RATE, 30.0, phase);
+        let response = Sampled::new(lead, SAMPLE_RATE).response(30.0);
+        assert_abs_diff_eq!(response.magnitude(), 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(response.phase(), phase, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn lag_for_gain_boosts_low_frequencies() {
+        let lag = Sampled::new(
+            Compensator::lag_for_gain(SAMPLE_RATE, 50.0, 10.0),
+            SAMPLE_RATE,
+        );
+        assert_abs_diff_eq!(lag.response(1e-9).magnitude(), 10.0, epsilon = 1e-6);
+        let crossover = lag.response(50.0);
+        assert_abs_diff_eq!(crossover.magnitude(), 1.0, epsilon = 0.01);
+        assert!(crossover.phase() > -6.0_f64.to_radians());
+    }
+
+    #[test]
+    fn lead_lag_is_series_of_sections() {
+        let lead_lag = Compensator::lead_lag(SAMPLE_RATE, 20.0, 80.0, 2.0, 0.5, 3.0);
+        let lead = Compensator::lead(SAMPLE_RATE, 20.0, 80.0, 3.0);
+        let lag = Compensator::lag(SAMPLE_RATE, 2.0, 0.5, 1.0);
+        let series = Series(
+            Sampled::new(lead, SAMPLE_RATE),
+            Sampled::new(lag, SAMPLE_RATE),
+        );
+        let biquad = Sampled::new(lead_lag, SAMPLE_RATE);
+        for freq in [0.1, 1.0, 10.0, 40.0, 200.0, 450.0] {
+            let expected = series.response(freq);
+            let actual = biquad.response(freq);
+            assert_abs_diff_eq!(actual.re, expected.re, epsilon = 1e-12);
+            assert_abs_diff_eq!(actual.im, expected.im, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn notch_depth_and_width() {
+        let notch = Sampled::new(
+            Compensator::notch(SAMPLE_RATE, 60.0, 2.0, 20.0),
+            SAMPLE_RATE,
+        );
+        assert_abs_diff_eq!(notch.response(60.0).magnitude(), 0.1, epsilon = 1e-12);
+        assert_abs_diff_eq!(notch.response(1e-9).magnitude(), 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(notch.response(1.0).magnitude(), 1.0, epsilon = 1e-3);
+
+        let full = Sampled::new(
+            Compensator::notch(SAMPLE_RATE, 60.0, 2.0, f64::INFINITY),
+            SAMPLE_RATE,
+        );
+        assert_abs_diff_eq!(full.response(60.0).magnitude(), 0.0, epsilon = 1e-12);
+    }
+
+    /// Returns the response of `gain / (1 + j·f)³`, three real poles at 1 Hz.
+    fn third_order_plant(gain: f64) -> impl Fn(f64) -> Bin<f64> {
+        move |freq: f64| {
+            let pole = complex_div(&Bin::new(1.0, 0.0), &Bin::new(1.0, freq));
+            let cubed = complex_mul(&complex_mul(&pole, &pole), &pole);
+            Bin::new(gain * cubed.re, gain * cubed.im)
+        }
+    }
+
+    #[test]
+    fn margins_of_third_order_plant() {
+        // The phase reaches −π at f = √3 Hz (each pole contributes −60°), where
+        // the magnitude is gain / 8.
+        let margins = margins(&third_order_plant(2.0), 0.01, 100.0, 400);
+        assert_abs_diff_eq!(
+            margins.phase_crossover.expect("phase crossover in range"),
+            3.0_f64.sqrt(),
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            margins.gain_margin_db.expect("phase crossover in range"),
+            20.0 * 4.0_f64.log10(),
+            epsilon = 1e-9
+        );
+        // |L| = 1 where 1 + f² = 2^(2/3).
+        let crossover = (2.0_f64.powf(2.0 / 3.0) - 1.0).sqrt();
+        assert_abs_diff_eq!(
+            margins.gain_crossover.expect("gain crossover in range"),
+            crossover,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            margins.phase_margin.expect("gain crossover in range"),
+            PI - 3.0 * crossover.atan(),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn lead_improves_phase_margin() {
+        let plant = third_order_plant(2.0);
+        let uncompensated = margins(&plant, 0.01, 100.0, 400);
+        let crossover = uncompensated
+            .gain_crossover
+            .expect("gain crossover in range");
+        let lead = Compensator::lead_for_phase(SAMPLE_RATE, crossover, 30.0_f64.to_radians());
+        let compensated = margins(
+            &Series(&plant, Sampled::new(lead, SAMPLE_RATE)),
+            0.01,
+            100.0,
+            400,
+        );
+        // Unit gain at the old crossover keeps it in place.
+        assert_abs_diff_eq!(
+            compensated.gain_crossover.expect("gain crossover in range"),
+            crossover,
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(
+            compensated.phase_margin.expect("gain crossover in range"),
+            uncompensated.phase_margin.expect("gain crossover in range") + 30.0_f64.to_radians(),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn margins_without_crossovers() {
+        let margins = margins(&|_freq: f64| Bin::new(0.5, 0.0), 1.0, 10.0, 10);
+        assert_eq!(margins.gain_crossover, None);
+        assert_eq!(margins.phase_crossover, None);
+        let integrator = |freq: f64| Bin::new(0.0, -1.0 / freq);
+        assert_abs_diff_eq!(
+            super::margins(&integrator, 0.1, 10.0, 10)
+                .phase_margin
+                .expect("gain crossover in range"),
+            FRAC_PI_2,
+            epsilon = 1e-12
+        );
+    }
+}
@@ -0,0 +1,581 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Proportional-integral-derivative (PID) controller.
+
+use num_traits::float::FloatCore;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The PID controller's gains, in continuous-time units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gains<T> {
+    /// The proportional gain `Kp`.
+    pub proportional: T,
+    /// The integral gain `Ki`, per second.
+    pub integral: T,
+    /// The derivative gain `Kd`, in seconds.
+    pub derivative: T,
+}
+
+/// How the integrator is kept from winding up while the output saturates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AntiWindup<T> {
+    /// No protection; the integrator keeps integrating while saturated.
+    None,
+    /// Conditional integration: the integrator holds while the output is
+    /// saturated and the error would drive it further into saturation.
+    Clamping,
+    /// Back-calculation: the integrator is bled by `tracking_gain` times the
+    /// amount of saturation, `(u − v)`, each second.
+    ///
+    /// A common choice is `tracking_gain = 1 / √(Ti·Td)` with `Ti = Kp / Ki` and
+    /// `Td = Kd / Kp`, or `1 / Ti` for PI control.
+    BackCalculation {
+        /// The tracking gain `Kt` (`> 0`), per second.
+        tracking_gain: T,
+    },
+}
+
+/// Whether the controller computes its output or follows a manual one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// The controller computes the output from the error.
+    Automatic,
+    /// The output is set by [`Pid::set_manual`]; the integrator tracks it so
+    /// that switching back to automatic is bumpless.
+    Manual,
+}
+
+/// The PID controller's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The controller gains.
+    ///
+    /// Changing them directly through the configuration causes a bump in the
+    /// output; use [`Pid::set_gains`] for gain scheduling.
+    pub gains: Gains<T>,
+    /// The sample period in seconds (`> 0`).
+    pub sample_time: T,
+    /// The setpoint weight `b` of the proportional term (usually `0..=1`).
+    ///
+    /// Values below `1` soften the proportional kick on setpoint steps without
+    /// affecting disturbance rejection.
+    pub proportional_weight: T,
+    /// The setpoint weight `c` of the derivative term (usually `0` or `1`).
+    ///
+    /// `0` differentiates the measurement only, avoiding a derivative kick on
+    /// setpoint steps.
+    pub derivative_weight: T,
+    /// The time constant `Tf` in seconds (`≥ 0`) of the derivative low-pass
+    /// filter; `0` disables filtering.
+    ///
+    /// A common choice is `Tf = Td / N` with `Td = Kd / Kp` and `N` between 2
+    /// and 20.
+    pub derivative_time_constant: T,
+    /// The lower output limit.
+    pub output_min: T,
+    /// The upper output limit.
+    pub output_max: T,
+    /// The anti-windup strategy.
+    pub anti_windup: AntiWindup<T>,
+}
+
+impl<T> Config<T>
+where
+    T: FloatCore,
+{
+    /// Creates an unlimited configuration with unit setpoint weights, no
+    /// derivative filter and clamping anti-windup.
+    pub fn new(gains: Gains<T>, sample_time: T) -> Self {
+        Self {
+            gains,
+            sample_time,
+            proportional_weight: T::one(),
+            derivative_weight: T::one(),
+            derivative_time_constant: T::zero(),
+            output_min: T::neg_infinity(),
+            output_max: T::infinity(),
+            anti_windup: AntiWindup::Clamping,
+        }
+    }
+}
+
+/// The PID controller's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The integral term, in output units.
+    pub integral: T,
+    /// The filtered derivative term, in output units.
+    pub derivative: T,
+    /// The previous weighted proportional error `b·r − y`.
+    pub proportional_error: T,
+    /// The previous weighted derivative error `c·r − y`, if any.
+    pub derivative_error: Option<T>,
+    /// The previous (saturated) output.
+    pub output: T,
+    /// The operating mode.
+    pub mode: Mode,
+}
+
+/// A PID controller, consuming `(setpoint, measurement)` pairs.
+///
+/// The output is computed in parallel form with setpoint weighting:
+///
+/// ```text
+/// P = Kp·(b·r − y)
+/// I = I + Ki·Ts·(r − y)                          (forward Euler)
+/// D = (Tf·D + Kd·(e_d − e_d_prev)) / (Tf + Ts)   (backward Euler, e_d = c·r − y)
+/// v = P + I + D
+/// u = clamp(v, output_min, output_max)
+/// ```
+///
+/// The integrator is protected from windup by the configured [`AntiWindup`]
+/// strategy. The derivative term is zero on the first sample, so starting the
+/// controller does not kick the output.
+///
+/// In [`Mode::Manual`] the output follows [`Pid::set_manual`], while the
+/// integrator tracks `u − P − D`; switching back to [`Mode::Automatic`] resumes
+/// from the manual output without a bump. [`Pid::set_gains`] likewise adjusts
+/// the integrator so that changing the gains does not bump the output.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1).
+/// - **Space:** O(1).
+#[derive(Clone, Debug)]
+pub struct Pid<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+impl<T> Pid<T>
+where
+    T: FloatCore,
+{
+    /// Returns the most recent output.
+    pub fn output(&self) -> T {
+        self.state.output
+    }
+
+    /// Returns the operating mode.
+    pub fn mode(&self) -> Mode {
+        self.state.mode
+    }
+
+    /// Switches to manual mode with the given output.
+    ///
+    /// The output is clamped to the output limits.
+    pub fn set_manual(&mut self, output: T) {
+        self.state.mode = Mode::Manual;
+        self.state.output = self.clamp(output);
+    }
+
+    /// Switches to automatic mode, continuing from the current output.
+    pub fn set_automatic(&mut self) {
+        self.state.mode = Mode::Automatic;
+    }
+
+    /// Changes the gains without bumping the output.
+    ///
+    /// The derivative term is rescaled to the new derivative gain, and the
+    /// integral term absorbs the change of the proportional and derivative
+    /// terms.
+    pub fn set_gains(&mut self, gains: Gains<T>) {
+        let old = self.config.gains;
+        let old_terms = old.proportional * self.state.proportional_error + self.state.derivative;
+        self.state.derivative = if old.derivative == T::zero() {
+            T::zero()
+        } else {
+            self.state.derivative * gains.derivative / old.derivative
+        };
+        let new_terms = gains.proportional * self.state.proportional_error + self.state.derivative;
+        self.state.integral = self.state.integral + old_terms - new_terms;
+        self.config.gains = gains;
+    }
+
+    fn clamp(&self, value: T) -> T {
+        value
+            .max(self.config.output_min)
+            .min(self.config.output_max)
+    }
+}
+
+impl<T> ConfigTrait for Pid<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for Pid<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for Pid<T>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// Creates a controller in automatic mode with zero output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_time` is not positive, `derivative_time_constant` is
+    /// negative, or `output_min` exceeds `output_max`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(
+            config.sample_time > T::zero(),
+            "Pid: sample time must be > 0"
+        );
+        assert!(
+            config.derivative_time_constant >= T::zero(),
+            "Pid: derivative time constant must be >= 0"
+        );
+        assert!(
+            config.output_min <= config.output_max,
+            "Pid: output_min must not exceed output_max"
+        );
+        let output = T::zero().max(config.output_min).min(config.output_max);
+        Self {
+            config,
+            state: State {
+                integral: output,
+                derivative: T::zero(),
+                proportional_error: T::zero(),
+                derivative_error: None,
+                output,
+                mode: Mode::Automatic,
+            },
+        }
+    }
+}
+
+impl<T> ConfigRef for Pid<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for Pid<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for Pid<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for Pid<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for Pid<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for Pid<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for Pid<T>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for Pid<T> where Self: Reset {}
+
+impl<T> Filter<(T, T)> for Pid<T>
+where
+    T: FloatCore,
+{
+    type Output = T;
+
+    fn filter(&mut self, (setpoint, measurement): (T, T)) -> Self::Output {
+        let config = &self.config;
+        let Gains {
+            proportional: kp,
+            integral: ki,
+            derivative: kd,
+        } = config.gains;
+        let ts = config.sample_time;
+
+        let error = setpoint - measurement;
+        let proportional_error = config.proportional_weight * setpoint - measurement;
+        let derivative_error = config.derivative_weight * setpoint - measurement;
+
+        let proportional = kp * proportional_error;
+        let tf = config.derivative_time_constant;
+        let derivative = match self.state.derivative_error {
+            Some(previous) => {
+                (tf * self.state.derivative + kd * (derivative_error - previous)) / (tf + ts)
+            }
+            None => T::zero(),
+        };
+
+        let output = match self.state.mode {
+            Mode::Automatic => {
+                let unsaturated = proportional + self.state.integral + derivative;
+                let output = self.clamp(unsaturated);
+                let increment = ki * ts * error;
+                self.state.integral = match config.anti_windup {
+                    AntiWindup::None => self.state.integral + increment,
+                    AntiWindup::Clamping => {
+                        let winding_up = (unsaturated > output && increment > T::zero())
+                            || (unsaturated < output && increment < T::zero());
+                        if winding_up {
+                            self.state.integral
+                        } else {
+                            self.state.integral + increment
+                        }
+                    }
+                    AntiWindup::BackCalculation { tracking_gain } => {
+                        self.state.integral
+                            + increment
+                            + tracking_gain * ts * (output - unsaturated)
+                    }
+                };
+                output
+            }
+            Mode::Manual => {
+                // Track the manual output, so that automatic mode resumes from it.
+                self.state.integral = self.state.output - proportional - derivative;
+                self.state.output
+            }
+        };
+
+        self.state.derivative = derivative;
+        self.state.proportional_error = proportional_error;
+        self.state.derivative_error = Some(derivative_error);
+        self.state.output = output;
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    const TS: f64 = 0.01;
+
+    /// A first-order plant `τ·dy/dt = −y + u` with `τ = 0.5 s`, integrated with
+    /// forward Euler.
+    struct Plant(f64);
+
+    impl Plant {
+        fn step(&mut self, input: f64) -> f64 {
+            self.0 += TS / 0.5 * (input - self.0);
+            self.0
+        }
+    }
+
+    fn gains() -> Gains<f64> {
+        Gains {
+            proportional: 2.0,
+            integral: 4.0,
+            derivative: 0.05,
+        }
+    }
+
+    /// Runs the closed loop for `steps` samples and returns the measurements.
+    fn run(pid: &mut Pid<f64>, plant: &mut Plant, setpoint: f64, steps: usize) -> Vec<f64> {
+        (0..steps)
+            .map(|_| {
+                let output = pid.filter((setpoint, plant.0));
+                plant.step(output)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tracks_setpoint_without_steady_state_error() {
+        let mut pid = Pid::with_config(Config {
+            derivative_time_constant: 0.02,
+            ..Config::new(gains(), TS)
+        });
+        let mut plant = Plant(0.0);
+        let response = run(&mut pid, &mut plant, 1.0, 2000);
+        assert_abs_diff_eq!(response[response.len() - 1], 1.0, epsilon = 1e-6);
+        // The integrator alone holds the plant at the setpoint.
+        assert_abs_diff_eq!(pid.state_mut().integral, 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn first_sample_has_no_derivative_kick() {
+        let mut pid = Pid::with_config(Config::new(
+            Gains {
+                proportional: 0.0,
+                integral: 0.0,
+                derivative: 1.0,
+            },
+            TS,
+        ));
+        assert_abs_diff_eq!(pid.filter((1.0, 0.0)), 0.0);
+        // A measurement ramp of 1 per second yields −Kd.
+        assert_abs_diff_eq!(pid.filter((1.0, TS)), -1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn derivative_filter_smooths_step() {
+        let mut pid = Pid::with_config(Config {
+            derivative_time_constant: 0.09,
+            ..Config::new(
+                Gains {
+                    proportional: 0.0,
+                    integral: 0.0,
+                    derivative: 0.1,
+                },
+                TS,
+            )
+        });
+        let _ = pid.filter((0.0, 0.0));
+        // Kd·Δe / (Tf + Ts), then decaying by Tf / (Tf + Ts).
+        assert_abs_diff_eq!(pid.filter((1.0, 0.0)), 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(pid.filter((1.0, 0.0)), 0.9, epsilon = 1e-12);
+        assert_abs_diff_eq!(pid.filter((1.0, 0.0)), 0.81, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn setpoint_weights_remove_kicks() {
+        let mut pid = Pid::with_config(Config {
+            proportional_weight: 0.0,
+            derivative_weight: 0.0,
+            ..Config::new(gains(), TS)
+        });
+        let _ = pid.filter((0.0, 0.0));
+        // Only the integral reacts to a setpoint step, one sample later.
+        assert_abs_diff_eq!(pid.filter((1.0, 0.0)), 0.0);
+        assert_abs_diff_eq!(pid.filter((1.0, 0.0)), 4.0 * TS, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn output_is_limited() {
+        let mut pid = Pid::with_config(Config {
+            output_min: -0.5,
+            output_max: 1.5,
+            ..Config::new(gains(), TS)
+        });
+        assert_abs_diff_eq!(pid.filter((10.0, 0.0)), 1.5);
+        assert_abs_diff_eq!(pid.filter((-10.0, 0.0)), -0.5);
+    }
+
+    /// Returns the overshoot of a limited loop settling to a setpoint of 1.
+    fn overshoot(anti_windup: AntiWindup<f64>) -> f64 {
+        let mut pid = Pid::with_config(Config {
+            output_max: 1.2,
+            output_min: -1.2,
+            anti_windup,
+            ..Config::new(gains(), TS)
+        });
+        let mut plant = Plant(0.0);
+        let response = run(&mut pid, &mut plant, 1.0, 3000);
+        assert_abs_diff_eq!(response[response.len() - 1], 1.0, epsilon = 1e-4);
+        response.iter().fold(0.0_f64, |max, &y| max.max(y)) - 1.0
+    }
+
+    #[test]
+    fn anti_windup_reduces_overshoot() {
+        let unprotected = overshoot(AntiWindup::None);
+        let clamping = overshoot(AntiWindup::Clamping);
+        let back_calculation = overshoot(AntiWindup::BackCalculation { tracking_gain: 2.0 });
+        assert!(unprotected > 0.05, "unprotected overshoot {unprotected}");
+        assert!(
+            clamping < unprotected / 2.0,
+            "clamping overshoot {clamping}"
+        );
+        assert!(
+            back_calculation < unprotected / 2.0,
+            "back-calculation overshoot {back_calculation}"
+        );
+    }
+
+    #[test]
+    fn manual_to_automatic_transfer_is_bumpless() {
+        let mut pid = Pid::with_config(Config::new(gains(), TS));
+        let mut plant = Plant(0.0);
+        pid.set_manual(0.7);
+        let manual = run(&mut pid, &mut plant, 1.0, 200);
+        assert_abs_diff_eq!(pid.output(), 0.7);
+        assert_eq!(pid.mode(), Mode::Manual);
+
+        pid.set_automatic();
+        let output = pid.filter((1.0, plant.0));
+        assert_abs_diff_eq!(output, 0.7, epsilon = 0.01);
+        assert!(manual.iter().all(|y| *y < 0.7));
+    }
+
+    #[test]
+    fn gain_change_is_bumpless() {
+        let config = Config {
+            proportional_weight: 0.5,
+            derivative_time_constant: 0.02,
+            ..Config::new(gains(), TS)
+        };
+        let new_gains = Gains {
+            proportional: 5.0,
+            integral: 1.0,
+            derivative: 0.2,
+        };
+        let mut pid = Pid::with_config(config);
+        let mut plant = Plant(0.0);
+        let _ = run(&mut pid, &mut plant, 1.0, 1000);
+        let before = pid.output();
+
+        // Swapping the gains in the configuration bumps the proportional term …
+        let (mut config, state) = pid.clone().into_guts();
+        config.gains = new_gains;
+        let mut bumped = Pid::from_guts((config, state));
+        assert!((bumped.filter((1.0, plant.0)) - before).abs() > 1.0);
+
+        // … while `set_gains` moves it into the integrator.
+        pid.set_gains(new_gains);
+        assert_abs_diff_eq!(pid.filter((1.0, plant.0)), before, epsilon = 1e-6);
+        let response = run(&mut pid, &mut plant, 1.0, 1000);
+        assert_abs_diff_eq!(response[response.len() - 1], 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn reset_clears_state() {
+        let mut pid = Pid::with_config(Config::new(gains(), TS));
+        let first = pid.filter((1.0, 0.0));
+        let _ = pid.filter((1.0, 0.5));
+        let mut pid = pid.reset();
+        assert_abs_diff_eq!(pid.filter((1.0, 0.0)), first);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pid: output_min must not exceed output_max")]
+    fn rejects_inverted_limits() {
+        let _ = Pid::with_config(Config {
+            output_min: 1.0,
+            output_max: -1.0,
+            ..Config::new(gains(), TS)
+        });
+    }
+}
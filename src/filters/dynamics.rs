@@ -0,0 +1,159 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Dynamics processors; level-dependent gain control.
+//!
+//! Dynamics processors measure the level of their input and apply a gain that
+//! depends on it, e.g. to hold the output at a constant level or to reduce its
+//! dynamic range.
+//!
+//! # When to use which filter
+//!
+//! | Filter                         | Purpose                                                    |
+//! | ------------------------------ | ---------------------------------------------------------- |
+//! | `agc::Agc`                     | Hold the output magnitude at a target level                |
+//! | `compressor::Compressor`       | Reduce the level above a threshold by a ratio              |
+//! | `limiter::Limiter`             | Guarantee the output never exceeds a ceiling (lookahead)   |
+//! | `expander::Expander`           | Reduce the level below a threshold by a ratio              |
+//! | `gate::Gate`                   | Mute the signal while its level is below a threshold       |
+//!
+//! - **`Agc`** (automatic gain control) normalizes a signal of unknown, slowly varying
+//!   level, e.g. a radio baseband or microphone front end. It works on real and (with
+//!   the `complex` feature) complex samples, and its gain doubles as an RSSI estimate.
+//! - **`Compressor`**, **`Expander`** and **`Gate`** are feed-forward
+//!   [`Processor`](processor::Processor)s that differ only in their
+//!   [`GainComputer`]: a static curve from input level to gain, both in dB.
+//!   The processor adds the level [`Detector`], attack/release smoothing of the
+//!   gain and makeup gain.
+//! - **`Limiter`** delays the signal by its lookahead so that the gain is already
+//!   reduced when a peak arrives. Unlike a compressor with an infinite ratio, it
+//!   never lets a sample through above the ceiling.
+//!
+//! # Multichannel input
+//!
+//! Every processor except `Agc` accepts any [`Frame`]: a single sample, or an array
+//! `[T; C]` of one sample per channel. Array frames are *linked*: the detector
+//! sees the loudest (peak) or mean power (RMS) across channels, and the same gain
+//! is applied to every channel, preserving the stereo image. For unlinked
+//! processing, run one processor per channel.
+//!
+//! # See also
+//!
+//! - [`super::iir::envelope`]: the attack/release level detector on its own.
+
+use num_traits::Float;
+
+pub mod agc;
+
+pub mod compressor;
+
+pub mod expander;
+
+pub mod gate;
+
+pub mod limiter;
+
+pub mod processor;
+
+/// A static gain curve, mapping a detected input level to a gain, both in dB.
+///
+/// Curves may keep per-instance state (e.g. a gate's open/closed flag). The
+/// state lives in the processor's state, so the curve itself stays part of the
+/// configuration.
+pub trait GainComputer<T> {
+    /// The curve's per-instance state.
+    type State;
+
+    /// Returns the state of a freshly constructed processor.
+    fn initial_state(&self) -> Self::State;
+
+    /// Returns the gain in dB for an input level of `level_db`.
+    fn gain_db(&self, state: &mut Self::State, level_db: T) -> T;
+}
+
+/// The level detector of a dynamics [`Processor`](processor::Processor).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Detector<T> {
+    /// The instantaneous peak magnitude of each frame.
+    ///
+    /// Reacts to every transient; pair with a non-zero attack time to avoid
+    /// distorting low frequencies.
+    Peak,
+    /// The root of the exponentially averaged mean power.
+    ///
+    /// `coefficient` (0..1) is the averaging weight of the newest frame; the
+    /// averaging time constant is about `1 / coefficient` samples. RMS detection
+    /// follows perceived loudness more closely than peak detection.
+    Rms {
+        /// The averaging weight of the newest frame (0..1).
+        coefficient: T,
+    },
+}
+
+/// A frame of one sample per channel, as processed by dynamics processors.
+pub trait Frame<T>: Clone {
+    /// Returns a silent frame.
+    fn silence() -> Self;
+
+    /// Returns the largest magnitude across channels.
+    fn peak(&self) -> T;
+
+    /// Returns the mean power across channels.
+    fn power(&self) -> T;
+
+    /// Returns the frame with every channel multiplied by `gain`.
+    #[must_use]
+    fn scale(self, gain: T) -> Self;
+}
+
+macro_rules! impl_scalar_frame {
+    ($($t:ty),*) => {
+        $(
+            impl Frame<$t> for $t {
+                fn silence() -> Self {
+                    0.0
+                }
+
+                fn peak(&self) -> $t {
+                    self.abs()
+                }
+
+                fn power(&self) -> $t {
+                    self * self
+                }
+
+                fn scale(self, gain: $t) -> Self {
+                    self * gain
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_frame!(f32, f64);
+
+impl<T, const C: usize> Frame<T> for [T; C]
+where
+    T: Float,
+{
+    fn silence() -> Self {
+        [T::zero(); C]
+    }
+
+    fn peak(&self) -> T {
+        self.iter()
+            .fold(T::zero(), |peak, sample| peak.max(sample.abs()))
+    }
+
+    fn power(&self) -> T {
+        let sum = self
+            .iter()
+            .fold(T::zero(), |sum, sample| sum + *sample * *sample);
+        T::from(C).map_or(T::zero(), |channels| sum / channels)
+    }
+
+    fn scale(self, gain: T) -> Self {
+        self.map(|sample| sample * gain)
+    }
+}
@@ -0,0 +1,410 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Automatic gain control.
+//!
+//! Scales a signal of unknown, slowly varying level so that its magnitude settles
+//! at a target level, e.g. ahead of a demodulator or a fixed-point stage.
+
+use num_traits::Float;
+
+#[cfg(feature = "complex")]
+use crate::complex::Complex;
+use crate::math::{amplitude_to_db, db_to_amplitude};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The domain in which an [`Agc`] smooths its level estimate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Domain {
+    /// Smooth the input magnitude itself.
+    ///
+    /// A level step by a factor `a` is tracked at a rate proportional to the
+    /// magnitude difference, so large drops recover slowly.
+    #[default]
+    Linear,
+    /// Smooth the input magnitude in decibels.
+    ///
+    /// The gain then moves by a constant number of dB per time constant
+    /// regardless of the absolute level, the usual choice for radio receivers.
+    Decibel,
+}
+
+/// The AGC's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The target output magnitude (> 0).
+    pub target: T,
+    /// The attack smoothing coefficient (0..1), used while the level rises.
+    /// Higher values make the gain drop faster on loud input.
+    pub attack: T,
+    /// The decay smoothing coefficient (0..1), used while the level falls.
+    /// Higher values make the gain recover faster on quiet input.
+    pub decay: T,
+    /// The maximum linear gain (> 0, finite).
+    ///
+    /// Input below `target / max_gain` is amplified by exactly `max_gain`, so
+    /// noise is not amplified without bound during silence.
+    pub max_gain: T,
+    /// The number of samples the level is held after a rise before decaying.
+    ///
+    /// Hang time keeps the gain from pumping up between syllables or symbols.
+    pub hang: usize,
+    /// The domain in which the level estimate is smoothed.
+    pub domain: Domain,
+}
+
+/// The AGC's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The smoothed level estimate, in units of the configured [`Domain`]
+    /// (`None` before the first sample).
+    pub level: Option<T>,
+    /// The number of samples left before the level may decay.
+    pub hold: usize,
+    /// The linear gain applied to the most recent sample.
+    pub gain: T,
+}
+
+/// An automatic gain control filter.
+///
+/// The filter estimates the input level with an attack/decay follower like
+/// [`Envelope`](crate::filters::iir::envelope::Envelope), holds it for
+/// `hang` samples after every rise, and multiplies the input by
+/// `target / level`, clamped to `max_gain`:
+///
+/// - `magnitude = max(|input|, target / max_gain)`
+/// - if `magnitude > level`: `level += attack · (magnitude − level)`, restart the hang timer
+/// - else if the hang timer is running: keep `level`
+/// - else: `level += decay · (magnitude − level)`
+/// - `output = input · target / level`
+///
+/// In the [`Domain::Decibel`] domain, `magnitude` and `level` are in dB and the
+/// gain is `target_dB − level_dB`. The first sample initializes the level
+/// directly, so there is no start-up transient.
+///
+/// The filter accepts real samples and, with the `complex` feature, complex
+/// samples (using their modulus as magnitude).
+///
+/// # RSSI
+///
+/// [`gain_db`](Self::gain_db) is the received signal strength relative to the
+/// target: `RSSI_dB = 20·log₁₀(target) − gain_db`.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); one magnitude, one smoothing step and one
+///   division (plus a logarithm and exponential in the decibel domain).
+/// - **Space:** O(1); stores the level, hang counter and gain.
+#[derive(Clone, Debug)]
+pub struct Agc<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+impl<T> Agc<T>
+where
+    T: Float,
+{
+    /// Returns the linear gain applied to the most recent sample.
+    #[must_use]
+    pub fn gain(&self) -> T {
+        self.state.gain
+    }
+
+    /// Returns the gain applied to the most recent sample, in dB.
+    #[must_use]
+    pub fn gain_db(&self) -> T {
+        amplitude_to_db(self.state.gain)
+    }
+
+    fn update(&mut self, magnitude: T) -> T {
+        let Config {
+            target,
+            attack,
+            decay,
+            max_gain,
+            hang,
+            domain,
+        } = self.config;
+
+        let magnitude = magnitude.max(target / max_gain);
+        let measurement = match domain {
+            Domain::Linear => magnitude,
+            Domain::Decibel => amplitude_to_db(magnitude),
+        };
+
+        let level = match self.state.level {
+            None => measurement,
+            Some(level) if measurement > level => {
+                self.state.hold = hang;
+                level + attack * (measurement - level)
+            }
+            Some(level) if self.state.hold > 0 => {
+                self.state.hold -= 1;
+                level
+            }
+            Some(level) => level + decay * (measurement - level),
+        };
+        self.state.level = Some(level);
+
+        let gain = match domain {
+            Domain::Linear => target / level,
+            Domain::Decibel => db_to_amplitude(amplitude_to_db(target) - level),
+        };
+        self.state.gain = gain.min(max_gain);
+        self.state.gain
+    }
+}
+
+impl<T> ConfigTrait for Agc<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for Agc<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for Agc<T>
+where
+    T: Float,
+{
+    type Output = Self;
+
+    /// Creates an [`Agc`] with unity gain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` or `max_gain` is not positive and finite, or if
+    /// `attack` or `decay` is outside `[0, 1]`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        let positive = |value: T| value > T::zero() && value.is_finite();
+        let unit = |value: T| value >= T::zero() && value <= T::one();
+        assert!(positive(config.target), "Agc: target must be > 0");
+        assert!(
+            positive(config.max_gain),
+            "Agc: max gain must be > 0 and finite"
+        );
+        assert!(unit(config.attack), "Agc: attack must be in [0, 1]");
+        assert!(unit(config.decay), "Agc: decay must be in [0, 1]");
+        let state = State {
+            level: None,
+            hold: 0,
+            gain: T::one(),
+        };
+        Self { config, state }
+    }
+}
+
+impl<T> ConfigRef for Agc<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for Agc<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for Agc<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for Agc<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for Agc<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for Agc<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for Agc<T>
+where
+    T: Float,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for Agc<T> where Self: Reset {}
+
+impl<T> Filter<T> for Agc<T>
+where
+    T: Float,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        input * self.update(input.abs())
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T> Filter<Complex<T>> for Agc<T>
+where
+    T: Float,
+{
+    type Output = Complex<T>;
+
+    fn filter(&mut self, input: Complex<T>) -> Self::Output {
+        input.scale(self.update(input.norm()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    fn config(domain: Domain) -> Config<f64> {
+        Config {
+            target: 1.0,
+            attack: 0.5,
+            decay: 0.1,
+            max_gain: 1000.0,
+            hang: 0,
+            domain,
+        }
+    }
+
+    #[test]
+    fn first_sample_is_normalized() {
+        for domain in [Domain::Linear, Domain::Decibel] {
+            let mut agc = Agc::with_config(config(domain));
+            assert_abs_diff_eq!(agc.gain(), 1.0);
+            assert_abs_diff_eq!(agc.filter(-0.25), -1.0, epsilon = 1e-12);
+            assert_abs_diff_eq!(agc.gain(), 4.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn level_drop_recovers_to_target() {
+        for domain in [Domain::Linear, Domain::Decibel] {
+            let mut agc = Agc::with_config(config(domain));
+            let _ = agc.filter(2.0);
+            let mut output = 0.0;
+            for _ in 0..500 {
+                output = agc.filter(0.01);
+            }
+            assert_abs_diff_eq!(output, 1.0, epsilon = 1e-6);
+            assert_abs_diff_eq!(agc.gain_db(), 40.0, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn decibel_domain_decays_by_constant_fraction_in_db() {
+        let mut agc = Agc::with_config(config(Domain::Decibel));
+        let _ = agc.filter(1.0);
+        let _ = agc.filter(0.01);
+        // The level moves 10% of the way from 0 dB to −40 dB.
+        assert_abs_diff_eq!(agc.gain_db(), 4.0, epsilon = 1e-9);
+
+        let mut agc = Agc::with_config(config(Domain::Linear));
+        let _ = agc.filter(1.0);
+        let _ = agc.filter(0.01);
+        // The level moves 10% of the way from 1.0 to 0.01.
+        assert_abs_diff_eq!(agc.gain(), 1.0 / 0.901, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn gain_is_clamped_to_max_gain() {
+        let mut agc = Agc::with_config(Config {
+            max_gain: 100.0,
+            ..config(Domain::Decibel)
+        });
+        for _ in 0..100 {
+            assert_abs_diff_eq!(agc.filter(1e-6), 1e-4, epsilon = 1e-12);
+        }
+        assert_abs_diff_eq!(agc.gain(), 100.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(agc.filter(0.0), 0.0);
+    }
+
+    #[test]
+    fn hang_holds_gain_after_a_peak() {
+        let mut agc = Agc::with_config(Config {
+            hang: 3,
+            ..config(Domain::Linear)
+        });
+        let _ = agc.filter(0.5);
+        let _ = agc.filter(1.5);
+        let held = agc.gain();
+        for _ in 0..3 {
+            let _ = agc.filter(0.1);
+            assert_abs_diff_eq!(agc.gain(), held);
+        }
+        let _ = agc.filter(0.1);
+        assert!(agc.gain() > held);
+    }
+
+    #[test]
+    fn attack_reduces_gain_on_louder_input() {
+        let mut agc = Agc::with_config(config(Domain::Linear));
+        let _ = agc.filter(1.0);
+        let _ = agc.filter(3.0);
+        // The level moves half-way from 1.0 to 3.0.
+        assert_abs_diff_eq!(agc.gain(), 0.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn reset_restores_unity_gain() {
+        let mut agc = Agc::with_config(config(Domain::Linear));
+        let _ = agc.filter(0.1);
+        let mut agc = agc.reset();
+        assert_abs_diff_eq!(agc.gain(), 1.0);
+        assert_abs_diff_eq!(agc.filter(4.0), 1.0, epsilon = 1e-12);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn complex_tone_settles_at_target_magnitude() {
+        use crate::complex::Complex64;
+
+        let mut agc = Agc::with_config(Config {
+            target: 0.5,
+            ..config(Domain::Decibel)
+        });
+        let mut output = Complex64::new(0.0, 0.0);
+        for n in 0..200_u32 {
+            let input = Complex64::from_polar(0.02, 0.3 * f64::from(n));
+            output = agc.filter(input);
+            assert_abs_diff_eq!(output.arg(), input.arg(), epsilon = 1e-9);
+        }
+        assert_abs_diff_eq!(output.norm(), 0.5, epsilon = 1e-9);
+        assert_abs_diff_eq!(agc.gain_db(), 27.958_800_173_440_75, epsilon = 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Agc: max gain must be > 0 and finite")]
+    fn rejects_infinite_max_gain() {
+        let _ = Agc::with_config(Config {
+            max_gain: f64::INFINITY,
+            ..config(Domain::Linear)
+        });
+    }
+}
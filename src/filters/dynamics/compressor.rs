@@ -0,0 +1,148 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Downward compressor.
+
+use num_traits::Float;
+
+use super::processor::{Config as ProcessorConfig, Processor};
+use super::GainComputer;
+
+/// The compressor's configuration.
+pub type Config<T> = ProcessorConfig<T, CompressorCurve<T>>;
+
+/// A downward compressor: a [`Processor`] with a [`CompressorCurve`].
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(C) for C channels; see [`Processor`].
+/// - **Space:** O(1).
+pub type Compressor<T> = Processor<T, CompressorCurve<T>>;
+
+/// The static curve of a downward compressor.
+///
+/// Above the threshold, every `ratio` dB of input level rise yields 1 dB of
+/// output level rise. A soft knee of width `knee_db` blends the two slopes with
+/// a quadratic segment centered on the threshold:
+///
+/// ```text
+/// gain = 0                                     for x − T < −W/2
+/// gain = (1/R − 1)·(x − T + W/2)² / (2W)       for |x − T| ≤ W/2
+/// gain = (1/R − 1)·(x − T)                     for x − T > W/2
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompressorCurve<T> {
+    /// The threshold `T` in dB.
+    pub threshold_db: T,
+    /// The compression ratio `R ≥ 1`; `1` disables compression.
+    pub ratio: T,
+    /// The knee width `W ≥ 0` in dB; `0` gives a hard knee.
+    pub knee_db: T,
+}
+
+impl<T> GainComputer<T> for CompressorCurve<T>
+where
+    T: Float,
+{
+    type State = ();
+
+    fn initial_state(&self) {}
+
+    fn gain_db(&self, _state: &mut (), level_db: T) -> T {
+        let two = T::one() + T::one();
+        let slope = self.ratio.recip() - T::one();
+        let over = level_db - self.threshold_db;
+        let half_knee = self.knee_db / two;
+        if over <= -half_knee {
+            T::zero()
+        } else if over < half_knee {
+            let into_knee = over + half_knee;
+            slope * into_knee * into_knee / (two * self.knee_db)
+        } else {
+            slope * over
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::super::Detector;
+    use super::*;
+    use crate::math::{amplitude_to_db, db_to_amplitude};
+    use crate::traits::{Filter, WithConfig};
+
+    fn curve(knee_db: f64) -> CompressorCurve<f64> {
+        CompressorCurve {
+            threshold_db: -20.0,
+            ratio: 4.0,
+            knee_db,
+        }
+    }
+
+    #[test]
+    fn hard_knee_curve() {
+        let curve = curve(0.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -40.0), 0.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -20.0), 0.0);
+        // 8 dB over the threshold comes out 2 dB over.
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -12.0), -6.0);
+    }
+
+    #[test]
+    fn soft_knee_is_continuous_and_centered() {
+        let curve = curve(10.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -25.0), 0.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -15.0), -3.75, epsilon = 1e-12);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -15.0 - 1e-9), -3.75, epsilon = 1e-6);
+        // At the threshold, the knee reduces by (1 − 1/R)·W/8.
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -20.0), -0.9375, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn soft_knee_never_exceeds_hard_knee_output() {
+        let hard = curve(0.0);
+        let soft = curve(10.0);
+        for step in 0..=60_u32 {
+            let level_db = -50.0 + f64::from(step);
+            assert!(soft.gain_db(&mut (), level_db) <= hard.gain_db(&mut (), level_db) + 1e-12);
+        }
+    }
+
+    #[test]
+    fn compresses_steady_tone_with_makeup_gain() {
+        let mut compressor = Compressor::with_config(Config {
+            curve: curve(0.0),
+            detector: Detector::Rms { coefficient: 0.01 },
+            attack: 0.1,
+            release: 0.01,
+            makeup_db: 3.0,
+        });
+        // A 0 dB RMS square wave is 20 dB over the threshold.
+        let mut output = 0.0;
+        for n in 0..5000_u32 {
+            let input = if n % 2 == 0 { 1.0 } else { -1.0 };
+            output = compressor.filter(input);
+        }
+        let expected = db_to_amplitude(-20.0 + 20.0 / 4.0 + 3.0);
+        assert_abs_diff_eq!(output.abs(), expected, epsilon = 1e-6);
+        assert_abs_diff_eq!(compressor.gain_reduction_db(), -15.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(amplitude_to_db(output.abs()), -12.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn quiet_input_passes_unchanged() {
+        let mut compressor = Compressor::with_config(Config {
+            curve: curve(6.0),
+            detector: Detector::Peak,
+            attack: 1.0,
+            release: 1.0,
+            makeup_db: 0.0,
+        });
+        for input in [0.0, 0.01, -0.02, 0.05] {
+            assert_abs_diff_eq!(compressor.filter(input), input, epsilon = 1e-15);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Downward expander.
+
+use num_traits::Float;
+
+use super::processor::{Config as ProcessorConfig, Processor};
+use super::GainComputer;
+
+/// The expander's configuration.
+pub type Config<T> = ProcessorConfig<T, ExpanderCurve<T>>;
+
+/// A downward expander: a [`Processor`] with an [`ExpanderCurve`].
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(C) for C channels; see [`Processor`].
+/// - **Space:** O(1).
+pub type Expander<T> = Processor<T, ExpanderCurve<T>>;
+
+/// The static curve of a downward expander.
+///
+/// Below the threshold, every 1 dB of input level drop yields `ratio` dB of
+/// output level drop, down to at most `range_db` of attenuation. A soft knee of
+/// width `knee_db` blends the two slopes with a quadratic segment centered on
+/// the threshold:
+///
+/// ```text
+/// gain = (R − 1)·(x − T)                       for x − T < −W/2
+/// gain = −(R − 1)·(x − T − W/2)² / (2W)        for |x − T| ≤ W/2
+/// gain = 0                                     for x − T > W/2
+/// ```
+///
+/// and the result is clamped to `gain ≥ range_db`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExpanderCurve<T> {
+    /// The threshold `T` in dB.
+    pub threshold_db: T,
+    /// The expansion ratio `R ≥ 1`; `1` disables expansion.
+    pub ratio: T,
+    /// The knee width `W ≥ 0` in dB; `0` gives a hard knee.
+    pub knee_db: T,
+    /// The maximum attenuation in dB (≤ 0), e.g. `−40`.
+    pub range_db: T,
+}
+
+impl<T> GainComputer<T> for ExpanderCurve<T>
+where
+    T: Float,
+{
+    type State = ();
+
+    fn initial_state(&self) {}
+
+    fn gain_db(&self, _state: &mut (), level_db: T) -> T {
+        let two = T::one() + T::one();
+        let slope = self.ratio - T::one();
+        let over = level_db - self.threshold_db;
+        let half_knee = self.knee_db / two;
+        let gain = if over >= half_knee {
+            T::zero()
+        } else if over > -half_knee {
+            let into_knee = over - half_knee;
+            -slope * into_knee * into_knee / (two * self.knee_db)
+        } else {
+            slope * over
+        };
+        gain.max(self.range_db)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::super::Detector;
+    use super::*;
+    use crate::traits::{Filter, WithConfig};
+
+    fn curve(knee_db: f64) -> ExpanderCurve<f64> {
+        ExpanderCurve {
+            threshold_db: -40.0,
+            ratio: 2.0,
+            knee_db,
+            range_db: -30.0,
+        }
+    }
+
+    #[test]
+    fn hard_knee_curve() {
+        let curve = curve(0.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -10.0), 0.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -40.0), 0.0);
+        // 10 dB under the threshold comes out 20 dB under.
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -50.0), -10.0);
+    }
+
+    #[test]
+    fn attenuation_is_limited_to_range() {
+        let curve = curve(0.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -100.0), -30.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -3000.0), -30.0);
+    }
+
+    #[test]
+    fn soft_knee_is_continuous() {
+        let curve = curve(8.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -36.0), 0.0);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -44.0), -4.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -44.0 + 1e-9), -4.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(curve.gain_db(&mut (), -40.0), -1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn silence_is_attenuated_by_range() {
+        let mut expander = Expander::with_config(Config {
+            curve: curve(0.0),
+            detector: Detector::Peak,
+            attack: 1.0,
+            release: 1.0,
+            makeup_db: 0.0,
+        });
+        assert_abs_diff_eq!(expander.filter(0.0), 0.0);
+        assert_abs_diff_eq!(expander.gain_db(), -30.0);
+        assert_abs_diff_eq!(expander.filter(0.5), 0.5, epsilon = 1e-12);
+    }
+}
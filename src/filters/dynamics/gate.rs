@@ -0,0 +1,149 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Noise gate with hysteresis.
+
+use num_traits::Float;
+
+use super::processor::{Config as ProcessorConfig, Processor};
+use super::GainComputer;
+
+/// The gate's configuration.
+pub type Config<T> = ProcessorConfig<T, GateCurve<T>>;
+
+/// A noise gate: a [`Processor`] with a [`GateCurve`].
+///
+/// The processor's `attack` coefficient sets how fast the gate closes and its
+/// `release` coefficient how fast it opens.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(C) for C channels; see [`Processor`].
+/// - **Space:** O(1).
+pub type Gate<T> = Processor<T, GateCurve<T>>;
+
+/// The gain curve of a noise gate with hysteresis.
+///
+/// The gate opens (gain 0 dB) once the level reaches `open_db`, and closes
+/// (gain `range_db`) once the level falls below `close_db`. Between the two
+/// thresholds it keeps its current state, like a
+/// [`Schmitt`](crate::filters::classify::schmitt::Schmitt) trigger, so a level
+/// hovering around a single threshold does not chatter. The gate starts closed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GateCurve<T> {
+    /// The level in dB at which the gate opens.
+    pub open_db: T,
+    /// The level in dB below which the gate closes (≤ `open_db`).
+    pub close_db: T,
+    /// The attenuation in dB (≤ 0, finite) while closed, e.g. `−80`.
+    pub range_db: T,
+}
+
+impl<T> GainComputer<T> for GateCurve<T>
+where
+    T: Float,
+{
+    /// Whether the gate is open.
+    type State = bool;
+
+    /// Returns a closed gate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `close_db` is above `open_db`.
+    fn initial_state(&self) -> bool {
+        assert!(
+            self.close_db <= self.open_db,
+            "GateCurve: close threshold must not exceed open threshold"
+        );
+        false
+    }
+
+    fn gain_db(&self, open: &mut bool, level_db: T) -> T {
+        *open = if level_db >= self.open_db {
+            true
+        } else if level_db < self.close_db {
+            false
+        } else {
+            *open
+        };
+        if *open {
+            T::zero()
+        } else {
+            self.range_db
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::super::Detector;
+    use super::*;
+    use crate::math::db_to_amplitude;
+    use crate::traits::{Filter, StateMut, WithConfig};
+
+    fn gate() -> Gate<f64> {
+        Gate::with_config(Config {
+            curve: GateCurve {
+                open_db: -20.0,
+                close_db: -30.0,
+                range_db: -80.0,
+            },
+            detector: Detector::Peak,
+            attack: 1.0,
+            release: 1.0,
+            makeup_db: 0.0,
+        })
+    }
+
+    #[test]
+    fn hysteresis_keeps_state_between_thresholds() {
+        let mut gate = gate();
+        let levels = [-25.0, -15.0, -25.0, -29.0, -31.0, -25.0, -20.0];
+        let states: Vec<bool> = levels
+            .iter()
+            .map(|&level_db| {
+                let _ = gate.filter(db_to_amplitude(level_db));
+                gate.state_mut().curve
+            })
+            .collect();
+        assert_eq!(states, [false, true, true, true, false, false, true]);
+    }
+
+    #[test]
+    fn closed_gate_attenuates_by_range() {
+        let mut gate = gate();
+        let quiet = db_to_amplitude(-40.0);
+        assert_abs_diff_eq!(gate.filter(quiet), quiet * 1e-4, epsilon = 1e-15);
+        assert_abs_diff_eq!(gate.filter(0.5), 0.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn linked_channels_open_together() {
+        let mut gate = gate();
+        let quiet = db_to_amplitude(-40.0);
+        let output = gate.filter([0.5, quiet]);
+        assert_abs_diff_eq!(output.as_slice(), [0.5, quiet].as_slice(), epsilon = 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "GateCurve: close threshold must not exceed open threshold")]
+    fn rejects_inverted_thresholds() {
+        let _ = Gate::with_config(Config {
+            curve: GateCurve {
+                open_db: -30.0,
+                close_db: -20.0,
+                range_db: -80.0,
+            },
+            detector: Detector::Peak,
+            attack: 1.0,
+            release: 1.0,
+            makeup_db: 0.0,
+        });
+    }
+}
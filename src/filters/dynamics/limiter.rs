@@ -0,0 +1,437 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Brickwall lookahead limiter.
+
+use core::marker::PhantomData;
+
+use circular_buffer::FixedCircularBuffer;
+use num_traits::Float;
+
+use crate::filters::util::delay::Delay;
+use crate::math::{amplitude_to_db, db_to_amplitude};
+use crate::storage::RingBuffer;
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{Frame, GainComputer};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The static curve of a limiter: unity gain up to the ceiling, infinite ratio above.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LimiterCurve<T> {
+    /// The ceiling in dB.
+    pub ceiling_db: T,
+}
+
+impl<T> GainComputer<T> for LimiterCurve<T>
+where
+    T: Float,
+{
+    type State = ();
+
+    fn initial_state(&self) {}
+
+    fn gain_db(&self, _state: &mut (), level_db: T) -> T {
+        (self.ceiling_db - level_db).min(T::zero())
+    }
+}
+
+/// The limiter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The gain curve.
+    pub curve: LimiterCurve<T>,
+    /// The release smoothing coefficient (0..1).
+    /// Higher values make the gain recover faster after a peak.
+    pub release: T,
+}
+
+/// The limiter's state.
+#[derive(Clone, Debug)]
+pub struct State<F, R, S> {
+    /// The lookahead delay line.
+    pub delay: Delay<F, R>,
+    /// The curve's linear target gains of the frames in the delay line.
+    pub targets: S,
+    /// The release-smoothed gains averaged into the applied gain.
+    pub smoothed: S,
+}
+
+/// A brickwall lookahead limiter generic over sample type `T`, frame type `F`,
+/// delay storage `R` and gain storage `S`.
+///
+/// The input is delayed by the lookahead `L` using a [`Delay`], so the gain can
+/// start falling `L` samples before a peak leaves the limiter:
+///
+/// 1. the target gain `t[n]` is the [`LimiterCurve`]'s gain for the frame's peak,
+/// 2. `h[n]` is the minimum of `t` over the last `L + 1` frames,
+/// 3. `r[n]` follows `h[n]` down instantly and back up with the `release`
+///    coefficient,
+/// 4. the applied gain is the mean of `r` over the last `L` frames.
+///
+/// Every gain averaged in step 4 is at most the target gain of the frame being
+/// output, so the output never exceeds the ceiling, while the averaging turns the
+/// gain reduction into a smooth `L`-sample ramp instead of a step.
+///
+/// Array frames are linked: all channels receive the gain of the loudest one.
+///
+/// # Latency
+///
+/// The output lags the input by `L` frames; see [`latency`](Self::latency).
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`LimiterArray<T, L, F>`] — stack-allocated, `no_std`-friendly.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`LimiterVec<T, F>`] — heap-allocated, requires the `alloc` feature."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `LimiterVec<T, F>` — heap-allocated, requires the `alloc` feature."
+)]
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(L + C) for lookahead L and C channels; one scan
+///   over the target and smoothed gains, plus a logarithm/exponential pair.
+/// - **Space:** O(L·C); the delay line of L frames plus 2·L gains.
+#[derive(Clone, Debug)]
+pub struct Limiter<T, F, R, S> {
+    config: Config<T>,
+    state: State<F, R, S>,
+    _pd: PhantomData<T>,
+}
+
+/// A limiter with lookahead `L` backed by [`FixedCircularBuffer`]s.
+pub type LimiterArray<T, const L: usize, F = T> =
+    Limiter<T, F, FixedCircularBuffer<F, L>, FixedCircularBuffer<T, L>>;
+
+/// A limiter backed by [`HeapCircularBuffer`]s.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type LimiterVec<T, F = T> = Limiter<T, F, HeapCircularBuffer<F>, HeapCircularBuffer<T>>;
+
+impl<T, F, R, S> Limiter<T, F, R, S>
+where
+    T: Float,
+    F: Frame<T>,
+    R: RingBuffer<F>,
+    S: RingBuffer<T>,
+{
+    /// Creates a [`Limiter`] from an already-constructed `config` and storage.
+    ///
+    /// The lookahead `L` is the capacity of the buffers. Their contents are
+    /// overwritten: the delay line with silence and the gains with unity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffers' capacities differ or are zero, if the ceiling is
+    /// not finite, or if `release` is outside `[0, 1]`.
+    pub fn from_parts(config: Config<T>, mut delay: R, mut targets: S, mut smoothed: S) -> Self {
+        let lookahead = delay.capacity();
+        assert!(lookahead > 0, "Limiter: lookahead must be > 0");
+        assert!(
+            targets.capacity() == lookahead && smoothed.capacity() == lookahead,
+            "Limiter: gain buffer capacities must equal the lookahead"
+        );
+        assert!(
+            config.curve.ceiling_db.is_finite(),
+            "Limiter: ceiling must be finite"
+        );
+        assert!(
+            config.release >= T::zero() && config.release <= T::one(),
+            "Limiter: release must be in [0, 1]"
+        );
+
+        delay.clear();
+        targets.clear();
+        smoothed.clear();
+        for _ in 0..lookahead {
+            let _ = delay.push_back(F::silence());
+            let _ = targets.push_back(T::one());
+            let _ = smoothed.push_back(T::one());
+        }
+
+        Self {
+            config,
+            state: State {
+                delay: Delay::from_parts(delay),
+                targets,
+                smoothed,
+            },
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, F, R, S> Limiter<T, F, R, S>
+where
+    S: RingBuffer<T>,
+{
+    /// Returns the lookahead `L`, i.e. the delay between input and output in frames.
+    pub fn latency(&self) -> usize {
+        self.state.targets.capacity()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, F> LimiterVec<T, F>
+where
+    T: Float,
+    F: Frame<T>,
+{
+    /// Creates a heap-backed limiter with a lookahead of `lookahead` frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lookahead` is zero, if the ceiling is not finite, or if
+    /// `release` is outside `[0, 1]`.
+    #[must_use]
+    pub fn new(config: Config<T>, lookahead: usize) -> Self {
+        Self::from_parts(
+            config,
+            HeapCircularBuffer::with_capacity(lookahead),
+            HeapCircularBuffer::with_capacity(lookahead),
+            HeapCircularBuffer::with_capacity(lookahead),
+        )
+    }
+}
+
+impl<T, F, R, S> ConfigTrait for Limiter<T, F, R, S> {
+    type Config = Config<T>;
+}
+
+impl<T, F, R, S> StateTrait for Limiter<T, F, R, S> {
+    type State = State<F, R, S>;
+}
+
+impl<T, const L: usize, F> WithConfig for LimiterArray<T, L, F>
+where
+    T: Float,
+    F: Frame<T>,
+{
+    type Output = Self;
+
+    /// Creates a [`LimiterArray`] with a lookahead of `L` frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `L` is zero, if the ceiling is not finite, or if `release` is
+    /// outside `[0, 1]`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(
+            config,
+            FixedCircularBuffer::new(),
+            FixedCircularBuffer::new(),
+            FixedCircularBuffer::new(),
+        )
+    }
+}
+
+impl<T, F, R, S> ConfigRef for Limiter<T, F, R, S> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, F, R, S> ConfigClone for Limiter<T, F, R, S>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, F, R, S> StateMut for Limiter<T, F, R, S> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, F, R, S> HasGuts for Limiter<T, F, R, S> {
+    type Guts = (Config<T>, State<F, R, S>);
+}
+
+impl<T, F, R, S> FromGuts for Limiter<T, F, R, S> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self {
+            config,
+            state,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, F, R, S> IntoGuts for Limiter<T, F, R, S> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const L: usize, F> Reset for LimiterArray<T, L, F>
+where
+    T: Float,
+    F: Frame<T>,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const L: usize, F> ResetMut for LimiterArray<T, L, F> where Self: Reset {}
+
+impl<T, F, R, S> Filter<F> for Limiter<T, F, R, S>
+where
+    T: Float,
+    F: Frame<T>,
+    R: RingBuffer<F>,
+    S: RingBuffer<T>,
+{
+    type Output = F;
+
+    fn filter(&mut self, input: F) -> Self::Output {
+        let State {
+            delay,
+            targets,
+            smoothed,
+        } = &mut self.state;
+
+        let target = db_to_amplitude(
+            self.config
+                .curve
+                .gain_db(&mut (), amplitude_to_db(input.peak())),
+        );
+        let held = targets.iter().fold(target, |min, &gain| min.min(gain));
+        let _ = targets.push_back(target);
+
+        let previous = smoothed.back().copied().unwrap_or_else(T::one);
+        let released = if held < previous {
+            held
+        } else {
+            previous + self.config.release * (held - previous)
+        };
+        let _ = smoothed.push_back(released);
+
+        let sum = smoothed.iter().fold(T::zero(), |sum, &gain| sum + gain);
+        let gain = T::from(smoothed.len()).map_or(released, |len| sum / len);
+
+        delay.filter(input).scale(gain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::util::test_fixtures::white_noise;
+
+    fn config() -> Config<f64> {
+        Config {
+            curve: LimiterCurve { ceiling_db: -6.0 },
+            release: 0.01,
+        }
+    }
+
+    #[test]
+    fn output_never_exceeds_ceiling() {
+        let mut limiter = LimiterArray::<f64, 16>::with_config(config());
+        let ceiling = db_to_amplitude(-6.0);
+        for (n, sample) in white_noise(5000, 0x0bad_cafe).into_iter().enumerate() {
+            // Bursts of up to +18 dB over full scale.
+            let burst = if (n / 500) % 2 == 0 { 8.0 } else { 0.1 };
+            let output = limiter.filter(sample * burst);
+            assert!(output.abs() <= ceiling * (1.0 + 1e-12), "{n}: {output}");
+        }
+    }
+
+    #[test]
+    fn quiet_input_is_only_delayed() {
+        let mut limiter = LimiterArray::<f64, 4>::with_config(config());
+        assert_eq!(limiter.latency(), 4);
+        let input = white_noise(100, 7)
+            .into_iter()
+            .map(|x| x * 0.25)
+            .collect::<Vec<_>>();
+        let output: Vec<f64> = input.iter().map(|&x| limiter.filter(x)).collect();
+        assert_abs_diff_eq!(output[..4], [0.0; 4].as_slice(), epsilon = 0.0);
+        assert_abs_diff_eq!(output[4..], input[..96], epsilon = 1e-15);
+    }
+
+    #[test]
+    fn gain_ramps_down_before_a_peak() {
+        let mut limiter = LimiterArray::<f64, 4>::with_config(Config {
+            curve: LimiterCurve { ceiling_db: 0.0 },
+            release: 0.0,
+        });
+        let mut input = [0.5; 12];
+        input[6] = 2.0;
+        let output: Vec<f64> = input.iter().map(|&x| limiter.filter(x)).collect();
+        // The peak enters at n = 6 and leaves at n = 10; in between, the gain
+        // ramps from 1 to 0.5 in steps of 0.125.
+        assert_abs_diff_eq!(
+            output[6..11],
+            [0.4375, 0.375, 0.3125, 0.25, 1.0].as_slice(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn linked_channels_share_the_gain() {
+        let mut limiter = LimiterArray::<f64, 2, [f64; 2]>::with_config(Config {
+            curve: LimiterCurve { ceiling_db: 0.0 },
+            release: 0.0,
+        });
+        let mut output = [0.0; 2];
+        for _ in 0..4 {
+            output = limiter.filter([4.0, 1.0]);
+        }
+        assert_abs_diff_eq!(output.as_slice(), [1.0, 0.25].as_slice(), epsilon = 1e-12);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let mut array = LimiterArray::<f64, 8>::with_config(config());
+        let mut vec = LimiterVec::new(config(), 8);
+        for sample in white_noise(500, 3) {
+            assert_abs_diff_eq!(array.filter(sample * 4.0), vec.filter(sample * 4.0));
+        }
+    }
+
+    #[test]
+    fn reset_restores_unity_gain() {
+        let mut limiter = LimiterArray::<f64, 2>::with_config(config());
+        for _ in 0..10 {
+            let _ = limiter.filter(10.0);
+        }
+        let mut limiter = limiter.reset();
+        assert_abs_diff_eq!(limiter.filter(0.25), 0.0);
+        assert_abs_diff_eq!(limiter.filter(0.25), 0.0);
+        assert_abs_diff_eq!(limiter.filter(0.25), 0.25);
+    }
+
+    #[test]
+    #[should_panic(expected = "Limiter: lookahead must be > 0")]
+    fn rejects_zero_lookahead() {
+        let _ = LimiterArray::<f64, 0>::with_config(config());
+    }
+}
@@ -0,0 +1,383 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Feed-forward dynamics processor, generic over its gain curve.
+
+use core::fmt;
+
+use num_traits::Float;
+
+use crate::math::{amplitude_to_db, db_to_amplitude};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{Detector, Frame, GainComputer};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The dynamics processor's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, G> {
+    /// The static gain curve.
+    pub curve: G,
+    /// The level detector.
+    pub detector: Detector<T>,
+    /// The attack smoothing coefficient (0..1), used while the gain falls.
+    /// Higher values make the gain respond faster to rising levels.
+    pub attack: T,
+    /// The release smoothing coefficient (0..1), used while the gain rises.
+    /// Higher values make the gain recover faster on falling levels.
+    pub release: T,
+    /// The makeup gain in dB, applied after the curve's gain.
+    pub makeup_db: T,
+}
+
+/// The dynamics processor's state.
+#[derive(Clone, Debug)]
+pub struct State<T, S> {
+    /// The RMS detector's averaged power (unused by the peak detector).
+    pub mean_square: T,
+    /// The smoothed curve gain in dB, excluding makeup gain.
+    pub gain_db: T,
+    /// The gain curve's own state.
+    pub curve: S,
+}
+
+/// A feed-forward dynamics processor.
+///
+/// For every frame the processor
+///
+/// 1. detects the frame's level with its [`Detector`] (linked across channels),
+/// 2. maps the level through its [`GainComputer`] to a target gain in dB,
+/// 3. moves the gain towards the target with the `attack` coefficient when the
+///    gain falls, or the `release` coefficient when it rises,
+/// 4. multiplies the frame by the gain plus `makeup_db`.
+///
+/// Smoothing the gain in dB (rather than the detected level) keeps the attack
+/// and release times independent of the curve, as described by Giannoulis,
+/// Massberg and Reiss (2012).
+///
+/// Prefer the aliases [`Compressor`](super::compressor::Compressor),
+/// [`Expander`](super::expander::Expander) and [`Gate`](super::gate::Gate).
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(C) for C channels; one detector pass, one curve
+///   evaluation and a logarithm/exponential pair.
+/// - **Space:** O(1); stores the averaged power, the gain and the curve's state.
+pub struct Processor<T, G>
+where
+    G: GainComputer<T>,
+{
+    config: Config<T, G>,
+    state: State<T, G::State>,
+}
+
+impl<T, G> Clone for Processor<T, G>
+where
+    T: Clone,
+    G: GainComputer<T> + Clone,
+    G::State: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T, G> fmt::Debug for Processor<T, G>
+where
+    T: fmt::Debug,
+    G: GainComputer<T> + fmt::Debug,
+    G::State: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Processor")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T, G> Processor<T, G>
+where
+    T: Float,
+    G: GainComputer<T>,
+{
+    /// Returns the smoothed curve gain in dB, excluding makeup gain.
+    ///
+    /// For a compressor this is the (non-positive) gain reduction to meter.
+    #[must_use]
+    pub fn gain_reduction_db(&self) -> T {
+        self.state.gain_db
+    }
+
+    /// Returns the total gain applied to the most recent frame, in dB.
+    #[must_use]
+    pub fn gain_db(&self) -> T {
+        self.state.gain_db + self.config.makeup_db
+    }
+
+    fn detect<F>(&mut self, frame: &F) -> T
+    where
+        F: Frame<T>,
+    {
+        match self.config.detector {
+            Detector::Peak => frame.peak(),
+            Detector::Rms { coefficient } => {
+                let mean_square = self.state.mean_square;
+                self.state.mean_square = mean_square + coefficient * (frame.power() - mean_square);
+                self.state.mean_square.sqrt()
+            }
+        }
+    }
+}
+
+impl<T, G> ConfigTrait for Processor<T, G>
+where
+    G: GainComputer<T>,
+{
+    type Config = Config<T, G>;
+}
+
+impl<T, G> StateTrait for Processor<T, G>
+where
+    G: GainComputer<T>,
+{
+    type State = State<T, G::State>;
+}
+
+impl<T, G> WithConfig for Processor<T, G>
+where
+    T: Float,
+    G: GainComputer<T>,
+{
+    type Output = Self;
+
+    /// Creates a [`Processor`] with unity curve gain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `attack`, `release` or an RMS detector's coefficient is
+    /// outside `[0, 1]`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        let unit = |value: T| value >= T::zero() && value <= T::one();
+        assert!(unit(config.attack), "Processor: attack must be in [0, 1]");
+        assert!(unit(config.release), "Processor: release must be in [0, 1]");
+        if let Detector::Rms { coefficient } = config.detector {
+            assert!(
+                unit(coefficient),
+                "Processor: RMS coefficient must be in [0, 1]"
+            );
+        }
+        let state = State {
+            mean_square: T::zero(),
+            gain_db: T::zero(),
+            curve: config.curve.initial_state(),
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, G> ConfigRef for Processor<T, G>
+where
+    G: GainComputer<T>,
+{
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, G> ConfigClone for Processor<T, G>
+where
+    G: GainComputer<T>,
+    Config<T, G>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, G> StateMut for Processor<T, G>
+where
+    G: GainComputer<T>,
+{
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, G> HasGuts for Processor<T, G>
+where
+    G: GainComputer<T>,
+{
+    type Guts = (Config<T, G>, State<T, G::State>);
+}
+
+impl<T, G> FromGuts for Processor<T, G>
+where
+    G: GainComputer<T>,
+{
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, G> IntoGuts for Processor<T, G>
+where
+    G: GainComputer<T>,
+{
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, G> Reset for Processor<T, G>
+where
+    T: Float,
+    G: GainComputer<T>,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, G> ResetMut for Processor<T, G>
+where
+    G: GainComputer<T>,
+    Self: Reset,
+{
+}
+
+impl<T, G, F> Filter<F> for Processor<T, G>
+where
+    T: Float,
+    G: GainComputer<T>,
+    F: Frame<T>,
+{
+    type Output = F;
+
+    fn filter(&mut self, input: F) -> Self::Output {
+        // Flooring the level keeps silence at a finite (very low) dB value.
+        let level = self.detect(&input).max(T::min_positive_value());
+        let target = self
+            .config
+            .curve
+            .gain_db(&mut self.state.curve, amplitude_to_db(level));
+
+        let gain_db = self.state.gain_db;
+        let coeff = if target < gain_db {
+            self.config.attack
+        } else {
+            self.config.release
+        };
+        self.state.gain_db = gain_db + coeff * (target - gain_db);
+
+        input.scale(db_to_amplitude(self.gain_db()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    /// A curve applying a fixed gain, counting its invocations.
+    struct Fixed(f64);
+
+    impl GainComputer<f64> for Fixed {
+        type State = usize;
+
+        fn initial_state(&self) -> usize {
+            0
+        }
+
+        fn gain_db(&self, calls: &mut usize, _level_db: f64) -> f64 {
+            *calls += 1;
+            self.0
+        }
+    }
+
+    fn processor(attack: f64, release: f64, makeup_db: f64) -> Processor<f64, Fixed> {
+        Processor::with_config(Config {
+            curve: Fixed(-20.0),
+            detector: Detector::Peak,
+            attack,
+            release,
+            makeup_db,
+        })
+    }
+
+    #[test]
+    fn instant_ballistics_apply_curve_gain() {
+        let mut processor = processor(1.0, 1.0, 0.0);
+        assert_abs_diff_eq!(processor.filter(0.5), 0.05, epsilon = 1e-12);
+        assert_abs_diff_eq!(processor.gain_reduction_db(), -20.0);
+        assert_eq!(processor.state_mut().curve, 1);
+    }
+
+    #[test]
+    fn attack_smooths_gain_in_db() {
+        let mut processor = processor(0.25, 1.0, 0.0);
+        let _ = processor.filter(1.0);
+        assert_abs_diff_eq!(processor.gain_reduction_db(), -5.0, epsilon = 1e-12);
+        let _ = processor.filter(1.0);
+        assert_abs_diff_eq!(processor.gain_reduction_db(), -8.75, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn makeup_gain_is_added_after_smoothing() {
+        let mut processor = processor(1.0, 1.0, 6.0);
+        let _ = processor.filter(1.0);
+        assert_abs_diff_eq!(processor.gain_reduction_db(), -20.0);
+        assert_abs_diff_eq!(processor.gain_db(), -14.0);
+    }
+
+    #[test]
+    fn linked_frames_share_one_gain() {
+        let mut processor = processor(1.0, 1.0, 0.0);
+        let output = processor.filter([1.0, -0.5]);
+        assert_abs_diff_eq!(output.as_slice(), [0.1, -0.05].as_slice(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn rms_detector_averages_power_across_channels() {
+        let mut processor = Processor::with_config(Config {
+            curve: Fixed(0.0),
+            detector: Detector::Rms { coefficient: 0.5 },
+            attack: 1.0,
+            release: 1.0,
+            makeup_db: 0.0,
+        });
+        let _ = processor.filter([2.0, 0.0]);
+        // mean power 2.0, averaged half-way from 0.0
+        assert_abs_diff_eq!(processor.state_mut().mean_square, 1.0, epsilon = 1e-12);
+        let _ = processor.filter([2.0, 0.0]);
+        assert_abs_diff_eq!(processor.state_mut().mean_square, 1.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn reset_restores_unity_gain() {
+        let mut processor = processor(1.0, 1.0, 0.0);
+        let _ = processor.filter(1.0);
+        let mut processor = processor.reset();
+        assert_abs_diff_eq!(processor.gain_reduction_db(), 0.0);
+        assert_eq!(processor.state_mut().curve, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Processor: release must be in [0, 1]")]
+    fn rejects_release_above_one() {
+        let _ = processor(1.0, 1.5, 0.0);
+    }
+}
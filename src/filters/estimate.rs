@@ -12,21 +12,42 @@
 //!
 //! # When to use which filter
 //!
-//! | Filter                         | Purpose                                                    |
-//! | ------------------------------ | ---------------------------------------------------------- |
-//! | `kalman::Kalman`               | Optimal linear estimator with known noise covariances      |
-//! | `alpha_beta::AlphaBeta`        | Fixed-gain observer (lighter-weight alternative)           |
+//! | Filter                                | Purpose                                                    |
+//! | ------------------------------------- | ---------------------------------------------------------- |
+//! | `kalman::Kalman`                      | Optimal linear estimator with known noise covariances      |
+//! | `kalman_n::KalmanN`                   | Multivariate Kalman filter with matrix models              |
+//! | `extended_kalman::ExtendedKalman`     | Nonlinear models with known Jacobians                      |
+//! | `unscented_kalman::UnscentedKalman`   | Nonlinear models without Jacobians                         |
+//! | `alpha_beta::AlphaBeta`               | Fixed-gain observer (lighter-weight alternative)           |
+//! | `alpha_beta_gamma::AlphaBetaGamma`    | Fixed-gain observer for accelerating targets               |
+//! | `orientation::*`                      | Orientation from gyroscope, accelerometer, magnetometer    |
 //!
 //! - **Kalman** is the optimal linear estimator when process and measurement noise are
 //!   Gaussian with known covariance. The filter adapts its gain (Kalman gain) online
 //!   based on the evolving state and measurement uncertainty. Use when you can
 //!   characterize sensor noise and system dynamics quantitatively, and you need
 //!   statistically optimal estimates.
+//! - **`KalmanN`** is the matrix form of the Kalman filter, for multi-dimensional
+//!   states (e.g. position, velocity and acceleration) observed by several sensors.
+//!   It provides separate predict and update steps, skips missing measurements, and
+//!   comes with constant-velocity and constant-acceleration models.
+//! - **`ExtendedKalman`** and **`UnscentedKalman`** handle nonlinear models
+//!   (e.g. bearing-only tracking, battery state of charge) given as a
+//!   [`NonlinearModel`]. The EKF linearizes the model with user-supplied
+//!   [`Differentiable`] Jacobians and is the cheaper choice for mildly nonlinear
+//!   models; the UKF propagates sigma points instead, needs no Jacobians and copes
+//!   better with strong nonlinearities. The UKF requires the `std` or `libm` feature.
 //! - **`AlphaBeta`** (also known as the `α`-`β` filter or `g`-`h` filter) uses fixed
 //!   gains instead of computing the optimal Kalman gain. It tracks position and velocity
 //!   with two tuning parameters. Much simpler to implement and tune than a full Kalman
 //!   filter, and sufficient for many tracking and smoothing applications where the
 //!   noise statistics are constant or unknown.
+//! - **`AlphaBetaGamma`** adds an acceleration estimate to the `α`-`β` filter, removing
+//!   its lag on accelerating targets. Optimal gains follow from a single tracking index,
+//!   and an adaptive mode switches to faster gains while the residual signals a maneuver.
+//! - **`orientation`** fuses gyroscope, accelerometer and optional magnetometer samples
+//!   into an orientation quaternion, with complementary, Mahony and Madgwick filters.
+//!   Requires the `std` or `libm` feature.
 //!
 //! # See also
 //!
@@ -34,6 +55,133 @@
 //! - [`super::fir::mean`]: non-recursive smoothing; no internal state model, just a windowed
 //!   average.
 
+use num_traits::float::FloatCore;
+
+use crate::math::matrix::{Matrix, Vector};
+
 pub mod alpha_beta;
 
+pub mod alpha_beta_gamma;
+
+pub mod extended_kalman;
+
 pub mod kalman;
+
+pub mod kalman_n;
+
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod orientation;
+
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod unscented_kalman;
+
+/// A nonlinear state-space model with `X` states, `Z` measurements and `U`
+/// control inputs:
+///
+/// ```text
+/// x[n] = f(x[n−1], u[n]) + w,   w ~ N(0, Q)
+/// z[n] = h(x[n]) + v,           v ~ N(0, R)
+/// ```
+///
+/// Used by the [`UnscentedKalman`](unscented_kalman::UnscentedKalman) filter
+/// directly, and by the [`ExtendedKalman`](extended_kalman::ExtendedKalman)
+/// filter together with its [`Differentiable`] Jacobians.
+pub trait NonlinearModel<T, const X: usize, const Z: usize, const U: usize> {
+    /// Returns the predicted state `f(x, u)`.
+    fn transition(&self, state: &Vector<T, X>, control: &[T; U]) -> Vector<T, X>;
+
+    /// Returns the predicted measurement `h(x)`.
+    fn measurement(&self, state: &Vector<T, X>) -> Vector<T, Z>;
+
+    /// Returns the difference `measured − predicted` of two measurements.
+    ///
+    /// Override this for measurements that wrap around, such as bearings,
+    /// to return the shortest angular difference.
+    fn residual(&self, measured: &Vector<T, Z>, predicted: &Vector<T, Z>) -> Vector<T, Z>
+    where
+        T: FloatCore,
+    {
+        *measured - *predicted
+    }
+}
+
+/// The Jacobians of a [`NonlinearModel`], as required by the
+/// [`ExtendedKalman`](extended_kalman::ExtendedKalman) filter.
+pub trait Differentiable<T, const X: usize, const Z: usize, const U: usize>:
+    NonlinearModel<T, X, Z, U>
+{
+    /// Returns the Jacobian `∂f/∂x` of the state transition at `(x, u)`.
+    fn transition_jacobian(&self, state: &Vector<T, X>, control: &[T; U]) -> Matrix<T, X, X>;
+
+    /// Returns the Jacobian `∂h/∂x` of the measurement at `x`.
+    fn measurement_jacobian(&self, state: &Vector<T, X>) -> Matrix<T, Z, X>;
+}
+
+/// Replaces the rows and columns of missing measurement components in `r`
+/// by those of the identity.
+///
+/// Together with zeroed residuals and measurement sensitivities, this zeroes
+/// the gain columns of missing components, which is equivalent to dropping
+/// them from the update.
+fn masked_noise<T, const Z: usize>(r: &Matrix<T, Z, Z>, available: &[bool; Z]) -> Matrix<T, Z, Z>
+where
+    T: FloatCore,
+{
+    let mut masked = *r;
+    for (i, _) in available
+        .iter()
+        .enumerate()
+        .filter(|(_, &available)| !available)
+    {
+        for j in 0..Z {
+            masked[(i, j)] = T::zero();
+            masked[(j, i)] = T::zero();
+        }
+        masked[(i, i)] = T::one();
+    }
+    masked
+}
+
+/// Corrects `estimate` and `covariance` with the measurement residual
+/// `z − ẑ`, linearized by the measurement matrix `h`, using the Joseph-form
+/// covariance update.
+///
+/// Missing components (`None`) are skipped. Returns `None` if no component is
+/// available or the innovation covariance is singular.
+fn joseph_update<T, const X: usize, const Z: usize>(
+    estimate: &Vector<T, X>,
+    covariance: &Matrix<T, X, X>,
+    h: &Matrix<T, Z, X>,
+    r: &Matrix<T, Z, Z>,
+    residual: &[Option<T>; Z],
+) -> Option<(Vector<T, X>, Matrix<T, X, X>)>
+where
+    T: FloatCore,
+{
+    let available = residual.map(|component| component.is_some());
+    if !available.contains(&true) {
+        return None;
+    }
+    let mut h = *h;
+    for (i, _) in available
+        .iter()
+        .enumerate()
+        .filter(|(_, &available)| !available)
+    {
+        for j in 0..X {
+            h[(i, j)] = T::zero();
+        }
+    }
+    let r = masked_noise(r, &available);
+    let residual = Vector::from_column(residual.map(|component| component.unwrap_or(T::zero())));
+
+    let ht = h.transpose();
+    let innovation_covariance = h * *covariance * ht + r;
+    let gain = *covariance * ht * innovation_covariance.inverse()?;
+
+    let correction = Matrix::identity() - gain * h;
+    Some((
+        *estimate + gain * residual,
+        correction * *covariance * correction.transpose() + gain * r * gain.transpose(),
+    ))
+}
@@ -0,0 +1,513 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Alpha-Beta-Gamma filter for tracking accelerating signals.
+//!
+//! Extends the [`AlphaBeta`](super::alpha_beta::AlphaBeta) observer with an
+//! acceleration estimate, so that targets with (roughly) constant acceleration
+//! are tracked without lag. An optional adaptive mode switches to a second set
+//! of gains while the residual indicates a maneuver.
+
+use num_traits::float::FloatCore;
+
+#[cfg(any(feature = "libm", feature = "std"))]
+use num_traits::Float;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The alpha-beta-gamma filter's gains.
+///
+/// The corrections are `α·r` for the position, `β·r / Δt` for the velocity and
+/// `γ·r / Δt²` for the acceleration, where `r` is the residual.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gains<T> {
+    /// Position gain `α` (`0 < α < 1`).
+    pub alpha: T,
+    /// Velocity gain `β`.
+    pub beta: T,
+    /// Acceleration gain `γ` (`0` for a plain alpha-beta filter).
+    pub gamma: T,
+}
+
+impl<T> Gains<T>
+where
+    T: FloatCore,
+{
+    /// Creates alpha-beta gains, with `γ = 0`.
+    pub fn alpha_beta(alpha: T, beta: T) -> Self {
+        Self {
+            alpha,
+            beta,
+            gamma: T::zero(),
+        }
+    }
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+impl<T> Gains<T>
+where
+    T: Float + FloatCore,
+{
+    /// Returns the optimal (steady-state Kalman) alpha-beta gains for the
+    /// tracking index `λ = σw·Δt² / σv` (Kalata).
+    ///
+    /// `σw` is the standard deviation of the white acceleration driving the
+    /// target and `σv` that of the measurement noise. Larger indices track
+    /// faster, smaller indices smooth more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` cannot represent the constants of the solution.
+    pub fn from_tracking_index(tracking_index: T) -> Self {
+        let lambda = tracking_index;
+        let two = T::one() + T::one();
+        let four = two + two;
+        let eight = four + four;
+        let r = (four + lambda - Float::sqrt(eight * lambda + lambda * lambda)) / four;
+        let alpha = T::one() - r * r;
+        let beta = two * (two - alpha) - four * Float::sqrt(T::one() - alpha);
+        Self::alpha_beta(alpha, beta)
+    }
+
+    /// Returns the optimal (steady-state Kalman) alpha-beta-gamma gains for the
+    /// tracking index `λ = σw·Δt² / σv` (Gray and Murray).
+    ///
+    /// `σw` is the standard deviation of the white acceleration increments
+    /// driving the target and `σv` that of the measurement noise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` cannot represent the constants of the solution.
+    pub fn alpha_beta_gamma_from_tracking_index(tracking_index: T) -> Self {
+        let constant = |value: f64| T::from(value).expect("constant is representable");
+        // `s = √(1 − α)` is the real root of `s³ + b·s² + c·s − 1 = 0`, solved
+        // with Cardano's formula for the depressed cubic `t³ + p·t + q = 0`.
+        let half_lambda = tracking_index / constant(2.0);
+        let (b, c) = (half_lambda - constant(3.0), half_lambda + constant(3.0));
+        let (p, q) = (
+            c - b * b / constant(3.0),
+            constant(2.0) * b * b * b / constant(27.0) - b * c / constant(3.0) - T::one(),
+        );
+        let discriminant = Float::sqrt(q * q + constant(4.0) * p * p * p / constant(27.0));
+        let cube_root = Float::cbrt((discriminant - q) / constant(2.0));
+        let root = cube_root - p / (constant(3.0) * cube_root) - b / constant(3.0);
+
+        let alpha = T::one() - root * root;
+        let beta = constant(2.0) * (T::one() - root) * (T::one() - root);
+        let gamma = beta * beta / (constant(2.0) * alpha);
+        Self { alpha, beta, gamma }
+    }
+}
+
+/// The adaptive mode's maneuver detection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Maneuver<T> {
+    /// The residual magnitude above which a maneuver is declared.
+    pub threshold: T,
+    /// The (usually larger) gains used during a maneuver.
+    pub gains: Gains<T>,
+    /// The number of samples the maneuver gains stay active after the
+    /// residual last exceeded the threshold.
+    pub hold: usize,
+}
+
+/// The alpha-beta-gamma filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The gains used while no maneuver is detected.
+    pub gains: Gains<T>,
+    /// The sample period `Δt` (`> 0`).
+    pub sample_time: T,
+    /// The maneuver detection of the adaptive mode, if enabled.
+    pub maneuver: Option<Maneuver<T>>,
+}
+
+/// The alpha-beta-gamma filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// Position estimate; `None` until the first sample.
+    pub position: Option<T>,
+    /// Velocity estimate, per unit of time.
+    pub velocity: T,
+    /// Acceleration estimate, per unit of time squared.
+    pub acceleration: T,
+    /// The remaining samples of the current maneuver (`0` if none).
+    pub maneuver_samples: usize,
+}
+
+/// An alpha-beta-gamma filter, tracking position, velocity and acceleration.
+///
+/// Each sample predicts the position, velocity and acceleration one sample
+/// ahead and corrects them with the residual `r` between the measurement and
+/// the predicted position. The first sample initializes the position, with
+/// zero velocity and acceleration.
+///
+/// With [`Config::maneuver`] set, the filter switches to the maneuver gains as
+/// soon as `|r|` exceeds the threshold, and back to the regular gains once the
+/// residual has stayed below it for `hold` samples. Setting `γ = 0` in both
+/// gain sets turns this into an adaptive alpha-beta filter.
+///
+/// The filter outputs the position estimate; [`velocity`](Self::velocity) and
+/// [`acceleration`](Self::acceleration) return the others.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); fixed number of arithmetic operations (predict + correct).
+/// - **Space:** O(1); stores the three estimates and the maneuver counter.
+#[derive(Clone, Debug)]
+pub struct AlphaBetaGamma<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+impl<T> AlphaBetaGamma<T>
+where
+    T: FloatCore,
+{
+    /// Returns the position estimate, or `None` before the first sample.
+    pub fn position(&self) -> Option<T> {
+        self.state.position
+    }
+
+    /// Returns the velocity estimate.
+    pub fn velocity(&self) -> T {
+        self.state.velocity
+    }
+
+    /// Returns the acceleration estimate.
+    pub fn acceleration(&self) -> T {
+        self.state.acceleration
+    }
+
+    /// Returns `true` while the maneuver gains are active.
+    pub fn is_maneuvering(&self) -> bool {
+        self.state.maneuver_samples > 0
+    }
+}
+
+impl<T> ConfigTrait for AlphaBetaGamma<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for AlphaBetaGamma<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for AlphaBetaGamma<T>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `sample_time` is not positive.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(
+            config.sample_time > T::zero(),
+            "AlphaBetaGamma: sample time must be > 0"
+        );
+        let state = State {
+            position: None,
+            velocity: T::zero(),
+            acceleration: T::zero(),
+            maneuver_samples: 0,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T> ConfigRef for AlphaBetaGamma<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for AlphaBetaGamma<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for AlphaBetaGamma<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for AlphaBetaGamma<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for AlphaBetaGamma<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for AlphaBetaGamma<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for AlphaBetaGamma<T>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for AlphaBetaGamma<T> where Self: Reset {}
+
+impl<T> Filter<T> for AlphaBetaGamma<T>
+where
+    T: FloatCore,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let Some(position) = self.state.position else {
+            self.state.position = Some(input);
+            return input;
+        };
+        let dt = self.config.sample_time;
+        let half = T::one() / (T::one() + T::one());
+
+        // Compute prediction:
+        let acceleration = self.state.acceleration;
+        let velocity = self.state.velocity + acceleration * dt;
+        let position = position + (self.state.velocity + half * acceleration * dt) * dt;
+
+        // Compute residual (error):
+        let residual = input - position;
+
+        // Detect maneuvers:
+        let gains = match self.config.maneuver {
+            Some(maneuver) => {
+                if residual.abs() > maneuver.threshold {
+                    self.state.maneuver_samples = maneuver.hold + 1;
+                }
+                if self.state.maneuver_samples > 0 {
+                    self.state.maneuver_samples -= 1;
+                    maneuver.gains
+                } else {
+                    self.config.gains
+                }
+            }
+            None => self.config.gains,
+        };
+
+        // Correction:
+        let position = position + gains.alpha * residual;
+        self.state.position = Some(position);
+        self.state.velocity = velocity + gains.beta * residual / dt;
+        self.state.acceleration = acceleration + gains.gamma * residual / (dt * dt);
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::filters::estimate::alpha_beta::{self, AlphaBeta};
+
+    const DT: f64 = 0.1;
+
+    fn config(gains: Gains<f64>) -> Config<f64> {
+        Config {
+            gains,
+            sample_time: DT,
+            maneuver: None,
+        }
+    }
+
+    /// A target accelerating at 2 units/s², starting at 1 unit/s.
+    fn trajectory(n: u32) -> f64 {
+        let t = DT * f64::from(n);
+        5.0 + t + t * t
+    }
+
+    #[test]
+    fn tracks_constant_acceleration_without_lag() {
+        let mut filter = AlphaBetaGamma::with_config(config(Gains {
+            alpha: 0.5,
+            beta: 0.4,
+            gamma: 0.1,
+        }));
+        let mut output = 0.0;
+        for n in 0_u32..500 {
+            output = filter.filter(trajectory(n));
+        }
+        assert_abs_diff_eq!(output, trajectory(499), epsilon = 1e-9);
+        assert_abs_diff_eq!(filter.velocity(), 1.0 + 2.0 * DT * 499.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(filter.acceleration(), 2.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn alpha_beta_lags_acceleration() {
+        let mut filter = AlphaBetaGamma::with_config(config(Gains::alpha_beta(0.5, 0.4)));
+        let mut output = 0.0;
+        for n in 0_u32..500 {
+            output = filter.filter(trajectory(n));
+        }
+        // The steady-state lag of an alpha-beta filter is a·Δt²·(1 − α)/β.
+        let lag = 2.0 * DT * DT * 0.5 / 0.4;
+        assert_abs_diff_eq!(trajectory(499) - output, lag, epsilon = 1e-9);
+        assert_abs_diff_eq!(filter.acceleration(), 0.0);
+    }
+
+    #[test]
+    fn matches_alpha_beta_with_unit_sample_time() {
+        let mut filter = AlphaBetaGamma::with_config(Config {
+            sample_time: 1.0,
+            ..config(Gains::alpha_beta(0.3, 0.1))
+        });
+        let mut reference = AlphaBeta::with_config(alpha_beta::Config {
+            alpha: 0.3,
+            beta: 0.1,
+        });
+        for n in 0_u32..50 {
+            let input = f64::from(n % 7) + 0.5 * f64::from(n);
+            assert_abs_diff_eq!(
+                filter.filter(input),
+                reference.filter(input),
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[cfg(any(feature = "libm", feature = "std"))]
+    #[test]
+    fn tracking_index_gains_match_steady_state_kalman() {
+        use crate::filters::estimate::kalman_n::{self, KalmanN};
+        use crate::math::matrix::{Matrix, Vector};
+
+        let dt = 0.5;
+        for lambda in [0.1, 0.5, 2.0] {
+            // White acceleration increments with σw = λ·σv / Δt², σv = 1.
+            let sigma = lambda / (dt * dt);
+            let g = Vector::from_column([dt * dt / 2.0, dt, 1.0]);
+            let mut kalman = KalmanN::with_config(kalman_n::Config::<f64, 3, 1, 0> {
+                q: (g * g.transpose()).scale(sigma * sigma),
+                r: Matrix::from_rows([[1.0]]),
+                ..kalman_n::Config::constant_acceleration(dt, 0.0, 1.0)
+            });
+            for _ in 0..2000 {
+                let _ = kalman.filter([0.0]);
+            }
+            // The steady-state gain is `P⁻·Hᵀ / (H·P⁻·Hᵀ + R)`.
+            kalman.predict([]);
+            let p = *kalman.covariance();
+            let gain = p.transpose().rows()[0].map(|value| value / (p[(0, 0)] + 1.0));
+
+            let gains = Gains::alpha_beta_gamma_from_tracking_index(lambda);
+            assert_abs_diff_eq!(gains.alpha, gain[0], epsilon = 1e-9);
+            assert_abs_diff_eq!(gains.beta, gain[1] * dt, epsilon = 1e-9);
+            assert_abs_diff_eq!(gains.gamma, gain[2] * dt * dt, epsilon = 1e-9);
+        }
+    }
+
+    #[cfg(any(feature = "libm", feature = "std"))]
+    #[test]
+    fn kalata_gains() {
+        // λ = 0.1 gives r = 0.8, so α = 0.36 and β = 0.08.
+        let gains = Gains::<f64>::from_tracking_index(0.1);
+        assert_abs_diff_eq!(gains.alpha, 0.36, epsilon = 1e-12);
+        assert_abs_diff_eq!(gains.beta, 0.08, epsilon = 1e-12);
+        assert_abs_diff_eq!(gains.gamma, 0.0);
+    }
+
+    #[test]
+    fn adaptive_mode_switches_gains_on_maneuver() {
+        let slow = Gains::alpha_beta(0.1, 0.005);
+        let fast = Gains::alpha_beta(0.8, 0.6);
+        let maneuver = Maneuver {
+            threshold: 0.5,
+            gains: fast,
+            hold: 5,
+        };
+        let mut adaptive = AlphaBetaGamma::with_config(Config {
+            maneuver: Some(maneuver),
+            ..config(slow)
+        });
+        let mut fixed = AlphaBetaGamma::with_config(config(slow));
+
+        // Constant position, then a sudden ramp at 5 units/s.
+        let input = |n: u32| {
+            if n < 100 {
+                1.0
+            } else {
+                1.0 + 5.0 * DT * f64::from(n - 100)
+            }
+        };
+        for n in 0_u32..100 {
+            let _ = adaptive.filter(input(n));
+            let _ = fixed.filter(input(n));
+        }
+        assert!(!adaptive.is_maneuvering());
+
+        let mut detected = false;
+        let (mut adaptive_error, mut fixed_error) = (0.0_f64, 0.0_f64);
+        for n in 100_u32..130 {
+            let target = input(n);
+            adaptive_error = adaptive_error.max((adaptive.filter(target) - target).abs());
+            fixed_error = fixed_error.max((fixed.filter(target) - target).abs());
+            detected |= adaptive.is_maneuvering();
+        }
+        assert!(detected);
+        assert!(
+            adaptive_error < fixed_error / 2.0,
+            "adaptive {adaptive_error}, fixed {fixed_error}"
+        );
+
+        // Once the target settles on the new velocity the regular gains return.
+        for n in 130_u32..400 {
+            let _ = adaptive.filter(input(n));
+        }
+        assert!(!adaptive.is_maneuvering());
+        assert_abs_diff_eq!(adaptive.velocity(), 5.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn reset_clears_estimates() {
+        let mut filter = AlphaBetaGamma::with_config(config(Gains {
+            alpha: 0.5,
+            beta: 0.4,
+            gamma: 0.1,
+        }));
+        for n in 0_u32..10 {
+            let _ = filter.filter(trajectory(n));
+        }
+        let filter = filter.reset();
+        assert_eq!(filter.position(), None);
+        assert_abs_diff_eq!(filter.velocity(), 0.0);
+        assert_abs_diff_eq!(filter.acceleration(), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "AlphaBetaGamma: sample time must be > 0")]
+    fn rejects_zero_sample_time() {
+        let _ = AlphaBetaGamma::with_config(Config {
+            sample_time: 0.0,
+            ..config(Gains::alpha_beta(0.5, 0.1))
+        });
+    }
+}
@@ -0,0 +1,418 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Extended Kalman filter for nonlinear state-space models.
+//!
+//! The extended Kalman filter (EKF) propagates the state estimate through the
+//! nonlinear model and the covariance through the model's Jacobians, i.e. a
+//! first-order linearization around the current estimate.
+
+use num_traits::float::FloatCore;
+
+use crate::math::matrix::{Matrix, Vector};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{joseph_update, Differentiable};
+
+pub use super::kalman_n::State;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The extended Kalman filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, M, const X: usize, const Z: usize, const U: usize> {
+    /// The nonlinear model and its Jacobians.
+    pub model: M,
+    /// Process noise covariance `Q`.
+    pub q: Matrix<T, X, X>,
+    /// Measurement noise covariance `R` (must be positive definite).
+    pub r: Matrix<T, Z, Z>,
+    /// Initial state estimate `x₀`.
+    pub initial_state: Vector<T, X>,
+    /// Initial estimate covariance `P₀`.
+    pub initial_covariance: Matrix<T, X, X>,
+}
+
+/// An extended Kalman filter for a [`Differentiable`] nonlinear model with
+/// `X` states, `Z` measurements and `U` control inputs.
+///
+/// The predict step evaluates `x = f(x, u)` and `P = F·P·Fᵀ + Q` with
+/// `F = ∂f/∂x`; the update step linearizes the measurement with `H = ∂h/∂x`
+/// and uses the Joseph-form covariance update. Missing measurement components
+/// are skipped, as in [`KalmanN`](super::kalman_n::KalmanN).
+///
+/// The EKF works well when the model is close to linear over the spread of
+/// the estimate. For strongly nonlinear models, or when Jacobians are hard to
+/// derive, prefer the
+/// [`UnscentedKalman`](super::unscented_kalman::UnscentedKalman) filter.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(X³ + X²·Z + X·Z² + Z³), plus one evaluation of
+///   the model and its Jacobians.
+/// - **Space:** O(X² + Z²); the noise covariances, state estimate and
+///   covariance, plus the model.
+#[derive(Clone, Debug)]
+pub struct ExtendedKalman<T, M, const X: usize, const Z: usize, const U: usize> {
+    config: Config<T, M, X, Z, U>,
+    state: State<T, X>,
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> ExtendedKalman<T, M, X, Z, U>
+where
+    T: FloatCore,
+    M: Differentiable<T, X, Z, U>,
+{
+    /// Returns the current state estimate.
+    pub fn estimate(&self) -> [T; X] {
+        self.state.estimate.column()
+    }
+
+    /// Returns the current estimate covariance.
+    pub fn covariance(&self) -> &Matrix<T, X, X> {
+        &self.state.covariance
+    }
+
+    /// Propagates the estimate by one step: `x = f(x, u)`,
+    /// `P = F·P·Fᵀ + Q`.
+    pub fn predict(&mut self, control: [T; U]) {
+        let Config { model, q, .. } = &self.config;
+        let State {
+            estimate,
+            covariance,
+        } = &self.state;
+        let jacobian = model.transition_jacobian(estimate, &control);
+        self.state = State {
+            estimate: model.transition(estimate, &control),
+            covariance: jacobian * *covariance * jacobian.transpose() + *q,
+        };
+    }
+
+    /// Corrects the estimate with the available components of `measurement`.
+    ///
+    /// Returns `false`, leaving the state untouched, if no component is
+    /// available or the innovation covariance is singular.
+    pub fn update(&mut self, measurement: [Option<T>; Z]) -> bool {
+        let Config { model, r, .. } = &self.config;
+        let State {
+            estimate,
+            covariance,
+        } = &self.state;
+        let predicted = model.measurement(estimate);
+        let measured =
+            Vector::from_column(measurement.map(|component| component.unwrap_or(T::zero())));
+        let difference = model.residual(&measured, &predicted);
+        let mut residual = measurement;
+        for (i, component) in residual.iter_mut().enumerate() {
+            *component = component.map(|_| difference[(i, 0)]);
+        }
+        let jacobian = model.measurement_jacobian(estimate);
+        match joseph_update(estimate, covariance, &jacobian, r, &residual) {
+            Some((estimate, covariance)) => {
+                self.state = State {
+                    estimate,
+                    covariance,
+                };
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> ConfigTrait
+    for ExtendedKalman<T, M, X, Z, U>
+{
+    type Config = Config<T, M, X, Z, U>;
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> StateTrait
+    for ExtendedKalman<T, M, X, Z, U>
+{
+    type State = State<T, X>;
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> WithConfig
+    for ExtendedKalman<T, M, X, Z, U>
+where
+    T: Copy,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        let state = State {
+            estimate: config.initial_state,
+            covariance: config.initial_covariance,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> ConfigRef
+    for ExtendedKalman<T, M, X, Z, U>
+{
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> ConfigClone
+    for ExtendedKalman<T, M, X, Z, U>
+where
+    Config<T, M, X, Z, U>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> StateMut
+    for ExtendedKalman<T, M, X, Z, U>
+{
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> HasGuts
+    for ExtendedKalman<T, M, X, Z, U>
+{
+    type Guts = (Config<T, M, X, Z, U>, State<T, X>);
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> FromGuts
+    for ExtendedKalman<T, M, X, Z, U>
+{
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> IntoGuts
+    for ExtendedKalman<T, M, X, Z, U>
+{
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> Reset for ExtendedKalman<T, M, X, Z, U>
+where
+    T: Copy,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, M, const X: usize, const Z: usize, const U: usize> ResetMut
+    for ExtendedKalman<T, M, X, Z, U>
+where
+    Self: Reset,
+{
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> Filter<[T; Z]>
+    for ExtendedKalman<T, M, X, Z, U>
+where
+    T: FloatCore,
+    M: Differentiable<T, X, Z, U>,
+{
+    type Output = [T; X];
+
+    fn filter(&mut self, input: [T; Z]) -> Self::Output {
+        self.predict([T::zero(); U]);
+        self.update(input.map(Some));
+        self.estimate()
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> Filter<([Option<T>; Z], [T; U])>
+    for ExtendedKalman<T, M, X, Z, U>
+where
+    T: FloatCore,
+    M: Differentiable<T, X, Z, U>,
+{
+    type Output = [T; X];
+
+    fn filter(&mut self, (measurement, control): ([Option<T>; Z], [T; U])) -> Self::Output {
+        self.predict(control);
+        self.update(measurement);
+        self.estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::filters::estimate::{
+        kalman_n::{self, KalmanN},
+        NonlinearModel,
+    };
+    use crate::util::test_fixtures::hash_noise;
+
+    /// A linear constant-velocity model with a position measurement.
+    struct ConstantVelocity(f64);
+
+    impl NonlinearModel<f64, 2, 1, 0> for ConstantVelocity {
+        fn transition(&self, state: &Vector<f64, 2>, _control: &[f64; 0]) -> Vector<f64, 2> {
+            let [position, velocity] = state.column();
+            Vector::from_column([position + self.0 * velocity, velocity])
+        }
+
+        fn measurement(&self, state: &Vector<f64, 2>) -> Vector<f64, 1> {
+            Vector::from_column([state[(0, 0)]])
+        }
+    }
+
+    impl Differentiable<f64, 2, 1, 0> for ConstantVelocity {
+        fn transition_jacobian(
+            &self,
+            _state: &Vector<f64, 2>,
+            _control: &[f64; 0],
+        ) -> Matrix<f64, 2, 2> {
+            Matrix::from_rows([[1.0, self.0], [0.0, 1.0]])
+        }
+
+        fn measurement_jacobian(&self, _state: &Vector<f64, 2>) -> Matrix<f64, 1, 2> {
+            Matrix::from_rows([[1.0, 0.0]])
+        }
+    }
+
+    /// A stationary target at `[x, y]`, observed by range and bearing from the
+    /// origin.
+    struct RangeBearing;
+
+    impl NonlinearModel<f64, 2, 2, 0> for RangeBearing {
+        fn transition(&self, state: &Vector<f64, 2>, _control: &[f64; 0]) -> Vector<f64, 2> {
+            *state
+        }
+
+        fn measurement(&self, state: &Vector<f64, 2>) -> Vector<f64, 2> {
+            let [x, y] = state.column();
+            Vector::from_column([x.hypot(y), y.atan2(x)])
+        }
+
+        fn residual(
+            &self,
+            measured: &Vector<f64, 2>,
+            predicted: &Vector<f64, 2>,
+        ) -> Vector<f64, 2> {
+            let [range, bearing] = (*measured - *predicted).column();
+            let wrapped = (bearing + core::f64::consts::PI).rem_euclid(core::f64::consts::TAU)
+                - core::f64::consts::PI;
+            Vector::from_column([range, wrapped])
+        }
+    }
+
+    impl Differentiable<f64, 2, 2, 0> for RangeBearing {
+        fn transition_jacobian(
+            &self,
+            _state: &Vector<f64, 2>,
+            _control: &[f64; 0],
+        ) -> Matrix<f64, 2, 2> {
+            Matrix::identity()
+        }
+
+        fn measurement_jacobian(&self, state: &Vector<f64, 2>) -> Matrix<f64, 2, 2> {
+            let [x, y] = state.column();
+            let range_squared = x * x + y * y;
+            let range = range_squared.sqrt();
+            Matrix::from_rows([
+                [x / range, y / range],
+                [-y / range_squared, x / range_squared],
+            ])
+        }
+    }
+
+    fn range_bearing_config(initial: [f64; 2]) -> Config<f64, RangeBearing, 2, 2, 0> {
+        Config {
+            model: RangeBearing,
+            q: Matrix::from_diagonal([1e-9, 1e-9]),
+            r: Matrix::from_diagonal([0.01, 1e-4]),
+            initial_state: Vector::from_column(initial),
+            initial_covariance: Matrix::from_diagonal([4.0, 4.0]),
+        }
+    }
+
+    #[test]
+    fn linear_model_matches_kalman_n() {
+        let linear = kalman_n::Config::constant_velocity(0.1, 0.5, 0.2);
+        let mut extended = ExtendedKalman::with_config(Config {
+            model: ConstantVelocity(0.1),
+            q: linear.q,
+            r: linear.r,
+            initial_state: linear.initial_state,
+            initial_covariance: linear.initial_covariance,
+        });
+        let mut kalman = KalmanN::with_config(linear);
+        for n in 0_u32..100 {
+            let measurement = if n % 7 == 3 {
+                None
+            } else {
+                Some(f64::from(n) * 0.05 + hash_noise(n))
+            };
+            let expected = kalman.filter(([measurement], []));
+            let actual = extended.filter(([measurement], []));
+            assert_abs_diff_eq!(actual[0], expected[0], epsilon = 1e-9);
+            assert_abs_diff_eq!(actual[1], expected[1], epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn range_bearing_converges() {
+        let target = [3.0_f64, 4.0];
+        let mut extended = ExtendedKalman::with_config(range_bearing_config([4.0, 2.0]));
+        let mut estimate = [0.0; 2];
+        for n in 0_u32..200 {
+            let range = 5.0 + 0.1 * hash_noise(n);
+            let bearing = target[1].atan2(target[0]) + 0.01 * hash_noise(n + 1000);
+            estimate = extended.filter([range, bearing]);
+        }
+        assert_abs_diff_eq!(estimate[0], target[0], epsilon = 0.02);
+        assert_abs_diff_eq!(estimate[1], target[1], epsilon = 0.02);
+        assert!(extended.covariance()[(0, 0)] < 1e-3);
+    }
+
+    #[test]
+    fn residual_wraps_bearing() {
+        // A target just across the ±π boundary.
+        let mut extended = ExtendedKalman::with_config(range_bearing_config([-5.0, -0.1]));
+        for _ in 0..50 {
+            let _ = extended.filter([5.0, core::f64::consts::PI - 0.01]);
+        }
+        let [x, y] = extended.estimate();
+        assert_abs_diff_eq!(x, -5.0 * 0.01_f64.cos(), epsilon = 1e-3);
+        assert_abs_diff_eq!(y, 5.0 * 0.01_f64.sin(), epsilon = 1e-3);
+    }
+
+    #[test]
+    fn missing_measurement_only_predicts() {
+        let mut extended = ExtendedKalman::with_config(range_bearing_config([4.0, 2.0]));
+        let _ = extended.filter([5.0, 0.9]);
+        let covariance = *extended.covariance();
+        assert!(!extended.update([None, None]));
+        assert_eq!(*extended.covariance(), covariance);
+        assert!(extended.update([Some(5.0), None]));
+        assert_ne!(*extended.covariance(), covariance);
+    }
+
+    #[test]
+    fn reset_restores_initial_state() {
+        let mut extended = ExtendedKalman::with_config(range_bearing_config([4.0, 2.0]));
+        let first = extended.filter([5.0, 0.9]);
+        let _ = extended.filter([5.5, 0.8]);
+        let mut extended = extended.reset();
+        assert_eq!(extended.filter([5.0, 0.9]), first);
+    }
+}
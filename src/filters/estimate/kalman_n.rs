@@ -0,0 +1,479 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Multivariate Kalman filter with const-generic dimensions.
+//!
+//! Implements the discrete linear Kalman filter for an `X`-dimensional state,
+//! `Z`-dimensional measurements and `U`-dimensional control inputs:
+//!
+//! ```text
+//! x[n] = F·x[n−1] + B·u[n] + w,   w ~ N(0, Q)
+//! z[n] = H·x[n] + v,              v ~ N(0, R)
+//! ```
+//!
+//! Matrices are stored inline as [`Matrix`], so the filter works in `no_std`
+//! environments without an allocator.
+
+use num_traits::float::FloatCore;
+
+use crate::math::matrix::{Matrix, Vector};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::joseph_update;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The multivariate Kalman filter's configuration.
+///
+/// Unlike the scalar [`Kalman`](super::kalman::Kalman), this follows the
+/// textbook naming: `q` is the process noise and `r` the measurement noise
+/// covariance.
+#[derive(Clone, Debug)]
+pub struct Config<T, const X: usize, const Z: usize, const U: usize> {
+    /// State transition matrix `F`.
+    pub f: Matrix<T, X, X>,
+    /// Measurement matrix `H`.
+    pub h: Matrix<T, Z, X>,
+    /// Control matrix `B`.
+    pub b: Matrix<T, X, U>,
+    /// Process noise covariance `Q`.
+    pub q: Matrix<T, X, X>,
+    /// Measurement noise covariance `R` (must be positive definite).
+    pub r: Matrix<T, Z, Z>,
+    /// Initial state estimate `x₀`.
+    pub initial_state: Vector<T, X>,
+    /// Initial estimate covariance `P₀`.
+    pub initial_covariance: Matrix<T, X, X>,
+}
+
+impl<T> Config<T, 2, 1, 0>
+where
+    T: FloatCore,
+{
+    /// Creates a constant-velocity model with state `[position, velocity]`
+    /// and a position measurement.
+    ///
+    /// `process_noise` is the spectral density of the white-noise acceleration
+    /// driving the model and `measurement_noise` the position measurement
+    /// variance. The filter starts at rest at the origin with a diffuse prior,
+    /// `P₀ = 10⁶·I`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` cannot represent the model's constants.
+    pub fn constant_velocity(sample_time: T, process_noise: T, measurement_noise: T) -> Self {
+        let dt = sample_time;
+        let two = T::one() + T::one();
+        let three = two + T::one();
+        let dt2 = dt * dt;
+        let dt3 = dt2 * dt;
+        Self {
+            f: Matrix::from_rows([[T::one(), dt], [T::zero(), T::one()]]),
+            h: Matrix::from_rows([[T::one(), T::zero()]]),
+            b: Matrix::zeros(),
+            q: Matrix::from_rows([[dt3 / three, dt2 / two], [dt2 / two, dt]]).scale(process_noise),
+            r: Matrix::from_rows([[measurement_noise]]),
+            initial_state: Vector::zeros(),
+            initial_covariance: Matrix::identity().scale(diffuse()),
+        }
+    }
+}
+
+impl<T> Config<T, 3, 1, 0>
+where
+    T: FloatCore,
+{
+    /// Creates a constant-acceleration model with state
+    /// `[position, velocity, acceleration]` and a position measurement.
+    ///
+    /// `process_noise` is the spectral density of the white-noise jerk driving
+    /// the model and `measurement_noise` the position measurement variance.
+    /// The filter starts at rest at the origin with a diffuse prior,
+    /// `P₀ = 10⁶·I`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` cannot represent the model's constants.
+    pub fn constant_acceleration(sample_time: T, process_noise: T, measurement_noise: T) -> Self {
+        let dt = sample_time;
+        let constant = |value: f64| T::from(value).expect("constant is representable");
+        let (dt2, dt3) = (dt * dt, dt * dt * dt);
+        let (dt4, dt5) = (dt3 * dt, dt3 * dt * dt);
+        let half = constant(0.5);
+        Self {
+            f: Matrix::from_rows([
+                [T::one(), dt, half * dt2],
+                [T::zero(), T::one(), dt],
+                [T::zero(), T::zero(), T::one()],
+            ]),
+            h: Matrix::from_rows([[T::one(), T::zero(), T::zero()]]),
+            b: Matrix::zeros(),
+            q: Matrix::from_rows([
+                [
+                    dt5 / constant(20.0),
+                    dt4 / constant(8.0),
+                    dt3 / constant(6.0),
+                ],
+                [dt4 / constant(8.0), dt3 / constant(3.0), half * dt2],
+                [dt3 / constant(6.0), half * dt2, dt],
+            ])
+            .scale(process_noise),
+            r: Matrix::from_rows([[measurement_noise]]),
+            initial_state: Vector::zeros(),
+            initial_covariance: Matrix::identity().scale(diffuse()),
+        }
+    }
+}
+
+/// The diffuse prior variance of the ready-made models.
+fn diffuse<T: FloatCore>() -> T {
+    T::from(1e6).expect("1e6 is representable")
+}
+
+/// The multivariate Kalman filter's state.
+///
+/// Shared by the [extended](super::extended_kalman) and
+/// [unscented](super::unscented_kalman) Kalman filters.
+#[derive(Clone, Debug)]
+pub struct State<T, const X: usize> {
+    /// State estimate `x`.
+    pub estimate: Vector<T, X>,
+    /// Estimate covariance `P`.
+    pub covariance: Matrix<T, X, X>,
+}
+
+/// An `X`-dimensional Kalman filter with `Z`-dimensional measurements and
+/// `U`-dimensional control inputs.
+///
+/// Each sample runs a [`predict`](Self::predict) step followed by an
+/// [`update`](Self::update) step; both are also available separately, e.g. to
+/// predict at a higher rate than measurements arrive.
+///
+/// Measurements are given as `[Option<T>; Z]`: missing components are skipped
+/// exactly, as if the corresponding rows of `H` and `R` were removed. The
+/// covariance update uses the Joseph form,
+/// `P = (I − K·H)·P·(I − K·H)ᵀ + K·R·Kᵀ`, which keeps `P` symmetric and
+/// positive semi-definite despite rounding.
+///
+/// Feeding `[T; Z]` predicts without control input and updates with all
+/// measurements; feeding `([Option<T>; Z], [T; U])` predicts with the given
+/// control input. Both return the updated state estimate.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(X³ + X²·Z + X·Z² + Z³ + X·U); matrix products of
+///   the predict and update steps and one `Z×Z` inversion.
+/// - **Space:** O(X² + X·Z + Z² + X·U); the model matrices, state estimate and
+///   covariance.
+#[derive(Clone, Debug)]
+pub struct KalmanN<T, const X: usize, const Z: usize, const U: usize> {
+    config: Config<T, X, Z, U>,
+    state: State<T, X>,
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> KalmanN<T, X, Z, U>
+where
+    T: FloatCore,
+{
+    /// Returns the current state estimate.
+    pub fn estimate(&self) -> [T; X] {
+        self.state.estimate.column()
+    }
+
+    /// Returns the current estimate covariance.
+    pub fn covariance(&self) -> &Matrix<T, X, X> {
+        &self.state.covariance
+    }
+
+    /// Propagates the estimate by one step: `x = F·x + B·u`,
+    /// `P = F·P·Fᵀ + Q`.
+    pub fn predict(&mut self, control: [T; U]) {
+        let Config { f, b, q, .. } = self.config;
+        let State {
+            estimate,
+            covariance,
+        } = self.state;
+        self.state.estimate = f * estimate + b * Vector::from_column(control);
+        self.state.covariance = f * covariance * f.transpose() + q;
+    }
+
+    /// Corrects the estimate with the available components of `measurement`.
+    ///
+    /// Returns `false`, leaving the state untouched, if no component is
+    /// available or the innovation covariance is singular.
+    pub fn update(&mut self, measurement: [Option<T>; Z]) -> bool {
+        let State {
+            estimate,
+            covariance,
+        } = &self.state;
+        let Config { h, r, .. } = &self.config;
+        let predicted = *h * *estimate;
+        let mut residual = measurement;
+        for (i, component) in residual.iter_mut().enumerate() {
+            *component = component.map(|value| value - predicted[(i, 0)]);
+        }
+        match joseph_update(estimate, covariance, h, r, &residual) {
+            Some((estimate, covariance)) => {
+                self.state = State {
+                    estimate,
+                    covariance,
+                };
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> ConfigTrait for KalmanN<T, X, Z, U> {
+    type Config = Config<T, X, Z, U>;
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> StateTrait for KalmanN<T, X, Z, U> {
+    type State = State<T, X>;
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> WithConfig for KalmanN<T, X, Z, U>
+where
+    T: Copy,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        let state = State {
+            estimate: config.initial_state,
+            covariance: config.initial_covariance,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> ConfigRef for KalmanN<T, X, Z, U> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> ConfigClone for KalmanN<T, X, Z, U>
+where
+    Config<T, X, Z, U>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> StateMut for KalmanN<T, X, Z, U> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> HasGuts for KalmanN<T, X, Z, U> {
+    type Guts = (Config<T, X, Z, U>, State<T, X>);
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> FromGuts for KalmanN<T, X, Z, U> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> IntoGuts for KalmanN<T, X, Z, U> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> Reset for KalmanN<T, X, Z, U>
+where
+    T: Copy,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const X: usize, const Z: usize, const U: usize> ResetMut for KalmanN<T, X, Z, U> where
+    Self: Reset
+{
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> Filter<[T; Z]> for KalmanN<T, X, Z, U>
+where
+    T: FloatCore,
+{
+    type Output = [T; X];
+
+    fn filter(&mut self, input: [T; Z]) -> Self::Output {
+        self.predict([T::zero(); U]);
+        self.update(input.map(Some));
+        self.estimate()
+    }
+}
+
+impl<T, const X: usize, const Z: usize, const U: usize> Filter<([Option<T>; Z], [T; U])>
+    for KalmanN<T, X, Z, U>
+where
+    T: FloatCore,
+{
+    type Output = [T; X];
+
+    fn filter(&mut self, (measurement, control): ([Option<T>; Z], [T; U])) -> Self::Output {
+        self.predict(control);
+        self.update(measurement);
+        self.estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::util::test_fixtures::hash_noise;
+
+    #[test]
+    fn scalar_step_matches_closed_form() {
+        let mut kalman = KalmanN::with_config(Config::<f64, 1, 1, 0> {
+            f: Matrix::identity(),
+            h: Matrix::identity(),
+            b: Matrix::zeros(),
+            q: Matrix::from_rows([[0.5]]),
+            r: Matrix::from_rows([[2.0]]),
+            initial_state: Vector::from_column([1.0]),
+            initial_covariance: Matrix::from_rows([[1.5]]),
+        });
+        // P⁻ = 2, K = P⁻ / (P⁻ + R) = 0.5.
+        let [estimate] = kalman.filter([3.0]);
+        assert_abs_diff_eq!(estimate, 2.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(kalman.covariance()[(0, 0)], 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn constant_velocity_tracks_ramp() {
+        let dt = 0.1;
+        let mut kalman = KalmanN::with_config(Config::constant_velocity(dt, 0.01, 0.25));
+        let mut estimate = [0.0; 2];
+        for n in 0_u32..500 {
+            let position = 3.0 + 2.0 * dt * f64::from(n);
+            estimate = kalman.filter([position + hash_noise(n)]);
+        }
+        assert_abs_diff_eq!(estimate[0], 3.0 + 2.0 * dt * 499.0, epsilon = 0.2);
+        assert_abs_diff_eq!(estimate[1], 2.0, epsilon = 0.1);
+    }
+
+    #[test]
+    fn constant_acceleration_tracks_parabola() {
+        let dt = 0.05;
+        let mut kalman = KalmanN::with_config(Config::constant_acceleration(dt, 1e-3, 1e-4));
+        let mut estimate = [0.0; 3];
+        for n in 0_u32..2000 {
+            let t = dt * f64::from(n);
+            estimate = kalman.filter([1.0 - 0.5 * t + 0.75 * t * t + 0.01 * hash_noise(n)]);
+        }
+        let t = dt * 1999.0;
+        assert_abs_diff_eq!(estimate[1], -0.5 + 1.5 * t, epsilon = 0.05);
+        assert_abs_diff_eq!(estimate[2], 1.5, epsilon = 0.05);
+    }
+
+    #[test]
+    fn missing_measurement_only_predicts() {
+        let mut kalman = KalmanN::with_config(Config::constant_velocity(1.0, 0.1, 1.0));
+        let _ = kalman.filter([0.0]);
+        let _ = kalman.filter([1.0]);
+        let mut predicted = kalman.clone();
+        predicted.predict([]);
+
+        let estimate = kalman.filter(([None], []));
+        assert_eq!(estimate, predicted.estimate());
+        assert_eq!(kalman.covariance(), predicted.covariance());
+    }
+
+    #[test]
+    fn partial_measurement_matches_reduced_model() {
+        let config = Config::<f64, 2, 2, 0> {
+            f: Matrix::from_rows([[1.0, 0.1], [0.0, 1.0]]),
+            h: Matrix::from_rows([[1.0, 0.0], [1.0, 0.0]]),
+            b: Matrix::zeros(),
+            q: Matrix::from_diagonal([1e-3, 1e-2]),
+            r: Matrix::from_rows([[0.5, 0.1], [0.1, 2.0]]),
+            initial_state: Vector::zeros(),
+            initial_covariance: Matrix::identity(),
+        };
+        let reduced = Config::<f64, 2, 1, 0> {
+            f: config.f,
+            h: Matrix::from_rows([[1.0, 0.0]]),
+            b: Matrix::zeros(),
+            q: config.q,
+            r: Matrix::from_rows([[0.5]]),
+            initial_state: Vector::zeros(),
+            initial_covariance: Matrix::identity(),
+        };
+        let mut full = KalmanN::with_config(config);
+        let mut single = KalmanN::with_config(reduced);
+        for n in 0_u32..20 {
+            let value = f64::from(n) * 0.3;
+            let [x0, x1] = full.filter(([Some(value), None], []));
+            let [y0, y1] = single.filter([value]);
+            assert_abs_diff_eq!(x0, y0, epsilon = 1e-12);
+            assert_abs_diff_eq!(x1, y1, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn control_input_drives_prediction() {
+        let dt = 0.1;
+        let config = Config::<f64, 2, 1, 1> {
+            f: Matrix::from_rows([[1.0, dt], [0.0, 1.0]]),
+            h: Matrix::from_rows([[1.0, 0.0]]),
+            b: Matrix::from_rows([[0.5 * dt * dt], [dt]]),
+            q: Matrix::from_diagonal([1e-6, 1e-6]),
+            r: Matrix::from_rows([[1.0]]),
+            initial_state: Vector::zeros(),
+            initial_covariance: Matrix::from_diagonal([1e-6, 1e-6]),
+        };
+        let mut kalman = KalmanN::with_config(config);
+        // With a precise model, a unit acceleration command is followed
+        // regardless of the (missing) measurements.
+        let mut estimate = [0.0; 2];
+        for _ in 0..10 {
+            estimate = kalman.filter(([None], [1.0]));
+        }
+        assert_abs_diff_eq!(estimate[1], 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(estimate[0], 0.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn covariance_stays_symmetric() {
+        let mut kalman = KalmanN::with_config(Config::constant_acceleration(0.01, 10.0, 1e-2));
+        for n in 0_u32..1000 {
+            let _ = kalman.filter([hash_noise(n)]);
+        }
+        let covariance = *kalman.covariance();
+        let transposed = covariance.transpose();
+        for (row, transposed_row) in covariance.rows().iter().zip(transposed.rows()) {
+            for (value, transposed_value) in row.iter().zip(transposed_row) {
+                assert_abs_diff_eq!(value, transposed_value, epsilon = 1e-15);
+            }
+        }
+        assert!(covariance.diagonal().iter().all(|&variance| variance > 0.0));
+    }
+
+    #[test]
+    fn reset_restores_initial_state() {
+        let mut kalman = KalmanN::with_config(Config::constant_velocity(1.0, 0.1, 1.0));
+        let first = kalman.filter([5.0]);
+        let _ = kalman.filter([7.0]);
+        let mut kalman = kalman.reset();
+        assert_eq!(kalman.filter([5.0]), first);
+    }
+}
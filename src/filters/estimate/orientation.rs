@@ -0,0 +1,296 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Orientation estimation from inertial and magnetic sensors (IMU/AHRS).
+//!
+//! The filters in this module fuse gyroscope, accelerometer and (optionally)
+//! magnetometer readings into an orientation [`Quaternion`]. Each sample is a
+//! [`Sample`] tuple `(gyroscope, accelerometer, magnetometer)`:
+//!
+//! - the gyroscope measures body angular rates in rad/s,
+//! - the accelerometer measures specific force, i.e. it reads `+1 g` along the
+//!   body z axis when lying flat (only its direction is used),
+//! - the magnetometer, if present, measures the magnetic field in the body
+//!   frame (only its direction is used).
+//!
+//! Orientations rotate body-frame vectors into an earth frame whose z axis
+//! points up and whose x axis points towards magnetic north (in its
+//! horizontal projection). Without a magnetometer, the heading (yaw) is
+//! integrated from the gyroscope only and drifts.
+//!
+//! # When to use which filter
+//!
+//! | Filter                                | Purpose                                                    |
+//! | ------------------------------------- | ---------------------------------------------------------- |
+//! | `complementary::Complementary`        | Tilt (roll/pitch) and heading from Euler-angle blending    |
+//! | `mahony::Mahony`                      | PI feedback on the quaternion, with gyro-bias estimation   |
+//! | `madgwick::Madgwick`                  | Gradient-descent correction with a single gain             |
+//!
+//! - **`Complementary`** blends gyro-integrated Euler angles with the tilt from
+//!   the accelerometer and the heading from the magnetometer. It is the
+//!   simplest to tune (one time constant), but suffers from gimbal lock near
+//!   ±90° pitch.
+//! - **`Mahony`** corrects the gyro rates with a proportional-integral
+//!   feedback of the direction error; the integral term cancels constant gyro
+//!   biases.
+//! - **`Madgwick`** corrects the quaternion rate by a gradient-descent step
+//!   towards the measured directions, weighted by a single gain `β`.
+//!
+//! # See also
+//!
+//! - [`super::extended_kalman`] and [`super::unscented_kalman`]: full Kalman
+//!   filters, for custom sensor models and explicit noise covariances.
+
+use core::ops::Mul;
+
+use num_traits::Float;
+
+pub mod complementary;
+
+pub mod madgwick;
+
+pub mod mahony;
+
+/// An orientation filter's sample: `(gyroscope, accelerometer, magnetometer)`.
+pub type Sample<T> = ([T; 3], [T; 3], Option<[T; 3]>);
+
+/// A quaternion `w + x·i + y·j + z·k`.
+///
+/// Unit quaternions represent orientations; they rotate body-frame vectors
+/// into the earth frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion<T> {
+    /// Scalar part.
+    pub w: T,
+    /// First vector component.
+    pub x: T,
+    /// Second vector component.
+    pub y: T,
+    /// Third vector component.
+    pub z: T,
+}
+
+impl<T> Quaternion<T> {
+    /// Creates a quaternion from its components.
+    pub const fn new(w: T, x: T, y: T, z: T) -> Self {
+        Self { w, x, y, z }
+    }
+}
+
+impl<T> Quaternion<T>
+where
+    T: Float,
+{
+    /// Returns the identity rotation.
+    #[must_use]
+    pub fn identity() -> Self {
+        Self::new(T::one(), T::zero(), T::zero(), T::zero())
+    }
+
+    /// Returns the rotation with the given aerospace (Z-Y-X) Euler angles in
+    /// radians: yaw about z, then pitch about y, then roll about x.
+    pub fn from_euler(roll: T, pitch: T, yaw: T) -> Self {
+        let half = T::one() / (T::one() + T::one());
+        let (sr, cr) = (roll * half).sin_cos();
+        let (sp, cp) = (pitch * half).sin_cos();
+        let (sy, cy) = (yaw * half).sin_cos();
+        Self::new(
+            cr * cp * cy + sr * sp * sy,
+            sr * cp * cy - cr * sp * sy,
+            cr * sp * cy + sr * cp * sy,
+            cr * cp * sy - sr * sp * cy,
+        )
+    }
+
+    /// Returns the aerospace (Z-Y-X) Euler angles `[roll, pitch, yaw]` in
+    /// radians.
+    ///
+    /// Pitch lies in `[−π/2, π/2]`; roll and yaw in `(−π, π]`.
+    pub fn to_euler(&self) -> [T; 3] {
+        let Self { w, x, y, z } = *self;
+        let two = T::one() + T::one();
+        let roll = (two * (w * x + y * z)).atan2(T::one() - two * (x * x + y * y));
+        let pitch = (two * (w * y - z * x)).max(-T::one()).min(T::one()).asin();
+        let yaw = (two * (w * z + x * y)).atan2(T::one() - two * (y * y + z * z));
+        [roll, pitch, yaw]
+    }
+
+    /// Returns the quaternion's norm.
+    pub fn norm(&self) -> T {
+        (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    /// Returns the quaternion scaled to unit norm.
+    #[must_use]
+    pub fn normalize(&self) -> Self {
+        let norm = self.norm();
+        Self::new(self.w / norm, self.x / norm, self.y / norm, self.z / norm)
+    }
+
+    /// Returns the conjugate, which is the inverse rotation of a unit
+    /// quaternion.
+    #[must_use]
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Rotates a body-frame vector into the earth frame.
+    pub fn rotate(&self, vector: [T; 3]) -> [T; 3] {
+        let [x, y, z] = vector;
+        let rotated = *self * Self::new(T::zero(), x, y, z) * self.conjugate();
+        [rotated.x, rotated.y, rotated.z]
+    }
+
+    /// Rotates an earth-frame vector into the body frame.
+    pub fn inverse_rotate(&self, vector: [T; 3]) -> [T; 3] {
+        self.conjugate().rotate(vector)
+    }
+}
+
+impl<T> Mul for Quaternion<T>
+where
+    T: Float,
+{
+    type Output = Self;
+
+    /// Returns the Hamilton product; `a * b` applies `b` first, then `a`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Self { w, x, y, z } = self;
+        Self::new(
+            w * rhs.w - x * rhs.x - y * rhs.y - z * rhs.z,
+            w * rhs.x + x * rhs.w + y * rhs.z - z * rhs.y,
+            w * rhs.y - x * rhs.z + y * rhs.w + z * rhs.x,
+            w * rhs.z + x * rhs.y - y * rhs.x + z * rhs.w,
+        )
+    }
+}
+
+/// Returns `vector` scaled to unit length, or `None` if it is zero or not
+/// finite.
+fn normalized<T: Float>(vector: [T; 3]) -> Option<[T; 3]> {
+    let [x, y, z] = vector;
+    let norm = (x * x + y * y + z * z).sqrt();
+    (norm > T::zero() && norm.is_finite()).then(|| [x / norm, y / norm, z / norm])
+}
+
+/// Returns the cross product `a × b`.
+fn cross<T: Float>(a: [T; 3], b: [T; 3]) -> [T; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Integrates the quaternion rate `rate` over `dt` (`q += q̇·Δt`) and
+/// renormalizes.
+fn integrate<T: Float>(orientation: Quaternion<T>, rate: Quaternion<T>, dt: T) -> Quaternion<T> {
+    let Quaternion { w, x, y, z } = orientation;
+    Quaternion::new(
+        w + rate.w * dt,
+        x + rate.x * dt,
+        y + rate.y * dt,
+        z + rate.z * dt,
+    )
+    .normalize()
+}
+
+/// Returns the quaternion rate `½·q ⊗ (0, ω)` for body rates `omega`.
+fn rate<T: Float>(orientation: Quaternion<T>, omega: [T; 3]) -> Quaternion<T> {
+    let half = T::one() / (T::one() + T::one());
+    let [x, y, z] = omega;
+    let product = orientation * Quaternion::new(T::zero(), x, y, z);
+    Quaternion::new(
+        product.w * half,
+        product.x * half,
+        product.y * half,
+        product.z * half,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::FRAC_PI_2;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    fn assert_vector_eq(actual: [f64; 3], expected: [f64; 3]) {
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert_abs_diff_eq!(actual, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn euler_round_trip() {
+        let angles = [0.3, -0.4, 2.5];
+        let quaternion = Quaternion::from_euler(angles[0], angles[1], angles[2]);
+        assert_abs_diff_eq!(quaternion.norm(), 1.0, epsilon = 1e-12);
+        assert_vector_eq(quaternion.to_euler(), angles);
+    }
+
+    #[test]
+    fn rotates_by_yaw_pitch_and_roll() {
+        // A quarter turn of yaw maps the body x axis onto the earth y axis.
+        let yaw = Quaternion::from_euler(0.0, 0.0, FRAC_PI_2);
+        assert_vector_eq(yaw.rotate([1.0, 0.0, 0.0]), [0.0, 1.0, 0.0]);
+        // With z up, a positive quarter turn of pitch maps x onto −z.
+        let pitch = Quaternion::from_euler(0.0, FRAC_PI_2, 0.0);
+        assert_vector_eq(pitch.rotate([1.0, 0.0, 0.0]), [0.0, 0.0, -1.0]);
+        let roll = Quaternion::from_euler(FRAC_PI_2, 0.0, 0.0);
+        assert_vector_eq(roll.rotate([0.0, 1.0, 0.0]), [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn product_composes_rotations() {
+        let first = Quaternion::from_euler(0.2, 0.0, 0.0);
+        let second = Quaternion::from_euler(0.0, 0.0, 0.7);
+        let vector = [0.3, -1.2, 0.8];
+        assert_vector_eq(
+            (second * first).rotate(vector),
+            second.rotate(first.rotate(vector)),
+        );
+        assert_vector_eq(first.inverse_rotate(first.rotate(vector)), vector);
+        let identity = first * first.conjugate();
+        assert_abs_diff_eq!(identity.w, 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn integrates_constant_rate() {
+        let mut orientation = Quaternion::identity();
+        for _ in 0..1000 {
+            orientation = integrate(orientation, rate(orientation, [0.0, 0.0, 0.5]), 1e-3);
+        }
+        assert_abs_diff_eq!(orientation.to_euler()[2], 0.5, epsilon = 1e-6);
+    }
+}
+
+#[cfg(test)]
+mod test_util {
+    use approx::assert_abs_diff_eq;
+
+    use super::Quaternion;
+
+    /// The earth's magnetic field direction: north and (in the northern
+    /// hemisphere) downwards.
+    const FIELD: [f64; 3] = [0.4, 0.0, -0.9];
+
+    /// Returns the noiseless `(accelerometer, magnetometer)` samples of a
+    /// sensor at rest in `orientation`.
+    pub(super) fn sensors(orientation: Quaternion<f64>) -> ([f64; 3], [f64; 3]) {
+        (
+            orientation.inverse_rotate([0.0, 0.0, 9.81]),
+            orientation.inverse_rotate(FIELD),
+        )
+    }
+
+    /// Asserts that the Euler angles of `actual` are within `epsilon` of
+    /// `expected`.
+    pub(super) fn assert_angles_eq(actual: Quaternion<f64>, expected: [f64; 3], epsilon: f64) {
+        for (actual, expected) in actual.to_euler().into_iter().zip(expected) {
+            assert_abs_diff_eq!(actual, expected, epsilon = epsilon);
+        }
+    }
+}
@@ -0,0 +1,316 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Complementary filter for tilt and heading.
+
+use num_traits::Float;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{normalized, Quaternion, Sample};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The complementary filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The sample period `Δt` in seconds (`> 0`).
+    pub sample_time: T,
+    /// The time constant `τ` in seconds (`≥ 0`) below which the gyroscope is
+    /// trusted; slower changes follow the accelerometer and magnetometer.
+    pub time_constant: T,
+}
+
+/// The complementary filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The Euler angles `[roll, pitch, yaw]` in radians; `None` until the
+    /// first sample.
+    pub angles: Option<[T; 3]>,
+}
+
+/// A complementary filter, estimating tilt (roll and pitch) and heading (yaw).
+///
+/// Each sample integrates the gyroscope rates into the Euler angles and then
+/// blends the result with the roll and pitch measured by the accelerometer
+/// and, if present, the tilt-compensated heading of the magnetometer:
+///
+/// ```text
+/// angle = predicted + (1 − k)·(measured − predicted),   k = τ / (τ + Δt)
+/// ```
+///
+/// The first sample initializes the angles from the accelerometer and
+/// magnetometer directly (roll, pitch and yaw default to zero when the
+/// respective sensor is unavailable). Samples with a zero accelerometer
+/// reading are integrated from the gyroscope only.
+///
+/// The Euler-angle kinematics are singular at ±90° pitch (gimbal lock); use
+/// [`Mahony`](super::mahony::Mahony) or [`Madgwick`](super::madgwick::Madgwick)
+/// for arbitrary orientations.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); a fixed number of trigonometric functions.
+/// - **Space:** O(1); stores the three Euler angles.
+#[derive(Clone, Debug)]
+pub struct Complementary<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+impl<T> Complementary<T>
+where
+    T: Float,
+{
+    /// Returns the Euler angles `[roll, pitch, yaw]` in radians, or `None`
+    /// before the first sample.
+    pub fn angles(&self) -> Option<[T; 3]> {
+        self.state.angles
+    }
+}
+
+impl<T> ConfigTrait for Complementary<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for Complementary<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for Complementary<T>
+where
+    T: Float,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `sample_time` is not positive or `time_constant` is negative.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(
+            config.sample_time > T::zero(),
+            "Complementary: sample time must be > 0"
+        );
+        assert!(
+            config.time_constant >= T::zero(),
+            "Complementary: time constant must be >= 0"
+        );
+        let state = State { angles: None };
+        Self { config, state }
+    }
+}
+
+impl<T> ConfigRef for Complementary<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for Complementary<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for Complementary<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for Complementary<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for Complementary<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for Complementary<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for Complementary<T>
+where
+    T: Float,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for Complementary<T> where Self: Reset {}
+
+impl<T> Filter<Sample<T>> for Complementary<T>
+where
+    T: Float,
+{
+    type Output = Quaternion<T>;
+
+    fn filter(&mut self, input: Sample<T>) -> Self::Output {
+        let (gyroscope, accelerometer, magnetometer) = input;
+        let tilt = normalized(accelerometer).map(tilt);
+
+        let [roll, pitch, yaw] = if let Some([roll, pitch, yaw]) = self.state.angles {
+            let dt = self.config.sample_time;
+            let tau = self.config.time_constant;
+            let weight = dt / (tau + dt);
+
+            // Integrate the gyroscope rates (Z-Y-X Euler kinematics):
+            let [p, q, r] = gyroscope;
+            let (sin_roll, cos_roll) = roll.sin_cos();
+            let yaw_rate = (q * sin_roll + r * cos_roll) / pitch.cos();
+            let roll = roll + (p + yaw_rate * pitch.sin()) * dt;
+            let pitch = pitch + (q * cos_roll - r * sin_roll) * dt;
+            let yaw = yaw + yaw_rate * dt;
+
+            // Blend with the measured tilt and heading:
+            let blend =
+                |predicted: T, measured: T| wrap(predicted + weight * wrap(measured - predicted));
+            let (roll, pitch) = match tilt {
+                Some([measured_roll, measured_pitch]) => {
+                    (blend(roll, measured_roll), blend(pitch, measured_pitch))
+                }
+                None => (wrap(roll), pitch),
+            };
+            let yaw = match magnetometer.and_then(normalized) {
+                Some(magnetometer) => blend(yaw, heading(magnetometer, roll, pitch)),
+                None => wrap(yaw),
+            };
+            [roll, pitch, yaw]
+        } else {
+            let [roll, pitch] = tilt.unwrap_or([T::zero(); 2]);
+            let yaw = magnetometer
+                .and_then(normalized)
+                .map_or(T::zero(), |magnetometer| heading(magnetometer, roll, pitch));
+            [roll, pitch, yaw]
+        };
+
+        self.state.angles = Some([roll, pitch, yaw]);
+        Quaternion::from_euler(roll, pitch, yaw)
+    }
+}
+
+/// Returns `[roll, pitch]` of the (unit) accelerometer reading.
+fn tilt<T: Float>(accelerometer: [T; 3]) -> [T; 2] {
+    let [x, y, z] = accelerometer;
+    [y.atan2(z), (-x).atan2(y.hypot(z))]
+}
+
+/// Returns the tilt-compensated heading of the (unit) magnetometer reading.
+fn heading<T: Float>(magnetometer: [T; 3], roll: T, pitch: T) -> T {
+    let [x, y, z] = magnetometer;
+    let (sin_roll, cos_roll) = roll.sin_cos();
+    let (sin_pitch, cos_pitch) = pitch.sin_cos();
+    let north = x * cos_pitch + (y * sin_roll + z * cos_roll) * sin_pitch;
+    let west = y * cos_roll - z * sin_roll;
+    (-west).atan2(north)
+}
+
+/// Wraps an angle into `(−π, π]`.
+fn wrap<T: Float>(angle: T) -> T {
+    angle.sin().atan2(angle.cos())
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::super::test_util::sensors;
+    use super::*;
+
+    fn filter(time_constant: f64) -> Complementary<f64> {
+        Complementary::with_config(Config {
+            sample_time: 0.01,
+            time_constant,
+        })
+    }
+
+    #[test]
+    fn initializes_from_accelerometer_and_magnetometer() {
+        let truth = Quaternion::from_euler(0.3, -0.2, 1.1);
+        let (accelerometer, magnetometer) = sensors(truth);
+        let mut filter = filter(1.0);
+        assert_eq!(filter.angles(), None);
+        filter.filter(([0.0; 3], accelerometer, Some(magnetometer)));
+        let angles = filter.angles().expect("initialized");
+        for (actual, expected) in angles.into_iter().zip([0.3, -0.2, 1.1]) {
+            assert_abs_diff_eq!(actual, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn converges_to_measured_tilt() {
+        let (level_accelerometer, _) = sensors(Quaternion::identity());
+        let (tilted_accelerometer, _) = sensors(Quaternion::from_euler(0.4, 0.25, 0.0));
+        let mut filter = filter(0.5);
+        filter.filter(([0.0; 3], level_accelerometer, None));
+        // A step in the measured tilt is followed with time constant 0.5 s.
+        for _ in 0..50 {
+            filter.filter(([0.0; 3], tilted_accelerometer, None));
+        }
+        let [roll, _, _] = filter.angles().expect("initialized");
+        assert_abs_diff_eq!(roll, 0.4 * (1.0 - (-1.0_f64).exp()), epsilon = 0.01);
+        for _ in 0..1000 {
+            filter.filter(([0.0; 3], tilted_accelerometer, None));
+        }
+        let [roll, pitch, yaw] = filter.angles().expect("initialized");
+        assert_abs_diff_eq!(roll, 0.4, epsilon = 1e-6);
+        assert_abs_diff_eq!(pitch, 0.25, epsilon = 1e-6);
+        assert_abs_diff_eq!(yaw, 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn follows_gyroscope_during_fast_rotation() {
+        // The accelerometer still reports level (e.g. lagging behind), so
+        // the short-term rotation comes from the gyroscope.
+        let (accelerometer, _) = sensors(Quaternion::identity());
+        let mut filter = filter(100.0);
+        filter.filter(([0.0; 3], accelerometer, None));
+        for _ in 0..50 {
+            filter.filter(([1.0, 0.0, 0.0], accelerometer, None));
+        }
+        let [roll, pitch, yaw] = filter.angles().expect("initialized");
+        assert_abs_diff_eq!(roll, 0.5, epsilon = 0.01);
+        assert_abs_diff_eq!(pitch, 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(yaw, 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn wraps_heading_across_pi() {
+        let truth = Quaternion::from_euler(0.0, 0.0, 3.1);
+        let (accelerometer, magnetometer) = sensors(truth);
+        let mut filter = filter(0.1);
+        filter.filter(([0.0; 3], accelerometer, Some(magnetometer)));
+        // Turning through ±π takes the short way round.
+        for _ in 0..100 {
+            filter.filter(([0.0, 0.0, 0.2], accelerometer, Some(magnetometer)));
+        }
+        let [_, _, yaw] = filter.angles().expect("initialized");
+        assert!(yaw.abs() > 3.0, "yaw = {yaw}");
+    }
+
+    #[test]
+    fn reset() {
+        let (accelerometer, _) = sensors(Quaternion::from_euler(0.1, 0.0, 0.0));
+        let mut filter = filter(1.0);
+        filter.filter(([0.0; 3], accelerometer, None));
+        let filter = filter.reset();
+        assert_eq!(filter.angles(), None);
+    }
+}
@@ -0,0 +1,357 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Madgwick's gradient-descent orientation filter.
+
+use num_traits::Float;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{integrate, normalized, rate, Quaternion, Sample};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The Madgwick filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The sample period `Δt` in seconds (`> 0`).
+    pub sample_time: T,
+    /// The gain `β` in rad/s (`≥ 0`), the rate at which the orientation is
+    /// corrected towards the accelerometer and magnetometer; typically the
+    /// gyroscope's measurement error (e.g. `0.04`).
+    pub beta: T,
+}
+
+/// The Madgwick filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The orientation estimate.
+    pub orientation: Quaternion<T>,
+}
+
+/// A Madgwick filter, estimating orientation by gradient descent.
+///
+/// Each sample integrates the gyroscope rates and subtracts a normalized
+/// gradient-descent step that rotates the predicted directions of gravity and
+/// of the magnetic field (if present) towards the measured ones:
+///
+/// ```text
+/// q̇ = ½·q ⊗ (0, ω) − β·∇f / |∇f|
+/// ```
+///
+/// As in [`Mahony`](super::mahony::Mahony), the magnetic reference direction
+/// is rebuilt from each measurement's own inclination. Samples with a zero
+/// accelerometer reading are integrated from the gyroscope only. The
+/// orientation starts at the identity.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); a fixed number of arithmetic operations.
+/// - **Space:** O(1); stores the orientation.
+#[derive(Clone, Debug)]
+pub struct Madgwick<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+impl<T> Madgwick<T>
+where
+    T: Float,
+{
+    /// Returns the orientation estimate.
+    pub fn orientation(&self) -> Quaternion<T> {
+        self.state.orientation
+    }
+}
+
+impl<T> ConfigTrait for Madgwick<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for Madgwick<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for Madgwick<T>
+where
+    T: Float,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `sample_time` is not positive or `beta` is negative.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(
+            config.sample_time > T::zero(),
+            "Madgwick: sample time must be > 0"
+        );
+        assert!(config.beta >= T::zero(), "Madgwick: beta must be >= 0");
+        let state = State {
+            orientation: Quaternion::identity(),
+        };
+        Self { config, state }
+    }
+}
+
+impl<T> ConfigRef for Madgwick<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for Madgwick<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for Madgwick<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for Madgwick<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for Madgwick<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for Madgwick<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for Madgwick<T>
+where
+    T: Float,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for Madgwick<T> where Self: Reset {}
+
+impl<T> Filter<Sample<T>> for Madgwick<T>
+where
+    T: Float,
+{
+    type Output = Quaternion<T>;
+
+    fn filter(&mut self, input: Sample<T>) -> Self::Output {
+        let (gyroscope, accelerometer, magnetometer) = input;
+        let orientation = self.state.orientation;
+        let mut rate = rate(orientation, gyroscope);
+
+        if let Some(accelerometer) = normalized(accelerometer) {
+            let mut gradient = gravity_gradient(orientation, accelerometer);
+            if let Some(magnetometer) = magnetometer.and_then(normalized) {
+                let field = field_gradient(orientation, magnetometer);
+                for (gradient, field) in gradient.iter_mut().zip(field) {
+                    *gradient = *gradient + field;
+                }
+            }
+
+            let [w, x, y, z] = gradient;
+            let norm = (w * w + x * x + y * y + z * z).sqrt();
+            if norm > T::zero() {
+                let step = self.config.beta / norm;
+                rate = Quaternion::new(
+                    rate.w - step * w,
+                    rate.x - step * x,
+                    rate.y - step * y,
+                    rate.z - step * z,
+                );
+            }
+        }
+
+        self.state.orientation = integrate(orientation, rate, self.config.sample_time);
+        self.state.orientation
+    }
+}
+
+/// Returns the gravity objective `f = q* ⊗ (0, 0, 1) ⊗ q − a` for the (unit)
+/// accelerometer reading `a`, in the polynomial form whose Jacobian the
+/// gradient uses.
+fn gravity_objective<T: Float>(orientation: Quaternion<T>, accelerometer: [T; 3]) -> [T; 3] {
+    let Quaternion { w, x, y, z } = orientation;
+    let two = T::one() + T::one();
+    let [ax, ay, az] = accelerometer;
+    [
+        two * (x * z - w * y) - ax,
+        two * (w * x + y * z) - ay,
+        T::one() - two * (x * x + y * y) - az,
+    ]
+}
+
+/// Returns the magnetic objective `f = q* ⊗ (0, bx, 0, bz) ⊗ q − m` for the
+/// (unit) magnetometer reading `m`, in the polynomial form whose Jacobian the
+/// gradient uses.
+fn field_objective<T: Float>(
+    orientation: Quaternion<T>,
+    reference: [T; 2],
+    magnetometer: [T; 3],
+) -> [T; 3] {
+    let Quaternion { w, x, y, z } = orientation;
+    let two = T::one() + T::one();
+    let [bx, bz] = reference;
+    let [mx, my, mz] = magnetometer;
+    [
+        bx * (T::one() - two * (y * y + z * z)) + two * bz * (x * z - w * y) - mx,
+        two * bx * (x * y - w * z) + two * bz * (w * x + y * z) - my,
+        two * bx * (w * y + x * z) + bz * (T::one() - two * (x * x + y * y)) - mz,
+    ]
+}
+
+/// Returns the gradient `Jᵀ·f` of the [gravity objective](gravity_objective).
+fn gravity_gradient<T: Float>(orientation: Quaternion<T>, accelerometer: [T; 3]) -> [T; 4] {
+    let Quaternion { w, x, y, z } = orientation;
+    let two = T::one() + T::one();
+    let [fx, fy, fz] = gravity_objective(orientation, accelerometer);
+    [
+        two * (-y * fx + x * fy),
+        two * (z * fx + w * fy - two * x * fz),
+        two * (-w * fx + z * fy - two * y * fz),
+        two * (x * fx + y * fy),
+    ]
+}
+
+/// Returns the reference direction `[bx, bz]` of the magnetic field: the
+/// measured field in the earth frame, rotated onto the north-up plane.
+fn field_reference<T: Float>(orientation: Quaternion<T>, magnetometer: [T; 3]) -> [T; 2] {
+    let [north, west, up] = orientation.rotate(magnetometer);
+    [north.hypot(west), up]
+}
+
+/// Returns the gradient `Jᵀ·f` of the [magnetic objective](field_objective).
+fn field_gradient<T: Float>(orientation: Quaternion<T>, magnetometer: [T; 3]) -> [T; 4] {
+    let Quaternion { w, x, y, z } = orientation;
+    let two = T::one() + T::one();
+    let reference = field_reference(orientation, magnetometer);
+    let [bx, bz] = reference;
+    let [fx, fy, fz] = field_objective(orientation, reference, magnetometer);
+    [
+        two * (-bz * y * fx + (-bx * z + bz * x) * fy + bx * y * fz),
+        two * (bz * z * fx + (bx * y + bz * w) * fy + (bx * z - two * bz * x) * fz),
+        two * ((-two * bx * y - bz * w) * fx
+            + (bx * x + bz * z) * fy
+            + (bx * w - two * bz * y) * fz),
+        two * ((-two * bx * z + bz * x) * fx + (-bx * w + bz * y) * fy + bx * x * fz),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::super::test_util::{assert_angles_eq, sensors};
+    use super::*;
+
+    fn filter(beta: f64) -> Madgwick<f64> {
+        Madgwick::with_config(Config {
+            sample_time: 0.01,
+            beta,
+        })
+    }
+
+    #[test]
+    fn gradients_match_finite_differences() {
+        let orientation = Quaternion::new(0.9, 0.2, -0.3, 0.25).normalize();
+        let [accelerometer, magnetometer] =
+            [[0.1, -0.4, 0.9], [0.6, 0.3, -0.7]].map(|vector| normalized(vector).expect("nonzero"));
+        let reference = field_reference(orientation, magnetometer);
+        let half_squared_norm = |vector: [f64; 3]| vector.iter().map(|v| v * v).sum::<f64>() / 2.0;
+        let objectives = |q: Quaternion<f64>| {
+            [
+                half_squared_norm(gravity_objective(q, accelerometer)),
+                half_squared_norm(field_objective(q, reference, magnetometer)),
+            ]
+        };
+        let gradients = [
+            gravity_gradient(orientation, accelerometer),
+            field_gradient(orientation, magnetometer),
+        ];
+
+        let step = 1e-6;
+        for component in 0..4 {
+            let offset = |delta: f64| {
+                let mut q = [orientation.w, orientation.x, orientation.y, orientation.z];
+                q[component] += delta;
+                objectives(Quaternion::new(q[0], q[1], q[2], q[3]))
+            };
+            let (plus, minus) = (offset(step), offset(-step));
+            for (index, gradient) in gradients.iter().enumerate() {
+                let numeric = (plus[index] - minus[index]) / (2.0 * step);
+                assert_abs_diff_eq!(gradient[component], numeric, epsilon = 1e-6);
+            }
+        }
+
+        // At the unit sphere, the polynomial forms are the rotated references.
+        let [x, y, z] = orientation.inverse_rotate([0.0, 0.0, 1.0]);
+        let objective = gravity_objective(orientation, [0.0; 3]);
+        for (actual, expected) in objective.into_iter().zip([x, y, z]) {
+            assert_abs_diff_eq!(actual, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn converges_to_static_orientation() {
+        let truth = [0.5, -0.3, 2.0];
+        let (accelerometer, magnetometer) = sensors(Quaternion::from_euler(0.5, -0.3, 2.0));
+        let mut filter = filter(0.5);
+        for _ in 0..2000 {
+            filter.filter(([0.0; 3], accelerometer, Some(magnetometer)));
+        }
+        // The fixed-size step leaves a residual of the order of `β·Δt`.
+        assert_angles_eq(filter.orientation(), truth, 1e-2);
+    }
+
+    #[test]
+    fn corrects_tilt_but_not_heading_without_magnetometer() {
+        let (accelerometer, _) = sensors(Quaternion::from_euler(-0.4, 0.2, 0.0));
+        let mut filter = filter(0.5);
+        for _ in 0..2000 {
+            filter.filter(([0.0; 3], accelerometer, None));
+        }
+        let [roll, pitch, _] = filter.orientation().to_euler();
+        assert_abs_diff_eq!(roll, -0.4, epsilon = 1e-2);
+        assert_abs_diff_eq!(pitch, 0.2, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn integrates_gyroscope() {
+        let (accelerometer, _) = sensors(Quaternion::identity());
+        let mut filter = filter(0.1);
+        for _ in 0..100 {
+            filter.filter(([0.0, 0.0, 0.5], accelerometer, None));
+        }
+        assert_angles_eq(filter.orientation(), [0.0, 0.0, 0.5], 1e-5);
+    }
+
+    #[test]
+    fn reset() {
+        let (accelerometer, _) = sensors(Quaternion::from_euler(0.1, 0.0, 0.0));
+        let mut filter = filter(0.1);
+        filter.filter(([0.1; 3], accelerometer, None));
+        let filter = filter.reset();
+        assert_eq!(filter.orientation(), Quaternion::identity());
+    }
+}
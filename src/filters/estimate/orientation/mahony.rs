@@ -0,0 +1,296 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Mahony's nonlinear complementary filter on the rotation group.
+
+use num_traits::Float;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{cross, integrate, normalized, rate, Quaternion, Sample};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The Mahony filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The sample period `Δt` in seconds (`> 0`).
+    pub sample_time: T,
+    /// The proportional gain `Kp` in rad/s (`≥ 0`); larger values trust the
+    /// accelerometer and magnetometer more.
+    pub proportional_gain: T,
+    /// The integral gain `Ki` in rad/s² (`≥ 0`); enables the estimation of
+    /// constant gyroscope biases.
+    pub integral_gain: T,
+}
+
+/// The Mahony filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The orientation estimate.
+    pub orientation: Quaternion<T>,
+    /// The integral of the direction error, scaled by `Ki`; the negated
+    /// gyroscope bias estimate in rad/s.
+    pub integral: [T; 3],
+}
+
+/// A Mahony filter, estimating orientation with proportional-integral
+/// feedback.
+///
+/// Each sample compares the measured directions of gravity (accelerometer)
+/// and of the magnetic field (magnetometer, if present) with those predicted
+/// by the current orientation. Their cross product `e` is the rotation error,
+/// which corrects the gyroscope rates before integration:
+///
+/// ```text
+/// ω' = ω + Kp·e + Ki·∫e dt
+/// ```
+///
+/// The magnetic reference direction is rebuilt from each measurement's own
+/// inclination, so the local field need not be known. Samples with a zero
+/// accelerometer reading are integrated from the gyroscope only. The
+/// orientation starts at the identity.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); a fixed number of arithmetic operations.
+/// - **Space:** O(1); stores the orientation and the error integral.
+#[derive(Clone, Debug)]
+pub struct Mahony<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+impl<T> Mahony<T>
+where
+    T: Float,
+{
+    /// Returns the orientation estimate.
+    pub fn orientation(&self) -> Quaternion<T> {
+        self.state.orientation
+    }
+
+    /// Returns the gyroscope bias estimate `[x, y, z]` in rad/s.
+    pub fn gyroscope_bias(&self) -> [T; 3] {
+        self.state.integral.map(|value| -value)
+    }
+}
+
+impl<T> ConfigTrait for Mahony<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for Mahony<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for Mahony<T>
+where
+    T: Float,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `sample_time` is not positive or a gain is negative.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(
+            config.sample_time > T::zero(),
+            "Mahony: sample time must be > 0"
+        );
+        assert!(
+            config.proportional_gain >= T::zero() && config.integral_gain >= T::zero(),
+            "Mahony: gains must be >= 0"
+        );
+        let state = State {
+            orientation: Quaternion::identity(),
+            integral: [T::zero(); 3],
+        };
+        Self { config, state }
+    }
+}
+
+impl<T> ConfigRef for Mahony<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for Mahony<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for Mahony<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for Mahony<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for Mahony<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for Mahony<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for Mahony<T>
+where
+    T: Float,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for Mahony<T> where Self: Reset {}
+
+impl<T> Filter<Sample<T>> for Mahony<T>
+where
+    T: Float,
+{
+    type Output = Quaternion<T>;
+
+    fn filter(&mut self, input: Sample<T>) -> Self::Output {
+        let (gyroscope, accelerometer, magnetometer) = input;
+        let dt = self.config.sample_time;
+        let orientation = self.state.orientation;
+        let mut omega = gyroscope;
+
+        if let Some(accelerometer) = normalized(accelerometer) {
+            // Compute the direction error of gravity:
+            let gravity = orientation.inverse_rotate([T::zero(), T::zero(), T::one()]);
+            let mut error = cross(accelerometer, gravity);
+
+            // Add the direction error of the magnetic field, whose reference
+            // is the measured field's horizontal direction in the earth frame:
+            if let Some(magnetometer) = magnetometer.and_then(normalized) {
+                let [north, west, up] = orientation.rotate(magnetometer);
+                let reference = [north.hypot(west), T::zero(), up];
+                let field = orientation.inverse_rotate(reference);
+                let [x, y, z] = cross(magnetometer, field);
+                error = [error[0] + x, error[1] + y, error[2] + z];
+            }
+
+            // Apply the proportional-integral feedback:
+            let Config {
+                proportional_gain,
+                integral_gain,
+                ..
+            } = self.config;
+            for ((omega, integral), error) in
+                omega.iter_mut().zip(&mut self.state.integral).zip(error)
+            {
+                *integral = *integral + integral_gain * error * dt;
+                *omega = *omega + proportional_gain * error + *integral;
+            }
+        }
+
+        self.state.orientation = integrate(orientation, rate(orientation, omega), dt);
+        self.state.orientation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::super::test_util::{assert_angles_eq, sensors};
+    use super::*;
+
+    fn filter(proportional_gain: f64, integral_gain: f64) -> Mahony<f64> {
+        Mahony::with_config(Config {
+            sample_time: 0.01,
+            proportional_gain,
+            integral_gain,
+        })
+    }
+
+    #[test]
+    fn converges_to_static_orientation() {
+        let truth = [0.5, -0.3, 2.0];
+        let (accelerometer, magnetometer) = sensors(Quaternion::from_euler(0.5, -0.3, 2.0));
+        let mut filter = filter(2.0, 0.0);
+        // The heading converges more slowly, by the field's horizontal share.
+        for _ in 0..10_000 {
+            filter.filter(([0.0; 3], accelerometer, Some(magnetometer)));
+        }
+        assert_angles_eq(filter.orientation(), truth, 1e-6);
+    }
+
+    #[test]
+    fn corrects_tilt_but_not_heading_without_magnetometer() {
+        let (accelerometer, _) = sensors(Quaternion::from_euler(-0.4, 0.2, 0.0));
+        let mut filter = filter(2.0, 0.0);
+        for _ in 0..2000 {
+            filter.filter(([0.0; 3], accelerometer, None));
+        }
+        let [roll, pitch, _] = filter.orientation().to_euler();
+        assert_abs_diff_eq!(roll, -0.4, epsilon = 1e-6);
+        assert_abs_diff_eq!(pitch, 0.2, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn integral_gain_cancels_gyroscope_bias() {
+        let bias = [0.02, -0.01, 0.015];
+        let (accelerometer, magnetometer) = sensors(Quaternion::identity());
+
+        let mut proportional = filter(1.0, 0.0);
+        let mut integral = filter(1.0, 0.3);
+        for _ in 0..30_000 {
+            proportional.filter((bias, accelerometer, Some(magnetometer)));
+            integral.filter((bias, accelerometer, Some(magnetometer)));
+        }
+
+        // A proportional-only filter settles with an offset of `bias / Kp`.
+        let [roll, _, _] = proportional.orientation().to_euler();
+        assert!(roll.abs() > 0.01, "roll = {roll}");
+
+        assert_angles_eq(integral.orientation(), [0.0; 3], 1e-4);
+        for (actual, expected) in integral.gyroscope_bias().into_iter().zip(bias) {
+            assert_abs_diff_eq!(actual, expected, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn integrates_gyroscope() {
+        let (accelerometer, _) = sensors(Quaternion::identity());
+        let mut filter = filter(2.0, 0.0);
+        // A rotation about the vertical is unobservable without magnetometer.
+        for _ in 0..100 {
+            filter.filter(([0.0, 0.0, 0.5], accelerometer, None));
+        }
+        assert_angles_eq(filter.orientation(), [0.0, 0.0, 0.5], 1e-5);
+    }
+
+    #[test]
+    fn reset() {
+        let (accelerometer, _) = sensors(Quaternion::from_euler(0.1, 0.0, 0.0));
+        let mut filter = filter(1.0, 0.1);
+        filter.filter(([0.1; 3], accelerometer, None));
+        let filter = filter.reset();
+        assert_eq!(filter.orientation(), Quaternion::identity());
+        assert_eq!(filter.gyroscope_bias(), [0.0; 3]);
+    }
+}
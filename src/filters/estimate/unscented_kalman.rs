@@ -0,0 +1,584 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Unscented Kalman filter for nonlinear state-space models.
+//!
+//! The unscented Kalman filter (UKF) propagates a deterministic set of
+//! `2·X + 1` sigma points through the nonlinear model and recovers the mean
+//! and covariance from the transformed points. It needs no Jacobians and
+//! captures the posterior mean and covariance to second order.
+
+use num_traits::{float::FloatCore, Float};
+
+use crate::math::matrix::{Matrix, Vector};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::{masked_noise, NonlinearModel};
+
+pub use super::kalman_n::State;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The scaled sigma-point parameters (van der Merwe).
+///
+/// With `λ = α²·(X + κ) − X`, the sigma points are `x` and
+/// `x ± columns of √((X + λ)·P)`. The mean weights are `λ / (X + λ)` for the
+/// center and `1 / (2·(X + λ))` for the others; the center's covariance weight
+/// adds `1 − α² + β`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SigmaPoints<T> {
+    /// The spread `α` (`0 < α ≤ 1`) of the points around the mean.
+    pub alpha: T,
+    /// Prior knowledge of the distribution; `β = 2` is optimal for Gaussians.
+    pub beta: T,
+    /// The secondary scaling `κ`, usually `0` or `3 − X`.
+    pub kappa: T,
+}
+
+impl<T> Default for SigmaPoints<T>
+where
+    T: Float + FloatCore,
+{
+    /// Returns `α = 1`, `β = 2`, `κ = 0`, which weights the center's mean by
+    /// zero and keeps all weights well-conditioned.
+    fn default() -> Self {
+        Self {
+            alpha: T::one(),
+            beta: T::one() + T::one(),
+            kappa: T::zero(),
+        }
+    }
+}
+
+/// The unscented Kalman filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, M, const X: usize, const Z: usize, const U: usize> {
+    /// The nonlinear model.
+    pub model: M,
+    /// Process noise covariance `Q`.
+    pub q: Matrix<T, X, X>,
+    /// Measurement noise covariance `R` (must be positive definite).
+    pub r: Matrix<T, Z, Z>,
+    /// Initial state estimate `x₀`.
+    pub initial_state: Vector<T, X>,
+    /// Initial estimate covariance `P₀` (must be positive definite).
+    pub initial_covariance: Matrix<T, X, X>,
+    /// The sigma-point parameters.
+    pub sigma_points: SigmaPoints<T>,
+}
+
+/// An unscented Kalman filter for a [`NonlinearModel`] with `X` states, `Z`
+/// measurements and `U` control inputs, with additive process and
+/// measurement noise.
+///
+/// Both the predict and the update step draw `2·X + 1` sigma points from the
+/// current estimate and covariance, using a Cholesky factorization. Missing
+/// measurement components are skipped, as in
+/// [`KalmanN`](super::kalman_n::KalmanN). Measurement means are weighted
+/// sums of the transformed sigma points, so wrapping measurements (e.g.
+/// bearings) should stay away from the wrap-around point; residuals use
+/// [`NonlinearModel::residual`].
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(X³ + X²·Z + X·Z² + Z³), plus `2·(2·X + 1)`
+///   evaluations of the model.
+/// - **Space:** O(X² + Z²); the noise covariances, state estimate and
+///   covariance, plus the model.
+#[derive(Clone, Debug)]
+pub struct UnscentedKalman<T, M, const X: usize, const Z: usize, const U: usize> {
+    config: Config<T, M, X, Z, U>,
+    state: State<T, X>,
+}
+
+/// The sigma points of an estimate, with their weights.
+struct Sigma<T, const X: usize> {
+    center: Vector<T, X>,
+    plus: [Vector<T, X>; X],
+    minus: [Vector<T, X>; X],
+    /// The mean and covariance weights of the center point.
+    center_weights: (T, T),
+    /// The (shared) weight of all other points.
+    weight: T,
+}
+
+impl<T, const X: usize> Sigma<T, X>
+where
+    T: Float + FloatCore,
+{
+    fn new(
+        estimate: &Vector<T, X>,
+        covariance: &Matrix<T, X, X>,
+        parameters: &SigmaPoints<T>,
+    ) -> Option<Self> {
+        let SigmaPoints { alpha, beta, kappa } = *parameters;
+        let n = T::from(X).expect("state dimension is representable");
+        let alpha2 = alpha * alpha;
+        let lambda = alpha2 * (n + kappa) - n;
+        let spread = n + lambda;
+        let root = covariance.scale(spread).cholesky()?;
+        let column = |i: usize| Vector::from_column(core::array::from_fn(|j| root[(j, i)]));
+        Some(Self {
+            center: *estimate,
+            plus: core::array::from_fn(|i| *estimate + column(i)),
+            minus: core::array::from_fn(|i| *estimate - column(i)),
+            center_weights: (lambda / spread, lambda / spread + T::one() - alpha2 + beta),
+            weight: T::one() / (spread + spread),
+        })
+    }
+
+    /// Returns the points as `(mean weight, covariance weight, point)`.
+    fn points(&self) -> impl Iterator<Item = (T, T, &Vector<T, X>)> {
+        let (mean_weight, covariance_weight) = self.center_weights;
+        let weight = self.weight;
+        core::iter::once((mean_weight, covariance_weight, &self.center))
+            .chain(self.plus.iter().map(move |point| (weight, weight, point)))
+            .chain(self.minus.iter().map(move |point| (weight, weight, point)))
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> UnscentedKalman<T, M, X, Z, U>
+where
+    T: Float + FloatCore,
+    M: NonlinearModel<T, X, Z, U>,
+{
+    /// Returns the current state estimate.
+    pub fn estimate(&self) -> [T; X] {
+        self.state.estimate.column()
+    }
+
+    /// Returns the current estimate covariance.
+    pub fn covariance(&self) -> &Matrix<T, X, X> {
+        &self.state.covariance
+    }
+
+    /// Propagates the estimate by one step through the state transition.
+    ///
+    /// Returns `false`, leaving the state untouched, if the covariance is not
+    /// positive definite.
+    pub fn predict(&mut self, control: [T; U]) -> bool {
+        let Config {
+            model,
+            q,
+            sigma_points,
+            ..
+        } = &self.config;
+        let Some(sigma) = Sigma::new(&self.state.estimate, &self.state.covariance, sigma_points)
+        else {
+            return false;
+        };
+
+        let transformed = Sigma {
+            center: model.transition(&sigma.center, &control),
+            plus: sigma.plus.map(|point| model.transition(&point, &control)),
+            minus: sigma.minus.map(|point| model.transition(&point, &control)),
+            ..sigma
+        };
+        let estimate = transformed
+            .points()
+            .fold(Vector::zeros(), |mean, (weight, _, point)| {
+                mean + point.scale(weight)
+            });
+        let covariance = transformed
+            .points()
+            .fold(*q, |covariance, (_, weight, point)| {
+                let deviation = *point - estimate;
+                covariance + (deviation * deviation.transpose()).scale(weight)
+            });
+        self.state = State {
+            estimate,
+            covariance: symmetrize(&covariance),
+        };
+        true
+    }
+
+    /// Corrects the estimate with the available components of `measurement`.
+    ///
+    /// Returns `false`, leaving the state untouched, if no component is
+    /// available, the covariance is not positive definite, or the innovation
+    /// covariance is singular.
+    pub fn update(&mut self, measurement: [Option<T>; Z]) -> bool {
+        let available = measurement.map(|component| component.is_some());
+        if !available.contains(&true) {
+            return false;
+        }
+        let Config {
+            model,
+            r,
+            sigma_points,
+            ..
+        } = &self.config;
+        let State {
+            estimate,
+            covariance,
+        } = &self.state;
+        let Some(sigma) = Sigma::new(estimate, covariance, sigma_points) else {
+            return false;
+        };
+
+        // Zeroing missing components of the measurement deviations and the
+        // residual, and masking `R`, drops them from the update exactly.
+        let mask = |vector: Vector<T, Z>| {
+            let mut masked = vector;
+            for (i, _) in available.iter().enumerate().filter(|(_, &a)| !a) {
+                masked[(i, 0)] = T::zero();
+            }
+            masked
+        };
+
+        let predicted = sigma
+            .points()
+            .fold(Vector::zeros(), |mean, (weight, _, point)| {
+                mean + model.measurement(point).scale(weight)
+            });
+        let mut innovation_covariance = masked_noise(r, &available);
+        let mut cross_covariance = Matrix::<T, X, Z>::zeros();
+        for (_, weight, point) in sigma.points() {
+            let deviation = mask(model.residual(&model.measurement(point), &predicted));
+            let state_deviation = *point - *estimate;
+            innovation_covariance =
+                innovation_covariance + (deviation * deviation.transpose()).scale(weight);
+            cross_covariance =
+                cross_covariance + (state_deviation * deviation.transpose()).scale(weight);
+        }
+        let Some(inverse) = innovation_covariance.inverse() else {
+            return false;
+        };
+        let gain = cross_covariance * inverse;
+
+        let measured =
+            Vector::from_column(measurement.map(|component| component.unwrap_or(T::zero())));
+        let residual = mask(model.residual(&measured, &predicted));
+        let covariance = *covariance - gain * innovation_covariance * gain.transpose();
+        self.state = State {
+            estimate: *estimate + gain * residual,
+            covariance: symmetrize(&covariance),
+        };
+        true
+    }
+}
+
+/// Returns `(P + Pᵀ) / 2`, removing the asymmetry accumulated by rounding.
+fn symmetrize<T, const X: usize>(matrix: &Matrix<T, X, X>) -> Matrix<T, X, X>
+where
+    T: Float + FloatCore,
+{
+    let half = T::from(0.5).expect("0.5 is representable");
+    (*matrix + matrix.transpose()).scale(half)
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> ConfigTrait
+    for UnscentedKalman<T, M, X, Z, U>
+{
+    type Config = Config<T, M, X, Z, U>;
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> StateTrait
+    for UnscentedKalman<T, M, X, Z, U>
+{
+    type State = State<T, X>;
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> WithConfig
+    for UnscentedKalman<T, M, X, Z, U>
+where
+    T: Copy,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        let state = State {
+            estimate: config.initial_state,
+            covariance: config.initial_covariance,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> ConfigRef
+    for UnscentedKalman<T, M, X, Z, U>
+{
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> ConfigClone
+    for UnscentedKalman<T, M, X, Z, U>
+where
+    Config<T, M, X, Z, U>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> StateMut
+    for UnscentedKalman<T, M, X, Z, U>
+{
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> HasGuts
+    for UnscentedKalman<T, M, X, Z, U>
+{
+    type Guts = (Config<T, M, X, Z, U>, State<T, X>);
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> FromGuts
+    for UnscentedKalman<T, M, X, Z, U>
+{
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> IntoGuts
+    for UnscentedKalman<T, M, X, Z, U>
+{
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> Reset for UnscentedKalman<T, M, X, Z, U>
+where
+    T: Copy,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, M, const X: usize, const Z: usize, const U: usize> ResetMut
+    for UnscentedKalman<T, M, X, Z, U>
+where
+    Self: Reset,
+{
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> Filter<[T; Z]>
+    for UnscentedKalman<T, M, X, Z, U>
+where
+    T: Float + FloatCore,
+    M: NonlinearModel<T, X, Z, U>,
+{
+    type Output = [T; X];
+
+    fn filter(&mut self, input: [T; Z]) -> Self::Output {
+        self.predict([T::zero(); U]);
+        self.update(input.map(Some));
+        self.estimate()
+    }
+}
+
+impl<T, M, const X: usize, const Z: usize, const U: usize> Filter<([Option<T>; Z], [T; U])>
+    for UnscentedKalman<T, M, X, Z, U>
+where
+    T: Float + FloatCore,
+    M: NonlinearModel<T, X, Z, U>,
+{
+    type Output = [T; X];
+
+    fn filter(&mut self, (measurement, control): ([Option<T>; Z], [T; U])) -> Self::Output {
+        self.predict(control);
+        self.update(measurement);
+        self.estimate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::filters::estimate::kalman_n::{self, KalmanN};
+    use crate::util::test_fixtures::hash_noise;
+
+    /// A linear constant-velocity model with a position measurement.
+    #[derive(Clone)]
+    struct ConstantVelocity(f64);
+
+    impl NonlinearModel<f64, 2, 1, 0> for ConstantVelocity {
+        fn transition(&self, state: &Vector<f64, 2>, _control: &[f64; 0]) -> Vector<f64, 2> {
+            let [position, velocity] = state.column();
+            Vector::from_column([position + self.0 * velocity, velocity])
+        }
+
+        fn measurement(&self, state: &Vector<f64, 2>) -> Vector<f64, 1> {
+            Vector::from_column([state[(0, 0)]])
+        }
+    }
+
+    /// A stationary target at `[x, y]`, observed by range and bearing from the
+    /// origin.
+    struct RangeBearing;
+
+    impl NonlinearModel<f64, 2, 2, 0> for RangeBearing {
+        fn transition(&self, state: &Vector<f64, 2>, _control: &[f64; 0]) -> Vector<f64, 2> {
+            *state
+        }
+
+        fn measurement(&self, state: &Vector<f64, 2>) -> Vector<f64, 2> {
+            let [x, y] = state.column();
+            Vector::from_column([x.hypot(y), y.atan2(x)])
+        }
+    }
+
+    /// A pendulum `[angle, angular velocity]` observed through the sine of its
+    /// angle.
+    struct Pendulum(f64);
+
+    impl NonlinearModel<f64, 2, 1, 0> for Pendulum {
+        fn transition(&self, state: &Vector<f64, 2>, _control: &[f64; 0]) -> Vector<f64, 2> {
+            let [angle, velocity] = state.column();
+            let velocity = velocity - self.0 * 9.81 * angle.sin();
+            Vector::from_column([angle + self.0 * velocity, velocity])
+        }
+
+        fn measurement(&self, state: &Vector<f64, 2>) -> Vector<f64, 1> {
+            Vector::from_column([state[(0, 0)].sin()])
+        }
+    }
+
+    #[test]
+    fn linear_model_matches_kalman_n() {
+        let linear = kalman_n::Config::constant_velocity(0.1, 0.5, 0.2);
+        let mut unscented = UnscentedKalman::with_config(Config {
+            model: ConstantVelocity(0.1),
+            q: linear.q,
+            r: linear.r,
+            initial_state: linear.initial_state,
+            initial_covariance: Matrix::from_diagonal([10.0, 10.0]),
+            sigma_points: SigmaPoints::default(),
+        });
+        let mut kalman = KalmanN::with_config(kalman_n::Config {
+            initial_covariance: Matrix::from_diagonal([10.0, 10.0]),
+            ..linear
+        });
+        for n in 0_u32..100 {
+            let measurement = if n % 7 == 3 {
+                None
+            } else {
+                Some(f64::from(n) * 0.05 + hash_noise(n))
+            };
+            let expected = kalman.filter(([measurement], []));
+            let actual = unscented.filter(([measurement], []));
+            assert_abs_diff_eq!(actual[0], expected[0], epsilon = 1e-9);
+            assert_abs_diff_eq!(actual[1], expected[1], epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn sigma_parameters_do_not_matter_for_linear_models() {
+        let config = Config {
+            model: ConstantVelocity(0.1),
+            q: Matrix::from_diagonal([1e-3, 1e-2]),
+            r: Matrix::from_rows([[0.5]]),
+            initial_state: Vector::zeros(),
+            initial_covariance: Matrix::identity(),
+            sigma_points: SigmaPoints {
+                alpha: 0.1,
+                beta: 2.0,
+                kappa: 1.0,
+            },
+        };
+        let mut scaled = UnscentedKalman::with_config(config.clone());
+        let mut default = UnscentedKalman::with_config(Config {
+            sigma_points: SigmaPoints::default(),
+            ..config
+        });
+        for n in 0_u32..50 {
+            let a = scaled.filter([f64::from(n) * 0.1]);
+            let b = default.filter([f64::from(n) * 0.1]);
+            assert_abs_diff_eq!(a[0], b[0], epsilon = 1e-9);
+            assert_abs_diff_eq!(a[1], b[1], epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn range_bearing_converges() {
+        let target = [3.0_f64, 4.0];
+        let mut unscented = UnscentedKalman::with_config(Config {
+            model: RangeBearing,
+            q: Matrix::from_diagonal([1e-9, 1e-9]),
+            r: Matrix::from_diagonal([0.01, 1e-4]),
+            initial_state: Vector::from_column([4.0, 2.0]),
+            initial_covariance: Matrix::from_diagonal([4.0, 4.0]),
+            sigma_points: SigmaPoints::default(),
+        });
+        let mut estimate = [0.0; 2];
+        for n in 0_u32..200 {
+            let range = 5.0 + 0.1 * hash_noise(n);
+            let bearing = target[1].atan2(target[0]) + 0.01 * hash_noise(n + 1000);
+            estimate = unscented.filter([range, bearing]);
+        }
+        assert_abs_diff_eq!(estimate[0], target[0], epsilon = 0.02);
+        assert_abs_diff_eq!(estimate[1], target[1], epsilon = 0.02);
+    }
+
+    #[test]
+    fn tracks_pendulum() {
+        let dt = 0.01;
+        let model = Pendulum(dt);
+        let mut truth = Vector::from_column([0.8, 0.0]);
+        let mut unscented = UnscentedKalman::with_config(Config {
+            model: Pendulum(dt),
+            q: Matrix::from_diagonal([1e-6, 1e-4]),
+            r: Matrix::from_rows([[1e-3]]),
+            initial_state: Vector::from_column([0.3, 0.0]),
+            initial_covariance: Matrix::from_diagonal([0.5, 0.5]),
+            sigma_points: SigmaPoints::default(),
+        });
+        let mut estimate = [0.0; 2];
+        for n in 0_u32..1000 {
+            truth = model.transition(&truth, &[]);
+            let measured = model.measurement(&truth)[(0, 0)] + 0.03 * hash_noise(n);
+            estimate = unscented.filter([measured]);
+        }
+        assert_abs_diff_eq!(estimate[0], truth[(0, 0)], epsilon = 0.02);
+        assert_abs_diff_eq!(estimate[1], truth[(1, 0)], epsilon = 0.1);
+    }
+
+    #[test]
+    fn rejects_indefinite_covariance() {
+        let mut unscented = UnscentedKalman::with_config(Config {
+            model: RangeBearing,
+            q: Matrix::zeros(),
+            r: Matrix::identity(),
+            initial_state: Vector::from_column([1.0, 1.0]),
+            initial_covariance: Matrix::zeros(),
+            sigma_points: SigmaPoints::default(),
+        });
+        assert!(!unscented.predict([]));
+        assert!(!unscented.update([Some(1.0), None]));
+        assert!(!unscented.update([None, None]));
+        assert_eq!(unscented.estimate(), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn reset_restores_initial_state() {
+        let mut unscented = UnscentedKalman::with_config(Config {
+            model: RangeBearing,
+            q: Matrix::from_diagonal([1e-6, 1e-6]),
+            r: Matrix::from_diagonal([0.01, 1e-4]),
+            initial_state: Vector::from_column([4.0, 2.0]),
+            initial_covariance: Matrix::from_diagonal([4.0, 4.0]),
+            sigma_points: SigmaPoints::default(),
+        });
+        let first = unscented.filter([5.0, 0.9]);
+        let _ = unscented.filter([5.5, 0.8]);
+        let mut unscented = unscented.reset();
+        assert_eq!(unscented.filter([5.0, 0.9]), first);
+    }
+}
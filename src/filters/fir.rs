@@ -13,17 +13,33 @@
 //!
 //! | Filter                  | Purpose                                                |
 //! | ----------------------- | ------------------------------------------------------ |
+//! | `analytic::Analytic`    | Real-to-analytic (I/Q) conversion via Hilbert taps     |
+//! | `cic`                   | Multiplier-free CIC decimation and interpolation       |
 //! | `convolve::Convolve`    | General-purpose FIR via arbitrary coefficient kernels  |
 //! | `differentiate`         | First derivative / rate-of-change estimation           |
+//! | `farrow`                | Arbitrary, time-varying-ratio resampling               |
+//! | `half_band`             | Efficient decimation/interpolation by two              |
 //! | `comb::FeedforwardComb` | Delay-line feedforward comb (no resonant feedback)    |
 //! | `mean`                  | Sliding-window arithmetic mean for noise reduction     |
 //! | `mean_variance`         | Sliding-window mean and variance (two statistics)      |
 //! | `savitzky_golay`        | Polynomial smoothing / differentiation (Savitzky-Golay)|
 //!
+//! - **Analytic** pairs a Hilbert transformer with a matching delay to produce
+//!   `Complex<T>` samples (requires the `complex` feature); use for envelope and
+//!   instantaneous-frequency measurement.
+//! - **CIC** decimators and interpolators change the sample rate by an integer
+//!   factor using only integer additions; pair them with
+//!   `design::cic_compensation` taps to flatten their passband droop.
 //! - **Convolve** is the general-purpose workhorse: supply any coefficient kernel
 //!   and it performs convolution. Use when no specialized filter fits.
 //! - **`FeedforwardComb`** adds a delayed copy of the input (feedforward). Unlike the IIR
 //!   `comb` filter, it has no feedback resonance; output is a finite-length echo.
+//! - **Farrow** resamplers interpolate a polynomial through recent samples at
+//!   any fractional position, for asynchronous rate conversion with drifting
+//!   clocks; `polyphase::arbitrary_resampler` offers sharper filtering.
+//! - **Half-band** decimators and interpolators change the sample rate by two,
+//!   skipping zero taps and folding symmetric ones; cascade them for
+//!   power-of-two rate changes.
 //! - **Mean** and **`mean_variance`** are sliding-window statistics, useful for basic
 //!   smoothing, noise estimation, and signal quality monitoring.
 //! - **Differentiate** computes a running estimate of the first derivative, useful for
@@ -40,10 +56,15 @@
 //!   linear smoothing provided by `mean` and `savitzky_golay`.
 //! - [`super::wavelet`]: multi-resolution decomposition built on FIR convolution.
 
+#[cfg(feature = "complex")]
+pub mod analytic;
+pub mod cic;
 pub mod comb;
 pub mod convolve;
 pub mod design;
 pub mod differentiate;
+pub mod farrow;
+pub mod half_band;
 pub mod mean;
 pub mod mean_variance;
 pub mod polyphase;
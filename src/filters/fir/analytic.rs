@@ -0,0 +1,406 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Analytic-signal (I/Q) filter built on an FIR Hilbert transformer.
+//!
+//! For a real input `x[n]`, the filter outputs
+//!
+//! ```text
+//! z[n] = x[n − D] + j·(h ∗ x)[n],   D = (N − 1) / 2
+//! ```
+//!
+//! where `h` is an `N`-tap antisymmetric Hilbert transformer such as the one
+//! produced by [`design::hilbert`](crate::filters::fir::design::hilbert). The
+//! in-phase path is delayed by the transformer's group delay, so both
+//! components are time-aligned.
+//!
+//! The magnitude `|z[n]|` is the instantaneous envelope of `x` and the phase
+//! `arg z[n]` its instantaneous phase, whose first difference is the
+//! instantaneous frequency.
+//!
+//! Requires the `complex` feature.
+
+use core::marker::PhantomData;
+
+use circular_buffer::{CircularBuffer, FixedCircularBuffer};
+use num_traits::Num;
+
+use crate::complex::Complex;
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The analytic-signal filter's configuration.
+///
+/// Holds the Hilbert-transformer coefficient storage `C`, which must
+/// implement [`AsSlice<T>`] on relevant impls.
+#[derive(Clone, Debug)]
+pub struct Config<C> {
+    /// The Hilbert-transformer coefficients, ordered like
+    /// [`Convolve`](crate::filters::fir::convolve::Convolve) coefficients.
+    ///
+    /// The length must be odd. Coefficients at an even offset from the center
+    /// tap are assumed to be zero and are skipped.
+    pub coefficients: C,
+}
+
+/// The analytic-signal filter's state.
+///
+/// Holds the tap ring-buffer `R`, which must implement [`RingBuffer<T>`]
+/// on relevant impls.
+#[derive(Clone, Debug)]
+pub struct State<R> {
+    /// The filter's taps (i.e. buffered input).
+    pub taps: R,
+}
+
+/// A filter converting a real signal into its delay-matched analytic signal.
+///
+/// # Latency
+///
+/// Output `z[n]` corresponds to input `x[n − (N − 1) / 2]`. For a
+/// lower-latency alternative with non-linear phase, see
+/// [`PhaseSplitter`](crate::filters::iir::phase_splitter::PhaseSplitter).
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); about `N / 2` multiplications, since every
+///   other Hilbert coefficient is zero.
+/// - **Space:** O(N); circular tap buffer of N elements plus N coefficients.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`AnalyticArray<T, N>`] — stack-allocated, `no_std`-friendly.
+/// - [`AnalyticVec<T>`] — heap-allocated, requires the `alloc` feature.
+#[derive(Clone, Debug)]
+pub struct Analytic<T, C, R> {
+    config: Config<C>,
+    state: State<R>,
+    _pd: PhantomData<T>,
+}
+
+/// An analytic-signal filter backed by a const-generic array of coefficients
+/// and a [`FixedCircularBuffer`] tap buffer.
+pub type AnalyticArray<T, const N: usize> = Analytic<T, [T; N], FixedCircularBuffer<T, N>>;
+
+/// An analytic-signal filter backed by heap-allocated
+/// [`Vec`](alloc::vec::Vec) coefficients and a [`HeapCircularBuffer`] tap
+/// buffer.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type AnalyticVec<T> = Analytic<T, alloc::vec::Vec<T>, HeapCircularBuffer<T>>;
+
+/// An analytic-signal filter that borrows a [`CircularBuffer`] tap buffer.
+///
+/// Construct via [`Analytic::from_parts`], passing a `&mut CircularBuffer<T>`
+/// for the tap buffer.
+pub type AnalyticRefMut<'a, T, C> = Analytic<T, C, &'a mut CircularBuffer<T>>;
+
+impl<T, C, R> Analytic<T, C, R>
+where
+    C: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    /// Creates an [`Analytic`] filter from an already-constructed `config` and
+    /// `taps` ring-buffer.
+    ///
+    /// The `taps` buffer is taken as-is with its current contents.
+    ///
+    /// # Expected storage state
+    ///
+    /// For the idiomatic zero-padded cold-start, pre-fill the buffer with
+    /// `N` zeros before passing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coefficients length is even or does not equal
+    /// `taps.capacity()`.
+    pub fn from_parts(config: Config<C>, taps: R) -> Self {
+        let n = config.coefficients.as_slice().len();
+        assert!(
+            !n.is_multiple_of(2),
+            "Analytic: coefficients length must be odd"
+        );
+        assert_eq!(
+            n,
+            taps.capacity(),
+            "Analytic: coefficients length ({n}) must equal taps capacity ({})",
+            taps.capacity()
+        );
+        Self {
+            config,
+            state: State { taps },
+            _pd: PhantomData,
+        }
+    }
+
+    /// Returns the filter's group delay `(N − 1) / 2` in samples.
+    pub fn delay(&self) -> usize {
+        self.config.coefficients.as_slice().len() / 2
+    }
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+impl<T, const N: usize> AnalyticArray<T, N>
+where
+    T: num_traits::Float + core::fmt::Debug,
+{
+    /// Creates a new [`AnalyticArray`] with default Kaiser-windowed Hilbert
+    /// taps from [`design::hilbert::taps`](crate::filters::fir::design::hilbert::taps).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N < 3` or `N` is even.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut coefficients = [T::zero(); N];
+        crate::filters::fir::design::hilbert::taps(&mut coefficients);
+        Self::with_config(Config { coefficients })
+    }
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+impl<T, const N: usize> Default for AnalyticArray<T, N>
+where
+    T: num_traits::Float + core::fmt::Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(feature = "alloc", any(feature = "libm", feature = "std")))]
+impl<T> AnalyticVec<T>
+where
+    T: num_traits::Float + core::fmt::Debug,
+{
+    /// Creates a new [`AnalyticVec`] with `num_taps` default Kaiser-windowed
+    /// Hilbert taps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_taps < 3` or `num_taps` is even.
+    #[must_use]
+    pub fn new(num_taps: usize) -> Self {
+        let coefficients = crate::filters::fir::design::hilbert::taps_vec(num_taps);
+        let mut taps = HeapCircularBuffer::with_capacity(num_taps);
+        for _ in 0..num_taps {
+            taps.push_back(T::zero());
+        }
+        Self::from_parts(Config { coefficients }, taps)
+    }
+}
+
+impl<T, C, R> ConfigTrait for Analytic<T, C, R> {
+    type Config = Config<C>;
+}
+
+impl<T, C, R> StateTrait for Analytic<T, C, R> {
+    type State = State<R>;
+}
+
+impl<T, const N: usize> WithConfig for AnalyticArray<T, N>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    /// Creates an [`AnalyticArray`] from a configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is even.
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(config, zero_filled_fixed_ring::<T, N>())
+    }
+}
+
+impl<T, C, R> ConfigRef for Analytic<T, C, R> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, C, R> ConfigClone for Analytic<T, C, R>
+where
+    Config<C>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, C, R> StateMut for Analytic<T, C, R> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, C, R> HasGuts for Analytic<T, C, R> {
+    type Guts = (Config<C>, State<R>);
+}
+
+impl<T, C, R> FromGuts for Analytic<T, C, R> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self {
+            config,
+            state,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, C, R> IntoGuts for Analytic<T, C, R> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize> Reset for AnalyticArray<T, N>
+where
+    T: Num,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for AnalyticArray<T, N> where Self: Reset {}
+
+impl<T, C, R> Filter<T> for Analytic<T, C, R>
+where
+    T: Clone + Num,
+    C: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    type Output = Complex<T>;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        self.state.taps.push_back(input);
+
+        let center = self.config.coefficients.as_slice().len() / 2;
+        let re = self
+            .state
+            .taps
+            .iter()
+            .nth(center)
+            .cloned()
+            .unwrap_or_else(T::zero);
+
+        // Taps iterate oldest->newest; reversing pairs h[N-1] with the oldest.
+        // Only taps at an odd offset from the center carry non-zero coefficients.
+        let coeff_iter = self.config.coefficients.as_slice().iter().rev();
+        let im = self
+            .state
+            .taps
+            .iter()
+            .zip(coeff_iter)
+            .skip((center + 1) % 2)
+            .step_by(2)
+            .fold(T::zero(), |sum, (tap, coeff)| {
+                sum + tap.clone() * coeff.clone()
+            });
+
+        Complex::new(re, im)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::filters::fir::convolve::{Config as ConvolveConfig, ConvolveArray};
+
+    use super::*;
+
+    #[test]
+    fn imaginary_part_matches_convolution() {
+        let mut analytic = AnalyticArray::<f64, 15>::new();
+        let mut convolve = ConvolveArray::<f64, 15>::with_config(ConvolveConfig {
+            coefficients: analytic.config_ref().coefficients,
+        });
+
+        for x in crate::util::test_fixtures::collatz() {
+            let z = analytic.filter(f64::from(x));
+            assert_abs_diff_eq!(z.im, convolve.filter(f64::from(x)), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn real_part_is_delayed_input() {
+        let mut filter = AnalyticArray::<f32, 9>::new();
+        assert_eq!(filter.delay(), 4);
+
+        let input: Vec<f32> = (1_u8..=12).map(f32::from).collect();
+        let output: Vec<f32> = input.iter().map(|&x| filter.filter(x).re).collect();
+
+        assert_eq!(&output[..4], &[0.0; 4]);
+        assert_eq!(&output[4..], &input[..8]);
+    }
+
+    #[test]
+    fn cosine_becomes_unit_phasor() {
+        let frequency = 0.1_f64;
+        let mut filter = AnalyticArray::<f64, 63>::new();
+
+        for n in 0_u32..400 {
+            let z = filter.filter((core::f64::consts::TAU * frequency * f64::from(n)).cos());
+            if n < 100 {
+                continue;
+            }
+            // cos(ωn) → e^{jω(n−D)}: unit envelope, phase advancing by ω.
+            let phase = core::f64::consts::TAU * frequency * f64::from(n - 31);
+            assert_abs_diff_eq!(z.norm(), 1.0, epsilon = 1e-2);
+            assert_abs_diff_eq!(z.re, phase.cos(), epsilon = 1e-2);
+            assert_abs_diff_eq!(z.im, phase.sin(), epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn reset_clears_taps() {
+        let mut filter = AnalyticArray::<f32, 5>::new();
+        filter.filter(1.0);
+
+        let mut filter = filter.reset();
+
+        assert!(filter.state_mut().taps.iter().all(|&x| x == 0.0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let mut array = AnalyticArray::<f64, 31>::new();
+        let mut vec = AnalyticVec::<f64>::new(31);
+
+        for x in crate::util::test_fixtures::collatz() {
+            let a = array.filter(f64::from(x));
+            let v = vec.filter(f64::from(x));
+            assert_abs_diff_eq!(a.re, v.re, epsilon = 1e-12);
+            assert_abs_diff_eq!(a.im, v.im, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Analytic: coefficients length must be odd")]
+    fn rejects_even_length() {
+        let _ = AnalyticArray::<f32, 4>::with_config(Config {
+            coefficients: [0.0; 4],
+        });
+    }
+}
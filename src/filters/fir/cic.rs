@@ -0,0 +1,132 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Cascaded integrator-comb (CIC) decimators and interpolators.
+//!
+//! A CIC filter of order `N`, differential delay `M` and rate `R` is a cascade
+//! of `N` moving sums of length `R·M`, implemented without multiplications as
+//! `N` integrators at the high rate and `N` combs `y[n] = x[n] − x[n − M]` at
+//! the low rate. Its transfer function, referred to the high rate, is
+//!
+//! ```text
+//! H(z) = ((1 − z^{−R·M}) / (1 − z^{−1}))^N
+//! ```
+//!
+//! with DC gain `G = (R·M)^N`. This makes CIC filters the usual first stage for
+//! high-ratio (e.g. 64× to 1024×) rate changes of sigma-delta and SDR streams,
+//! followed by a short compensation FIR from
+//! [`design::cic_compensation`](super::design::cic_compensation) that
+//! flattens the `sinc^N` passband droop.
+//!
+//! # Wrap-around arithmetic
+//!
+//! The integrators have a pole at DC and overflow on any input with a DC
+//! component. This is harmless as long as all stages use two's-complement
+//! wrap-around arithmetic and the register width is at least
+//! `input_bits + bit_growth` bits (Hogenauer, 1981): the combs undo the
+//! overflow exactly. The filters therefore operate on integer types through
+//! [`WrappingAdd`](num_traits::WrappingAdd) and
+//! [`WrappingSub`](num_traits::WrappingSub); choose the sample type with
+//! [`CicDecimator::bit_growth`](decimator::CicDecimator::bit_growth) or
+//! [`CicInterpolator::bit_growth`](interpolator::CicInterpolator::bit_growth).
+//!
+//! # References
+//!
+//! E. B. Hogenauer, "An economical class of digital filters for decimation and
+//! interpolation," IEEE Trans. ASSP, vol. 29, no. 2, pp. 155–162, 1981.
+
+pub mod decimator;
+pub mod interpolator;
+
+/// The CIC filter's configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Number of integrator and comb stages `N`.
+    pub order: usize,
+    /// Differential delay `M` of each comb, in low-rate samples (usually 1 or 2).
+    pub differential_delay: usize,
+    /// Rate-change factor `R`.
+    pub rate: usize,
+}
+
+impl Config {
+    /// Creates a CIC configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order`, `differential_delay` or `rate` is zero.
+    #[must_use]
+    pub fn new(order: usize, differential_delay: usize, rate: usize) -> Self {
+        assert!(order > 0, "CIC: order N must be > 0");
+        assert!(
+            differential_delay > 0,
+            "CIC: differential delay M must be > 0"
+        );
+        assert!(rate > 0, "CIC: rate R must be > 0");
+        Self {
+            order,
+            differential_delay,
+            rate,
+        }
+    }
+
+    /// Returns the DC gain `(R·M)^N` of the integrator-comb cascade, or `None`
+    /// if it does not fit in a `u128`.
+    #[must_use]
+    pub fn gain(&self) -> Option<u128> {
+        let rm = u128::try_from(self.rate.checked_mul(self.differential_delay)?).ok()?;
+        let order = u32::try_from(self.order).ok()?;
+        rm.checked_pow(order)
+    }
+}
+
+/// Returns `⌈log₂(value)⌉` for `value >= 1`.
+fn ceil_log2(value: u128) -> u32 {
+    debug_assert!(value > 0);
+    u128::BITS - (value - 1).leading_zeros()
+}
+
+fn assert_config(config: &Config, order: usize, differential_delay: usize, name: &str) {
+    assert!(config.rate > 0, "{name}: rate R must be > 0");
+    assert_eq!(
+        config.order, order,
+        "{name}: stage storage length must equal order N"
+    );
+    assert_eq!(
+        config.differential_delay, differential_delay,
+        "{name}: comb capacity must equal differential delay M"
+    );
+    assert!(order > 0, "{name}: order N must be > 0");
+    assert!(
+        differential_delay > 0,
+        "{name}: differential delay M must be > 0"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gain_is_rm_to_the_n() {
+        assert_eq!(Config::new(4, 1, 64).gain(), Some(1 << 24));
+        assert_eq!(Config::new(3, 2, 5).gain(), Some(1000));
+        assert_eq!(Config::new(20, 1, 1 << 10).gain(), None);
+    }
+
+    #[test]
+    fn ceil_log2_rounds_up() {
+        assert_eq!(ceil_log2(1), 0);
+        assert_eq!(ceil_log2(2), 1);
+        assert_eq!(ceil_log2(1000), 10);
+        assert_eq!(ceil_log2(1024), 10);
+        assert_eq!(ceil_log2(1025), 11);
+    }
+
+    #[test]
+    #[should_panic(expected = "CIC: rate R must be > 0")]
+    fn rejects_zero_rate() {
+        let _ = Config::new(3, 1, 0);
+    }
+}
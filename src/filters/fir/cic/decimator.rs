@@ -0,0 +1,450 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! CIC decimation.
+
+use core::marker::PhantomData;
+
+use circular_buffer::FixedCircularBuffer;
+use num_traits::{Num, WrappingAdd, WrappingSub, Zero};
+
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, MultirateFilter, Reset, WithConfig,
+};
+
+use super::{assert_config, ceil_log2, Config};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The CIC decimator's state.
+#[derive(Clone, Debug)]
+pub struct State<I, C> {
+    /// Integrator accumulators, one per stage.
+    pub integrators: I,
+    /// Comb delay lines of `M` low-rate samples, one per stage.
+    pub combs: C,
+    /// Number of input samples to integrate before the next output, minus one.
+    pub phase: usize,
+}
+
+/// A stateful R-to-1 CIC decimator.
+///
+/// Every input sample runs through the `N` integrators. Every `R`th input
+/// sample, the last integrator's value runs through the `N` combs and is
+/// emitted. Like [`PolyphaseDecimator`](crate::filters::fir::polyphase::decimator::PolyphaseDecimator),
+/// the first output is produced after `R` input samples have been consumed.
+///
+/// # Gain
+///
+/// The output is scaled by [`gain`](Self::gain) `= (R·M)^N`. Shift or scale the
+/// output down if unity gain is required.
+///
+/// # Streaming
+///
+/// The [`MultirateFilter::process`] implementation supports streaming operation
+/// with arbitrary input and output slice sizes. If the output slice is full
+/// when an input would complete a decimation block, that input remains
+/// unconsumed and should be passed again on a later call.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`CicDecimatorArray<T, N, M>`] for stack-allocated stage storage.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`CicDecimatorVec<T>`] for heap-allocated stage storage."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `CicDecimatorVec<T>` for heap-allocated stage storage."
+)]
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N + N/R) amortized; N wrapping additions per input
+///   sample plus N wrapping subtractions per output sample.
+/// - **Space:** O(N·M); N integrators plus N comb delay lines of M samples.
+#[derive(Clone, Debug)]
+pub struct CicDecimator<T, I, C, B> {
+    config: Config,
+    state: State<I, C>,
+    _pd: PhantomData<(T, B)>,
+}
+
+/// A CIC decimator with `N` stages and differential delay `M`, backed by
+/// fixed-size stage storage.
+pub type CicDecimatorArray<T, const N: usize, const M: usize> =
+    CicDecimator<T, [T; N], [FixedCircularBuffer<T, M>; N], FixedCircularBuffer<T, M>>;
+
+/// A CIC decimator backed by heap-allocated stage storage.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type CicDecimatorVec<T> = CicDecimator<
+    T,
+    alloc::vec::Vec<T>,
+    alloc::vec::Vec<HeapCircularBuffer<T>>,
+    HeapCircularBuffer<T>,
+>;
+
+impl<T, I, C, B> CicDecimator<T, I, C, B>
+where
+    I: AsSlice<T>,
+    C: AsSlice<B>,
+    B: RingBuffer<T>,
+{
+    /// Creates a [`CicDecimator`] from an already-constructed `config` and
+    /// stage storage.
+    ///
+    /// The storage is taken as-is with its current contents.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-initialized cold-start behavior, zero the integrators and
+    /// prefill every comb buffer with `M` zeros before passing them here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.rate` is zero, if the number of integrators or combs
+    /// does not equal `config.order`, or if any comb capacity does not equal
+    /// `config.differential_delay`.
+    pub fn from_parts(config: Config, integrators: I, combs: C) -> Self {
+        let order = integrators.as_slice().len();
+        assert_eq!(
+            combs.as_slice().len(),
+            order,
+            "CicDecimator: comb count must equal integrator count"
+        );
+        let differential_delay = combs.as_slice().first().map_or(0, RingBuffer::capacity);
+        assert!(
+            combs
+                .as_slice()
+                .iter()
+                .all(|comb| comb.capacity() == differential_delay),
+            "CicDecimator: comb capacity must equal differential delay M"
+        );
+        assert_config(&config, order, differential_delay, "CicDecimator");
+        Self {
+            state: State {
+                integrators,
+                combs,
+                phase: config.rate - 1,
+            },
+            config,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, I, C, B> CicDecimator<T, I, C, B> {
+    /// Returns the DC gain `(R·M)^N`, or `None` if it does not fit in a `u128`.
+    #[must_use]
+    pub fn gain(&self) -> Option<u128> {
+        self.config.gain()
+    }
+
+    /// Returns the register bit growth `⌈N·log₂(R·M)⌉`, or `None` if the gain
+    /// does not fit in a `u128`.
+    ///
+    /// Samples of `B_in` bits need a sample type of at least
+    /// `B_in + bit_growth` bits to decimate without error.
+    #[must_use]
+    pub fn bit_growth(&self) -> Option<u32> {
+        self.gain().map(ceil_log2)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CicDecimatorVec<T>
+where
+    T: Clone + Zero,
+{
+    /// Creates a heap-backed CIC decimator with zero-initialized stages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order`, `differential_delay` or `rate` is zero.
+    #[must_use]
+    pub fn new(order: usize, differential_delay: usize, rate: usize) -> Self {
+        let config = Config::new(order, differential_delay, rate);
+        let integrators = alloc::vec![T::zero(); order];
+        let combs = (0..order)
+            .map(|_| {
+                let mut comb = HeapCircularBuffer::with_capacity(differential_delay);
+                for _ in 0..differential_delay {
+                    let _ = comb.push_back(T::zero());
+                }
+                comb
+            })
+            .collect();
+        Self::from_parts(config, integrators, combs)
+    }
+}
+
+impl<T, const N: usize, const M: usize> CicDecimatorArray<T, N, M>
+where
+    T: Num,
+{
+    /// Creates a CIC decimator with `N` stages, differential delay `M` and
+    /// decimation factor `rate`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N`, `M` or `rate` is zero.
+    #[must_use]
+    pub fn new(rate: usize) -> Self {
+        Self::with_config(Config::new(N, M, rate))
+    }
+}
+
+impl<T, I, C, B> ConfigTrait for CicDecimator<T, I, C, B> {
+    type Config = Config;
+}
+
+impl<T, const N: usize, const M: usize> WithConfig for CicDecimatorArray<T, N, M>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        let integrators = core::array::from_fn(|_| T::zero());
+        let combs = core::array::from_fn(|_| zero_filled_fixed_ring::<T, M>());
+        Self::from_parts(config, integrators, combs)
+    }
+}
+
+impl<T, I, C, B> ConfigRef for CicDecimator<T, I, C, B> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, I, C, B> ConfigClone for CicDecimator<T, I, C, B> {
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, I, C, B> HasGuts for CicDecimator<T, I, C, B> {
+    type Guts = (Config, State<I, C>);
+}
+
+impl<T, I, C, B> FromGuts for CicDecimator<T, I, C, B> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self {
+            config,
+            state,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, I, C, B> IntoGuts for CicDecimator<T, I, C, B> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize, const M: usize> Reset for CicDecimatorArray<T, N, M>
+where
+    T: Num,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize, const M: usize> ResetMut for CicDecimatorArray<T, N, M> where Self: Reset {}
+
+impl<T, I, C, B> MultirateFilter<T> for CicDecimator<T, I, C, B>
+where
+    T: Clone + Zero + WrappingAdd + WrappingSub,
+    I: AsSlice<T>,
+    C: AsSlice<B>,
+    B: RingBuffer<T>,
+{
+    type Output = T;
+
+    fn process(&mut self, input: &[T], output: &mut [Self::Output]) -> (usize, usize) {
+        let mut input_consumed = 0;
+        let mut output_produced = 0;
+
+        while input_consumed < input.len() {
+            let phase = self.state.phase;
+            if phase == 0 && output_produced == output.len() {
+                break;
+            }
+
+            let mut sample = input[input_consumed].clone();
+            input_consumed += 1;
+            for integrator in self.state.integrators.as_mut_slice() {
+                *integrator = integrator.wrapping_add(&sample);
+                sample = integrator.clone();
+            }
+
+            if phase > 0 {
+                self.state.phase = phase - 1;
+                continue;
+            }
+            self.state.phase = self.config.rate - 1;
+
+            for comb in self.state.combs.as_mut_slice() {
+                let delayed = comb.push_back(sample.clone()).unwrap_or_else(T::zero);
+                sample = sample.wrapping_sub(&delayed);
+            }
+            output[output_produced] = sample;
+            output_produced += 1;
+        }
+
+        (input_consumed, output_produced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    use crate::filters::fir::convolve::{Config as ConvolveConfig, ConvolveArray};
+    use crate::traits::Filter;
+
+    /// Reference: `N` cascaded moving sums of length `R·M`, then keep every `R`th output.
+    fn moving_sum_reference<const L: usize>(order: usize, rate: usize, input: &[i64]) -> Vec<i64> {
+        let mut signal = input.to_vec();
+        for _ in 0..order {
+            let mut boxcar = ConvolveArray::<i64, L>::with_config(ConvolveConfig {
+                coefficients: [1; L],
+            });
+            signal = signal.iter().map(|&x| boxcar.filter(x)).collect();
+        }
+        signal.into_iter().skip(rate - 1).step_by(rate).collect()
+    }
+
+    fn test_input() -> Vec<i64> {
+        (0..50).map(|n: i64| (n * 37 + 11) % 23 - 11).collect()
+    }
+
+    #[test]
+    fn matches_cascaded_moving_sums() {
+        let input = test_input();
+
+        let mut decimator = CicDecimatorArray::<i64, 3, 1>::new(4);
+        let mut output = [0; 64];
+        let (consumed, produced) = decimator.process(&input, &mut output);
+
+        assert_eq!(consumed, input.len());
+        assert_eq!(&output[..produced], moving_sum_reference::<4>(3, 4, &input));
+    }
+
+    #[test]
+    fn differential_delay_two_matches_reference() {
+        let input = test_input();
+
+        let mut decimator = CicDecimatorArray::<i64, 2, 2>::new(3);
+        let mut output = [0; 64];
+        let (_, produced) = decimator.process(&input, &mut output);
+
+        assert_eq!(&output[..produced], moving_sum_reference::<6>(2, 3, &input));
+    }
+
+    #[test]
+    fn dc_input_settles_at_gain() {
+        let mut decimator = CicDecimatorArray::<i32, 4, 1>::new(8);
+        assert_eq!(decimator.gain(), Some(4096));
+        assert_eq!(decimator.bit_growth(), Some(12));
+
+        let input = [3; 64];
+        let mut output = [0; 8];
+        assert_eq!(decimator.process(&input, &mut output), (64, 8));
+        assert_eq!(&output[4..], &[3 * 4096; 4]);
+    }
+
+    #[test]
+    fn integrator_wraparound_is_harmless() {
+        // 8-bit registers with 4 bits of growth: a 4-bit signed input (±7)
+        // stays exact even though the integrators overflow repeatedly.
+        let mut narrow = CicDecimatorArray::<i8, 2, 1>::new(4);
+        let mut wide = CicDecimatorArray::<i64, 2, 1>::new(4);
+        assert_eq!(narrow.bit_growth(), Some(4));
+
+        let input: Vec<i8> = (0..256).map(|n| if n % 7 < 5 { 7 } else { -8 }).collect();
+        let wide_input: Vec<i64> = input.iter().map(|&x| i64::from(x)).collect();
+        let mut narrow_output = [0_i8; 64];
+        let mut wide_output = [0_i64; 64];
+
+        assert_eq!(narrow.process(&input, &mut narrow_output), (256, 64));
+        assert_eq!(wide.process(&wide_input, &mut wide_output), (256, 64));
+
+        for (&narrow, &wide) in narrow_output.iter().zip(&wide_output) {
+            assert_eq!(i64::from(narrow), wide);
+        }
+    }
+
+    #[test]
+    fn streaming_matches_single_call() {
+        let input = test_input();
+
+        let mut single = CicDecimatorArray::<i64, 3, 1>::new(3);
+        let mut expected = [0; 64];
+        let (_, expected_len) = single.process(&input, &mut expected);
+
+        let mut streamed = CicDecimatorArray::<i64, 3, 1>::new(3);
+        let mut output = Vec::new();
+        let mut remaining = input.as_slice();
+        while !remaining.is_empty() {
+            let mut chunk = [0; 1];
+            let (consumed, produced) =
+                streamed.process(&remaining[..remaining.len().min(5)], &mut chunk);
+            output.extend_from_slice(&chunk[..produced]);
+            remaining = &remaining[consumed..];
+        }
+
+        assert_eq!(output, &expected[..expected_len]);
+    }
+
+    #[test]
+    fn reset_clears_stages_and_phase() {
+        let mut decimator = CicDecimatorArray::<i32, 2, 1>::new(2);
+        let mut output = [0; 1];
+        assert_eq!(decimator.process(&[5, 6, 7], &mut output), (3, 1));
+
+        let mut decimator = decimator.reset();
+
+        assert_eq!(decimator.process(&[1, 1], &mut output), (2, 1));
+        assert_eq!(output, [3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let input = test_input();
+
+        let mut array = CicDecimatorArray::<i64, 3, 2>::new(5);
+        let mut vec = CicDecimatorVec::<i64>::new(3, 2, 5);
+        let mut array_output = [0; 32];
+        let mut vec_output = [0; 32];
+
+        assert_eq!(
+            array.process(&input, &mut array_output),
+            vec.process(&input, &mut vec_output)
+        );
+        assert_eq!(array_output, vec_output);
+    }
+
+    #[test]
+    #[should_panic(expected = "CicDecimator: stage storage length must equal order N")]
+    fn rejects_mismatched_order() {
+        let _ = CicDecimatorArray::<i32, 3, 1>::with_config(Config::new(2, 1, 4));
+    }
+}
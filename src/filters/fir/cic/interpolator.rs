@@ -0,0 +1,453 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! CIC interpolation.
+
+use core::marker::PhantomData;
+
+use circular_buffer::FixedCircularBuffer;
+use num_traits::{Num, WrappingAdd, WrappingSub, Zero};
+
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, MultirateFilter, Reset, WithConfig,
+};
+
+use super::{assert_config, ceil_log2, Config};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The CIC interpolator's state.
+#[derive(Clone, Debug)]
+pub struct State<T, C, I> {
+    /// Comb delay lines of `M` low-rate samples, one per stage.
+    pub combs: C,
+    /// Integrator accumulators, one per stage.
+    pub integrators: I,
+    /// Comb output of the most recently consumed input sample, fed to the
+    /// integrators on the first output of its block.
+    pub pending: T,
+    /// The index of the next output within the current block.
+    ///
+    /// `phase == rate` means no output is pending.
+    pub phase: usize,
+}
+
+/// A stateful 1-to-R CIC interpolator.
+///
+/// Each consumed input sample runs through the `N` combs. The result is
+/// zero-stuffed to `R` samples, which run through the `N` integrators and are
+/// emitted.
+///
+/// # Gain
+///
+/// The output is scaled by [`gain`](Self::gain) `= (R·M)^N / R`. Zero-stuffing
+/// accounts for the division by `R`.
+///
+/// # Streaming
+///
+/// The [`MultirateFilter::process`] implementation supports streaming operation
+/// with arbitrary input and output slice sizes. Like
+/// [`PolyphaseInterpolator`](crate::filters::fir::polyphase::interpolator::PolyphaseInterpolator),
+/// if the output buffer fills before a block is complete, the next call resumes
+/// the block without consuming another input sample first. An empty output
+/// slice consumes no input.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`CicInterpolatorArray<T, N, M>`] for stack-allocated stage storage.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`CicInterpolatorVec<T>`] for heap-allocated stage storage."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `CicInterpolatorVec<T>` for heap-allocated stage storage."
+)]
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N) per output sample; N wrapping additions per
+///   output sample plus N wrapping subtractions per input sample.
+/// - **Space:** O(N·M); N comb delay lines of M samples plus N integrators.
+#[derive(Clone, Debug)]
+pub struct CicInterpolator<T, C, I, B> {
+    config: Config,
+    state: State<T, C, I>,
+    _pd: PhantomData<B>,
+}
+
+/// A CIC interpolator with `N` stages and differential delay `M`, backed by
+/// fixed-size stage storage.
+pub type CicInterpolatorArray<T, const N: usize, const M: usize> =
+    CicInterpolator<T, [FixedCircularBuffer<T, M>; N], [T; N], FixedCircularBuffer<T, M>>;
+
+/// A CIC interpolator backed by heap-allocated stage storage.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type CicInterpolatorVec<T> = CicInterpolator<
+    T,
+    alloc::vec::Vec<HeapCircularBuffer<T>>,
+    alloc::vec::Vec<T>,
+    HeapCircularBuffer<T>,
+>;
+
+impl<T, C, I, B> CicInterpolator<T, C, I, B>
+where
+    T: Zero,
+    C: AsSlice<B>,
+    I: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    /// Creates a [`CicInterpolator`] from an already-constructed `config` and
+    /// stage storage.
+    ///
+    /// The storage is taken as-is with its current contents.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-initialized cold-start behavior, prefill every comb buffer
+    /// with `M` zeros and zero the integrators before passing them here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.rate` is zero, if the number of combs or integrators
+    /// does not equal `config.order`, or if any comb capacity does not equal
+    /// `config.differential_delay`.
+    pub fn from_parts(config: Config, combs: C, integrators: I) -> Self {
+        let order = integrators.as_slice().len();
+        assert_eq!(
+            combs.as_slice().len(),
+            order,
+            "CicInterpolator: comb count must equal integrator count"
+        );
+        let differential_delay = combs.as_slice().first().map_or(0, RingBuffer::capacity);
+        assert!(
+            combs
+                .as_slice()
+                .iter()
+                .all(|comb| comb.capacity() == differential_delay),
+            "CicInterpolator: comb capacity must equal differential delay M"
+        );
+        assert_config(&config, order, differential_delay, "CicInterpolator");
+        Self {
+            state: State {
+                combs,
+                integrators,
+                pending: T::zero(),
+                phase: config.rate,
+            },
+            config,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, C, I, B> CicInterpolator<T, C, I, B> {
+    /// Returns the DC gain `(R·M)^N / R`, or `None` if `(R·M)^N` does not fit
+    /// in a `u128`.
+    #[must_use]
+    pub fn gain(&self) -> Option<u128> {
+        let rate = u128::try_from(self.config.rate).ok()?;
+        self.config.gain().map(|gain| gain / rate)
+    }
+
+    /// Returns the output register bit growth `⌈log₂((R·M)^N / R)⌉`, or
+    /// `None` if `(R·M)^N` does not fit in a `u128`.
+    ///
+    /// Samples of `B_in` bits need a sample type of at least
+    /// `B_in + bit_growth` bits to interpolate without error.
+    #[must_use]
+    pub fn bit_growth(&self) -> Option<u32> {
+        self.gain().map(ceil_log2)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> CicInterpolatorVec<T>
+where
+    T: Clone + Zero,
+{
+    /// Creates a heap-backed CIC interpolator with zero-initialized stages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order`, `differential_delay` or `rate` is zero.
+    #[must_use]
+    pub fn new(order: usize, differential_delay: usize, rate: usize) -> Self {
+        let config = Config::new(order, differential_delay, rate);
+        let combs = (0..order)
+            .map(|_| {
+                let mut comb = HeapCircularBuffer::with_capacity(differential_delay);
+                for _ in 0..differential_delay {
+                    let _ = comb.push_back(T::zero());
+                }
+                comb
+            })
+            .collect();
+        let integrators = alloc::vec![T::zero(); order];
+        Self::from_parts(config, combs, integrators)
+    }
+}
+
+impl<T, const N: usize, const M: usize> CicInterpolatorArray<T, N, M>
+where
+    T: Num,
+{
+    /// Creates a CIC interpolator with `N` stages, differential delay `M` and
+    /// interpolation factor `rate`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N`, `M` or `rate` is zero.
+    #[must_use]
+    pub fn new(rate: usize) -> Self {
+        Self::with_config(Config::new(N, M, rate))
+    }
+}
+
+impl<T, C, I, B> ConfigTrait for CicInterpolator<T, C, I, B> {
+    type Config = Config;
+}
+
+impl<T, const N: usize, const M: usize> WithConfig for CicInterpolatorArray<T, N, M>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        let combs = core::array::from_fn(|_| zero_filled_fixed_ring::<T, M>());
+        let integrators = core::array::from_fn(|_| T::zero());
+        Self::from_parts(config, combs, integrators)
+    }
+}
+
+impl<T, C, I, B> ConfigRef for CicInterpolator<T, C, I, B> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, C, I, B> ConfigClone for CicInterpolator<T, C, I, B> {
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, C, I, B> HasGuts for CicInterpolator<T, C, I, B> {
+    type Guts = (Config, State<T, C, I>);
+}
+
+impl<T, C, I, B> FromGuts for CicInterpolator<T, C, I, B> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self {
+            config,
+            state,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, C, I, B> IntoGuts for CicInterpolator<T, C, I, B> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize, const M: usize> Reset for CicInterpolatorArray<T, N, M>
+where
+    T: Num,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize, const M: usize> ResetMut for CicInterpolatorArray<T, N, M> where Self: Reset {}
+
+impl<T, C, I, B> MultirateFilter<T> for CicInterpolator<T, C, I, B>
+where
+    T: Clone + Zero + WrappingAdd + WrappingSub,
+    C: AsSlice<B>,
+    I: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    type Output = T;
+
+    fn process(&mut self, input: &[T], output: &mut [Self::Output]) -> (usize, usize) {
+        let mut input_consumed = 0;
+        let mut output_produced = 0;
+        let rate = self.config.rate;
+
+        while output_produced < output.len() {
+            if self.state.phase == rate {
+                if input_consumed == input.len() {
+                    break;
+                }
+
+                let mut sample = input[input_consumed].clone();
+                input_consumed += 1;
+                for comb in self.state.combs.as_mut_slice() {
+                    let delayed = comb.push_back(sample.clone()).unwrap_or_else(T::zero);
+                    sample = sample.wrapping_sub(&delayed);
+                }
+                self.state.pending = sample;
+                self.state.phase = 0;
+            }
+
+            // Zero-stuffing: only the first sample of each block carries input.
+            let mut sample = core::mem::replace(&mut self.state.pending, T::zero());
+            for integrator in self.state.integrators.as_mut_slice() {
+                *integrator = integrator.wrapping_add(&sample);
+                sample = integrator.clone();
+            }
+            output[output_produced] = sample;
+            output_produced += 1;
+            self.state.phase += 1;
+        }
+
+        (input_consumed, output_produced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    use crate::filters::fir::convolve::{Config as ConvolveConfig, ConvolveArray};
+    use crate::traits::Filter;
+
+    /// Reference: zero-stuff by `R`, then `N` cascaded moving sums of length `R·M`.
+    fn moving_sum_reference<const L: usize>(order: usize, rate: usize, input: &[i64]) -> Vec<i64> {
+        let mut signal: Vec<i64> = input
+            .iter()
+            .flat_map(|&x| core::iter::once(x).chain(core::iter::repeat_n(0, rate - 1)))
+            .collect();
+        for _ in 0..order {
+            let mut boxcar = ConvolveArray::<i64, L>::with_config(ConvolveConfig {
+                coefficients: [1; L],
+            });
+            signal = signal.iter().map(|&x| boxcar.filter(x)).collect();
+        }
+        signal
+    }
+
+    fn test_input() -> Vec<i64> {
+        (0..20).map(|n: i64| (n * 37 + 11) % 23 - 11).collect()
+    }
+
+    #[test]
+    fn matches_zero_stuffed_moving_sums() {
+        let input = test_input();
+
+        let mut interpolator = CicInterpolatorArray::<i64, 3, 1>::new(4);
+        let mut output = [0; 80];
+        assert_eq!(interpolator.process(&input, &mut output), (20, 80));
+
+        assert_eq!(output.to_vec(), moving_sum_reference::<4>(3, 4, &input));
+    }
+
+    #[test]
+    fn differential_delay_two_matches_reference() {
+        let input = test_input();
+
+        let mut interpolator = CicInterpolatorArray::<i64, 2, 2>::new(3);
+        let mut output = [0; 60];
+        assert_eq!(interpolator.process(&input, &mut output), (20, 60));
+
+        assert_eq!(output.to_vec(), moving_sum_reference::<6>(2, 3, &input));
+    }
+
+    #[test]
+    fn dc_input_settles_at_gain() {
+        let mut interpolator = CicInterpolatorArray::<i32, 4, 1>::new(8);
+        assert_eq!(interpolator.gain(), Some(512));
+        assert_eq!(interpolator.bit_growth(), Some(9));
+
+        let mut output = [0; 64];
+        assert_eq!(interpolator.process(&[3; 8], &mut output), (8, 64));
+        assert_eq!(&output[32..], &[3 * 512; 32]);
+    }
+
+    #[test]
+    fn integrator_wraparound_is_harmless() {
+        let mut narrow = CicInterpolatorArray::<i8, 2, 1>::new(4);
+        let mut wide = CicInterpolatorArray::<i64, 2, 1>::new(4);
+        assert_eq!(narrow.bit_growth(), Some(2));
+
+        let input: Vec<i8> = (0..64).map(|n| if n % 5 < 3 { 31 } else { -32 }).collect();
+        let wide_input: Vec<i64> = input.iter().map(|&x| i64::from(x)).collect();
+        let mut narrow_output = [0_i8; 256];
+        let mut wide_output = [0_i64; 256];
+
+        assert_eq!(narrow.process(&input, &mut narrow_output), (64, 256));
+        assert_eq!(wide.process(&wide_input, &mut wide_output), (64, 256));
+
+        for (&narrow, &wide) in narrow_output.iter().zip(&wide_output) {
+            assert_eq!(i64::from(narrow), wide);
+        }
+    }
+
+    #[test]
+    fn resumes_pending_block_before_consuming_input() {
+        let mut interpolator = CicInterpolatorArray::<i32, 1, 1>::new(3);
+        let mut first = [0; 2];
+        let mut second = [0; 4];
+
+        assert_eq!(interpolator.process(&[10, 20], &mut first), (1, 2));
+        assert_eq!(first, [10, 10]);
+
+        assert_eq!(interpolator.process(&[20], &mut second), (1, 4));
+        assert_eq!(second, [10, 20, 20, 20]);
+    }
+
+    #[test]
+    fn empty_output_consumes_no_input() {
+        let mut interpolator = CicInterpolatorArray::<i32, 2, 1>::new(3);
+
+        assert_eq!(interpolator.process(&[10], &mut []), (0, 0));
+    }
+
+    #[test]
+    fn reset_clears_stages_and_phase() {
+        let mut interpolator = CicInterpolatorArray::<i32, 1, 1>::new(2);
+        let mut output = [0; 1];
+        assert_eq!(interpolator.process(&[5], &mut output), (1, 1));
+
+        let mut interpolator = interpolator.reset();
+
+        let mut output = [0; 2];
+        assert_eq!(interpolator.process(&[1], &mut output), (1, 2));
+        assert_eq!(output, [1, 1]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let input = test_input();
+
+        let mut array = CicInterpolatorArray::<i64, 3, 2>::new(5);
+        let mut vec = CicInterpolatorVec::<i64>::new(3, 2, 5);
+        let mut array_output = [0; 100];
+        let mut vec_output = [0; 100];
+
+        assert_eq!(
+            array.process(&input, &mut array_output),
+            vec.process(&input, &mut vec_output)
+        );
+        assert_eq!(array_output, vec_output);
+    }
+}
@@ -4,12 +4,18 @@
 
 //! FIR coefficient design helpers.
 //!
-//! This module contains two FIR tap-design families:
+//! This module contains five FIR tap-design families:
 //!
 //! - Pulse-shaping and matched-filter helpers, specified in symbol-time
 //!   parameters such as span and samples per symbol.
 //! - Windowed-sinc helpers, specified by normalized cutoff or band-edge
 //!   frequencies and named window families.
+//! - Windowed Hilbert-transformer helpers, specified by tap count, for
+//!   building analytic (I/Q) signals from real input.
+//! - CIC droop-compensation helpers, specified by the CIC order, differential
+//!   delay and rate, for flattening the passband after a CIC decimator.
+//! - Half-band helpers (windowed and equiripple), specified by tap count and
+//!   passband edge, for efficient decimation and interpolation by two.
 //!
 //! The helpers in this module generate coefficients into caller-provided
 //! storage. With `alloc`, matching helpers can return `Vec`s. Use the FIR
@@ -41,16 +47,24 @@
 //!
 //! # Feature flags
 //!
-//! Raised-cosine, square-root raised-cosine, Kaiser order estimation, and
-//! windowed-sinc design require either `std` or `libm`. GMSK Gaussian pulse
-//! design requires `libm` for error-function support.
+//! Raised-cosine, square-root raised-cosine, Kaiser order estimation,
+//! windowed-sinc, Hilbert-transformer, CIC compensation, and half-band design
+//! require either `std` or `libm`. Equiripple half-band design additionally
+//! requires `alloc`. GMSK Gaussian pulse design requires `libm` for
+//! error-function support.
 
 #[cfg(any(feature = "libm", feature = "std"))]
 use num_traits::{Float, ToPrimitive};
 
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod cic_compensation;
 #[cfg(feature = "libm")]
 pub mod gaussian_pulse;
 #[cfg(any(feature = "libm", feature = "std"))]
+pub mod half_band;
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod hilbert;
+#[cfg(any(feature = "libm", feature = "std"))]
 pub mod raised_cosine;
 #[cfg(any(feature = "libm", feature = "std"))]
 pub mod root_raised_cosine;
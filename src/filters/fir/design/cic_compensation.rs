@@ -0,0 +1,174 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! CIC droop-compensation FIR taps.
+//!
+//! A CIC filter of order `N`, differential delay `M` and rate `R` (see
+//! [`fir::cic`](crate::filters::fir::cic)) has, at the low sample rate, the
+//! normalized magnitude response
+//!
+//! ```text
+//! |H(f)| = |sin(π·M·f) / (R·M·sin(π·f / R))|^N
+//! ```
+//!
+//! where `f` is in cycles per low-rate sample. The helpers in this module
+//! design a linear-phase low-pass FIR running at the low rate whose passband
+//! follows `1 / |H(f)|` up to `cutoff` and which is zero above it. The taps are
+//! computed by numerically integrating the inverse DTFT of that response and
+//! applying a Kaiser window, then normalized to unit DC gain.
+//!
+//! Keep `cutoff` well below the first CIC null at `f = 1 / M`; the inverse
+//! response grows quickly as it approaches the null.
+
+use num_traits::Float;
+
+use crate::filters::util::window as helpers;
+
+use super::{normalize, Normalization};
+
+/// Fill a slice with Kaiser-windowed CIC compensation taps using custom β.
+///
+/// `order`, `differential_delay` and `rate` describe the CIC filter to
+/// compensate. `cutoff` is the compensated passband edge in cycles per
+/// low-rate sample (`0 < cutoff < 0.5`). Taps are normalized to unit DC gain.
+///
+/// # Panics
+///
+/// Panics if `weights.len() < 3`, `weights.len()` is even, `order`,
+/// `differential_delay` or `rate` is zero, `cutoff` is outside `(0, 0.5)` or
+/// not below `1 / differential_delay`, or `beta` is negative.
+pub fn taps_with_beta<T>(
+    weights: &mut [T],
+    order: usize,
+    differential_delay: usize,
+    rate: usize,
+    cutoff: T,
+    beta: T,
+) where
+    T: Float + core::fmt::Debug,
+{
+    let len = weights.len();
+    assert!(len >= 3, "cic_compensation: tap count must be >= 3");
+    assert!(
+        !len.is_multiple_of(2),
+        "cic_compensation: tap count must be odd"
+    );
+    assert!(order > 0, "cic_compensation: order must be > 0");
+    assert!(
+        differential_delay > 0,
+        "cic_compensation: differential delay must be > 0"
+    );
+    assert!(rate > 0, "cic_compensation: rate must be > 0");
+    let half = T::from(0.5).expect("0.5 is representable");
+    let m = T::from(differential_delay).expect("differential delay is representable");
+    assert!(
+        cutoff > T::zero() && cutoff < half && cutoff * m < T::one(),
+        "cic_compensation: cutoff must be in (0, 0.5) and below 1 / differential_delay (got {cutoff:?})"
+    );
+    assert!(beta >= T::zero(), "Kaiser beta must be non-negative");
+
+    let pi = T::from(core::f64::consts::PI).expect("π is representable");
+    let r = T::from(rate).expect("rate is representable");
+    let n = i32::try_from(order).expect("order fits in i32");
+    let droop = |f: T| {
+        if f.is_zero() {
+            T::one()
+        } else {
+            ((pi * m * f).sin() / (r * m * (pi * f / r).sin()))
+                .abs()
+                .powi(n)
+        }
+    };
+
+    // Midpoint-rule inverse DTFT of the even desired response over [0, cutoff].
+    let steps = (len * 32).max(1024);
+    let df = cutoff / T::from(steps).expect("step count is representable");
+    let center = T::from(len / 2).expect("tap count is representable");
+    let two = T::from(2.0).expect("2 is representable");
+    let window = helpers::kaiser(beta);
+
+    for (k, weight) in weights.iter_mut().enumerate() {
+        let offset = T::from(k).expect("tap index is representable") - center;
+        let integral = (0..steps).fold(T::zero(), |sum, step| {
+            let f = (T::from(step).expect("step is representable") + half) * df;
+            sum + (two * pi * f * offset).cos() / droop(f)
+        });
+        *weight = two * integral * df * window(k, len);
+    }
+
+    normalize(weights, Normalization::PassbandGain);
+}
+
+/// Fill a slice with Kaiser-windowed (β = 6) CIC compensation taps.
+///
+/// See [`taps_with_beta`] for the parameters.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`taps_with_beta`].
+pub fn taps<T>(weights: &mut [T], order: usize, differential_delay: usize, rate: usize, cutoff: T)
+where
+    T: Float + core::fmt::Debug,
+{
+    taps_with_beta(
+        weights,
+        order,
+        differential_delay,
+        rate,
+        cutoff,
+        T::from(6.0).expect("6 is representable"),
+    );
+}
+
+/// Create heap-backed Kaiser-windowed CIC compensation taps using custom β.
+///
+/// This is the allocating equivalent of [`taps_with_beta`].
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`taps_with_beta`].
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn taps_with_beta_vec<T>(
+    num_taps: usize,
+    order: usize,
+    differential_delay: usize,
+    rate: usize,
+    cutoff: T,
+    beta: T,
+) -> alloc::vec::Vec<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    let mut weights = alloc::vec![T::zero(); num_taps];
+    taps_with_beta(&mut weights, order, differential_delay, rate, cutoff, beta);
+    weights
+}
+
+/// Create heap-backed Kaiser-windowed (β = 6) CIC compensation taps.
+///
+/// This is the allocating equivalent of [`taps`].
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`taps_with_beta`].
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn taps_vec<T>(
+    num_taps: usize,
+    order: usize,
+    differential_delay: usize,
+    rate: usize,
+    cutoff: T,
+) -> alloc::vec::Vec<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    let mut weights = alloc::vec![T::zero(); num_taps];
+    taps(&mut weights, order, differential_delay, rate, cutoff);
+    weights
+}
+
+#[cfg(test)]
+mod tests;
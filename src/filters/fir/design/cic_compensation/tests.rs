@@ -0,0 +1,81 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use approx::assert_abs_diff_eq;
+
+use super::taps;
+
+fn magnitude(weights: &[f64], frequency: f64) -> f64 {
+    let (re, im) = (0_u32..)
+        .zip(weights)
+        .fold((0.0, 0.0), |(re, im), (k, &h)| {
+            let theta = -core::f64::consts::TAU * frequency * f64::from(k);
+            (re + h * theta.cos(), im + h * theta.sin())
+        });
+    re.hypot(im)
+}
+
+fn cic_droop(order: i32, differential_delay: f64, rate: f64, frequency: f64) -> f64 {
+    let pi = core::f64::consts::PI;
+    if frequency == 0.0 {
+        return 1.0;
+    }
+    ((pi * differential_delay * frequency).sin()
+        / (rate * differential_delay * (pi * frequency / rate).sin()))
+    .abs()
+    .powi(order)
+}
+
+#[test]
+fn taps_are_symmetric_with_unit_dc_gain() {
+    let mut weights = [0.0_f64; 21];
+    taps(&mut weights, 4, 1, 16, 0.2);
+
+    for (&a, &b) in weights.iter().zip(weights.iter().rev()) {
+        assert_abs_diff_eq!(a, b, epsilon = 1e-12);
+    }
+    assert_abs_diff_eq!(weights.iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn flattens_cic_passband() {
+    let (order, rate, cutoff) = (4, 32.0, 0.2);
+    let mut weights = [0.0_f64; 47];
+    taps(&mut weights, 4, 1, 32, cutoff);
+
+    // Uncompensated droop at the band edge is ~2.3 dB.
+    assert!(cic_droop(order, 1.0, rate, cutoff) < 0.77);
+
+    for step in 0..=16 {
+        let f = cutoff * 0.8 * f64::from(step) / 16.0;
+        let combined = cic_droop(order, 1.0, rate, f) * magnitude(&weights, f);
+        assert_abs_diff_eq!(combined, 1.0, epsilon = 0.01);
+    }
+}
+
+#[test]
+fn attenuates_above_cutoff() {
+    let mut weights = [0.0_f64; 41];
+    taps(&mut weights, 3, 1, 16, 0.15);
+
+    for f in [0.3, 0.4, 0.5] {
+        assert!(magnitude(&weights, f) < 0.02, "gain at {f} too high");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_matches_slice() {
+    let mut weights = [0.0_f32; 15];
+    taps(&mut weights, 3, 2, 8, 0.1);
+
+    assert_eq!(super::taps_vec::<f32>(15, 3, 2, 8, 0.1), weights.to_vec());
+}
+
+#[test]
+#[should_panic(expected = "cic_compensation: cutoff must be in (0, 0.5)")]
+fn rejects_cutoff_beyond_first_null() {
+    let mut weights = [0.0_f32; 15];
+    taps(&mut weights, 3, 3, 8, 0.4);
+}
@@ -0,0 +1,334 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Half-band low-pass FIR taps.
+//!
+//! A half-band filter is a linear-phase low-pass filter whose response
+//! satisfies `H(f) + H(0.5 − f) = 1`: it is symmetric about the quarter
+//! sample rate, with equal passband and stopband ripple. In the time domain,
+//! the center tap is `0.5` and every other tap on either side of it is zero,
+//! which the [`fir::half_band`](crate::filters::fir::half_band) filters exploit
+//! to decimate or interpolate by two at about a quarter of the generic cost.
+//!
+//! Tap counts must be of the form `4·K − 1` (3, 7, 11, ...), so the outermost
+//! taps are non-zero. Frequencies are normalized cycles per sample, so Nyquist
+//! is `0.5`.
+//!
+//! - [`windowed_taps`] and [`windowed_taps_with_beta`] apply a Kaiser window
+//!   to the ideal `sinc(n / 2) / 2` response and have unit DC gain.
+//! - [`equiripple_taps`] minimizes the maximum passband (and, by symmetry,
+//!   stopband) error for a given passband edge using the Remez exchange
+//!   algorithm; the DC gain is within the ripple of one. It requires the
+//!   `alloc` feature for scratch storage.
+
+use num_traits::Float;
+
+use crate::filters::util::window as helpers;
+
+fn assert_tap_count(len: usize) {
+    assert!(len >= 3, "half_band: tap count must be >= 3");
+    assert!(len % 4 == 3, "half_band: tap count must be 4·K − 1");
+}
+
+/// Write the half-band taps `h[c ± (2i + 1)] = side[i]`, `h[c] = 0.5`, zeroing
+/// the remaining taps.
+fn assemble<T>(weights: &mut [T], side: impl Iterator<Item = T>)
+where
+    T: Float,
+{
+    let center = weights.len() / 2;
+    weights.fill(T::zero());
+    weights[center] = T::from(0.5).expect("0.5 is representable");
+    for (i, value) in side.enumerate() {
+        weights[center - 2 * i - 1] = value;
+        weights[center + 2 * i + 1] = value;
+    }
+}
+
+/// Fill a slice with Kaiser-windowed half-band taps using custom β.
+///
+/// The non-zero side taps are rescaled so the DC gain is exactly one, which
+/// keeps the half-band symmetry `H(f) + H(0.5 − f) = 1` exact.
+///
+/// # Panics
+///
+/// Panics if `weights.len()` is not `4·K − 1` for some `K >= 1`, or `beta` is
+/// negative.
+pub fn windowed_taps_with_beta<T>(weights: &mut [T], beta: T)
+where
+    T: Float + core::fmt::Debug,
+{
+    let len = weights.len();
+    assert_tap_count(len);
+    assert!(beta >= T::zero(), "Kaiser beta must be non-negative");
+
+    let center = len / 2;
+    let window = helpers::kaiser(beta);
+    let pi = T::from(core::f64::consts::PI).expect("π is representable");
+    let side = |i: usize| {
+        let offset = 2 * i + 1;
+        let sign = if i.is_multiple_of(2) {
+            T::one()
+        } else {
+            -T::one()
+        };
+        let d = T::from(offset).expect("tap offset is representable");
+        sign / (pi * d) * window(center + offset, len)
+    };
+
+    // Each side tap appears twice; their sum must be 0.5 for unit DC gain.
+    let count = (len + 1) / 4;
+    let sum = (0..count).fold(T::zero(), |sum, i| sum + side(i));
+    let two = T::from(2.0).expect("2 is representable");
+    let scale = T::from(0.5).expect("0.5 is representable") / (two * sum);
+    assemble(weights, (0..count).map(|i| side(i) * scale));
+}
+
+/// Fill a slice with Kaiser-windowed (β = 8) half-band taps.
+///
+/// # Panics
+///
+/// Panics if `weights.len()` is not `4·K − 1` for some `K >= 1`.
+pub fn windowed_taps<T>(weights: &mut [T])
+where
+    T: Float + core::fmt::Debug,
+{
+    windowed_taps_with_beta(weights, T::from(8.0).expect("8 is representable"));
+}
+
+/// Create heap-backed Kaiser-windowed half-band taps using custom β.
+///
+/// This is the allocating equivalent of [`windowed_taps_with_beta`].
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`windowed_taps_with_beta`].
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn windowed_taps_with_beta_vec<T>(num_taps: usize, beta: T) -> alloc::vec::Vec<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    let mut weights = alloc::vec![T::zero(); num_taps];
+    windowed_taps_with_beta(&mut weights, beta);
+    weights
+}
+
+/// Create heap-backed Kaiser-windowed (β = 8) half-band taps.
+///
+/// This is the allocating equivalent of [`windowed_taps`].
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`windowed_taps`].
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn windowed_taps_vec<T>(num_taps: usize) -> alloc::vec::Vec<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    let mut weights = alloc::vec![T::zero(); num_taps];
+    windowed_taps(&mut weights);
+    weights
+}
+
+/// Fill a slice with equiripple (minimax) half-band taps.
+///
+/// `passband_edge` is the passband edge in cycles per sample
+/// (`0 < passband_edge < 0.25`); the stopband edge is
+/// `0.5 − passband_edge`. The maximum passband deviation from one equals the
+/// maximum stopband magnitude.
+///
+/// The side taps `c_i` at offsets `±(2i + 1)` from the center give
+/// `H(f) = 0.5 + 2·Σ c_i·cos(2π(2i + 1)f)`, so the design reduces to a
+/// single-band Chebyshev approximation of `0.25` over `[0, passband_edge]`,
+/// solved with the Remez exchange algorithm.
+///
+/// Requires the `alloc` feature.
+///
+/// # Panics
+///
+/// Panics if `weights.len()` is not `4·K − 1` for some `K >= 1`, or
+/// `passband_edge` is outside `(0, 0.25)`.
+#[cfg(feature = "alloc")]
+pub fn equiripple_taps<T>(weights: &mut [T], passband_edge: T)
+where
+    T: Float + core::fmt::Debug,
+{
+    let len = weights.len();
+    assert_tap_count(len);
+    let quarter = T::from(0.25).expect("0.25 is representable");
+    assert!(
+        passband_edge > T::zero() && passband_edge < quarter,
+        "half_band: passband edge must be in (0, 0.25) (got {passband_edge:?})"
+    );
+
+    let edge = passband_edge
+        .to_f64()
+        .expect("passband edge is representable as f64");
+    let side = remez((len + 1) / 4, core::f64::consts::TAU * edge);
+    assemble(
+        weights,
+        side.into_iter()
+            .map(|c| T::from(c).expect("tap is representable")),
+    );
+}
+
+/// Create heap-backed equiripple half-band taps.
+///
+/// This is the allocating equivalent of [`equiripple_taps`].
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`equiripple_taps`].
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn equiripple_taps_vec<T>(num_taps: usize, passband_edge: T) -> alloc::vec::Vec<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    let mut weights = alloc::vec![T::zero(); num_taps];
+    equiripple_taps(&mut weights, passband_edge);
+    weights
+}
+
+/// Remez exchange for `Σ c_i·cos((2i + 1)θ) ≈ 0.25` over `θ ∈ [0, edge]`.
+#[cfg(feature = "alloc")]
+fn remez(count: usize, edge: f64) -> alloc::vec::Vec<f64> {
+    use alloc::vec::Vec;
+
+    const MAX_ITERATIONS: usize = 100;
+
+    let grid_len = 32 * count + 1;
+    let grid: Vec<f64> = (0..grid_len)
+        .map(|g| edge * to_f64(g) / to_f64(grid_len - 1))
+        .collect();
+    let basis = |i: usize, theta: f64| (to_f64(2 * i + 1) * theta).cos();
+    let response = |c: &[f64], theta: f64| {
+        c.iter()
+            .enumerate()
+            .map(|(i, &c)| c * basis(i, theta))
+            .sum::<f64>()
+    };
+
+    // Start from evenly spaced extremal frequencies on the grid.
+    let mut extremals: Vec<usize> = (0..=count).map(|j| j * (grid_len - 1) / count).collect();
+    let mut coefficients = alloc::vec![0.0; count];
+
+    for _ in 0..MAX_ITERATIONS {
+        // Solve Σ c_i·φ_i(θ_j) + (−1)^j·δ = 0.25 for the current extremals.
+        let n = count + 1;
+        let mut system: Vec<f64> = Vec::with_capacity(n * (n + 1));
+        for (j, &g) in extremals.iter().enumerate() {
+            system.extend((0..count).map(|i| basis(i, grid[g])));
+            system.push(if j % 2 == 0 { 1.0 } else { -1.0 });
+            system.push(0.25);
+        }
+        let solution = solve(&mut system, n);
+        coefficients.copy_from_slice(&solution[..count]);
+        let delta = solution[count].abs();
+
+        let error: Vec<f64> = grid
+            .iter()
+            .map(|&theta| response(&coefficients, theta) - 0.25)
+            .collect();
+        let max_error = error.iter().fold(0.0_f64, |max, e| max.max(e.abs()));
+        if max_error - delta <= 1e-12 * max_error.max(f64::MIN_POSITIVE) {
+            break;
+        }
+
+        match exchange(&error, n) {
+            Some(next) if next != extremals => extremals = next,
+            _ => break,
+        }
+    }
+
+    coefficients
+}
+
+/// Select `n` alternating local extrema of `error`.
+#[cfg(feature = "alloc")]
+fn exchange(error: &[f64], n: usize) -> Option<alloc::vec::Vec<usize>> {
+    let last = error.len() - 1;
+    let mut candidates: alloc::vec::Vec<usize> = alloc::vec::Vec::new();
+    for (g, &e) in error.iter().enumerate() {
+        let left = if g == 0 {
+            f64::NEG_INFINITY
+        } else {
+            error[g - 1].abs()
+        };
+        let right = if g == last {
+            f64::NEG_INFINITY
+        } else {
+            error[g + 1].abs()
+        };
+        if e.abs() < left || e.abs() < right {
+            continue;
+        }
+        // Of consecutive same-signed extrema keep the larger one.
+        match candidates.last_mut() {
+            Some(previous) if (error[*previous] > 0.0) == (e > 0.0) => {
+                if e.abs() > error[*previous].abs() {
+                    *previous = g;
+                }
+            }
+            _ => candidates.push(g),
+        }
+    }
+
+    // Drop the smaller end extremum until exactly `n` remain.
+    while candidates.len() > n {
+        let first = error[candidates[0]].abs();
+        let end = error[candidates[candidates.len() - 1]].abs();
+        if first < end {
+            candidates.remove(0);
+        } else {
+            candidates.pop();
+        }
+    }
+
+    (candidates.len() == n).then_some(candidates)
+}
+
+/// Solve the row-major augmented `n × (n + 1)` system in place.
+#[cfg(feature = "alloc")]
+fn solve(system: &mut [f64], n: usize) -> alloc::vec::Vec<f64> {
+    let width = n + 1;
+    for column in 0..n {
+        let pivot = (column..n)
+            .max_by(|&a, &b| {
+                system[a * width + column]
+                    .abs()
+                    .total_cmp(&system[b * width + column].abs())
+            })
+            .expect("pivot range is non-empty");
+        for k in 0..width {
+            system.swap(column * width + k, pivot * width + k);
+        }
+        let diagonal = system[column * width + column];
+        for row in (column + 1)..n {
+            let factor = system[row * width + column] / diagonal;
+            for k in column..width {
+                system[row * width + k] -= factor * system[column * width + k];
+            }
+        }
+    }
+
+    let mut solution = alloc::vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = ((row + 1)..n)
+            .map(|k| system[row * width + k] * solution[k])
+            .sum();
+        solution[row] = (system[row * width + n] - tail) / system[row * width + row];
+    }
+    solution
+}
+
+#[cfg(feature = "alloc")]
+fn to_f64(value: usize) -> f64 {
+    num_traits::cast(value).expect("usize is representable as f64")
+}
+
+#[cfg(test)]
+mod tests;
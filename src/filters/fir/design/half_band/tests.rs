@@ -0,0 +1,102 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use approx::assert_abs_diff_eq;
+
+use super::windowed_taps;
+
+fn response(weights: &[f64], frequency: f64) -> f64 {
+    // Zero-phase response of a symmetric odd-length FIR.
+    let center = weights.len() / 2;
+    (0_u32..)
+        .zip(&weights[center..])
+        .map(|(d, &h)| {
+            let scale = if d == 0 { 1.0 } else { 2.0 };
+            scale * h * (core::f64::consts::TAU * frequency * f64::from(d)).cos()
+        })
+        .sum()
+}
+
+fn assert_half_band(weights: &[f64], dc_tolerance: f64) {
+    let center = weights.len() / 2;
+    assert_abs_diff_eq!(weights[center], 0.5, epsilon = 1e-15);
+    for (offset, (&a, &b)) in weights[center..]
+        .iter()
+        .zip(weights[..=center].iter().rev())
+        .enumerate()
+    {
+        assert_abs_diff_eq!(a, b, epsilon = 1e-15);
+        if offset > 0 && offset % 2 == 0 {
+            assert_eq!(a, 0.0);
+        }
+    }
+    assert_abs_diff_eq!(weights.iter().sum::<f64>(), 1.0, epsilon = dc_tolerance);
+    for step in 0_u32..=50 {
+        let f = 0.5 * f64::from(step) / 50.0;
+        assert_abs_diff_eq!(
+            response(weights, f) + response(weights, 0.5 - f),
+            1.0,
+            epsilon = 1e-12
+        );
+    }
+}
+
+#[test]
+fn windowed_taps_are_half_band() {
+    let mut weights = [0.0_f64; 23];
+    windowed_taps(&mut weights);
+
+    assert_half_band(&weights, 1e-12);
+    assert_abs_diff_eq!(response(&weights, 0.5), 0.0, epsilon = 1e-3);
+}
+
+#[test]
+fn shortest_windowed_taps() {
+    let mut weights = [0.0_f64; 3];
+    windowed_taps(&mut weights);
+
+    assert_eq!(weights, [0.25, 0.5, 0.25]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn equiripple_taps_have_equal_ripple() {
+    let (passband_edge, num_taps) = (0.2, 31);
+    let weights = super::equiripple_taps_vec::<f64>(num_taps, passband_edge);
+    assert_half_band(&weights, 5e-3);
+
+    let ripple = (0_u32..=400)
+        .map(|step| passband_edge * f64::from(step) / 400.0)
+        .map(|f| (response(&weights, f) - 1.0).abs())
+        .fold(0.0_f64, f64::max);
+    let stopband = (0_u32..=400)
+        .map(|step| 0.5 - passband_edge * f64::from(step) / 400.0)
+        .map(|f| response(&weights, f).abs())
+        .fold(0.0_f64, f64::max);
+    assert_abs_diff_eq!(ripple, stopband, epsilon = 1e-9);
+    // About -46 dB for 31 taps with a 0.1 transition band.
+    assert!(stopband < 5e-3, "stopband ripple {stopband}");
+
+    // Equiripple beats the windowed design at the band edges.
+    let windowed = super::windowed_taps_vec::<f64>(num_taps);
+    assert!(response(&windowed, 0.5 - passband_edge).abs() > stopband);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn equiripple_matches_f32() {
+    let wide = super::equiripple_taps_vec::<f64>(11, 0.15);
+    let narrow = super::equiripple_taps_vec::<f32>(11, 0.15);
+
+    for (&a, &b) in wide.iter().zip(&narrow) {
+        assert_abs_diff_eq!(a, f64::from(b), epsilon = 1e-6);
+    }
+}
+
+#[test]
+#[should_panic(expected = "half_band: tap count must be 4·K − 1")]
+fn rejects_tap_count_with_zero_outer_taps() {
+    let mut weights = [0.0_f32; 9];
+    windowed_taps(&mut weights);
+}
@@ -0,0 +1,126 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Windowed Hilbert-transformer taps.
+//!
+//! The ideal discrete Hilbert transformer has frequency response
+//! `H(e^{jω}) = −j·sgn(ω)` and impulse response
+//!
+//! ```text
+//! h[n] = 2 / (π·n)   for odd n,
+//! h[n] = 0           for even n (including n = 0).
+//! ```
+//!
+//! The helpers in this module truncate that response to an odd number of taps
+//! centered on `(len − 1) / 2` and apply a window. The result is an
+//! antisymmetric (type III) FIR with an integer group delay of `(len − 1) / 2`
+//! samples. Every other tap is exactly zero, so half of the multiplications
+//! can be skipped.
+//!
+//! Type III filters have zeros at DC and Nyquist, so the passband is
+//! necessarily narrower than `(0, 0.5)`; longer filters and larger Kaiser `β`
+//! trade transition width against passband ripple.
+
+use num_traits::Float;
+
+use crate::filters::util::window as helpers;
+
+/// Fill a slice with Kaiser-windowed Hilbert-transformer taps using custom β.
+///
+/// Taps are ordered for [`Convolve`](crate::filters::fir::convolve::Convolve):
+/// `weights[k]` multiplies `x[n − k]`. The filter delays its input by
+/// `(weights.len() − 1) / 2` samples, so pair it with an equal delay on the
+/// in-phase path.
+///
+/// # Panics
+///
+/// Panics if `weights.len() < 3`, `weights.len()` is even, or `beta` is
+/// negative.
+pub fn taps_with_beta<T>(weights: &mut [T], beta: T)
+where
+    T: Float + core::fmt::Debug,
+{
+    assert!(beta >= T::zero(), "Kaiser beta must be non-negative");
+    taps_with_window(weights, helpers::kaiser(beta));
+}
+
+/// Fill a slice with Kaiser-windowed (β = 8) Hilbert-transformer taps.
+///
+/// See [`taps_with_beta`] for the tap layout.
+///
+/// # Panics
+///
+/// Panics if `weights.len() < 3` or `weights.len()` is even.
+pub fn taps<T>(weights: &mut [T])
+where
+    T: Float + core::fmt::Debug,
+{
+    taps_with_beta(weights, default_beta());
+}
+
+/// Create heap-backed Kaiser-windowed Hilbert-transformer taps using custom β.
+///
+/// This is the allocating equivalent of [`taps_with_beta`].
+///
+/// # Panics
+///
+/// Panics if `num_taps < 3`, `num_taps` is even, or `beta` is negative.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn taps_with_beta_vec<T>(num_taps: usize, beta: T) -> alloc::vec::Vec<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    let mut weights = alloc::vec![T::zero(); num_taps];
+    taps_with_beta(&mut weights, beta);
+    weights
+}
+
+/// Create heap-backed Kaiser-windowed (β = 8) Hilbert-transformer taps.
+///
+/// This is the allocating equivalent of [`taps`].
+///
+/// # Panics
+///
+/// Panics if `num_taps < 3` or `num_taps` is even.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn taps_vec<T>(num_taps: usize) -> alloc::vec::Vec<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    taps_with_beta_vec(num_taps, default_beta())
+}
+
+fn default_beta<T: Float>() -> T {
+    T::from(8.0).expect("8 is representable")
+}
+
+fn taps_with_window<T, W>(weights: &mut [T], window: W)
+where
+    T: Float + core::fmt::Debug,
+    W: Fn(usize, usize) -> T,
+{
+    let len = weights.len();
+    assert!(len >= 3, "hilbert: tap count must be >= 3");
+    assert!(!len.is_multiple_of(2), "hilbert: tap count must be odd");
+
+    let center = len / 2;
+    let two_over_pi = T::from(core::f64::consts::FRAC_2_PI).expect("2/π is representable");
+
+    for (k, weight) in weights.iter_mut().enumerate() {
+        let offset = k.abs_diff(center);
+        *weight = if offset.is_multiple_of(2) {
+            T::zero()
+        } else {
+            let n = T::from(offset).expect("tap offset is representable");
+            let ideal = two_over_pi / n;
+            let signed = if k > center { ideal } else { -ideal };
+            signed * window(k, len)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests;
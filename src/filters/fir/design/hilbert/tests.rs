@@ -0,0 +1,86 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use approx::assert_abs_diff_eq;
+
+use super::{taps, taps_with_beta};
+
+/// Frequency response `Σ h[k]·e^{−jωk}` with the group delay removed.
+fn response(weights: &[f64], frequency: f64) -> (f64, f64) {
+    let center = i32::try_from(weights.len() / 2).expect("tap count fits in i32");
+    (-center..)
+        .zip(weights)
+        .fold((0.0, 0.0), |(re, im), (offset, &h)| {
+            let theta = -core::f64::consts::TAU * frequency * f64::from(offset);
+            (re + h * theta.cos(), im + h * theta.sin())
+        })
+}
+
+#[test]
+fn taps_are_antisymmetric_with_zero_even_offsets() {
+    let mut weights = [0.0_f64; 15];
+    taps(&mut weights);
+
+    let center = weights.len() / 2;
+    for (k, (&weight, &mirrored)) in weights.iter().zip(weights.iter().rev()).enumerate() {
+        assert_abs_diff_eq!(weight, -mirrored, epsilon = 1e-15);
+        if k.abs_diff(center) % 2 == 0 {
+            assert_abs_diff_eq!(weight, 0.0);
+        }
+    }
+}
+
+#[test]
+fn rectangular_taps_match_ideal_response() {
+    // β = 0 is a rectangular window: taps are the truncated ideal response.
+    let mut weights = [0.0_f64; 7];
+    taps_with_beta(&mut weights, 0.0);
+
+    let pi = core::f64::consts::PI;
+    let expected = [
+        -2.0 / (3.0 * pi),
+        0.0,
+        -2.0 / pi,
+        0.0,
+        2.0 / pi,
+        0.0,
+        2.0 / (3.0 * pi),
+    ];
+    assert_abs_diff_eq!(weights.as_slice(), expected.as_slice(), epsilon = 1e-15);
+}
+
+#[test]
+fn passband_is_minus_j() {
+    let mut weights = [0.0_f64; 63];
+    taps(&mut weights);
+
+    for frequency in [0.05, 0.1, 0.25, 0.4, 0.45] {
+        let (re, im) = response(&weights, frequency);
+        assert_abs_diff_eq!(re, 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(im, -1.0, epsilon = 1e-2);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_matches_slice() {
+    let mut weights = [0.0_f32; 31];
+    taps(&mut weights);
+
+    assert_eq!(super::taps_vec::<f32>(31), weights.to_vec());
+}
+
+#[test]
+#[should_panic(expected = "hilbert: tap count must be odd")]
+fn rejects_even_length() {
+    let mut weights = [0.0_f32; 8];
+    taps(&mut weights);
+}
+
+#[test]
+#[should_panic(expected = "hilbert: tap count must be >= 3")]
+fn rejects_short_length() {
+    let mut weights = [0.0_f32; 1];
+    taps(&mut weights);
+}
@@ -0,0 +1,452 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Farrow-structure arbitrary-ratio resampling.
+//!
+//! A Farrow filter evaluates a piecewise polynomial through the most recent
+//! `L` input samples at a fractional position `μ ∈ [0, 1)`. It is realized as
+//! `D` fixed FIR branches whose outputs are the polynomial's coefficients:
+//!
+//! ```text
+//! y(μ) = Σ_m μ^m · Σ_k c[m][k]·x[k]
+//! ```
+//!
+//! evaluated with Horner's scheme. Since `μ` enters only at the end, the
+//! resampling ratio can change continuously without redesigning any filter,
+//! which suits asynchronous sample-rate conversion (e.g. 44.1 kHz ↔ 48 kHz with
+//! clock drift) and timing recovery.
+//!
+//! For sharper anti-aliasing at a fixed tap budget, see
+//! [`ArbitraryResampler`](super::polyphase::arbitrary_resampler::ArbitraryResampler),
+//! which interpolates between the phases of a polyphase filter bank.
+
+use core::ops::{Add, Mul};
+
+use circular_buffer::FixedCircularBuffer;
+use num_traits::{Num, Zero};
+
+use crate::storage::{zero_filled_fixed_ring, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, MultirateFilter, Reset, WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The Farrow resampler's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, const L: usize, const D: usize> {
+    /// Branch coefficients: `coefficients[m][k]` weighs the `k`-th oldest
+    /// sample in the coefficient of `μ^m`.
+    pub coefficients: [[T; L]; D],
+    /// Output samples per input sample (`output_rate / input_rate`).
+    pub ratio: T,
+}
+
+/// The Farrow resampler's state.
+#[derive(Clone, Debug)]
+pub struct State<T, const L: usize> {
+    /// The `L` most recent input samples.
+    pub taps: FixedCircularBuffer<T, L>,
+    /// Position of the next output, in input sample periods, after the start
+    /// of the current interpolation interval.
+    ///
+    /// `phase >= 1` means input must be consumed before the next output can be
+    /// produced.
+    pub phase: T,
+}
+
+/// A stateful arbitrary-ratio resampler using the Farrow structure.
+///
+/// The interpolation interval lies between the samples at window positions
+/// `L / 2 − 1` and `L / 2` (oldest first), so outputs lag the input by
+/// `L / 2` samples. The first input sample is consumed before the first
+/// output is produced, at `μ = 0`.
+///
+/// Use [`cubic`](FarrowResampler::cubic) or
+/// [`linear`](FarrowResampler::linear) for Lagrange interpolation, or
+/// supply custom branch coefficients through [`Config`].
+///
+/// # Ratio changes
+///
+/// [`set_ratio`](Self::set_ratio) may be called between (or during) blocks.
+/// The new ratio applies from the next phase increment; the position of an
+/// already pending output is unchanged.
+///
+/// # Streaming
+///
+/// The [`MultirateFilter::process`] implementation supports streaming operation
+/// with arbitrary input and output slice sizes. When output capacity is full,
+/// the resampler can still consume input while advancing its phase until the
+/// next output is pending. Pass the unconsumed input suffix to the next call.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(L·D) per output sample.
+/// - **Space:** O(L·D) for coefficients plus O(L) for the delay line.
+#[derive(Clone, Debug)]
+pub struct FarrowResampler<T, const L: usize, const D: usize> {
+    config: Config<T, L, D>,
+    state: State<T, L>,
+    step: T,
+}
+
+fn assert_ratio<T>(ratio: T)
+where
+    T: Copy + Num + PartialOrd,
+{
+    // `ratio == ratio` rejects NaN.
+    #[allow(clippy::eq_op)]
+    let is_number = ratio == ratio;
+    assert!(
+        is_number && ratio > T::zero(),
+        "FarrowResampler: ratio must be > 0"
+    );
+}
+
+impl<T> FarrowResampler<T, 2, 2>
+where
+    T: Copy + Num + PartialOrd,
+{
+    /// Creates a linear-interpolation resampler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not positive.
+    #[must_use]
+    pub fn linear(ratio: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        Self::with_config(Config {
+            coefficients: [[one, zero], [zero - one, one]],
+            ratio,
+        })
+    }
+}
+
+impl<T> FarrowResampler<T, 4, 4>
+where
+    T: Copy + Num + PartialOrd,
+{
+    /// Creates a cubic Lagrange-interpolation resampler.
+    ///
+    /// The output is exact for polynomial inputs up to degree three.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not positive.
+    #[must_use]
+    pub fn cubic(ratio: T) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let two = one + one;
+        let half = one / two;
+        let third = one / (two + one);
+        let sixth = half * third;
+        Self::with_config(Config {
+            coefficients: [
+                [zero, one, zero, zero],
+                [zero - third, zero - half, one, zero - sixth],
+                [half, zero - one, half, zero],
+                [zero - sixth, half, zero - half, sixth],
+            ],
+            ratio,
+        })
+    }
+}
+
+impl<T, const L: usize, const D: usize> FarrowResampler<T, L, D>
+where
+    T: Copy + Num + PartialOrd,
+{
+    /// Returns the current ratio of output samples per input sample.
+    #[must_use]
+    pub fn ratio(&self) -> T {
+        self.config.ratio
+    }
+
+    /// Sets the ratio of output samples per input sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not positive.
+    pub fn set_ratio(&mut self, ratio: T) {
+        assert_ratio(ratio);
+        self.config.ratio = ratio;
+        self.step = T::one() / ratio;
+    }
+
+    /// Returns the position of the next output, in input sample periods, after
+    /// the start of the current interpolation interval.
+    ///
+    /// Values in `[0, 1)` are the fractional interpolation phase `μ` of the
+    /// pending output; values `>= 1` mean input must be consumed first.
+    #[must_use]
+    pub fn fractional_phase(&self) -> T {
+        self.state.phase
+    }
+}
+
+impl<T, const L: usize, const D: usize> FarrowResampler<T, L, D>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    fn evaluate(&self, mu: T) -> T {
+        self.config
+            .coefficients
+            .iter()
+            .rev()
+            .fold(T::zero(), |acc, branch| {
+                let value = branch
+                    .iter()
+                    .zip(self.state.taps.iter())
+                    .fold(T::zero(), |sum, (&c, &x)| sum + c * x);
+                acc * mu + value
+            })
+    }
+}
+
+impl<T, const L: usize, const D: usize> ConfigTrait for FarrowResampler<T, L, D> {
+    type Config = Config<T, L, D>;
+}
+
+impl<T, const L: usize, const D: usize> WithConfig for FarrowResampler<T, L, D>
+where
+    T: Copy + Num + PartialOrd,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(L >= 2, "FarrowResampler: tap count L must be >= 2");
+        assert!(D >= 1, "FarrowResampler: branch count D must be >= 1");
+        assert_ratio(config.ratio);
+        let step = T::one() / config.ratio;
+        Self {
+            config,
+            state: State {
+                taps: zero_filled_fixed_ring(),
+                phase: T::one(),
+            },
+            step,
+        }
+    }
+}
+
+impl<T, const L: usize, const D: usize> ConfigRef for FarrowResampler<T, L, D> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, const L: usize, const D: usize> ConfigClone for FarrowResampler<T, L, D>
+where
+    T: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, const L: usize, const D: usize> HasGuts for FarrowResampler<T, L, D> {
+    type Guts = (Config<T, L, D>, State<T, L>);
+}
+
+impl<T, const L: usize, const D: usize> FromGuts for FarrowResampler<T, L, D>
+where
+    T: Copy + Num + PartialOrd,
+{
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        assert_ratio(config.ratio);
+        let step = T::one() / config.ratio;
+        Self {
+            config,
+            state,
+            step,
+        }
+    }
+}
+
+impl<T, const L: usize, const D: usize> IntoGuts for FarrowResampler<T, L, D> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const L: usize, const D: usize> Reset for FarrowResampler<T, L, D>
+where
+    T: Copy + Num + PartialOrd,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const L: usize, const D: usize> ResetMut for FarrowResampler<T, L, D> where Self: Reset {}
+
+impl<T, const L: usize, const D: usize> MultirateFilter<T> for FarrowResampler<T, L, D>
+where
+    T: Copy + Num + PartialOrd,
+{
+    type Output = T;
+
+    fn process(&mut self, input: &[T], output: &mut [Self::Output]) -> (usize, usize) {
+        let mut input_consumed = 0;
+        let mut output_produced = 0;
+
+        loop {
+            if self.state.phase >= T::one() {
+                if input_consumed == input.len() {
+                    break;
+                }
+
+                let _ = self.state.taps.push_back(input[input_consumed]);
+                input_consumed += 1;
+                self.state.phase = self.state.phase - T::one();
+                continue;
+            }
+
+            if output_produced == output.len() {
+                break;
+            }
+
+            output[output_produced] = self.evaluate(self.state.phase);
+            output_produced += 1;
+            self.state.phase = self.state.phase + self.step;
+        }
+
+        (input_consumed, output_produced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    fn cubic_polynomial(t: f64) -> f64 {
+        0.02 * t * t * t - 0.5 * t * t + 3.0 * t - 7.0
+    }
+
+    #[test]
+    fn unit_ratio_delays_by_half_window() {
+        let input = [1.0_f64, 2.0, 3.0, 5.0, 8.0, 13.0];
+        let mut resampler = FarrowResampler::cubic(1.0);
+        let mut output = [0.0; 6];
+
+        assert_eq!(resampler.process(&input, &mut output), (6, 6));
+        assert_eq!(output, [0.0, 0.0, 1.0, 2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn cubic_is_exact_for_cubic_polynomials() {
+        let input: Vec<f64> = (0_u32..64)
+            .map(|n| cubic_polynomial(f64::from(n)))
+            .collect();
+        let ratio = 1.37;
+        let mut resampler = FarrowResampler::cubic(ratio);
+        let mut output = [0.0; 96];
+
+        let (consumed, produced) = resampler.process(&input, &mut output);
+        assert_eq!(consumed, input.len());
+
+        // Output k lies at input time k / ratio − 2; skip the zero-padded warm-up.
+        for (k, &y) in (0_u32..).zip(&output[..produced]).skip(8) {
+            let t = f64::from(k) / ratio - 2.0;
+            assert_abs_diff_eq!(y, cubic_polynomial(t), epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn linear_interpolates_between_samples() {
+        let mut resampler = FarrowResampler::linear(4.0);
+        let mut output = [0.0_f64; 8];
+
+        assert_eq!(resampler.process(&[4.0, 8.0], &mut output), (2, 8));
+        assert_eq!(output, [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+    }
+
+    #[test]
+    fn reports_fractional_phase() {
+        let mut resampler = FarrowResampler::linear(4.0 / 3.0);
+        let mut output = [0.0_f64; 3];
+
+        assert_abs_diff_eq!(resampler.fractional_phase(), 1.0);
+        let _ = resampler.process(&[1.0], &mut output[..1]);
+        assert_abs_diff_eq!(resampler.fractional_phase(), 0.75);
+        let _ = resampler.process(&[2.0], &mut output[1..2]);
+        assert_abs_diff_eq!(resampler.fractional_phase(), 0.5);
+        let _ = resampler.process(&[3.0], &mut output[2..]);
+        assert_abs_diff_eq!(resampler.fractional_phase(), 0.25);
+    }
+
+    #[test]
+    fn ratio_can_change_per_block() {
+        let input: Vec<f64> = (0_u32..4000).map(|n| (f64::from(n) * 0.01).sin()).collect();
+        let mut resampler = FarrowResampler::cubic(1.0);
+        let mut output = alloc::vec![0.0; 8000];
+        let mut produced = 0;
+
+        for (block, chunk) in input.chunks(100).enumerate() {
+            // Drift slowly between 0.9 and 1.1.
+            let drift = f64::from(u32::try_from(block).expect("block fits in u32"));
+            resampler.set_ratio(1.0 + 0.1 * (drift * 0.3).sin());
+            let (consumed, p) = resampler.process(chunk, &mut output[produced..]);
+            assert_eq!(consumed, chunk.len());
+            produced += p;
+        }
+
+        assert!((3800..4200).contains(&produced), "produced {produced}");
+        // A slowly varying ratio keeps the output smooth.
+        for pair in output[..produced].windows(2).skip(4) {
+            assert!((pair[1] - pair[0]).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    fn streaming_matches_single_call() {
+        let input: Vec<f64> = (0_u32..50).map(|n| f64::from(n % 7)).collect();
+        let mut whole = FarrowResampler::cubic(0.77);
+        let mut expected = [0.0; 64];
+        let (_, expected_len) = whole.process(&input, &mut expected);
+
+        let mut streamed = FarrowResampler::cubic(0.77);
+        let mut output = [0.0; 64];
+        let (mut consumed, mut produced) = (0, 0);
+        while consumed < input.len() {
+            let end = (consumed + 3).min(input.len());
+            let out_end = (produced + 2).min(output.len());
+            let (c, p) = streamed.process(&input[consumed..end], &mut output[produced..out_end]);
+            consumed += c;
+            produced += p;
+        }
+        let (_, p) = streamed.process(&[], &mut output[produced..]);
+        produced += p;
+
+        assert_eq!(produced, expected_len);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn reset_restores_initial_state() {
+        let mut resampler = FarrowResampler::cubic(1.5_f64);
+        let mut output = [0.0; 8];
+        let _ = resampler.process(&[1.0, 2.0, 3.0], &mut output);
+
+        let resampler = resampler.reset();
+        assert_abs_diff_eq!(resampler.fractional_phase(), 1.0);
+        assert_abs_diff_eq!(resampler.ratio(), 1.5);
+        assert!(resampler.state.taps.iter().all(|&x| x == 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "FarrowResampler: ratio must be > 0")]
+    fn rejects_non_positive_ratio() {
+        let mut resampler = FarrowResampler::<f32, 2, 2>::linear(1.0);
+        resampler.set_ratio(0.0);
+    }
+}
@@ -0,0 +1,106 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Half-band decimate-by-2 and interpolate-by-2 filters.
+//!
+//! A half-band FIR of length `4·K − 1` has a center tap and `2·K` non-zero
+//! side taps at odd offsets from the center; every tap at an even, non-zero
+//! offset is zero. With linear-phase symmetry, only `K` distinct side
+//! coefficients remain. Design such taps with
+//! [`design::half_band`](super::design::half_band).
+//!
+//! The [`decimator`] and [`interpolator`] filters exploit that structure:
+//!
+//! - Zero taps are never multiplied. One of the two polyphase branches
+//!   reduces to the scaled, delayed center tap.
+//! - The other branch is symmetric, so each pair of samples sharing a
+//!   coefficient is added before multiplying ("folding").
+//!
+//! Each output therefore costs `K + 1` multiplications, compared with
+//! `2·K` for the non-zero branch of a generic
+//! [`polyphase`](super::polyphase) filter, or `4·K − 1` for a dense FIR.
+//!
+//! For power-of-two rate changes, chain stages with
+//! [`HalfBandDecimatorCascade`](decimator::HalfBandDecimatorCascade) and
+//! [`HalfBandInterpolatorCascade`](interpolator::HalfBandInterpolatorCascade).
+//!
+//! The output samples equal those of
+//! [`PolyphaseDecimator`](super::polyphase::decimator::PolyphaseDecimator) and
+//! [`PolyphaseInterpolator`](super::polyphase::interpolator::PolyphaseInterpolator)
+//! with two phases and the same dense taps.
+
+use core::ops::{Add, Mul};
+
+use num_traits::Zero;
+
+use crate::storage::RingBuffer;
+
+pub mod decimator;
+pub mod interpolator;
+
+/// The half-band filter's configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config<T, C> {
+    /// The center tap, `0.5` for a unit-gain decimator.
+    pub center: T,
+    /// The `K` distinct side taps, from the center outward: `coefficients[i]`
+    /// is the tap at offsets `±(2·i + 1)` from the center.
+    pub coefficients: C,
+}
+
+/// Splits dense half-band taps into the center tap and the `K` distinct side
+/// taps, from the center outward.
+fn split_taps<'a, T>(taps: &'a [T], name: &str) -> (T, impl Iterator<Item = T> + 'a)
+where
+    T: Clone + PartialEq + Zero,
+{
+    assert!(taps.len() % 4 == 3, "{name}: tap count must be 4·K − 1");
+    let center = taps.len() / 2;
+    for offset in 1..=center {
+        assert!(
+            taps[center - offset] == taps[center + offset],
+            "{name}: taps must be symmetric"
+        );
+        assert!(
+            offset % 2 == 1 || taps[center + offset].is_zero(),
+            "{name}: taps at even offsets from the center must be zero"
+        );
+    }
+    (
+        taps[center].clone(),
+        taps[center + 1..].iter().step_by(2).cloned(),
+    )
+}
+
+/// Evaluates the symmetric branch over `2·K` samples held in two `K`-sample
+/// halves, each ordered oldest first.
+fn folded_sum<T, B>(coefficients: &[T], newer: &B, older: &B) -> T
+where
+    T: Clone + Zero + Add<Output = T> + Mul<Output = T>,
+    B: RingBuffer<T>,
+{
+    let half = coefficients.len();
+    coefficients.iter().zip(newer.iter()).enumerate().fold(
+        T::zero(),
+        |sum, (i, (coefficient, newer))| {
+            let older = older.get(half - 1 - i).expect("tap is in range").clone();
+            sum + coefficient.clone() * (newer.clone() + older)
+        },
+    )
+}
+
+/// Checks that every buffer has capacity `K`, the side coefficient count.
+fn assert_buffers<T, B>(half: usize, buffers: &[&B], name: &str)
+where
+    B: RingBuffer<T>,
+{
+    assert!(half > 0, "{name}: coefficient count K must be > 0");
+    for buffer in buffers {
+        assert_eq!(
+            buffer.capacity(),
+            half,
+            "{name}: buffer capacity must equal coefficient count K"
+        );
+    }
+}
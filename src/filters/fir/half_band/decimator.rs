@@ -0,0 +1,553 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Half-band decimation by two.
+
+use core::marker::PhantomData;
+use core::ops::{Add, Mul};
+
+use circular_buffer::FixedCircularBuffer;
+use num_traits::{Num, Zero};
+
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, MultirateFilter, Reset, WithConfig,
+};
+
+use super::{assert_buffers, folded_sum, split_taps, Config};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The half-band decimator's state.
+#[derive(Clone, Debug)]
+pub struct State<B> {
+    /// The newer half of the symmetric branch's `2·K` input samples.
+    pub newer: B,
+    /// The older half of the symmetric branch's `2·K` input samples.
+    pub older: B,
+    /// The `K` most recent input samples of the center-tap branch.
+    pub delayed: B,
+    /// The branch that will receive the next input sample; an output is
+    /// produced when branch zero is updated.
+    pub phase: usize,
+}
+
+/// A stateful 2-to-1 half-band decimator.
+///
+/// Input samples alternate between the center-tap branch, a plain `K`-sample
+/// delay, and the symmetric branch of `2·K` samples. Whenever the symmetric
+/// branch is updated, both branches are evaluated and one output sample is
+/// produced. Like
+/// [`PolyphaseDecimator`](crate::filters::fir::polyphase::decimator::PolyphaseDecimator),
+/// the first output is produced after two input samples have been consumed.
+///
+/// # Gain
+///
+/// This type does not apply gain correction. Construct it from unit-DC-gain
+/// taps, such as those from [`design::half_band`](crate::filters::fir::design::half_band).
+///
+/// # Streaming
+///
+/// The [`MultirateFilter::process`] implementation supports streaming operation
+/// with arbitrary input and output slice sizes. If the output slice is full
+/// before the input that would produce an output can be consumed, that input
+/// remains unconsumed and should be passed again on a later call.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`HalfBandDecimatorArray<T, K>`] for stack-allocated storage.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`HalfBandDecimatorVec<T>`] for heap-allocated storage."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `HalfBandDecimatorVec<T>` for heap-allocated storage."
+)]
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(K) amortized; every second input sample triggers
+///   `K + 1` multiplications and `2·K` additions.
+/// - **Space:** O(K); three delay lines of `K` samples.
+#[derive(Clone, Debug)]
+pub struct HalfBandDecimator<T, C, B> {
+    config: Config<T, C>,
+    state: State<B>,
+}
+
+/// A half-band decimator with `K` distinct side taps (`4·K − 1` dense taps),
+/// backed by fixed-size storage.
+pub type HalfBandDecimatorArray<T, const K: usize> =
+    HalfBandDecimator<T, [T; K], FixedCircularBuffer<T, K>>;
+
+/// A half-band decimator backed by heap-allocated storage.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type HalfBandDecimatorVec<T> = HalfBandDecimator<T, alloc::vec::Vec<T>, HeapCircularBuffer<T>>;
+
+impl<T, C, B> HalfBandDecimator<T, C, B>
+where
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    /// Creates a [`HalfBandDecimator`] from an already-constructed `config` and
+    /// delay lines.
+    ///
+    /// The delay lines are taken as-is with their current contents.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-padded cold-start behavior, prefill every delay line with `K`
+    /// zeros before passing it here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.coefficients` is empty, or if any delay-line capacity
+    /// does not equal the number of side coefficients `K`.
+    pub fn from_parts(config: Config<T, C>, newer: B, older: B, delayed: B) -> Self {
+        assert_buffers(
+            config.coefficients.as_slice().len(),
+            &[&newer, &older, &delayed],
+            "HalfBandDecimator",
+        );
+        Self {
+            config,
+            state: State {
+                newer,
+                older,
+                delayed,
+                phase: 1,
+            },
+        }
+    }
+}
+
+impl<T, C, B> HalfBandDecimator<T, C, B>
+where
+    C: AsSlice<T>,
+{
+    /// Returns the dense tap count `4·K − 1`.
+    #[must_use]
+    pub fn num_taps(&self) -> usize {
+        4 * self.config.coefficients.as_slice().len() - 1
+    }
+}
+
+impl<T, C, B> HalfBandDecimator<T, C, B>
+where
+    T: Clone + Zero + Add<Output = T> + Mul<Output = T>,
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    /// Consumes one input sample, returning an output every second sample.
+    fn push(&mut self, input: T) -> Option<T> {
+        let state = &mut self.state;
+        if state.phase > 0 {
+            let _ = state.delayed.push_back(input);
+            state.phase = 0;
+            return None;
+        }
+        state.phase = 1;
+
+        if let Some(evicted) = state.newer.push_back(input) {
+            let _ = state.older.push_back(evicted);
+        }
+        let center = state.delayed.front().cloned().unwrap_or_else(T::zero);
+        let side = folded_sum(
+            self.config.coefficients.as_slice(),
+            &state.newer,
+            &state.older,
+        );
+        Some(self.config.center.clone() * center + side)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> HalfBandDecimatorVec<T>
+where
+    T: Clone + PartialEq + Zero,
+{
+    /// Creates a heap-backed half-band decimator from dense taps.
+    ///
+    /// The delay lines are zero-filled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `taps.len()` is not `4·K − 1` for some `K >= 1`, if `taps` is
+    /// not symmetric, or if a tap at an even, non-zero offset from the center
+    /// is non-zero.
+    #[must_use]
+    pub fn from_taps(taps: &[T]) -> Self {
+        let (center, side) = split_taps(taps, "HalfBandDecimator");
+        let coefficients: alloc::vec::Vec<T> = side.collect();
+        let half = coefficients.len();
+        let buffer = || {
+            let mut buffer = HeapCircularBuffer::with_capacity(half);
+            for _ in 0..half {
+                let _ = buffer.push_back(T::zero());
+            }
+            buffer
+        };
+        Self::from_parts(
+            Config {
+                center,
+                coefficients,
+            },
+            buffer(),
+            buffer(),
+            buffer(),
+        )
+    }
+}
+
+impl<T, const K: usize> HalfBandDecimatorArray<T, K>
+where
+    T: Num + Clone,
+{
+    /// Creates a half-band decimator from `4·K − 1` dense taps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `taps.len()` is not `4·K − 1`, if `taps` is not symmetric, or
+    /// if a tap at an even, non-zero offset from the center is non-zero.
+    #[must_use]
+    pub fn from_taps(taps: &[T]) -> Self {
+        assert_eq!(
+            taps.len(),
+            4 * K - 1,
+            "HalfBandDecimator: tap count must be 4·K − 1"
+        );
+        let (center, mut side) = split_taps(taps, "HalfBandDecimator");
+        let coefficients = core::array::from_fn(|_| side.next().expect("K side taps"));
+        Self::with_config(Config {
+            center,
+            coefficients,
+        })
+    }
+}
+
+impl<T, C, B> ConfigTrait for HalfBandDecimator<T, C, B> {
+    type Config = Config<T, C>;
+}
+
+impl<T, const K: usize> WithConfig for HalfBandDecimatorArray<T, K>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(
+            config,
+            zero_filled_fixed_ring(),
+            zero_filled_fixed_ring(),
+            zero_filled_fixed_ring(),
+        )
+    }
+}
+
+impl<T, C, B> ConfigRef for HalfBandDecimator<T, C, B> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, C, B> ConfigClone for HalfBandDecimator<T, C, B>
+where
+    Config<T, C>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, C, B> HasGuts for HalfBandDecimator<T, C, B> {
+    type Guts = (Config<T, C>, State<B>);
+}
+
+impl<T, C, B> FromGuts for HalfBandDecimator<T, C, B> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, C, B> IntoGuts for HalfBandDecimator<T, C, B> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const K: usize> Reset for HalfBandDecimatorArray<T, K>
+where
+    T: Num,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const K: usize> ResetMut for HalfBandDecimatorArray<T, K> where Self: Reset {}
+
+impl<T, C, B> MultirateFilter<T> for HalfBandDecimator<T, C, B>
+where
+    T: Clone + Zero + Add<Output = T> + Mul<Output = T>,
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    type Output = T;
+
+    fn process(&mut self, input: &[T], output: &mut [Self::Output]) -> (usize, usize) {
+        let mut input_consumed = 0;
+        let mut output_produced = 0;
+
+        while input_consumed < input.len() {
+            if self.state.phase == 0 && output_produced == output.len() {
+                break;
+            }
+
+            let sample = input[input_consumed].clone();
+            input_consumed += 1;
+            if let Some(value) = self.push(sample) {
+                output[output_produced] = value;
+                output_produced += 1;
+            }
+        }
+
+        (input_consumed, output_produced)
+    }
+}
+
+/// A cascade of half-band decimators for decimation by `2^S`.
+///
+/// Each stage decimates the previous stage's output by two. Stages are stored
+/// in any [`AsSlice`] container; with heap-backed stages, later stages (which
+/// see a narrower relative transition band) can use longer filters.
+///
+/// The cascade implements [`MultirateFilter`] with the same streaming rules as
+/// a single [`HalfBandDecimator`]: an input that would produce a final output
+/// while the output slice is full remains unconsumed.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(K) amortized; the work of stage `s` is halved
+///   relative to stage `s − 1`, so the first stage dominates.
+/// - **Space:** O(S·K).
+#[derive(Clone, Debug)]
+pub struct HalfBandDecimatorCascade<T, C, B, S> {
+    stages: S,
+    _pd: PhantomData<(T, C, B)>,
+}
+
+impl<T, C, B, S> HalfBandDecimatorCascade<T, C, B, S>
+where
+    S: AsSlice<HalfBandDecimator<T, C, B>>,
+{
+    /// Creates a cascade from its stages, first (highest-rate) stage first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stages` is empty.
+    pub fn from_stages(stages: S) -> Self {
+        assert!(
+            !stages.as_slice().is_empty(),
+            "HalfBandDecimatorCascade: stage count must be > 0"
+        );
+        Self {
+            stages,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Returns the stages, first (highest-rate) stage first.
+    #[must_use]
+    pub fn stages(&self) -> &[HalfBandDecimator<T, C, B>] {
+        self.stages.as_slice()
+    }
+
+    /// Consumes the cascade, returning its stages.
+    #[must_use]
+    pub fn into_stages(self) -> S {
+        self.stages
+    }
+
+    /// Returns the overall decimation factor `2^S`, or `None` on overflow.
+    #[must_use]
+    pub fn factor(&self) -> Option<usize> {
+        let count = u32::try_from(self.stages.as_slice().len()).ok()?;
+        2_usize.checked_pow(count)
+    }
+}
+
+impl<T, C, B, S> MultirateFilter<T> for HalfBandDecimatorCascade<T, C, B, S>
+where
+    T: Clone + Zero + Add<Output = T> + Mul<Output = T>,
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+    S: AsSlice<HalfBandDecimator<T, C, B>>,
+{
+    type Output = T;
+
+    fn process(&mut self, input: &[T], output: &mut [Self::Output]) -> (usize, usize) {
+        let mut input_consumed = 0;
+        let mut output_produced = 0;
+
+        while input_consumed < input.len() {
+            let stages = self.stages.as_mut_slice();
+            let output_ready = stages.iter().all(|stage| stage.state.phase == 0);
+            if output_ready && output_produced == output.len() {
+                break;
+            }
+
+            let sample = input[input_consumed].clone();
+            input_consumed += 1;
+            let decimated = stages
+                .iter_mut()
+                .try_fold(sample, |sample, stage| stage.push(sample));
+            if let Some(value) = decimated {
+                output[output_produced] = value;
+                output_produced += 1;
+            }
+        }
+
+        (input_consumed, output_produced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // K = 2: [-1, 0, 9, 16, 9, 0, -1]
+    const TAPS: [i64; 7] = [-1, 0, 9, 16, 9, 0, -1];
+
+    fn test_input() -> [i64; 41] {
+        core::array::from_fn(|n| {
+            let n = i64::try_from(n).expect("index fits in i64");
+            (n * 37 + 11) % 23 - 11
+        })
+    }
+
+    /// Dense reference: full convolution, keeping every second output.
+    fn reference(taps: &[i64], input: &[i64]) -> [i64; 20] {
+        core::array::from_fn(|m| {
+            let t = 2 * m + 1;
+            taps.iter()
+                .enumerate()
+                .filter(|&(k, _)| k <= t)
+                .map(|(k, &h)| h * input[t - k])
+                .sum()
+        })
+    }
+
+    #[test]
+    fn matches_dense_reference() {
+        let input = test_input();
+        let mut decimator = HalfBandDecimatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut output = [0; 20];
+
+        assert_eq!(decimator.process(&input, &mut output), (41, 20));
+        assert_eq!(output, reference(&TAPS, &input));
+        assert_eq!(decimator.num_taps(), 7);
+    }
+
+    #[cfg(all(feature = "alloc", any(feature = "libm", feature = "std")))]
+    #[test]
+    fn matches_polyphase_decimator() {
+        use alloc::vec::Vec;
+
+        use crate::filters::fir::design::half_band::equiripple_taps_vec;
+        use crate::filters::fir::polyphase::decimator::PolyphaseDecimatorVec;
+        use crate::util::test_fixtures::collatz;
+
+        let taps = equiripple_taps_vec::<f32>(23, 0.2);
+        let input = collatz();
+
+        let mut polyphase = PolyphaseDecimatorVec::<f32>::from_prototype_taps(2, &taps);
+        let mut expected = alloc::vec![0.0; input.len() / 2];
+        let _ = polyphase.process(&input, &mut expected);
+
+        let mut decimator = HalfBandDecimatorVec::from_taps(&taps);
+        let mut output = alloc::vec![0.0; input.len() / 2];
+        let _ = decimator.process(&input, &mut output);
+
+        let error: Vec<f32> = output
+            .iter()
+            .zip(&expected)
+            .map(|(a, b)| (a - b).abs())
+            .collect();
+        assert!(error.iter().all(|&e| e < 1e-3), "{error:?}");
+    }
+
+    #[test]
+    fn streaming_defers_output_input() {
+        let input = test_input();
+        let mut decimator = HalfBandDecimatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut output = [0; 20];
+
+        // Room for one output: the fourth input is deferred.
+        assert_eq!(decimator.process(&input, &mut output[..1]), (3, 1));
+        assert_eq!(decimator.process(&input[3..], &mut output[1..]), (38, 19));
+        assert_eq!(output, reference(&TAPS, &input));
+    }
+
+    #[test]
+    fn cascade_matches_chained_stages() {
+        let input = test_input();
+        let mut first = HalfBandDecimatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut second = HalfBandDecimatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut intermediate = [0; 20];
+        let mut expected = [0; 10];
+        let _ = first.process(&input, &mut intermediate);
+        let _ = second.process(&intermediate, &mut expected);
+
+        let stage = HalfBandDecimatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut cascade = HalfBandDecimatorCascade::from_stages([stage.clone(), stage]);
+        assert_eq!(cascade.factor(), Some(4));
+
+        // Stream in small chunks with a one-sample output window.
+        let mut output = [0; 10];
+        let (mut consumed, mut produced) = (0, 0);
+        while consumed < input.len() {
+            let end = (consumed + 3).min(input.len());
+            let (c, p) = cascade.process(
+                &input[consumed..end],
+                &mut output[produced..=produced.min(9)],
+            );
+            consumed += c;
+            produced += p;
+        }
+        assert_eq!(produced, 10);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn reset_clears_delay_lines() {
+        let input = test_input();
+        let mut decimator = HalfBandDecimatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut output = [0; 20];
+        let _ = decimator.process(&input[..5], &mut output);
+
+        let mut decimator = decimator.reset();
+        let _ = decimator.process(&input, &mut output);
+        assert_eq!(output, reference(&TAPS, &input));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "HalfBandDecimator: taps at even offsets from the center must be zero"
+    )]
+    fn rejects_non_half_band_taps() {
+        let _ = HalfBandDecimatorArray::<i64, 2>::from_taps(&[-1, 1, 9, 16, 9, 1, -1]);
+    }
+}
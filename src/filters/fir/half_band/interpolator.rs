@@ -0,0 +1,570 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Half-band interpolation by two.
+
+use core::marker::PhantomData;
+use core::ops::{Add, Mul};
+
+use circular_buffer::FixedCircularBuffer;
+use num_traits::{Num, Zero};
+
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, MultirateFilter, Reset, WithConfig,
+};
+
+use super::{assert_buffers, folded_sum, split_taps, Config};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The half-band interpolator's state.
+#[derive(Clone, Debug)]
+pub struct State<B> {
+    /// The newer half of the `2·K` most recent input samples.
+    pub newer: B,
+    /// The older half of the `2·K` most recent input samples.
+    pub older: B,
+    /// The phase branch that will produce the next output sample.
+    ///
+    /// `phase == 2` means no phase output is pending.
+    pub phase: usize,
+}
+
+/// A stateful 1-to-2 half-band interpolator.
+///
+/// Each consumed input sample is pushed into a `2·K`-sample delay line. The
+/// first output of each pair is the folded symmetric branch; the second is the
+/// center tap times the delay line's middle sample.
+///
+/// # Gain
+///
+/// Like [`PolyphaseInterpolator`](crate::filters::fir::polyphase::interpolator::PolyphaseInterpolator),
+/// this type does not apply interpolation gain. Multiply unit-DC-gain
+/// half-band taps by two (center tap `1`) to preserve unity passband
+/// amplitude.
+///
+/// # Streaming
+///
+/// The [`MultirateFilter::process`] implementation supports streaming operation
+/// with arbitrary input and output slice sizes. If the output buffer fills
+/// after the first output of a pair, the next call emits the second output
+/// before consuming another input sample. An empty output slice consumes no
+/// input.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`HalfBandInterpolatorArray<T, K>`] for stack-allocated storage.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`HalfBandInterpolatorVec<T>`] for heap-allocated storage."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `HalfBandInterpolatorVec<T>` for heap-allocated storage."
+)]
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(K) per input sample; `K + 1` multiplications and
+///   `2·K` additions per output pair.
+/// - **Space:** O(K); two delay lines of `K` samples.
+#[derive(Clone, Debug)]
+pub struct HalfBandInterpolator<T, C, B> {
+    config: Config<T, C>,
+    state: State<B>,
+}
+
+/// A half-band interpolator with `K` distinct side taps (`4·K − 1` dense
+/// taps), backed by fixed-size storage.
+pub type HalfBandInterpolatorArray<T, const K: usize> =
+    HalfBandInterpolator<T, [T; K], FixedCircularBuffer<T, K>>;
+
+/// A half-band interpolator backed by heap-allocated storage.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type HalfBandInterpolatorVec<T> =
+    HalfBandInterpolator<T, alloc::vec::Vec<T>, HeapCircularBuffer<T>>;
+
+impl<T, C, B> HalfBandInterpolator<T, C, B>
+where
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    /// Creates a [`HalfBandInterpolator`] from an already-constructed `config`
+    /// and delay lines.
+    ///
+    /// The delay lines are taken as-is with their current contents.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-padded cold-start behavior, prefill both delay lines with `K`
+    /// zeros before passing them here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.coefficients` is empty, or if either delay-line
+    /// capacity does not equal the number of side coefficients `K`.
+    pub fn from_parts(config: Config<T, C>, newer: B, older: B) -> Self {
+        assert_buffers(
+            config.coefficients.as_slice().len(),
+            &[&newer, &older],
+            "HalfBandInterpolator",
+        );
+        Self {
+            config,
+            state: State {
+                newer,
+                older,
+                phase: 2,
+            },
+        }
+    }
+}
+
+impl<T, C, B> HalfBandInterpolator<T, C, B>
+where
+    C: AsSlice<T>,
+{
+    /// Returns the dense tap count `4·K − 1`.
+    #[must_use]
+    pub fn num_taps(&self) -> usize {
+        4 * self.config.coefficients.as_slice().len() - 1
+    }
+}
+
+impl<T, C, B> HalfBandInterpolator<T, C, B>
+where
+    T: Clone + Zero + Add<Output = T> + Mul<Output = T>,
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    /// Returns `true` if an output is pending without further input.
+    fn is_pending(&self) -> bool {
+        self.state.phase < 2
+    }
+
+    /// Consumes one input sample, starting a new output pair.
+    fn push(&mut self, input: T) {
+        if let Some(evicted) = self.state.newer.push_back(input) {
+            let _ = self.state.older.push_back(evicted);
+        }
+        self.state.phase = 0;
+    }
+
+    /// Emits the pending output of the current pair.
+    fn emit(&mut self) -> T {
+        let state = &mut self.state;
+        let phase = state.phase;
+        state.phase += 1;
+        if phase == 0 {
+            folded_sum(
+                self.config.coefficients.as_slice(),
+                &state.newer,
+                &state.older,
+            )
+        } else {
+            let middle = state.newer.front().cloned().unwrap_or_else(T::zero);
+            self.config.center.clone() * middle
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> HalfBandInterpolatorVec<T>
+where
+    T: Clone + PartialEq + Zero,
+{
+    /// Creates a heap-backed half-band interpolator from dense taps.
+    ///
+    /// The taps are used as-is; see the [gain](HalfBandInterpolator#gain)
+    /// notes. The delay lines are zero-filled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `taps.len()` is not `4·K − 1` for some `K >= 1`, if `taps` is
+    /// not symmetric, or if a tap at an even, non-zero offset from the center
+    /// is non-zero.
+    #[must_use]
+    pub fn from_taps(taps: &[T]) -> Self {
+        let (center, side) = split_taps(taps, "HalfBandInterpolator");
+        let coefficients: alloc::vec::Vec<T> = side.collect();
+        let half = coefficients.len();
+        let buffer = || {
+            let mut buffer = HeapCircularBuffer::with_capacity(half);
+            for _ in 0..half {
+                let _ = buffer.push_back(T::zero());
+            }
+            buffer
+        };
+        Self::from_parts(
+            Config {
+                center,
+                coefficients,
+            },
+            buffer(),
+            buffer(),
+        )
+    }
+}
+
+impl<T, const K: usize> HalfBandInterpolatorArray<T, K>
+where
+    T: Num + Clone,
+{
+    /// Creates a half-band interpolator from `4·K − 1` dense taps.
+    ///
+    /// The taps are used as-is; see the [gain](HalfBandInterpolator#gain)
+    /// notes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `taps.len()` is not `4·K − 1`, if `taps` is not symmetric, or
+    /// if a tap at an even, non-zero offset from the center is non-zero.
+    #[must_use]
+    pub fn from_taps(taps: &[T]) -> Self {
+        assert_eq!(
+            taps.len(),
+            4 * K - 1,
+            "HalfBandInterpolator: tap count must be 4·K − 1"
+        );
+        let (center, mut side) = split_taps(taps, "HalfBandInterpolator");
+        let coefficients = core::array::from_fn(|_| side.next().expect("K side taps"));
+        Self::with_config(Config {
+            center,
+            coefficients,
+        })
+    }
+}
+
+impl<T, C, B> ConfigTrait for HalfBandInterpolator<T, C, B> {
+    type Config = Config<T, C>;
+}
+
+impl<T, const K: usize> WithConfig for HalfBandInterpolatorArray<T, K>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(config, zero_filled_fixed_ring(), zero_filled_fixed_ring())
+    }
+}
+
+impl<T, C, B> ConfigRef for HalfBandInterpolator<T, C, B> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, C, B> ConfigClone for HalfBandInterpolator<T, C, B>
+where
+    Config<T, C>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, C, B> HasGuts for HalfBandInterpolator<T, C, B> {
+    type Guts = (Config<T, C>, State<B>);
+}
+
+impl<T, C, B> FromGuts for HalfBandInterpolator<T, C, B> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, C, B> IntoGuts for HalfBandInterpolator<T, C, B> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const K: usize> Reset for HalfBandInterpolatorArray<T, K>
+where
+    T: Num,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const K: usize> ResetMut for HalfBandInterpolatorArray<T, K> where Self: Reset {}
+
+impl<T, C, B> MultirateFilter<T> for HalfBandInterpolator<T, C, B>
+where
+    T: Clone + Zero + Add<Output = T> + Mul<Output = T>,
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    type Output = T;
+
+    fn process(&mut self, input: &[T], output: &mut [Self::Output]) -> (usize, usize) {
+        let mut input_consumed = 0;
+        let mut output_produced = 0;
+
+        while output_produced < output.len() {
+            if !self.is_pending() {
+                if input_consumed == input.len() {
+                    break;
+                }
+                self.push(input[input_consumed].clone());
+                input_consumed += 1;
+            }
+
+            output[output_produced] = self.emit();
+            output_produced += 1;
+        }
+
+        (input_consumed, output_produced)
+    }
+}
+
+/// A cascade of half-band interpolators for interpolation by `2^S`.
+///
+/// Each stage interpolates the previous stage's output by two. Stages are
+/// stored in any [`AsSlice`] container; with heap-backed stages, earlier
+/// stages (which see a narrower relative transition band) can use longer
+/// filters.
+///
+/// The cascade implements [`MultirateFilter`] with the same streaming rules as
+/// a single [`HalfBandInterpolator`]: outputs pending in any stage are emitted
+/// before another input sample is consumed.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(K) amortized per output sample; the last stage
+///   dominates.
+/// - **Space:** O(S·K).
+#[derive(Clone, Debug)]
+pub struct HalfBandInterpolatorCascade<T, C, B, S> {
+    stages: S,
+    _pd: PhantomData<(T, C, B)>,
+}
+
+impl<T, C, B, S> HalfBandInterpolatorCascade<T, C, B, S>
+where
+    S: AsSlice<HalfBandInterpolator<T, C, B>>,
+{
+    /// Creates a cascade from its stages, first (lowest-rate) stage first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stages` is empty.
+    pub fn from_stages(stages: S) -> Self {
+        assert!(
+            !stages.as_slice().is_empty(),
+            "HalfBandInterpolatorCascade: stage count must be > 0"
+        );
+        Self {
+            stages,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Returns the stages, first (lowest-rate) stage first.
+    #[must_use]
+    pub fn stages(&self) -> &[HalfBandInterpolator<T, C, B>] {
+        self.stages.as_slice()
+    }
+
+    /// Consumes the cascade, returning its stages.
+    #[must_use]
+    pub fn into_stages(self) -> S {
+        self.stages
+    }
+
+    /// Returns the overall interpolation factor `2^S`, or `None` on overflow.
+    #[must_use]
+    pub fn factor(&self) -> Option<usize> {
+        let count = u32::try_from(self.stages.as_slice().len()).ok()?;
+        2_usize.checked_pow(count)
+    }
+}
+
+/// Pulls the next output of the last stage in `stages`, consuming cascade
+/// input only when no stage has a pending output.
+fn pull<T, C, B>(
+    stages: &mut [HalfBandInterpolator<T, C, B>],
+    input: &[T],
+    input_consumed: &mut usize,
+) -> Option<T>
+where
+    T: Clone + Zero + Add<Output = T> + Mul<Output = T>,
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+{
+    let (last, earlier) = stages.split_last_mut()?;
+    if !last.is_pending() {
+        let sample = if earlier.is_empty() {
+            let sample = input.get(*input_consumed)?.clone();
+            *input_consumed += 1;
+            sample
+        } else {
+            pull(earlier, input, input_consumed)?
+        };
+        last.push(sample);
+    }
+    Some(last.emit())
+}
+
+impl<T, C, B, S> MultirateFilter<T> for HalfBandInterpolatorCascade<T, C, B, S>
+where
+    T: Clone + Zero + Add<Output = T> + Mul<Output = T>,
+    C: AsSlice<T>,
+    B: RingBuffer<T>,
+    S: AsSlice<HalfBandInterpolator<T, C, B>>,
+{
+    type Output = T;
+
+    fn process(&mut self, input: &[T], output: &mut [Self::Output]) -> (usize, usize) {
+        let mut input_consumed = 0;
+        let mut output_produced = 0;
+
+        while output_produced < output.len() {
+            let stages = self.stages.as_mut_slice();
+            let Some(value) = pull(stages, input, &mut input_consumed) else {
+                break;
+            };
+            output[output_produced] = value;
+            output_produced += 1;
+        }
+
+        (input_consumed, output_produced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // K = 2, scaled by two for unit interpolation gain: [-1, 0, 9, 16, 9, 0, -1] / 16.
+    const TAPS: [i64; 7] = [-1, 0, 9, 16, 9, 0, -1];
+
+    fn test_input() -> [i64; 20] {
+        core::array::from_fn(|n| {
+            let n = i64::try_from(n).expect("index fits in i64");
+            (n * 37 + 11) % 23 - 11
+        })
+    }
+
+    /// Dense reference: zero-stuff, then full convolution.
+    fn reference(taps: &[i64], input: &[i64]) -> [i64; 40] {
+        core::array::from_fn(|t| {
+            taps.iter()
+                .enumerate()
+                .filter(|&(k, _)| k <= t && (t - k) % 2 == 0)
+                .map(|(k, &h)| h * input[(t - k) / 2])
+                .sum()
+        })
+    }
+
+    #[test]
+    fn matches_dense_reference() {
+        let input = test_input();
+        let mut interpolator = HalfBandInterpolatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut output = [0; 40];
+
+        assert_eq!(interpolator.process(&input, &mut output), (20, 40));
+        assert_eq!(output, reference(&TAPS, &input));
+    }
+
+    #[cfg(all(feature = "alloc", any(feature = "libm", feature = "std")))]
+    #[test]
+    fn matches_polyphase_interpolator() {
+        use crate::filters::fir::design::half_band::windowed_taps_vec;
+        use crate::filters::fir::polyphase::interpolator::PolyphaseInterpolatorVec;
+        use crate::util::test_fixtures::collatz;
+
+        let taps: alloc::vec::Vec<f32> = windowed_taps_vec::<f32>(19)
+            .into_iter()
+            .map(|h| 2.0 * h)
+            .collect();
+        let input = collatz();
+
+        let mut polyphase = PolyphaseInterpolatorVec::<f32>::from_prototype_taps(2, &taps);
+        let mut expected = alloc::vec![0.0; input.len() * 2];
+        let _ = polyphase.process(&input, &mut expected);
+
+        let mut interpolator = HalfBandInterpolatorVec::from_taps(&taps);
+        let mut output = alloc::vec![0.0; input.len() * 2];
+        let _ = interpolator.process(&input, &mut output);
+
+        for (a, b) in output.iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-3, "{a} != {b}");
+        }
+    }
+
+    #[test]
+    fn resumes_pending_output() {
+        let input = test_input();
+        let mut interpolator = HalfBandInterpolatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut output = [0; 40];
+
+        assert_eq!(interpolator.process(&input, &mut output[..3]), (2, 3));
+        assert_eq!(
+            interpolator.process(&input[2..], &mut output[3..]),
+            (18, 37)
+        );
+        assert_eq!(output, reference(&TAPS, &input));
+    }
+
+    #[test]
+    fn empty_output_consumes_no_input() {
+        let mut interpolator = HalfBandInterpolatorArray::<i64, 2>::from_taps(&TAPS);
+
+        assert_eq!(interpolator.process(&[1, 2], &mut []), (0, 0));
+    }
+
+    #[test]
+    fn cascade_matches_chained_stages() {
+        let input = test_input();
+        let mut first = HalfBandInterpolatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut second = HalfBandInterpolatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut intermediate = [0; 40];
+        let mut expected = [0; 80];
+        let _ = first.process(&input, &mut intermediate);
+        let _ = second.process(&intermediate, &mut expected);
+
+        let stage = HalfBandInterpolatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut cascade = HalfBandInterpolatorCascade::from_stages([stage.clone(), stage]);
+        assert_eq!(cascade.factor(), Some(4));
+
+        // Stream with output windows that split the four outputs per input.
+        let mut output = [0; 80];
+        let (mut consumed, mut produced) = (0, 0);
+        while produced < output.len() {
+            let end = (produced + 3).min(output.len());
+            let (c, p) = cascade.process(&input[consumed..], &mut output[produced..end]);
+            consumed += c;
+            produced += p;
+        }
+        assert_eq!(consumed, input.len());
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn reset_clears_delay_lines() {
+        let input = test_input();
+        let mut interpolator = HalfBandInterpolatorArray::<i64, 2>::from_taps(&TAPS);
+        let mut output = [0; 40];
+        let _ = interpolator.process(&input[..3], &mut output[..5]);
+
+        let mut interpolator = interpolator.reset();
+        let _ = interpolator.process(&input, &mut output);
+        assert_eq!(output, reference(&TAPS, &input));
+    }
+}
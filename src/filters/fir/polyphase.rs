@@ -5,8 +5,8 @@
 //! Polyphase FIR filter banks, executors, and multirate filters.
 //!
 //! [`filter_bank`] contains the coefficient storage and selected-phase execution
-//! primitive. [`fir`] adds sample history. The [`interpolator`], [`decimator`], and
-//! [`rational_resampler`] modules build streaming
+//! primitive. [`fir`] adds sample history. The [`interpolator`], [`decimator`],
+//! [`rational_resampler`], and [`arbitrary_resampler`] modules build streaming
 //! [`MultirateFilter`](crate::traits::MultirateFilter) adapters on top of those
 //! primitives.
 //!
@@ -23,6 +23,7 @@
 //! | [Interpolator `L`](interpolator::PolyphaseInterpolator) | `input_rate * L` | The prototype runs at the output rate, where it suppresses interpolation images. |
 //! | [Decimator `M`](decimator::PolyphaseDecimator) | `input_rate` | The anti-aliasing filter sees the input-rate spectrum before downsampling. |
 //! | [Rational `L/M`](rational_resampler::RationalResampler) | `input_rate * L` | The filter runs at the intermediate rate after interpolation and before decimation. |
+//! | [Arbitrary, `P` phases](arbitrary_resampler::ArbitraryResampler) | `input_rate * P` | Outputs interpolate linearly between adjacent phases. |
 //!
 //! For rational resamplers, `max(L, M)` is useful when choosing a low-pass
 //! anti-image and anti-aliasing prototype. At `input_rate * L`, the input
@@ -33,20 +34,21 @@
 //!
 //! # Prototype gain
 //!
-//! [`interpolator`] and [`rational_resampler`] do not apply interpolation gain
-//! scaling. If a prototype designer normalizes taps to unity passband gain,
-//! multiply the prototype coefficients by `L` before polyphase construction
-//! when unity amplitude should be preserved. This compensates for the
-//! interpolation step that inserts `L - 1` zero-valued samples between input
-//! samples. A unity-gain prototype preserves that upsampled sequence's
-//! `1 / L` baseband/DC gain, while scaling the prototype by `L` restores unity
-//! passband amplitude.
+//! [`interpolator`], [`rational_resampler`], and [`arbitrary_resampler`] do not
+//! apply interpolation gain scaling. If a prototype designer normalizes taps to
+//! unity passband gain, multiply the prototype coefficients by `L` before
+//! polyphase construction when unity amplitude should be preserved. This
+//! compensates for the interpolation step that inserts `L - 1` zero-valued
+//! samples between input samples. A unity-gain prototype preserves that
+//! upsampled sequence's `1 / L` baseband/DC gain, while scaling the prototype
+//! by `L` restores unity passband amplitude.
 //!
 //! Decimators are usually constructed with a unity-passband-gain prototype. A
 //! decimator is equivalent to filtering at the input rate and then keeping every
 //! `M`th output sample, so downsampling changes sample spacing but does not
 //! require any amplitude correction.
 
+pub mod arbitrary_resampler;
 pub mod decimator;
 pub mod filter_bank;
 pub mod fir;
@@ -0,0 +1,566 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Polyphase arbitrary-ratio resampling with inter-phase interpolation.
+
+use circular_buffer::{CircularBuffer, FixedCircularBuffer};
+use num_traits::{Num, NumCast, ToPrimitive};
+
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, MultirateFilter, Reset, WithConfig,
+};
+
+use super::filter_bank::{Config as BankConfig, PolyphaseFilterBank};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "alloc")]
+use super::filter_bank::PolyphaseFilterBankVec;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The arbitrary resampler's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<C, T> {
+    /// Number of coefficient phases `P`.
+    pub num_phases: usize,
+    /// Number of coefficients in each phase branch.
+    pub taps_per_phase: usize,
+    /// Coefficients in the phase-major layout described by
+    /// [`PolyphaseFilterBank`], with `num_phases` phases.
+    pub coefficients: C,
+    /// Output samples per input sample (`output_rate / input_rate`).
+    pub ratio: T,
+}
+
+/// The arbitrary resampler's state.
+#[derive(Clone, Debug)]
+pub struct State<R, T> {
+    /// The `taps_per_phase + 1` most recent input samples.
+    pub taps: R,
+    /// Position of the next output, in input sample periods, after the start
+    /// of the current interpolation interval.
+    ///
+    /// `phase >= 1` means input must be consumed before the next output can be
+    /// produced.
+    pub phase: T,
+}
+
+/// A stateful arbitrary-ratio polyphase resampler.
+///
+/// The filter bank's `P` phases sample the prototype's impulse response at
+/// `P` sub-sample offsets. For an output at fractional position `μ` between two
+/// input samples, the resampler evaluates the two neighboring phases
+/// `⌊μ·P⌋` and `⌊μ·P⌋ + 1` and interpolates linearly between their outputs.
+/// The right neighbor of the last phase is phase zero one input sample later,
+/// so the delay line holds `taps_per_phase + 1` samples and outputs lag by one
+/// extra input sample compared to
+/// [`PolyphaseInterpolator`](super::interpolator::PolyphaseInterpolator).
+///
+/// With enough phases (typically 32 to 256) the linear interpolation error is
+/// far below the prototype's stopband, giving the selectivity of a long
+/// windowed-sinc filter at an arbitrary, time-varying ratio. Compare with the
+/// [`FarrowResampler`](crate::filters::fir::farrow::FarrowResampler), which
+/// needs no coefficient table but offers less control over aliasing.
+///
+/// # Prototype design
+///
+/// Design dense prototype taps at `input_rate * P`. For low-pass anti-image
+/// and anti-aliasing, place the cutoff below `0.5 / P · min(1, ratio)` at that
+/// rate.
+///
+/// # Gain
+///
+/// This type does not apply interpolation gain scaling. Multiply unit-DC-gain
+/// prototype coefficients by `P` before polyphase construction to preserve
+/// unity amplitude.
+///
+/// # Ratio changes
+///
+/// [`set_ratio`](Self::set_ratio) may be called between (or during) blocks.
+/// The new ratio applies from the next phase increment; the position of an
+/// already pending output is unchanged.
+///
+/// # Streaming
+///
+/// The [`MultirateFilter::process`] implementation supports streaming operation
+/// with arbitrary input and output slice sizes. When output capacity is full,
+/// the resampler can still consume input while advancing its phase until the
+/// next output is pending. Pass the unconsumed input suffix to the next call.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`ArbitraryResamplerArray<T, N, R>`] for stack-allocated coefficients and
+///   delay-line storage.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`ArbitraryResamplerVec<T>`] for heap-allocated coefficients and delay-line storage."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `ArbitraryResamplerVec<T>` for heap-allocated coefficients and delay-line storage."
+)]
+/// - [`ArbitraryResamplerRefMut<'_, T, C>`] for caller-owned delay-line
+///   storage.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(H/P) per output, where H is the total tap count; each output
+///   evaluates two phase branches of H/P taps.
+/// - **Space:** O(H) for coefficients plus O(H/P) for the delay line.
+#[derive(Clone, Debug)]
+pub struct ArbitraryResampler<T, C, R> {
+    bank: PolyphaseFilterBank<C>,
+    ratio: T,
+    step: T,
+    state: State<R, T>,
+}
+
+/// An arbitrary resampler backed by fixed coefficient and delay-line storage.
+///
+/// `N` is the total coefficient count. `R` is the delay-line length and must
+/// equal the configuration's `taps_per_phase + 1`.
+pub type ArbitraryResamplerArray<T, const N: usize, const R: usize> =
+    ArbitraryResampler<T, [T; N], FixedCircularBuffer<T, R>>;
+
+/// An arbitrary resampler backed by heap-allocated storage.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type ArbitraryResamplerVec<T> =
+    ArbitraryResampler<T, alloc::vec::Vec<T>, HeapCircularBuffer<T>>;
+
+/// An arbitrary resampler that borrows caller-owned delay-line storage.
+pub type ArbitraryResamplerRefMut<'a, T, C> = ArbitraryResampler<T, C, &'a mut CircularBuffer<T>>;
+
+fn assert_ratio<T>(ratio: T)
+where
+    T: Copy + Num + PartialOrd,
+{
+    // `ratio == ratio` rejects NaN.
+    #[allow(clippy::eq_op)]
+    let is_number = ratio == ratio;
+    assert!(
+        is_number && ratio > T::zero(),
+        "ArbitraryResampler: ratio must be > 0"
+    );
+}
+
+impl<T, C, R> ArbitraryResampler<T, C, R>
+where
+    T: Copy + Num + PartialOrd,
+    C: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    /// Creates an [`ArbitraryResampler`] from an already-constructed `config`
+    /// and delay-line buffer.
+    ///
+    /// The delay-line buffer is taken as-is with its current contents. It must
+    /// contain `taps_per_phase + 1` samples before the first output is
+    /// produced.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-padded cold-start behavior, prefill the buffer with
+    /// `taps_per_phase + 1` zeros before passing it here.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.ratio` is not positive, if `config.num_phases` or
+    /// `config.taps_per_phase` is zero, if the coefficient count does not
+    /// equal `config.num_phases * config.taps_per_phase`, or if the delay-line
+    /// capacity does not equal `config.taps_per_phase + 1`.
+    pub fn from_parts(config: Config<C, T>, taps: R) -> Self {
+        let bank = PolyphaseFilterBank::from_parts(BankConfig {
+            num_phases: config.num_phases,
+            taps_per_phase: config.taps_per_phase,
+            coefficients: config.coefficients,
+        });
+        Self::from_bank(bank, config.ratio, taps)
+    }
+
+    fn from_bank(bank: PolyphaseFilterBank<C>, ratio: T, taps: R) -> Self {
+        assert_ratio(ratio);
+        assert_eq!(
+            taps.capacity(),
+            bank.taps_per_phase() + 1,
+            "ArbitraryResampler: taps capacity must equal taps_per_phase + 1"
+        );
+        Self {
+            bank,
+            ratio,
+            step: T::one() / ratio,
+            state: State {
+                taps,
+                phase: T::one(),
+            },
+        }
+    }
+}
+
+impl<T, C, R> ArbitraryResampler<T, C, R>
+where
+    T: Copy + Num + PartialOrd,
+{
+    /// Returns the current ratio of output samples per input sample.
+    #[must_use]
+    pub fn ratio(&self) -> T {
+        self.ratio
+    }
+
+    /// Sets the ratio of output samples per input sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not positive.
+    pub fn set_ratio(&mut self, ratio: T) {
+        assert_ratio(ratio);
+        self.ratio = ratio;
+        self.step = T::one() / ratio;
+    }
+
+    /// Returns the position of the next output, in input sample periods, after
+    /// the start of the current interpolation interval.
+    ///
+    /// Values in `[0, 1)` are the fractional phase `μ` of the pending output;
+    /// values `>= 1` mean input must be consumed first.
+    #[must_use]
+    pub fn fractional_phase(&self) -> T {
+        self.state.phase
+    }
+}
+
+impl<T, C, R> ArbitraryResampler<T, C, R> {
+    /// Returns the number of polyphase branches.
+    #[must_use]
+    pub fn num_phases(&self) -> usize {
+        self.bank.num_phases()
+    }
+
+    /// Returns the number of coefficients in each phase branch.
+    #[must_use]
+    pub fn taps_per_phase(&self) -> usize {
+        self.bank.taps_per_phase()
+    }
+
+    /// Returns the total number of coefficients.
+    #[must_use]
+    pub fn total_taps(&self) -> usize {
+        self.bank.total_taps()
+    }
+}
+
+impl<T, C, R> ArbitraryResampler<T, C, R>
+where
+    T: Copy + Num + PartialOrd + ToPrimitive + NumCast,
+    C: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    fn evaluate(&self, mu: T) -> T {
+        let num_phases = self.num_phases();
+        let taps_per_phase = self.taps_per_phase();
+        let scaled = mu * T::from(num_phases).expect("phase count is representable");
+        let index = scaled
+            .to_usize()
+            .expect("phase position is representable")
+            .min(num_phases - 1);
+        let fraction = scaled - T::from(index).expect("phase index is representable");
+
+        let taps = &self.state.taps;
+        let older = || taps.iter().take(taps_per_phase);
+        let left: T = self.bank.execute(index, older());
+        if fraction == T::zero() {
+            return left;
+        }
+        let right: T = if index + 1 < num_phases {
+            self.bank.execute(index + 1, older())
+        } else {
+            self.bank.execute(0, taps.iter().skip(1))
+        };
+        left + fraction * (right - left)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ArbitraryResamplerVec<T>
+where
+    T: Copy + Num + PartialOrd,
+{
+    /// Creates a heap-backed arbitrary resampler from dense prototype
+    /// coefficients.
+    ///
+    /// `prototype` is passed to [`PolyphaseFilterBankVec::from_prototype_taps`]
+    /// for ordering and padding behavior. The delay line is zero-filled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_phases` is zero, `prototype` is empty, `ratio` is not
+    /// positive, or the padded coefficient count overflows `usize`.
+    #[must_use]
+    pub fn from_prototype_taps(num_phases: usize, prototype: &[T], ratio: T) -> Self {
+        let bank = PolyphaseFilterBankVec::from_prototype_taps(num_phases, prototype);
+        let len = bank.taps_per_phase() + 1;
+        let mut taps = HeapCircularBuffer::with_capacity(len);
+        for _ in 0..len {
+            let _ = taps.push_back(T::zero());
+        }
+        Self::from_bank(bank, ratio, taps)
+    }
+}
+
+impl<T, C, R> ConfigTrait for ArbitraryResampler<T, C, R> {
+    type Config = Config<C, T>;
+}
+
+impl<T, const N: usize, const R: usize> WithConfig for ArbitraryResamplerArray<T, N, R>
+where
+    T: Copy + Num + PartialOrd,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(config, zero_filled_fixed_ring::<T, R>())
+    }
+}
+
+impl<T, C, R> ConfigClone for ArbitraryResampler<T, C, R>
+where
+    T: Clone,
+    C: Clone,
+{
+    fn config(&self) -> Self::Config {
+        let bank = self.bank.config();
+        Config {
+            num_phases: bank.num_phases,
+            taps_per_phase: bank.taps_per_phase,
+            coefficients: bank.coefficients,
+            ratio: self.ratio.clone(),
+        }
+    }
+}
+
+impl<T, C, R> HasGuts for ArbitraryResampler<T, C, R> {
+    type Guts = (BankConfig<C>, T, State<R, T>);
+}
+
+impl<T, C, R> FromGuts for ArbitraryResampler<T, C, R>
+where
+    T: Copy + Num + PartialOrd,
+{
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (bank, ratio, state) = guts;
+        assert_ratio(ratio);
+        Self {
+            bank: PolyphaseFilterBank::from_guts(bank),
+            ratio,
+            step: T::one() / ratio,
+            state,
+        }
+    }
+}
+
+impl<T, C, R> IntoGuts for ArbitraryResampler<T, C, R> {
+    fn into_guts(self) -> Self::Guts {
+        (self.bank.into_guts(), self.ratio, self.state)
+    }
+}
+
+impl<T, const N: usize, const R: usize> Reset for ArbitraryResamplerArray<T, N, R>
+where
+    T: Copy + Num + PartialOrd,
+{
+    fn reset(self) -> Self {
+        Self::from_bank(self.bank, self.ratio, zero_filled_fixed_ring::<T, R>())
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize, const R: usize> ResetMut for ArbitraryResamplerArray<T, N, R> where
+    Self: Reset
+{
+}
+
+impl<T, C, R> MultirateFilter<T> for ArbitraryResampler<T, C, R>
+where
+    T: Copy + Num + PartialOrd + ToPrimitive + NumCast,
+    C: AsSlice<T>,
+    R: RingBuffer<T>,
+{
+    type Output = T;
+
+    fn process(&mut self, input: &[T], output: &mut [Self::Output]) -> (usize, usize) {
+        let mut input_consumed = 0;
+        let mut output_produced = 0;
+
+        loop {
+            if self.state.phase >= T::one() {
+                if input_consumed == input.len() {
+                    break;
+                }
+
+                let _ = self.state.taps.push_back(input[input_consumed]);
+                input_consumed += 1;
+                self.state.phase = self.state.phase - T::one();
+                continue;
+            }
+
+            if output_produced == output.len() {
+                break;
+            }
+
+            output[output_produced] = self.evaluate(self.state.phase);
+            output_produced += 1;
+            self.state.phase = self.state.phase + self.step;
+        }
+
+        (input_consumed, output_produced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    #[cfg(all(feature = "alloc", any(feature = "libm", feature = "std")))]
+    use super::ArbitraryResamplerVec;
+    use super::{ArbitraryResamplerArray, BankConfig, Config};
+    use crate::traits::{MultirateFilter, Reset, WithConfig};
+
+    #[test]
+    fn integer_phases_match_interpolator_delayed_by_one_sample() {
+        use crate::filters::fir::polyphase::interpolator::PolyphaseInterpolatorArray;
+
+        let bank = BankConfig {
+            num_phases: 4,
+            taps_per_phase: 2,
+            coefficients: [1.0, 5.0, 2.0, 6.0, 3.0, 7.0, 4.0, 8.0],
+        };
+        let mut interpolator = PolyphaseInterpolatorArray::<f64, 8, 2>::with_config(bank.clone());
+        let mut resampler = ArbitraryResamplerArray::<f64, 8, 3>::with_config(Config {
+            num_phases: bank.num_phases,
+            taps_per_phase: bank.taps_per_phase,
+            coefficients: bank.coefficients,
+            ratio: 4.0,
+        });
+
+        let input = [1.0, -2.0, 3.0, 0.5, 4.0];
+        let mut expected = [0.0; 16];
+        let _ = interpolator.process(&input[..4], &mut expected);
+        let mut output = [0.0; 20];
+        assert_eq!(resampler.process(&input, &mut output), (5, 20));
+
+        assert_eq!(output[..4], [0.0; 4]);
+        assert_eq!(output[4..], expected);
+    }
+
+    #[test]
+    fn interpolates_between_adjacent_phases() {
+        // Two phases of a single tap each: phase outputs are x·1 and x·3, and
+        // the phase after the last is the next sample times 1.
+        let mut resampler = ArbitraryResamplerArray::<f64, 2, 2>::with_config(Config {
+            num_phases: 2,
+            taps_per_phase: 1,
+            coefficients: [1.0, 3.0],
+            ratio: 4.0,
+        });
+        let mut output = [0.0; 8];
+
+        assert_eq!(resampler.process(&[10.0, 20.0], &mut output), (2, 8));
+        // First interval uses the zero-padded sample before 10.
+        assert_eq!(output[..4], [0.0, 0.0, 0.0, 5.0]);
+        // μ = 0, 0.25, 0.5, 0.75 → phases 0, 0.5, 1, 1.5.
+        assert_eq!(output[4..], [10.0, 20.0, 30.0, 25.0]);
+    }
+
+    #[cfg(all(feature = "alloc", any(feature = "libm", feature = "std")))]
+    #[test]
+    fn resamples_sine_at_arbitrary_ratio() {
+        use alloc::vec::Vec;
+
+        use crate::filters::fir::design::windowed_sinc::kaiser;
+
+        const PHASES: u16 = 32;
+        let num_taps = 32 * 16;
+        let prototype: Vec<f64> = kaiser::lowpass_vec::<f64>(num_taps, 0.4 / f64::from(PHASES))
+            .into_iter()
+            .map(|h| h * f64::from(PHASES))
+            .collect();
+
+        let frequency = 0.03;
+        let input: Vec<f64> = (0_u32..2000)
+            .map(|n| (core::f64::consts::TAU * frequency * f64::from(n)).sin())
+            .collect();
+
+        let ratio = 48_000.0 / 44_100.0;
+        let mut resampler =
+            ArbitraryResamplerVec::from_prototype_taps(usize::from(PHASES), &prototype, ratio);
+        let mut output = alloc::vec![0.0; 2400];
+        let (_, produced) = resampler.process(&input, &mut output);
+
+        // One extra sample of latency plus the prototype's group delay.
+        let delay = 1.0
+            + (f64::from(u16::try_from(num_taps).expect("fits")) - 1.0) / 2.0 / f64::from(PHASES);
+        for (k, &y) in (0_u32..).zip(&output[..produced]).skip(100) {
+            let t = f64::from(k) / ratio - delay;
+            let expected = (core::f64::consts::TAU * frequency * t).sin();
+            assert_abs_diff_eq!(y, expected, epsilon = 2e-3);
+        }
+    }
+
+    #[test]
+    fn ratio_and_phase_are_queryable() {
+        let mut resampler = ArbitraryResamplerArray::<f64, 2, 2>::with_config(Config {
+            num_phases: 2,
+            taps_per_phase: 1,
+            coefficients: [1.0, 1.0],
+            ratio: 2.5,
+        });
+        let mut output = [0.0; 2];
+
+        assert_abs_diff_eq!(resampler.fractional_phase(), 1.0);
+        let _ = resampler.process(&[1.0], &mut output);
+        assert_abs_diff_eq!(resampler.fractional_phase(), 0.8);
+
+        resampler.set_ratio(0.5);
+        assert_abs_diff_eq!(resampler.ratio(), 0.5);
+        let _ = resampler.process(&[], &mut output);
+        assert_abs_diff_eq!(resampler.fractional_phase(), 2.8);
+    }
+
+    #[test]
+    fn reset_clears_delay_line() {
+        let config = Config {
+            num_phases: 2,
+            taps_per_phase: 1,
+            coefficients: [1.0, 3.0],
+            ratio: 4.0,
+        };
+        let mut resampler = ArbitraryResamplerArray::<f64, 2, 2>::with_config(config.clone());
+        let mut output = [0.0; 8];
+        let _ = resampler.process(&[10.0, 20.0], &mut output[..5]);
+
+        let mut resampler = resampler.reset();
+        let mut fresh = ArbitraryResamplerArray::<f64, 2, 2>::with_config(config);
+        let mut expected = [0.0; 8];
+        let _ = fresh.process(&[10.0, 20.0], &mut expected);
+        let _ = resampler.process(&[10.0, 20.0], &mut output);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "ArbitraryResampler: taps capacity must equal taps_per_phase + 1")]
+    fn rejects_short_delay_line() {
+        let _ = ArbitraryResamplerArray::<f64, 4, 2>::with_config(Config {
+            num_phases: 2,
+            taps_per_phase: 2,
+            coefficients: [1.0; 4],
+            ratio: 1.0,
+        });
+    }
+}
@@ -29,8 +29,17 @@
 //!   directly or when computing them from physics/control-system equations.
 //! - `dc_blocker`: convenience wrapper for the specific `H(z) = (1−z⁻¹)/(1−r·z⁻¹)` form.
 //! - `allpass`: phase manipulation without gain change; use in reverb networks and
-//!   crossover/all-pass EQ chains.
+//!   crossover/all-pass EQ chains. `allpass::SecondOrderAllpass` adds complex
+//!   poles for resonant phase responses.
+//! - `crossover`: Linkwitz-Riley (LR2/LR4/LR8) band splitting whose bands sum back to
+//!   a flat magnitude; use for multi-band processing.
+//! - `equalizer`: multi-band parametric EQ (peaking, shelf, pass and notch bands) with
+//!   per-band bypass and a combined magnitude response for display.
+//! - `one_euro`: adaptive low-pass whose cutoff rises with the signal's speed; use to
+//!   remove jitter from pointer, touch or tracking input without lagging fast movements.
 //! - `comb`: resonant delay-line filter; use in reverb, flanger, and chorus effects.
+//! - `phase_splitter`: allpass-pair approximation of an analytic signal (requires the
+//!   `complex` feature); a low-latency alternative to the FIR `fir::analytic` filter.
 //!
 //! # See also
 //!
@@ -48,8 +57,19 @@ pub mod envelope;
 
 pub mod exp;
 
+pub mod one_euro;
+
 pub mod allpass;
 
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod crossover;
+
+#[cfg(any(feature = "libm", feature = "std"))]
+pub mod equalizer;
+
+#[cfg(feature = "complex")]
+pub mod phase_splitter;
+
 pub mod comb;
 
 pub mod integrate;
@@ -17,6 +17,8 @@
 //! Difference equation: `y[n] = c·(x[n]−y[n−1]) + x[n−1]`
 //!
 //! When `c = 0`, the filter becomes a pure delay (output equals previous input).
+//!
+//! [`SecondOrderAllpass`] provides the second-order counterpart with complex poles.
 
 use num_traits::Num;
 
@@ -29,6 +31,10 @@ use crate::traits::{
 #[cfg(feature = "derive")]
 use crate::traits::ResetMut;
 
+pub mod second_order;
+
+pub use second_order::SecondOrderAllpass;
+
 /// The allpass filter's configuration.
 ///
 /// # Stability
@@ -0,0 +1,221 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Second-order allpass filter.
+//!
+//! Transfer function: `H(z) = (c2 + c1·z⁻¹ + z⁻²) / (1 + c1·z⁻¹ + c2·z⁻²)`
+//!
+//! Difference equation: `y[n] = c2·(x[n]−y[n−2]) + c1·(x[n−1]−y[n−1]) + x[n−2]`
+//!
+//! The numerator mirrors the denominator, so the magnitude response is flat while
+//! the phase turns by a full `2π` across the band. Unlike the first-order
+//! [`Allpass`](super::Allpass), its poles may be complex, which is required for
+//! resonant phase responses such as the sum of a Linkwitz-Riley crossover.
+//!
+//! When `c1 = c2 = 0`, the filter becomes a pure 2-sample delay.
+
+use num_traits::Num;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The second-order allpass filter's configuration.
+///
+/// # Stability
+///
+/// The filter is stable when `|c2| < 1` and `|c1| < 1 + c2`, the usual stability
+/// triangle of a biquad denominator `1 + c1·z⁻¹ + c2·z⁻²`.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// First-order coefficient (biquad `a1`).
+    pub c1: T,
+    /// Second-order coefficient (biquad `a2`), the squared pole radius.
+    pub c2: T,
+}
+
+/// The second-order allpass filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// Previous input samples, most recent first (x[n-1], x[n-2]).
+    pub prev_inputs: [T; 2],
+    /// Previous output samples, most recent first (y[n-1], y[n-2]).
+    pub prev_outputs: [T; 2],
+}
+
+/// A second-order allpass filter.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); two multiplies and four additions.
+/// - **Space:** O(1); stores two previous inputs and two previous outputs.
+#[derive(Clone, Debug)]
+pub struct SecondOrderAllpass<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+impl<T> ConfigTrait for SecondOrderAllpass<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for SecondOrderAllpass<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for SecondOrderAllpass<T>
+where
+    T: Clone + num_traits::Zero,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        let state = State {
+            prev_inputs: [T::zero(), T::zero()],
+            prev_outputs: [T::zero(), T::zero()],
+        };
+        Self { config, state }
+    }
+}
+
+impl<T> ConfigRef for SecondOrderAllpass<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for SecondOrderAllpass<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for SecondOrderAllpass<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for SecondOrderAllpass<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for SecondOrderAllpass<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for SecondOrderAllpass<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for SecondOrderAllpass<T>
+where
+    T: Clone + num_traits::Zero,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for SecondOrderAllpass<T> where Self: Reset {}
+
+impl<T> Filter<T> for SecondOrderAllpass<T>
+where
+    T: Clone + Num,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let [x1, x2] = self.state.prev_inputs.clone();
+        let [y1, y2] = self.state.prev_outputs.clone();
+
+        // y[n] = c2·(x[n]−y[n−2]) + c1·(x[n−1]−y[n−1]) + x[n−2]
+        let output = self.config.c2.clone() * (input.clone() - y2)
+            + self.config.c1.clone() * (x1.clone() - y1.clone())
+            + x2;
+
+        self.state.prev_inputs = [input, x1];
+        self.state.prev_outputs = [output.clone(), y1];
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    fn impulse_response(config: Config<f64>, len: usize) -> Vec<f64> {
+        let mut filter = SecondOrderAllpass::with_config(config);
+        (0..len)
+            .map(|n| filter.filter(if n == 0 { 1.0 } else { 0.0 }))
+            .collect()
+    }
+
+    #[test]
+    fn pure_delay_at_zero_coefficients() {
+        let output = impulse_response(Config { c1: 0.0, c2: 0.0 }, 5);
+        assert_eq!(output, [0.0, 0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn impulse_response_matches_difference_equation() {
+        let output = impulse_response(Config { c1: -0.5, c2: 0.25 }, 4);
+        // y[0] = c2 = 0.25
+        // y[1] = c1 − c1·y[0] = −0.5 + 0.125 = −0.375
+        // y[2] = −c2·y[0] − c1·y[1] + 1 = −0.0625 − 0.1875 + 1 = 0.75
+        // y[3] = −c2·y[1] − c1·y[2] = 0.09375 + 0.375 = 0.46875
+        let expected = [0.25, -0.375, 0.75, 0.46875];
+        assert_abs_diff_eq!(output.as_slice(), expected.as_slice(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn unit_magnitude_response() {
+        // Complex poles at radius 0.9, and a pair of real poles.
+        for (c1, c2) in [(-1.2_f64, 0.81), (0.6, 0.81), (-0.3, -0.4)] {
+            let response = impulse_response(Config { c1, c2 }, 4096);
+            for step in 1..20_u32 {
+                let omega = core::f64::consts::PI * f64::from(step) / 20.0;
+                let (re, im) =
+                    response
+                        .iter()
+                        .zip(0_u32..)
+                        .fold((0.0, 0.0), |(re, im), (&h, n)| {
+                            let phase = omega * f64::from(n);
+                            (re + h * phase.cos(), im - h * phase.sin())
+                        });
+                assert_abs_diff_eq!(re.hypot(im), 1.0, epsilon = 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter = SecondOrderAllpass::with_config(Config { c1: -0.5, c2: 0.25 });
+        let first = filter.filter(1.0);
+        filter.filter(2.0);
+        let mut filter = filter.reset();
+        assert_eq!(filter.state_mut().prev_inputs, [0.0, 0.0]);
+        assert_eq!(filter.state_mut().prev_outputs, [0.0, 0.0]);
+        assert_eq!(filter.filter(1.0), first);
+    }
+}
@@ -241,7 +241,7 @@ where
 pub use cascade::BiquadCascade;
 
 #[cfg(any(feature = "libm", feature = "std"))]
-pub use coefficients::Butterworth;
+pub use coefficients::{Butterworth, Cookbook};
 
 #[cfg(test)]
 mod tests;
@@ -276,5 +276,200 @@ impl Butterworth {
     }
 }
 
+/// Audio EQ Cookbook coefficient calculator with adjustable quality factor and gain.
+///
+/// Complements [`Butterworth`] with the cookbook's parametric designs: low-pass and
+/// high-pass filters of arbitrary `q`, peaking EQ, and low/high shelves.
+///
+/// All methods take `(sample_rate, frequency, q[, gain_db])` in that order. For
+/// shelves, `q = 1/√2` gives the steepest slope without overshoot.
+///
+/// # Panics
+///
+/// Every method panics if `T` cannot represent the constants `PI` or `2.0`; this is
+/// infallible for standard `f32` and `f64` types.
+///
+/// In debug builds, every method panics if `sample_rate <= 0`, `freq <= 0`,
+/// `freq >= sample_rate / 2`, or `q <= 0`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cookbook;
+
+impl Cookbook {
+    /// Compute low-pass coefficients with cutoff `freq` Hz and quality factor `q`.
+    ///
+    /// Returns `[b0, b1, b2, a1, a2]` normalized by `a0`. With `q = 1/√2` this
+    /// matches [`Butterworth::lowpass`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::iir::biquad::coefficients::Cookbook;
+    /// let coeffs = Cookbook::lowpass(44100.0, 1000.0, 2.0);
+    /// assert!(coeffs[0] > 0.0);
+    /// ```
+    pub fn lowpass<T: Float>(sample_rate: T, freq: T, q: T) -> [T; 5] {
+        let (cos_omega, alpha) = cookbook_terms(sample_rate, freq, q);
+        let two = T::one() + T::one();
+
+        let one_minus_cos = T::one() - cos_omega;
+        normalize([
+            one_minus_cos / two,
+            one_minus_cos,
+            one_minus_cos / two,
+            T::one() + alpha,
+            -two * cos_omega,
+            T::one() - alpha,
+        ])
+    }
+
+    /// Compute high-pass coefficients with cutoff `freq` Hz and quality factor `q`.
+    ///
+    /// Returns `[b0, b1, b2, a1, a2]` normalized by `a0`. With `q = 1/√2` this
+    /// matches [`Butterworth::highpass`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::iir::biquad::coefficients::Cookbook;
+    /// let coeffs = Cookbook::highpass(44100.0, 1000.0, 2.0);
+    /// assert!(coeffs[0] > 0.0);
+    /// ```
+    pub fn highpass<T: Float>(sample_rate: T, freq: T, q: T) -> [T; 5] {
+        let (cos_omega, alpha) = cookbook_terms(sample_rate, freq, q);
+        let two = T::one() + T::one();
+
+        let one_plus_cos = T::one() + cos_omega;
+        normalize([
+            one_plus_cos / two,
+            -one_plus_cos,
+            one_plus_cos / two,
+            T::one() + alpha,
+            -two * cos_omega,
+            T::one() - alpha,
+        ])
+    }
+
+    /// Compute peaking EQ coefficients: a boost or cut of `gain_db` centered on
+    /// `center` Hz with bandwidth set by `q`.
+    ///
+    /// Returns `[b0, b1, b2, a1, a2]` normalized by `a0`. The gain is exactly
+    /// `gain_db` at `center` and unity at DC and Nyquist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::iir::biquad::coefficients::Cookbook;
+    /// let [b0, b1, b2, a1, a2] = Cookbook::peaking(44100.0, 1000.0, 1.0, 6.0);
+    /// let dc_gain = (b0 + b1 + b2) / (1.0 + a1 + a2);
+    /// assert!((dc_gain - 1.0_f64).abs() < 1e-12);
+    /// ```
+    pub fn peaking<T: Float>(sample_rate: T, center: T, q: T, gain_db: T) -> [T; 5] {
+        let (cos_omega, alpha) = cookbook_terms(sample_rate, center, q);
+        let two = T::one() + T::one();
+        let amplitude = shelf_amplitude(gain_db);
+
+        normalize([
+            T::one() + alpha * amplitude,
+            -two * cos_omega,
+            T::one() - alpha * amplitude,
+            T::one() + alpha / amplitude,
+            -two * cos_omega,
+            T::one() - alpha / amplitude,
+        ])
+    }
+
+    /// Compute low-shelf coefficients: a gain of `gain_db` below `freq` Hz and unity
+    /// gain above it.
+    ///
+    /// Returns `[b0, b1, b2, a1, a2]` normalized by `a0`. The gain at `freq` is
+    /// `gain_db / 2`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::iir::biquad::coefficients::Cookbook;
+    /// let [b0, b1, b2, a1, a2] = Cookbook::low_shelf(44100.0, 200.0, 0.707, 6.0);
+    /// let dc_gain = (b0 + b1 + b2) / (1.0 + a1 + a2);
+    /// assert!((dc_gain - 10.0_f64.powf(6.0 / 20.0)).abs() < 1e-9);
+    /// ```
+    pub fn low_shelf<T: Float>(sample_rate: T, freq: T, q: T, gain_db: T) -> [T; 5] {
+        let (cos_omega, alpha) = cookbook_terms(sample_rate, freq, q);
+        let two = T::one() + T::one();
+        let amplitude = shelf_amplitude(gain_db);
+        let plus = amplitude + T::one();
+        let minus = amplitude - T::one();
+        let root_alpha = two * amplitude.sqrt() * alpha;
+
+        normalize([
+            amplitude * (plus - minus * cos_omega + root_alpha),
+            two * amplitude * (minus - plus * cos_omega),
+            amplitude * (plus - minus * cos_omega - root_alpha),
+            plus + minus * cos_omega + root_alpha,
+            -two * (minus + plus * cos_omega),
+            plus + minus * cos_omega - root_alpha,
+        ])
+    }
+
+    /// Compute high-shelf coefficients: a gain of `gain_db` above `freq` Hz and unity
+    /// gain below it.
+    ///
+    /// Returns `[b0, b1, b2, a1, a2]` normalized by `a0`. The gain at `freq` is
+    /// `gain_db / 2`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::iir::biquad::coefficients::Cookbook;
+    /// let [b0, b1, b2, a1, a2] = Cookbook::high_shelf(44100.0, 5000.0, 0.707, -6.0);
+    /// let dc_gain = (b0 + b1 + b2) / (1.0 + a1 + a2);
+    /// assert!((dc_gain - 1.0_f64).abs() < 1e-12);
+    /// ```
+    pub fn high_shelf<T: Float>(sample_rate: T, freq: T, q: T, gain_db: T) -> [T; 5] {
+        let (cos_omega, alpha) = cookbook_terms(sample_rate, freq, q);
+        let two = T::one() + T::one();
+        let amplitude = shelf_amplitude(gain_db);
+        let plus = amplitude + T::one();
+        let minus = amplitude - T::one();
+        let root_alpha = two * amplitude.sqrt() * alpha;
+
+        normalize([
+            amplitude * (plus + minus * cos_omega + root_alpha),
+            -two * amplitude * (minus + plus * cos_omega),
+            amplitude * (plus + minus * cos_omega - root_alpha),
+            plus - minus * cos_omega + root_alpha,
+            two * (minus - plus * cos_omega),
+            plus - minus * cos_omega - root_alpha,
+        ])
+    }
+}
+
+/// Returns `(cos ω, α)` for the cookbook designs, with `ω = 2π·freq / sample_rate`
+/// and `α = sin ω / (2q)`.
+fn cookbook_terms<T: Float>(sample_rate: T, freq: T, q: T) -> (T, T) {
+    let (pi, two, _) = float_constants::<T>();
+    debug_assert!(sample_rate > T::zero(), "sample_rate must be positive");
+    debug_assert!(freq > T::zero(), "freq must be positive");
+    debug_assert!(
+        freq < sample_rate / two,
+        "freq must be below Nyquist (sample_rate / 2)"
+    );
+    debug_assert!(q > T::zero(), "q must be positive");
+
+    let omega = two * pi * freq / sample_rate;
+    (omega.cos(), omega.sin() / (two * q))
+}
+
+/// Returns the cookbook's `A = 10^(gain_db / 40)`.
+fn shelf_amplitude<T: Float>(gain_db: T) -> T {
+    let forty = T::from(40.0).expect("40 is representable");
+    let ten = T::from(10.0).expect("10 is representable");
+    ten.powf(gain_db / forty)
+}
+
+/// Normalizes `[b0, b1, b2, a0, a1, a2]` to `[b0, b1, b2, a1, a2]` by `a0`.
+fn normalize<T: Float>([b0, b1, b2, a0, a1, a2]: [T; 6]) -> [T; 5] {
+    [b0 / a0, b1 / a0, b2 / a0, a1 / a0, a2 / a0]
+}
+
 #[cfg(test)]
 mod tests;
@@ -441,3 +441,77 @@ fn test_bandstop_3db_bandwidth() {
         assert_abs_diff_eq!(gain, 1.0 / 2.0f64.sqrt(), epsilon = 3e-2);
     }
 }
+
+/// Evaluates `|H(e^{jω})|` of a normalized biquad at `freq` Hz.
+fn gain_at(coeffs: [f64; 5], sample_rate: f64, freq: f64) -> f64 {
+    let [b0, b1, b2, a1, a2] = coeffs;
+    let omega = 2.0 * core::f64::consts::PI * freq / sample_rate;
+    let (s1, c1) = omega.sin_cos();
+    let (s2, c2) = (2.0 * omega).sin_cos();
+    let num = (b0 + b1 * c1 + b2 * c2).hypot(b1 * s1 + b2 * s2);
+    let den = (1.0 + a1 * c1 + a2 * c2).hypot(a1 * s1 + a2 * s2);
+    num / den
+}
+
+#[test]
+fn test_cookbook_lowpass_highpass_match_butterworth() {
+    let q = core::f64::consts::FRAC_1_SQRT_2;
+    let lowpass = Cookbook::lowpass(44100.0, 1000.0, q);
+    let highpass = Cookbook::highpass(44100.0, 1000.0, q);
+    for (cookbook, butterworth) in lowpass
+        .iter()
+        .zip(Butterworth::lowpass(44100.0, 1000.0).iter())
+        .chain(
+            highpass
+                .iter()
+                .zip(Butterworth::highpass(44100.0, 1000.0).iter()),
+        )
+    {
+        assert_abs_diff_eq!(cookbook, butterworth, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_cookbook_resonant_lowpass_peaks_at_q() {
+    // At the cutoff, a cookbook low-pass has gain `q`.
+    let coeffs = Cookbook::lowpass(48000.0, 1000.0, 4.0);
+    assert_abs_diff_eq!(gain_at(coeffs, 48000.0, 1000.0), 4.0, epsilon = 1e-9);
+    assert_abs_diff_eq!(gain_at(coeffs, 48000.0, 0.0), 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_cookbook_peaking_gain_at_center() {
+    let boost = Cookbook::peaking(48000.0, 2000.0, 1.5, 9.0);
+    let cut = Cookbook::peaking(48000.0, 2000.0, 1.5, -9.0);
+    let amplitude = 10.0f64.powf(9.0 / 20.0);
+    assert_abs_diff_eq!(gain_at(boost, 48000.0, 2000.0), amplitude, epsilon = 1e-9);
+    assert_abs_diff_eq!(
+        gain_at(cut, 48000.0, 2000.0),
+        1.0 / amplitude,
+        epsilon = 1e-9
+    );
+    assert_abs_diff_eq!(gain_at(boost, 48000.0, 0.0), 1.0, epsilon = 1e-12);
+    assert_abs_diff_eq!(gain_at(boost, 48000.0, 24000.0), 1.0, epsilon = 1e-9);
+}
+
+#[test]
+fn test_cookbook_shelves() {
+    let amplitude = 10.0f64.powf(6.0 / 20.0);
+    let low = Cookbook::low_shelf(48000.0, 300.0, core::f64::consts::FRAC_1_SQRT_2, 6.0);
+    assert_abs_diff_eq!(gain_at(low, 48000.0, 0.0), amplitude, epsilon = 1e-9);
+    assert_abs_diff_eq!(gain_at(low, 48000.0, 24000.0), 1.0, epsilon = 1e-9);
+    assert_abs_diff_eq!(
+        gain_at(low, 48000.0, 300.0),
+        amplitude.sqrt(),
+        epsilon = 1e-9
+    );
+
+    let high = Cookbook::high_shelf(48000.0, 3000.0, core::f64::consts::FRAC_1_SQRT_2, 6.0);
+    assert_abs_diff_eq!(gain_at(high, 48000.0, 0.0), 1.0, epsilon = 1e-9);
+    assert_abs_diff_eq!(gain_at(high, 48000.0, 24000.0), amplitude, epsilon = 1e-9);
+    assert_abs_diff_eq!(
+        gain_at(high, 48000.0, 3000.0),
+        amplitude.sqrt(),
+        epsilon = 1e-9
+    );
+}
@@ -0,0 +1,757 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Linkwitz-Riley crossovers for phase-coherent band splitting.
+//!
+//! A Linkwitz-Riley crossover of order `2n` squares an order-`n` Butterworth
+//! low-pass and high-pass. Both outputs are −6 dB at the crossover frequency,
+//! share the same phase response at every frequency, and sum to an allpass:
+//! recombining the bands leaves the magnitude flat.
+//!
+//! | Design | Slope        | Sections per path | Allpass sum order |
+//! | ------ | ------------ | ----------------- | ----------------- |
+//! | LR2    | 12 dB/octave | 1                 | 1                 |
+//! | LR4    | 24 dB/octave | 2                 | 2                 |
+//! | LR8    | 48 dB/octave | 4                 | 4                 |
+//!
+//! The LR2 high-pass is designed with inverted polarity, so that for every order
+//! the bands sum (rather than subtract) to an allpass.
+//!
+//! [`LinkwitzRiley`] designs the [`BiquadCascade`](super::biquad::BiquadCascade)
+//! configurations of a single two-way [`Split`], along with the
+//! [`allpass`](super::allpass) filters equal to their sum. [`Crossover`] chains `B − 1`
+//! splits into a `B`-band filter bank whose output is an array of bands.
+//!
+//! # Phase compensation
+//!
+//! Splitting the high output of one crossover again would leave the lower bands
+//! without the phase shift of the later splits, and the bands would no longer sum
+//! flat. [`Crossover`] therefore passes every band through the allpass of each
+//! split above it (see [`Split::allpass`]), so that the sum of all bands is the
+//! product of every split's allpass. Only the `C·(C − 1) / 2` (band, higher split)
+//! pairs are stored.
+
+use core::fmt;
+
+use num_traits::Float;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::allpass::{
+    second_order::Config as SecondOrderAllpassConfig, Allpass, Config as AllpassConfig,
+    SecondOrderAllpass,
+};
+use super::biquad::cascade::{BiquadCascadeArray, Config as CascadeConfig};
+use super::biquad::Config as BiquadConfig;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The coefficients of a single two-way Linkwitz-Riley crossover.
+///
+/// `S` is the number of biquad sections per path, and the phase-compensating
+/// allpass is a cascade of `A` allpass filters of type `F`. Designed by
+/// [`LinkwitzRiley`].
+#[derive(Clone, Debug)]
+pub struct Split<T, F, const S: usize, const A: usize>
+where
+    F: ConfigTrait,
+{
+    /// The low-pass path.
+    pub lowpass: CascadeConfig<T, [BiquadConfig<T>; S]>,
+    /// The high-pass path.
+    pub highpass: CascadeConfig<T, [BiquadConfig<T>; S]>,
+    /// The allpass equal to the sum of both paths.
+    ///
+    /// Bands split off below this crossover pass through it to stay in phase
+    /// with the bands it produces. For LR2 it is a first-order [`Allpass`], and
+    /// one [`SecondOrderAllpass`] per Butterworth pole pair otherwise.
+    pub allpass: [F::Config; A],
+}
+
+/// A two-way LR2 split: 12 dB/octave, one section per path.
+pub type Lr2Split<T> = Split<T, Allpass<T>, 1, 1>;
+
+/// A two-way LR4 split: 24 dB/octave, two sections per path.
+pub type Lr4Split<T> = Split<T, SecondOrderAllpass<T>, 2, 1>;
+
+/// A two-way LR8 split: 48 dB/octave, four sections per path.
+pub type Lr8Split<T> = Split<T, SecondOrderAllpass<T>, 4, 2>;
+
+/// Linkwitz-Riley crossover designer.
+///
+/// All methods take `(sample_rate, freq)` in Hz and use the bilinear transform
+/// with frequency pre-warping, so the −6 dB point lands exactly on `freq`.
+///
+/// # Panics
+///
+/// In debug builds, every method panics if `sample_rate <= 0`, `freq <= 0`, or
+/// `freq >= sample_rate / 2`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LinkwitzRiley;
+
+impl LinkwitzRiley {
+    /// Designs a second-order (LR2) split.
+    ///
+    /// Each path is a squared first-order Butterworth filter. The high-pass
+    /// has inverted polarity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::iir::crossover::LinkwitzRiley;
+    /// let split = LinkwitzRiley::lr2(48000.0, 1000.0);
+    /// assert_eq!(split.lowpass.sections.len(), 1);
+    /// ```
+    pub fn lr2<T: Float>(sample_rate: T, freq: T) -> Lr2Split<T> {
+        let k = prewarp(sample_rate, freq);
+        let two = T::one() + T::one();
+        // First-order sections: LP = g·(1 + z⁻¹) / (1 + a·z⁻¹), HP = h·(1 − z⁻¹) / (1 + a·z⁻¹).
+        let a = (k - T::one()) / (k + T::one());
+        let g = k / (k + T::one());
+        let h = T::one() / (k + T::one());
+        let g2 = g * g;
+        let h2 = h * h;
+        Split {
+            lowpass: [[g2, two * g2, g2, two * a, a * a]].into(),
+            highpass: [[-h2, two * h2, -h2, two * a, a * a]].into(),
+            allpass: [AllpassConfig { c: a }],
+        }
+    }
+
+    /// Designs a fourth-order (LR4) split.
+    ///
+    /// Each path is a squared second-order Butterworth filter.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::iir::crossover::LinkwitzRiley;
+    /// let split = LinkwitzRiley::lr4(48000.0, 1000.0);
+    /// assert_eq!(split.lowpass.sections.len(), 2);
+    /// ```
+    pub fn lr4<T: Float>(sample_rate: T, freq: T) -> Lr4Split<T> {
+        let k = prewarp(sample_rate, freq);
+        let q = butterworth_q(2, 0);
+        Split {
+            lowpass: [lowpass(k, q), lowpass(k, q)].into(),
+            highpass: [highpass(k, q), highpass(k, q)].into(),
+            allpass: [allpass(k, q)],
+        }
+    }
+
+    /// Designs an eighth-order (LR8) split.
+    ///
+    /// Each path is a squared fourth-order Butterworth filter.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use signalo::filters::iir::crossover::LinkwitzRiley;
+    /// let split = LinkwitzRiley::lr8(48000.0, 1000.0);
+    /// assert_eq!(split.lowpass.sections.len(), 4);
+    /// ```
+    pub fn lr8<T: Float>(sample_rate: T, freq: T) -> Lr8Split<T> {
+        let k = prewarp(sample_rate, freq);
+        let q0 = butterworth_q(4, 0);
+        let q1 = butterworth_q(4, 1);
+        Split {
+            lowpass: [
+                lowpass(k, q0),
+                lowpass(k, q1),
+                lowpass(k, q0),
+                lowpass(k, q1),
+            ]
+            .into(),
+            highpass: [
+                highpass(k, q0),
+                highpass(k, q1),
+                highpass(k, q0),
+                highpass(k, q1),
+            ]
+            .into(),
+            allpass: [allpass(k, q0), allpass(k, q1)],
+        }
+    }
+}
+
+/// Returns the pre-warped analog frequency `K = tan(π·freq / sample_rate)`.
+fn prewarp<T: Float>(sample_rate: T, freq: T) -> T {
+    let two = T::one() + T::one();
+    debug_assert!(sample_rate > T::zero(), "sample_rate must be positive");
+    debug_assert!(freq > T::zero(), "freq must be positive");
+    debug_assert!(
+        freq < sample_rate / two,
+        "freq must be below Nyquist (sample_rate / 2)"
+    );
+    let pi = T::from(core::f64::consts::PI).expect("π is representable");
+    (pi * freq / sample_rate).tan()
+}
+
+/// Returns the quality factor of the `index`-th pole pair of an even-order
+/// Butterworth filter.
+fn butterworth_q<T: Float>(order: u32, index: u32) -> T {
+    let pi = T::from(core::f64::consts::PI).expect("π is representable");
+    let two = T::one() + T::one();
+    let order = T::from(order).expect("order is representable");
+    let index = T::from(index).expect("index is representable");
+    let angle = pi * (two * index + T::one()) / (two * order);
+    T::one() / (two * angle.cos())
+}
+
+/// Returns the denominator `[a1, a2]` and normalization `1 / a0` of a
+/// second-order section with pre-warped frequency `k` and quality factor `q`.
+fn denominator<T: Float>(k: T, q: T) -> (T, T, T) {
+    let two = T::one() + T::one();
+    let k2 = k * k;
+    let norm = T::one() / (T::one() + k / q + k2);
+    let a1 = two * (k2 - T::one()) * norm;
+    let a2 = (T::one() - k / q + k2) * norm;
+    (a1, a2, norm)
+}
+
+fn lowpass<T: Float>(k: T, q: T) -> [T; 5] {
+    let (a1, a2, norm) = denominator(k, q);
+    let b0 = k * k * norm;
+    [b0, b0 + b0, b0, a1, a2]
+}
+
+fn highpass<T: Float>(k: T, q: T) -> [T; 5] {
+    let (a1, a2, norm) = denominator(k, q);
+    [norm, -(norm + norm), norm, a1, a2]
+}
+
+fn allpass<T: Float>(k: T, q: T) -> SecondOrderAllpassConfig<T> {
+    let (c1, c2, _) = denominator(k, q);
+    SecondOrderAllpassConfig { c1, c2 }
+}
+
+/// The crossover's configuration: the `C` splits, in ascending frequency order.
+pub struct Config<T, F, const C: usize, const S: usize, const A: usize>
+where
+    F: ConfigTrait,
+{
+    /// The splits between adjacent bands, lowest frequency first.
+    pub splits: [Split<T, F, S, A>; C],
+}
+
+impl<T, F, const C: usize, const S: usize, const A: usize> Clone for Config<T, F, C, S, A>
+where
+    F: ConfigTrait,
+    Split<T, F, S, A>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            splits: self.splits.clone(),
+        }
+    }
+}
+
+impl<T, F, const C: usize, const S: usize, const A: usize> fmt::Debug for Config<T, F, C, S, A>
+where
+    F: ConfigTrait,
+    Split<T, F, S, A>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("splits", &self.splits)
+            .finish()
+    }
+}
+
+/// The crossover's state.
+#[derive(Clone, Debug)]
+pub struct State<T, F, const C: usize, const S: usize, const M: usize, const A: usize> {
+    /// The low-pass path of each split.
+    pub lowpass: [BiquadCascadeArray<T, S>; C],
+    /// The high-pass path of each split.
+    pub highpass: [BiquadCascadeArray<T, S>; C],
+    /// The compensating allpasses of each (band, higher split) pair, ordered by
+    /// band and then by split: `(0, 1), …, (0, C − 1), (1, 2), …, (C − 2, C − 1)`.
+    pub allpass: [[F; A]; M],
+}
+
+/// A `B`-band Linkwitz-Riley crossover filter bank.
+///
+/// Splits each input sample into `[T; B]` bands, lowest frequency first, using
+/// `C = B − 1` [`Split`]s in series: band `k` is the low-pass output of split `k`
+/// applied to the high-pass output of all splits below it. Every band but the top
+/// one passes through the allpass of each split above it, so the bands sum to an
+/// allpass response with a flat magnitude.
+///
+/// `S` is the number of biquad sections per path, `F` the allpass filter type and
+/// `A` the number of allpass filters per split. `M = C·(C − 1) / 2` is the number
+/// of (band, higher split) pairs needing compensation. Use the [`Lr2Crossover`],
+/// [`Lr4Crossover`] and [`Lr8Crossover`] aliases rather than spelling them out.
+///
+/// `B` must be `C + 1` and `M` must be `C·(C − 1) / 2`; other combinations are
+/// rejected at compile time via [`WithConfig`].
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(C·S + C²·A); two `S`-section cascades per split plus
+///   `C·(C − 1) / 2` compensating allpasses.
+/// - **Space:** O(C·S + C²·A).
+pub struct Crossover<
+    T,
+    F,
+    const B: usize,
+    const C: usize,
+    const S: usize,
+    const M: usize,
+    const A: usize,
+> where
+    F: ConfigTrait,
+{
+    config: Config<T, F, C, S, A>,
+    state: State<T, F, C, S, M, A>,
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> Clone
+    for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+    Config<T, F, C, S, A>: Clone,
+    State<T, F, C, S, M, A>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize>
+    fmt::Debug for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+    Config<T, F, C, S, A>: fmt::Debug,
+    State<T, F, C, S, M, A>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Crossover")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// A `B`-band [`Crossover`] with `C = B − 1` LR2 splits and `M = C·(C − 1) / 2`.
+pub type Lr2Crossover<T, const B: usize, const C: usize, const M: usize> =
+    Crossover<T, Allpass<T>, B, C, 1, M, 1>;
+
+/// A `B`-band [`Crossover`] with `C = B − 1` LR4 splits and `M = C·(C − 1) / 2`.
+pub type Lr4Crossover<T, const B: usize, const C: usize, const M: usize> =
+    Crossover<T, SecondOrderAllpass<T>, B, C, 2, M, 1>;
+
+/// A `B`-band [`Crossover`] with `C = B − 1` LR8 splits and `M = C·(C − 1) / 2`.
+pub type Lr8Crossover<T, const B: usize, const C: usize, const M: usize> =
+    Crossover<T, SecondOrderAllpass<T>, B, C, 4, M, 2>;
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize>
+    ConfigTrait for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+{
+    type Config = Config<T, F, C, S, A>;
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize>
+    StateTrait for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+{
+    type State = State<T, F, C, S, M, A>;
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize>
+    WithConfig for Crossover<T, F, B, C, S, M, A>
+where
+    T: Float,
+    F: WithConfig<Output = F>,
+    F::Config: Clone,
+{
+    type Output = Self;
+
+    /// Creates a crossover with cleared filter state.
+    fn with_config(config: Self::Config) -> Self::Output {
+        const {
+            assert!(B == C + 1, "Crossover: B bands require C = B - 1 splits");
+            assert!(
+                M == C * C.saturating_sub(1) / 2,
+                "Crossover: C splits require M = C * (C - 1) / 2 allpasses"
+            );
+        };
+        let mut splits = (0..C).flat_map(|k| (k + 1)..C);
+        let allpass = core::array::from_fn(|_| {
+            let split = &config.splits[splits.next().expect("M matches the pair count")];
+            core::array::from_fn(|i| F::with_config(split.allpass[i].clone()))
+        });
+        let state = State {
+            lowpass: core::array::from_fn(|j| {
+                BiquadCascadeArray::with_config(config.splits[j].lowpass.clone())
+            }),
+            highpass: core::array::from_fn(|j| {
+                BiquadCascadeArray::with_config(config.splits[j].highpass.clone())
+            }),
+            allpass,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> ConfigRef
+    for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+{
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize>
+    ConfigClone for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+    Config<T, F, C, S, A>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> StateMut
+    for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+{
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> HasGuts
+    for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+{
+    type Guts = (Config<T, F, C, S, A>, State<T, F, C, S, M, A>);
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> FromGuts
+    for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+{
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> IntoGuts
+    for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+{
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> Reset
+    for Crossover<T, F, B, C, S, M, A>
+where
+    T: Float,
+    F: WithConfig<Output = F>,
+    F::Config: Clone,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> ResetMut
+    for Crossover<T, F, B, C, S, M, A>
+where
+    F: ConfigTrait,
+    Self: Reset,
+{
+}
+
+impl<T, F, const B: usize, const C: usize, const S: usize, const M: usize, const A: usize> Filter<T>
+    for Crossover<T, F, B, C, S, M, A>
+where
+    T: Float,
+    F: ConfigTrait + Filter<T, Output = T>,
+{
+    type Output = [T; B];
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let mut bands = [T::zero(); B];
+        let mut rest = input;
+        let mut pairs = self.state.allpass.iter_mut();
+        for (k, band) in bands.iter_mut().take(C).enumerate() {
+            let mut low = self.state.lowpass[k].filter(rest);
+            for allpass in pairs.by_ref().take(C - 1 - k).flatten() {
+                low = allpass.filter(low);
+            }
+            *band = low;
+            rest = self.state.highpass[k].filter(rest);
+        }
+        bands[C] = rest;
+        bands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 1000.0;
+
+    /// Returns the impulse responses of every band.
+    fn impulse_responses<
+        F,
+        const B: usize,
+        const C: usize,
+        const S: usize,
+        const M: usize,
+        const A: usize,
+    >(
+        crossover: &mut Crossover<f64, F, B, C, S, M, A>,
+        len: usize,
+    ) -> Vec<[f64; B]>
+    where
+        F: ConfigTrait + Filter<f64, Output = f64>,
+    {
+        (0..len)
+            .map(|n| crossover.filter(if n == 0 { 1.0 } else { 0.0 }))
+            .collect()
+    }
+
+    /// Returns the magnitude of the DTFT of `response` at `freq` Hz.
+    fn magnitude(response: &[f64], freq: f64) -> f64 {
+        let omega = 2.0 * core::f64::consts::PI * freq / SAMPLE_RATE;
+        let (re, im) = response
+            .iter()
+            .zip(0_u32..)
+            .fold((0.0, 0.0), |(re, im), (&h, n)| {
+                let phase = omega * f64::from(n);
+                (re + h * phase.cos(), im - h * phase.sin())
+            });
+        re.hypot(im)
+    }
+
+    fn assert_flat_sum<
+        F,
+        const B: usize,
+        const C: usize,
+        const S: usize,
+        const M: usize,
+        const A: usize,
+    >(
+        mut crossover: Crossover<f64, F, B, C, S, M, A>,
+    ) where
+        F: ConfigTrait + Filter<f64, Output = f64>,
+    {
+        let responses = impulse_responses(&mut crossover, 4096);
+        let sum: Vec<f64> = responses.iter().map(|bands| bands.iter().sum()).collect();
+        for step in 1..50_u32 {
+            let freq = f64::from(step) * 10.0;
+            assert_abs_diff_eq!(magnitude(&sum, freq), 1.0, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn lr2_bands_sum_flat() {
+        let crossover = Lr2Crossover::<f64, 4, 3, 3>::with_config(Config {
+            splits: [
+                LinkwitzRiley::lr2(SAMPLE_RATE, 50.0),
+                LinkwitzRiley::lr2(SAMPLE_RATE, 150.0),
+                LinkwitzRiley::lr2(SAMPLE_RATE, 300.0),
+            ],
+        });
+        assert_flat_sum(crossover);
+    }
+
+    #[test]
+    fn lr4_bands_sum_flat() {
+        let crossover = Lr4Crossover::<f64, 4, 3, 3>::with_config(Config {
+            splits: [
+                LinkwitzRiley::lr4(SAMPLE_RATE, 50.0),
+                LinkwitzRiley::lr4(SAMPLE_RATE, 150.0),
+                LinkwitzRiley::lr4(SAMPLE_RATE, 300.0),
+            ],
+        });
+        assert_flat_sum(crossover);
+    }
+
+    #[test]
+    fn lr8_bands_sum_flat() {
+        let crossover = Lr8Crossover::<f64, 3, 2, 1>::with_config(Config {
+            splits: [
+                LinkwitzRiley::lr8(SAMPLE_RATE, 80.0),
+                LinkwitzRiley::lr8(SAMPLE_RATE, 250.0),
+            ],
+        });
+        assert_flat_sum(crossover);
+    }
+
+    #[test]
+    fn uncompensated_bands_do_not_sum_flat() {
+        let mut crossover = Lr4Crossover::<f64, 3, 2, 1>::with_config(Config {
+            splits: [
+                LinkwitzRiley::lr4(SAMPLE_RATE, 100.0),
+                LinkwitzRiley::lr4(SAMPLE_RATE, 200.0),
+            ],
+        });
+        let responses = impulse_responses(&mut crossover, 4096);
+        let low: Vec<f64> = responses.iter().map(|bands| bands[0]).collect();
+        // Sum the upper bands with band 0 taken before its compensating allpass.
+        let mut split =
+            BiquadCascadeArray::with_config(LinkwitzRiley::lr4(SAMPLE_RATE, 100.0).lowpass);
+        let raw_low: Vec<f64> = (0..4096)
+            .map(|n| split.filter(if n == 0 { 1.0 } else { 0.0 }))
+            .collect();
+        let raw_sum: Vec<f64> = responses
+            .iter()
+            .zip(&raw_low)
+            .map(|(bands, &raw)| raw + bands[1] + bands[2])
+            .collect();
+        let max_error = (1..50_u32)
+            .map(|step| (magnitude(&raw_sum, f64::from(step) * 10.0) - 1.0).abs())
+            .fold(0.0, f64::max);
+        assert!(max_error > 0.1);
+        // Compensation leaves the low band's magnitude untouched.
+        assert_abs_diff_eq!(
+            magnitude(&low, 100.0),
+            magnitude(&raw_low, 100.0),
+            epsilon = 1e-9
+        );
+    }
+
+    fn band(responses: &[[f64; 2]], band: usize) -> Vec<f64> {
+        responses.iter().map(|bands| bands[band]).collect()
+    }
+
+    #[test]
+    fn bands_are_six_db_down_at_crossover() {
+        let mut lr2 = Lr2Crossover::<f64, 2, 1, 0>::with_config(Config {
+            splits: [LinkwitzRiley::lr2(SAMPLE_RATE, 120.0)],
+        });
+        let mut lr4 = Lr4Crossover::<f64, 2, 1, 0>::with_config(Config {
+            splits: [LinkwitzRiley::lr4(SAMPLE_RATE, 120.0)],
+        });
+        let mut lr8 = Lr8Crossover::<f64, 2, 1, 0>::with_config(Config {
+            splits: [LinkwitzRiley::lr8(SAMPLE_RATE, 120.0)],
+        });
+        for responses in [
+            impulse_responses(&mut lr2, 4096),
+            impulse_responses(&mut lr4, 4096),
+            impulse_responses(&mut lr8, 4096),
+        ] {
+            assert_abs_diff_eq!(magnitude(&band(&responses, 0), 120.0), 0.5, epsilon = 1e-9);
+            assert_abs_diff_eq!(magnitude(&band(&responses, 1), 120.0), 0.5, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn slopes_match_order() {
+        let mut lr2 = Lr2Crossover::<f64, 2, 1, 0>::with_config(Config {
+            splits: [LinkwitzRiley::lr2(SAMPLE_RATE, 2.5)],
+        });
+        let mut lr4 = Lr4Crossover::<f64, 2, 1, 0>::with_config(Config {
+            splits: [LinkwitzRiley::lr4(SAMPLE_RATE, 2.5)],
+        });
+        let mut lr8 = Lr8Crossover::<f64, 2, 1, 0>::with_config(Config {
+            splits: [LinkwitzRiley::lr8(SAMPLE_RATE, 2.5)],
+        });
+        for (responses, slope_db) in [
+            (impulse_responses(&mut lr2, 8192), 12.0),
+            (impulse_responses(&mut lr4, 8192), 24.0),
+            (impulse_responses(&mut lr8, 8192), 48.0),
+        ] {
+            let low = band(&responses, 0);
+            // Measured far below Nyquist, where bilinear frequency warping is negligible.
+            let octave_db = 20.0 * (magnitude(&low, 20.0) / magnitude(&low, 40.0)).log10();
+            assert_abs_diff_eq!(octave_db, slope_db, epsilon = 1.0);
+        }
+    }
+
+    /// Returns the impulse response of a split's two paths summed, and of its allpass.
+    fn path_sum_and_allpass<F, const S: usize, const A: usize>(
+        split: Split<f64, F, S, A>,
+    ) -> (Vec<f64>, Vec<f64>)
+    where
+        F: WithConfig<Output = F> + Filter<f64, Output = f64>,
+    {
+        let mut lowpass = BiquadCascadeArray::with_config(split.lowpass);
+        let mut highpass = BiquadCascadeArray::with_config(split.highpass);
+        let mut allpass = split.allpass.map(F::with_config);
+        (0..64)
+            .map(|n| {
+                let input = if n == 0 { 1.0 } else { 0.0 };
+                let sum = lowpass.filter(input) + highpass.filter(input);
+                let phase = allpass.iter_mut().fold(input, |x, f| f.filter(x));
+                (sum, phase)
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn allpass_equals_sum_of_paths() {
+        for (sum, allpass) in [
+            path_sum_and_allpass(LinkwitzRiley::lr2(SAMPLE_RATE, 100.0)),
+            path_sum_and_allpass(LinkwitzRiley::lr4(SAMPLE_RATE, 100.0)),
+            path_sum_and_allpass(LinkwitzRiley::lr8(SAMPLE_RATE, 100.0)),
+        ] {
+            assert_abs_diff_eq!(sum.as_slice(), allpass.as_slice(), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn stores_one_allpass_per_band_and_higher_split() {
+        let mut crossover = Lr4Crossover::<f64, 5, 4, 6>::with_config(Config {
+            splits: [
+                LinkwitzRiley::lr4(SAMPLE_RATE, 50.0),
+                LinkwitzRiley::lr4(SAMPLE_RATE, 100.0),
+                LinkwitzRiley::lr4(SAMPLE_RATE, 200.0),
+                LinkwitzRiley::lr4(SAMPLE_RATE, 400.0),
+            ],
+        });
+        let splits = crossover.config().splits;
+        let expected = [1, 2, 3, 2, 3, 3];
+        for (allpass, j) in crossover.state_mut().allpass.iter().zip(expected) {
+            assert_eq!(allpass[0].config_ref().c1, splits[j].allpass[0].c1);
+            assert_eq!(allpass[0].config_ref().c2, splits[j].allpass[0].c2);
+        }
+        assert_flat_sum(crossover);
+    }
+
+    #[test]
+    fn reset_clears_state() {
+        let mut crossover = Lr4Crossover::<f64, 3, 2, 1>::with_config(Config {
+            splits: [
+                LinkwitzRiley::lr4(SAMPLE_RATE, 50.0),
+                LinkwitzRiley::lr4(SAMPLE_RATE, 250.0),
+            ],
+        });
+        let first = crossover.filter(1.0);
+        let _ = crossover.filter(0.5);
+        let mut crossover = crossover.reset();
+        assert_eq!(crossover.filter(1.0), first);
+    }
+}
@@ -0,0 +1,542 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Multi-band parametric equalizer.
+//!
+//! An [`Equalizer`] is configured by a list of [`Band`]s, each a cookbook biquad
+//! described by its [`Kind`], frequency, gain and quality factor. The biquad
+//! coefficients are derived from the bands (see [`Cookbook`]) and recomputed
+//! whenever a band changes through [`Equalizer::set_band`]. Bands can be bypassed
+//! without removing them, and [`Equalizer::magnitude_db`] reports the combined
+//! response, e.g. for drawing the curve in a user interface.
+
+use num_traits::Float;
+
+use crate::math::amplitude_to_db;
+use crate::storage::AsSlice;
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::biquad::coefficients::{Butterworth, Cookbook};
+use super::biquad::{df2t_step, Config as BiquadConfig, State as BiquadState};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The response shape of an equalizer [`Band`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// A boost or cut of `gain_db` around `freq`; see [`Cookbook::peaking`].
+    Peaking,
+    /// A boost or cut of `gain_db` below `freq`; see [`Cookbook::low_shelf`].
+    LowShelf,
+    /// A boost or cut of `gain_db` above `freq`; see [`Cookbook::high_shelf`].
+    HighShelf,
+    /// A low-pass with cutoff `freq`; see [`Cookbook::lowpass`]. Ignores `gain_db`.
+    LowPass,
+    /// A high-pass with cutoff `freq`; see [`Cookbook::highpass`]. Ignores `gain_db`.
+    HighPass,
+    /// A 0 dB peak band-pass around `freq`; see [`Butterworth::bandpass`]. Ignores `gain_db`.
+    BandPass,
+    /// A notch at `freq`; see [`Butterworth::bandstop`]. Ignores `gain_db`.
+    Notch,
+}
+
+/// A single equalizer band.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Band<T> {
+    /// The response shape.
+    pub kind: Kind,
+    /// The center, corner or cutoff frequency in Hz (`0 < freq < sample_rate / 2`).
+    pub freq: T,
+    /// The boost (positive) or cut (negative) in dB, for peaking and shelf bands.
+    pub gain_db: T,
+    /// The quality factor (`> 0`).
+    pub q: T,
+    /// Whether the band is applied; disabled bands are bypassed.
+    pub enabled: bool,
+}
+
+impl<T> Band<T> {
+    /// Creates an enabled band.
+    pub fn new(kind: Kind, freq: T, gain_db: T, q: T) -> Self {
+        Self {
+            kind,
+            freq,
+            gain_db,
+            q,
+            enabled: true,
+        }
+    }
+}
+
+impl<T> Band<T>
+where
+    T: Float,
+{
+    /// Returns the band's biquad coefficients `[b0, b1, b2, a1, a2]`, regardless
+    /// of whether it is enabled.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `freq` is not strictly between zero and
+    /// `sample_rate / 2`, or if `q` is not positive.
+    pub fn coefficients(&self, sample_rate: T) -> [T; 5] {
+        let Self {
+            freq, gain_db, q, ..
+        } = *self;
+        match self.kind {
+            Kind::Peaking => Cookbook::peaking(sample_rate, freq, q, gain_db),
+            Kind::LowShelf => Cookbook::low_shelf(sample_rate, freq, q, gain_db),
+            Kind::HighShelf => Cookbook::high_shelf(sample_rate, freq, q, gain_db),
+            Kind::LowPass => Cookbook::lowpass(sample_rate, freq, q),
+            Kind::HighPass => Cookbook::highpass(sample_rate, freq, q),
+            Kind::BandPass => Butterworth::bandpass(sample_rate, freq, q),
+            Kind::Notch => Butterworth::bandstop(sample_rate, freq, q),
+        }
+    }
+}
+
+/// The equalizer's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, BS> {
+    /// The sample rate in Hz.
+    pub sample_rate: T,
+    /// The bands, applied in order.
+    pub bands: BS,
+}
+
+/// The equalizer's state.
+#[derive(Clone, Debug)]
+pub struct State<CS, SS> {
+    /// The biquad coefficients derived from each band.
+    pub sections: CS,
+    /// The delay line of each band's biquad.
+    pub states: SS,
+}
+
+/// A multi-band parametric equalizer generic over sample type `T`, band storage
+/// `BS`, section storage `CS` and delay-line storage `SS`.
+///
+/// Each enabled band is one Direct Form II Transposed biquad; the bands run in
+/// series, like a [`BiquadCascade`](super::biquad::BiquadCascade) whose sections
+/// are derived from the configured [`Band`]s.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`EqualizerArray<T, N>`] — stack-allocated, `no_std`-friendly.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`EqualizerVec<T>`] — heap-allocated, requires the `alloc` feature."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `EqualizerVec<T>` — heap-allocated, requires the `alloc` feature."
+)]
+/// - [`EqualizerRefMut<'a, T>`] — borrows caller-owned slices.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N) for N enabled bands; five multiplications and four
+///   additions per band.
+/// - **Space:** O(N); five coefficients and two delay-line values per band.
+#[derive(Clone, Debug)]
+pub struct Equalizer<T, BS, CS, SS> {
+    config: Config<T, BS>,
+    state: State<CS, SS>,
+}
+
+/// An [`Equalizer`] backed by fixed-size arrays of `N` bands.
+pub type EqualizerArray<T, const N: usize> =
+    Equalizer<T, [Band<T>; N], [BiquadConfig<T>; N], [BiquadState<T>; N]>;
+
+/// An [`Equalizer`] backed by heap-allocated vectors.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type EqualizerVec<T> = Equalizer<
+    T,
+    alloc::vec::Vec<Band<T>>,
+    alloc::vec::Vec<BiquadConfig<T>>,
+    alloc::vec::Vec<BiquadState<T>>,
+>;
+
+/// An [`Equalizer`] that borrows caller-owned band, section and delay-line slices.
+///
+/// Construct via [`Equalizer::from_parts`].
+pub type EqualizerRefMut<'a, T> =
+    Equalizer<T, &'a mut [Band<T>], &'a mut [BiquadConfig<T>], &'a mut [BiquadState<T>]>;
+
+impl<T, BS, CS, SS> Equalizer<T, BS, CS, SS>
+where
+    T: Float,
+    BS: AsSlice<Band<T>>,
+    CS: AsSlice<BiquadConfig<T>>,
+    SS: AsSlice<BiquadState<T>>,
+{
+    /// Creates an equalizer from caller-provided section and delay-line storage.
+    ///
+    /// The sections are computed from `config.bands` and the delay lines are
+    /// cleared; their initial contents are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sections` or `states` differ in length from `config.bands`.
+    pub fn from_parts(config: Config<T, BS>, sections: CS, states: SS) -> Self {
+        let bands = config.bands.len();
+        assert_eq!(
+            sections.len(),
+            bands,
+            "Equalizer: expected {bands} sections, got {}",
+            sections.len()
+        );
+        assert_eq!(
+            states.len(),
+            bands,
+            "Equalizer: expected {bands} states, got {}",
+            states.len()
+        );
+        let mut equalizer = Self {
+            config,
+            state: State { sections, states },
+        };
+        for index in 0..bands {
+            equalizer.update_section(index);
+            equalizer.state.states.as_mut_slice()[index] = BiquadState::default();
+        }
+        equalizer
+    }
+
+    /// Returns the bands.
+    pub fn bands(&self) -> &[Band<T>] {
+        self.config.bands.as_slice()
+    }
+
+    /// Replaces band `index` and recomputes its section.
+    ///
+    /// The band's delay line is kept, so retuning an enabled band does not
+    /// interrupt the signal, unless the band is enabled by this call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_band(&mut self, index: usize, band: Band<T>) {
+        let was_enabled = self.config.bands.as_slice()[index].enabled;
+        self.config.bands.as_mut_slice()[index] = band;
+        self.update_section(index);
+        if band.enabled && !was_enabled {
+            self.state.states.as_mut_slice()[index] = BiquadState::default();
+        }
+    }
+
+    /// Enables or bypasses band `index`.
+    ///
+    /// Enabling a bypassed band starts it from a cleared delay line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        let band = Band {
+            enabled,
+            ..self.config.bands.as_slice()[index]
+        };
+        self.set_band(index, band);
+    }
+
+    /// Returns the combined linear magnitude response of all enabled bands at
+    /// `freq` Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` cannot represent `π`; this is infallible for `f32` and `f64`.
+    pub fn magnitude(&self, freq: T) -> T {
+        let two = T::one() + T::one();
+        let pi = T::from(core::f64::consts::PI).expect("π is representable");
+        let omega = two * pi * freq / self.config.sample_rate;
+        self.enabled_sections().fold(T::one(), |product, section| {
+            product * section_magnitude(section, omega)
+        })
+    }
+
+    /// Returns the combined magnitude response of all enabled bands at `freq` Hz,
+    /// in dB.
+    pub fn magnitude_db(&self, freq: T) -> T {
+        amplitude_to_db(self.magnitude(freq))
+    }
+
+    fn enabled_sections(&self) -> impl Iterator<Item = &BiquadConfig<T>> {
+        self.config
+            .bands
+            .as_slice()
+            .iter()
+            .zip(self.state.sections.as_slice())
+            .filter(|(band, _)| band.enabled)
+            .map(|(_, section)| section)
+    }
+
+    fn update_section(&mut self, index: usize) {
+        let band = &self.config.bands.as_slice()[index];
+        let coefficients = band.coefficients(self.config.sample_rate);
+        self.state.sections.as_mut_slice()[index] = BiquadConfig::from(coefficients);
+    }
+}
+
+/// Returns `|H(e^{jω})|` of a single biquad section.
+fn section_magnitude<T: Float>(section: &BiquadConfig<T>, omega: T) -> T {
+    let two = T::one() + T::one();
+    let (sin1, cos1) = omega.sin_cos();
+    let (sin2, cos2) = (two * omega).sin_cos();
+    let numerator = (section.b0 + section.b1 * cos1 + section.b2 * cos2)
+        .hypot(section.b1 * sin1 + section.b2 * sin2);
+    let denominator = (T::one() + section.a1 * cos1 + section.a2 * cos2)
+        .hypot(section.a1 * sin1 + section.a2 * sin2);
+    numerator / denominator
+}
+
+#[cfg(feature = "alloc")]
+impl<T> EqualizerVec<T>
+where
+    T: Float,
+{
+    /// Creates a heap-backed equalizer with one section per configured band.
+    #[must_use]
+    pub fn new(config: Config<T, alloc::vec::Vec<Band<T>>>) -> Self {
+        let bands = config.bands.len();
+        let sections = alloc::vec![BiquadConfig::default(); bands];
+        let states = alloc::vec![BiquadState::default(); bands];
+        Self::from_parts(config, sections, states)
+    }
+}
+
+impl<T, BS, CS, SS> ConfigTrait for Equalizer<T, BS, CS, SS> {
+    type Config = Config<T, BS>;
+}
+
+impl<T, BS, CS, SS> StateTrait for Equalizer<T, BS, CS, SS> {
+    type State = State<CS, SS>;
+}
+
+impl<T, const N: usize> WithConfig for EqualizerArray<T, N>
+where
+    T: Float,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        let sections = core::array::from_fn(|_| BiquadConfig::default());
+        let states = core::array::from_fn(|_| BiquadState::default());
+        Self::from_parts(config, sections, states)
+    }
+}
+
+impl<T, BS, CS, SS> ConfigRef for Equalizer<T, BS, CS, SS> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, BS, CS, SS> ConfigClone for Equalizer<T, BS, CS, SS>
+where
+    Config<T, BS>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, BS, CS, SS> StateMut for Equalizer<T, BS, CS, SS> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, BS, CS, SS> HasGuts for Equalizer<T, BS, CS, SS> {
+    type Guts = (Config<T, BS>, State<CS, SS>);
+}
+
+impl<T, BS, CS, SS> FromGuts for Equalizer<T, BS, CS, SS> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, BS, CS, SS> IntoGuts for Equalizer<T, BS, CS, SS> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize> Reset for EqualizerArray<T, N>
+where
+    T: Float,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for EqualizerArray<T, N> where Self: Reset {}
+
+impl<T, BS, CS, SS> Filter<T> for Equalizer<T, BS, CS, SS>
+where
+    T: Float,
+    BS: AsSlice<Band<T>>,
+    CS: AsSlice<BiquadConfig<T>>,
+    SS: AsSlice<BiquadState<T>>,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let bands = self.config.bands.as_slice();
+        let sections = self.state.sections.as_slice();
+        let states = self.state.states.as_mut_slice();
+        bands
+            .iter()
+            .zip(sections)
+            .zip(states)
+            .filter(|((band, _), _)| band.enabled)
+            .fold(input, |x, ((_, section), state)| {
+                df2t_step(section, state, x)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::filters::iir::biquad::Biquad;
+    use crate::math::db_to_amplitude;
+
+    const SAMPLE_RATE: f64 = 48000.0;
+
+    fn equalizer() -> EqualizerArray<f64, 3> {
+        EqualizerArray::with_config(Config {
+            sample_rate: SAMPLE_RATE,
+            bands: [
+                Band::new(Kind::LowShelf, 100.0, 4.0, core::f64::consts::FRAC_1_SQRT_2),
+                Band::new(Kind::Peaking, 1000.0, -6.0, 2.0),
+                Band::new(
+                    Kind::HighShelf,
+                    8000.0,
+                    3.0,
+                    core::f64::consts::FRAC_1_SQRT_2,
+                ),
+            ],
+        })
+    }
+
+    /// Returns the steady-state amplitude of the equalizer's response to a sine at
+    /// `freq` Hz, from its RMS over 19200 samples (a whole number of periods).
+    fn measured_gain<BS, CS, SS>(equalizer: &mut Equalizer<f64, BS, CS, SS>, freq: f64) -> f64
+    where
+        BS: AsSlice<Band<f64>>,
+        CS: AsSlice<BiquadConfig<f64>>,
+        SS: AsSlice<BiquadState<f64>>,
+    {
+        let omega = 2.0 * core::f64::consts::PI * freq / SAMPLE_RATE;
+        let mut power = 0.0;
+        for n in 0..40_000_u32 {
+            let output = equalizer.filter((omega * f64::from(n)).sin());
+            if n >= 20_800 {
+                power += output * output;
+            }
+        }
+        (2.0 * power / 19_200.0).sqrt()
+    }
+
+    #[test]
+    fn reported_response_matches_bands() {
+        let equalizer = equalizer();
+        // Each band hits its gain where the others are negligible.
+        assert_abs_diff_eq!(equalizer.magnitude_db(10.0), 4.0, epsilon = 0.05);
+        assert_abs_diff_eq!(equalizer.magnitude_db(1000.0), -6.0, epsilon = 0.05);
+        assert_abs_diff_eq!(equalizer.magnitude_db(23000.0), 3.0, epsilon = 0.05);
+    }
+
+    #[test]
+    fn filtered_sine_matches_reported_response() {
+        let equalizer = equalizer();
+        for freq in [50.0, 1000.0, 3000.0] {
+            let expected = equalizer.magnitude(freq);
+            let mut fresh = equalizer.clone();
+            assert_abs_diff_eq!(measured_gain(&mut fresh, freq), expected, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn bypassed_band_is_skipped() {
+        let mut equalizer = equalizer();
+        equalizer.set_enabled(1, false);
+        assert_abs_diff_eq!(equalizer.magnitude_db(1000.0), 0.0, epsilon = 0.05);
+        assert_abs_diff_eq!(measured_gain(&mut equalizer, 1000.0), 1.0, epsilon = 0.01);
+
+        let mut all_bypassed = equalizer.clone();
+        all_bypassed.set_enabled(0, false);
+        all_bypassed.set_enabled(2, false);
+        for input in [1.0, -0.5, 0.25] {
+            assert_abs_diff_eq!(all_bypassed.filter(input), input);
+        }
+        assert_abs_diff_eq!(all_bypassed.magnitude(440.0), 1.0);
+    }
+
+    #[test]
+    fn set_band_recomputes_section() {
+        let mut equalizer = equalizer();
+        equalizer.set_band(1, Band::new(Kind::Peaking, 2000.0, 9.0, 1.0));
+        assert_abs_diff_eq!(equalizer.magnitude_db(2000.0), 9.0, epsilon = 0.1);
+        let expected = Cookbook::peaking(SAMPLE_RATE, 2000.0, 1.0, 9.0);
+        let section: [f64; 5] = equalizer.state_mut().sections[1].clone().into();
+        assert_abs_diff_eq!(section.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn single_band_matches_biquad() {
+        let band = Band::new(Kind::LowPass, 2000.0, 0.0, 3.0);
+        let mut equalizer = EqualizerArray::with_config(Config {
+            sample_rate: SAMPLE_RATE,
+            bands: [band],
+        });
+        let mut biquad: Biquad<f64> =
+            Biquad::with_config(BiquadConfig::from(band.coefficients(SAMPLE_RATE)));
+        for input in [1.0, 0.0, -0.5, 0.75, 0.0, 0.0] {
+            assert_abs_diff_eq!(
+                equalizer.filter(input),
+                biquad.filter(input),
+                epsilon = 1e-15
+            );
+        }
+    }
+
+    #[test]
+    fn notch_rejects_center_frequency() {
+        let equalizer = EqualizerArray::with_config(Config {
+            sample_rate: SAMPLE_RATE,
+            bands: [Band::new(Kind::Notch, 60.0, 0.0, 10.0)],
+        });
+        assert!(equalizer.magnitude(60.0) < db_to_amplitude(-100.0));
+        assert_abs_diff_eq!(equalizer.magnitude(1000.0), 1.0, epsilon = 1e-3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let mut array = equalizer();
+        let mut vec = EqualizerVec::new(Config {
+            sample_rate: SAMPLE_RATE,
+            bands: array.bands().to_vec(),
+        });
+        for input in [1.0, 0.0, -0.5, 0.75, 0.0, 0.0] {
+            assert_abs_diff_eq!(vec.filter(input), array.filter(input), epsilon = 1e-15);
+        }
+    }
+}
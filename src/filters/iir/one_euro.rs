@@ -0,0 +1,373 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! One Euro (1€) filter for adaptive jitter smoothing.
+//!
+//! A first-order low-pass whose cutoff frequency rises with the signal's speed
+//! (Casiez, Roussel and Vogel, 2012): slow movements are smoothed strongly to
+//! remove jitter, fast movements are passed with little lag.
+
+use num_traits::float::FloatCore;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+use super::first_order::{self, FirstOrder};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// The One Euro filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The cutoff frequency in Hz at zero speed (`> 0`); lower values remove
+    /// more jitter from slow movements.
+    pub min_cutoff: T,
+    /// The cutoff's increase per unit of speed (`≥ 0`), in Hz per (unit / s);
+    /// higher values reduce the lag of fast movements.
+    pub beta: T,
+    /// The cutoff frequency in Hz of the speed estimate (`> 0`), typically `1`.
+    pub derivative_cutoff: T,
+    /// The sample rate in Hz (`> 0`), used for untimestamped input.
+    pub sample_rate: T,
+}
+
+/// The One Euro filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// The value low-pass's state; `None` until the first sample.
+    pub value: Option<first_order::State<T>>,
+    /// The speed low-pass's state.
+    pub derivative: first_order::State<T>,
+    /// The previous sample's timestamp, for timestamped input.
+    pub timestamp: Option<T>,
+}
+
+/// A One Euro filter, smoothing with a speed-dependent cutoff.
+///
+/// Each sample estimates the signal's speed from the difference to the
+/// previous output, smooths it with a fixed low-pass at `derivative_cutoff`,
+/// and then low-passes the signal with the cutoff
+///
+/// ```text
+/// fc = min_cutoff + β·|speed|
+/// ```
+///
+/// Both low-passes are [`FirstOrder`] filters with the smoothing factor
+/// `α = 1 / (1 + 1 / (2π·fc·Δt))`. The first sample passes through unchanged.
+///
+/// The filter accepts either plain samples at `sample_rate`, or
+/// `(timestamp, value)` pairs with timestamps in seconds for irregularly
+/// sampled input (e.g. pointer events). A timestamped sample that does not
+/// advance in time is ignored and returns the previous output.
+///
+/// # Tuning
+///
+/// Set `beta` to zero and lower `min_cutoff` until the jitter of a slowly
+/// moving signal is acceptable; then increase `beta` until fast movements no
+/// longer lag.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); two first-order low-passes and a coefficient update.
+/// - **Space:** O(1); stores the two low-pass states and the last timestamp.
+#[derive(Clone, Debug)]
+pub struct OneEuro<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+impl<T> OneEuro<T>
+where
+    T: FloatCore,
+{
+    /// Returns the current speed estimate in units per second.
+    pub fn speed(&self) -> T {
+        self.state.derivative.prev_output
+    }
+
+    fn smooth(&mut self, input: T, sample_time: T) -> T {
+        let Some(value) = self.state.value.clone() else {
+            self.state.value = Some(first_order::State {
+                prev_input: input,
+                prev_output: input,
+            });
+            return input;
+        };
+
+        let speed = (input - value.prev_output) / sample_time;
+        let derivative = lowpass(
+            self.config.derivative_cutoff,
+            sample_time,
+            self.state.derivative.clone(),
+        );
+        let (speed, derivative) = run(derivative, speed);
+        self.state.derivative = derivative;
+
+        let cutoff = self.config.min_cutoff + self.config.beta * speed.abs();
+        let (output, value) = run(lowpass(cutoff, sample_time, value), input);
+        self.state.value = Some(value);
+        output
+    }
+}
+
+/// Returns a first-order low-pass at `cutoff` Hz for sample period
+/// `sample_time`, continuing from `state`.
+fn lowpass<T: FloatCore>(cutoff: T, sample_time: T, state: first_order::State<T>) -> FirstOrder<T> {
+    let tau = T::from(core::f64::consts::TAU).unwrap_or_else(T::nan);
+    let alpha = T::one() / (T::one() + T::one() / (tau * cutoff * sample_time));
+    let config = first_order::Config {
+        b0: alpha,
+        b1: T::zero(),
+        a1: alpha - T::one(),
+    };
+    FirstOrder::from_guts((config, state))
+}
+
+/// Filters `input` and returns the output with the filter's new state.
+fn run<T: FloatCore>(mut filter: FirstOrder<T>, input: T) -> (T, first_order::State<T>) {
+    let output = filter.filter(input);
+    let (_, state) = filter.into_guts();
+    (output, state)
+}
+
+impl<T> ConfigTrait for OneEuro<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for OneEuro<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for OneEuro<T>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if a cutoff or the sample rate is not positive, or `beta` is
+    /// negative.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(
+            config.min_cutoff > T::zero() && config.derivative_cutoff > T::zero(),
+            "OneEuro: cutoffs must be > 0"
+        );
+        assert!(config.beta >= T::zero(), "OneEuro: beta must be >= 0");
+        assert!(
+            config.sample_rate > T::zero(),
+            "OneEuro: sample rate must be > 0"
+        );
+        let state = State {
+            value: None,
+            derivative: first_order::State {
+                prev_input: T::zero(),
+                prev_output: T::zero(),
+            },
+            timestamp: None,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T> ConfigRef for OneEuro<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for OneEuro<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for OneEuro<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for OneEuro<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for OneEuro<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for OneEuro<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for OneEuro<T>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for OneEuro<T> where Self: Reset {}
+
+impl<T> Filter<T> for OneEuro<T>
+where
+    T: FloatCore,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let sample_time = T::one() / self.config.sample_rate;
+        self.smooth(input, sample_time)
+    }
+}
+
+impl<T> Filter<(T, T)> for OneEuro<T>
+where
+    T: FloatCore,
+{
+    type Output = T;
+
+    /// Filters a `(timestamp, value)` pair, with the timestamp in seconds.
+    fn filter(&mut self, input: (T, T)) -> Self::Output {
+        let (timestamp, value) = input;
+        let sample_time = match self.state.timestamp {
+            Some(previous) => timestamp - previous,
+            None => T::one() / self.config.sample_rate,
+        };
+        if sample_time <= T::zero() {
+            return self
+                .state
+                .value
+                .as_ref()
+                .map_or(value, |state| state.prev_output);
+        }
+        self.state.timestamp = Some(timestamp);
+        self.smooth(value, sample_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::util::test_fixtures::hash_noise;
+
+    fn filter(beta: f64) -> OneEuro<f64> {
+        OneEuro::with_config(Config {
+            min_cutoff: 1.0,
+            beta,
+            derivative_cutoff: 1.0,
+            sample_rate: 100.0,
+        })
+    }
+
+    #[test]
+    fn first_sample_passes_through() {
+        let mut filter = filter(0.1);
+        assert_eq!(filter.filter(3.5), 3.5);
+        assert_eq!(filter.speed(), 0.0);
+    }
+
+    #[test]
+    fn zero_beta_is_fixed_first_order_lowpass() {
+        let mut filter = filter(0.0);
+        filter.filter(0.0);
+        let alpha = 1.0 / (1.0 + 100.0 / core::f64::consts::TAU);
+        let mut expected = 0.0;
+        for _ in 0..50 {
+            expected += alpha * (1.0 - expected);
+            assert_abs_diff_eq!(filter.filter(1.0), expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn removes_jitter_of_slow_signal() {
+        let mut filter = filter(0.05);
+        let mut input_power = 0.0;
+        let mut output_power = 0.0;
+        for n in 0_u32..2000 {
+            let jitter = 0.1 * hash_noise(n);
+            let output = filter.filter(5.0 + jitter);
+            if n >= 1000 {
+                input_power += jitter * jitter;
+                output_power += (output - 5.0) * (output - 5.0);
+            }
+        }
+        assert!(
+            output_power < 0.05 * input_power,
+            "{output_power} vs {input_power}"
+        );
+    }
+
+    #[test]
+    fn fast_movement_lags_less_with_beta() {
+        let mut fixed = filter(0.0);
+        let mut adaptive = filter(0.5);
+        let (mut fixed_lag, mut adaptive_lag) = (0.0, 0.0);
+        for n in 0_u32..100 {
+            // A ramp of 100 units per second.
+            let input = f64::from(n);
+            fixed_lag = input - fixed.filter(input);
+            adaptive_lag = input - adaptive.filter(input);
+        }
+        assert!(
+            adaptive_lag < 0.1 * fixed_lag,
+            "{adaptive_lag} vs {fixed_lag}"
+        );
+        // The speed is measured against the (lagging) previous output.
+        let speed = adaptive.speed();
+        assert!((100.0..150.0).contains(&speed), "speed = {speed}");
+    }
+
+    #[test]
+    fn timestamped_input_matches_regular_sampling() {
+        let mut regular = filter(0.1);
+        let mut timestamped = filter(0.1);
+        for n in 0_u32..200 {
+            let value = (f64::from(n) * 0.05).sin() * 10.0;
+            let expected = regular.filter(value);
+            let actual = timestamped.filter((f64::from(n) * 0.01, value));
+            assert_abs_diff_eq!(actual, expected, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn timestamped_input_adapts_to_gaps() {
+        let mut filter = filter(0.0);
+        filter.filter((0.0, 0.0));
+        // A long gap lets the output move much further towards the input.
+        let short = filter.clone().filter((0.01, 1.0));
+        let long = filter.clone().filter((1.0, 1.0));
+        assert!(long > 0.8 && short < 0.1, "{short} vs {long}");
+        // A stale timestamp is ignored.
+        let mut stale = filter.clone();
+        assert_eq!(stale.filter((0.0, 1.0)), 0.0);
+        assert_eq!(stale.state.timestamp, Some(0.0));
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter = filter(0.1);
+        filter.filter(1.0);
+        filter.filter(2.0);
+        let mut filter = filter.reset();
+        assert_eq!(filter.filter(7.0), 7.0);
+    }
+}
@@ -0,0 +1,299 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Allpass-pair phase splitter (IIR Hilbert transformer).
+//!
+//! Two parallel chains of allpass sections in `z⁻²` are designed so that their
+//! phase responses differ by 90° over most of the band. Taking one chain as the
+//! in-phase (real) output and the other as the quadrature (imaginary) output
+//! yields an approximate analytic signal with only a few samples of latency.
+//!
+//! Each `z⁻²` section `H(z) = (c + z⁻²) / (1 + c·z⁻²)` is realized as a pair of
+//! first-order [`Allpass`] filters that process even and odd samples in turn.
+//!
+//! Unlike the FIR [`Analytic`](crate::filters::fir::analytic::Analytic) filter,
+//! the phase of each output is not linear; only the *difference* between the
+//! two outputs is (approximately) 90°. Use it where latency matters more than
+//! waveform fidelity, e.g. envelope detection, single-sideband modulation and
+//! frequency shifting.
+//!
+//! Requires the `complex` feature.
+
+use num_traits::Num;
+
+use crate::complex::Complex;
+use crate::filters::iir::allpass::{Allpass, Config as AllpassConfig};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+/// Niemitalo's 4+4 section design: 90° ± 0.7° for `0.0021 < f < 0.4979`
+/// cycles/sample. Each section of a chain has allpass coefficient `c = −a²`.
+const NIEMITALO_REAL: [f64; 4] = [
+    0.402_192_116_242_6,
+    0.856_171_088_242,
+    0.972_290_954_565_1,
+    0.995_288_479_127_8,
+];
+const NIEMITALO_IMAG: [f64; 4] = [
+    0.692_387_8,
+    0.936_065_432_295_9,
+    0.988_229_522_686,
+    0.998_748_845_273_7,
+];
+
+/// The phase splitter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T, const S: usize> {
+    /// Allpass coefficients `c` of the `S` sections in the in-phase chain.
+    pub real: [T; S],
+    /// Allpass coefficients `c` of the `S` sections in the quadrature chain.
+    pub imag: [T; S],
+}
+
+/// The phase splitter's state.
+#[derive(Clone, Debug)]
+pub struct State<T, const S: usize> {
+    /// Even/odd allpass pairs of the in-phase chain.
+    pub real: [[Allpass<T>; 2]; S],
+    /// Even/odd allpass pairs of the quadrature chain.
+    pub imag: [[Allpass<T>; 2]; S],
+    /// Quadrature chain output from the previous sample.
+    pub delayed: T,
+    /// Whether the next sample has odd parity.
+    pub odd: bool,
+}
+
+/// An allpass-pair phase splitter producing an approximate analytic signal.
+///
+/// The quadrature chain output is delayed by one sample to align it with the
+/// in-phase chain, as in Niemitalo's design. For a cosine input the output
+/// rotates counter-clockwise, like the analytic signal `e^{jωn}`.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(S); one first-order allpass update per section in each chain.
+/// - **Space:** O(S); two first-order allpass states per section in each chain.
+#[derive(Clone, Debug)]
+pub struct PhaseSplitter<T, const S: usize> {
+    config: Config<T, S>,
+    state: State<T, S>,
+}
+
+impl<T> PhaseSplitter<T, 4>
+where
+    T: Clone + Num + num_traits::NumCast,
+{
+    /// Creates a phase splitter using Olli Niemitalo's 8th-order design.
+    ///
+    /// The phase difference between the outputs is within 0.7° of 90° for
+    /// `0.0021 < f < 0.4979` cycles/sample (20 Hz to 22 kHz at 44.1 kHz).
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn new() -> Self {
+        let cast =
+            |a: f64| num_traits::cast::<f64, T>(-a * a).expect("coefficient is representable");
+        Self::with_config(Config {
+            real: NIEMITALO_REAL.map(cast),
+            imag: NIEMITALO_IMAG.map(cast),
+        })
+    }
+}
+
+impl<T> Default for PhaseSplitter<T, 4>
+where
+    T: Clone + Num + num_traits::NumCast,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const S: usize> ConfigTrait for PhaseSplitter<T, S> {
+    type Config = Config<T, S>;
+}
+
+impl<T, const S: usize> StateTrait for PhaseSplitter<T, S> {
+    type State = State<T, S>;
+}
+
+fn sections<T, const S: usize>(coefficients: &[T; S]) -> [[Allpass<T>; 2]; S]
+where
+    T: Clone + num_traits::Zero,
+{
+    coefficients.clone().map(|c| {
+        let section = Allpass::with_config(AllpassConfig { c });
+        [section.clone(), section]
+    })
+}
+
+impl<T, const S: usize> WithConfig for PhaseSplitter<T, S>
+where
+    T: Clone + num_traits::Zero,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        let state = State {
+            real: sections(&config.real),
+            imag: sections(&config.imag),
+            delayed: T::zero(),
+            odd: false,
+        };
+        Self { config, state }
+    }
+}
+
+impl<T, const S: usize> ConfigRef for PhaseSplitter<T, S> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, const S: usize> ConfigClone for PhaseSplitter<T, S>
+where
+    Config<T, S>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, const S: usize> StateMut for PhaseSplitter<T, S> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, const S: usize> HasGuts for PhaseSplitter<T, S> {
+    type Guts = (Config<T, S>, State<T, S>);
+}
+
+impl<T, const S: usize> FromGuts for PhaseSplitter<T, S> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, const S: usize> IntoGuts for PhaseSplitter<T, S> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const S: usize> Reset for PhaseSplitter<T, S>
+where
+    T: Clone + num_traits::Zero,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const S: usize> ResetMut for PhaseSplitter<T, S> where Self: Reset {}
+
+impl<T, const S: usize> Filter<T> for PhaseSplitter<T, S>
+where
+    T: Clone + Num,
+{
+    type Output = Complex<T>;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let parity = usize::from(self.state.odd);
+        self.state.odd = !self.state.odd;
+
+        let real = self
+            .state
+            .real
+            .iter_mut()
+            .fold(input.clone(), |x, pair| pair[parity].filter(x));
+        let imag = self
+            .state
+            .imag
+            .iter_mut()
+            .fold(input, |x, pair| pair[parity].filter(x));
+
+        let im = core::mem::replace(&mut self.state.delayed, imag);
+        Complex::new(real, im)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn section_pair_matches_second_order_allpass() {
+        // y[n] = c·(x[n] − y[n−2]) + x[n−2]
+        let c = -0.5_f64;
+        let mut splitter = PhaseSplitter::with_config(Config {
+            real: [c],
+            imag: [0.0],
+        });
+
+        let input: [f64; 8] = [1.0, 7.0, 2.0, 5.0, 8.0, 16.0, 3.0, 19.0];
+        let mut y = [0.0_f64; 8];
+        for n in 0..input.len() {
+            let (x2, y2) = if n >= 2 {
+                (input[n - 2], y[n - 2])
+            } else {
+                (0.0, 0.0)
+            };
+            y[n] = c * (input[n] - y2) + x2;
+        }
+
+        let output: Vec<_> = input.iter().map(|&x| splitter.filter(x)).collect();
+        for n in 0..input.len() {
+            assert_abs_diff_eq!(output[n].re, y[n], epsilon = 1e-12);
+        }
+        // c = 0 degenerates to a pure two-sample delay, plus the one-sample
+        // alignment delay of the quadrature chain.
+        assert_abs_diff_eq!(output[3].im, input[0], epsilon = 1e-12);
+    }
+
+    #[test]
+    fn outputs_are_in_quadrature() {
+        for frequency in [0.01_f64, 0.05, 0.125, 0.25, 0.4, 0.48] {
+            let mut splitter = PhaseSplitter::<f64, 4>::new();
+            let mut previous = Complex::new(0.0, 0.0);
+
+            for n in 0_u32..4000 {
+                let x = (core::f64::consts::TAU * frequency * f64::from(n)).cos();
+                let z = splitter.filter(x);
+                if n >= 3000 {
+                    // Unit envelope, rotating counter-clockwise by ω per sample.
+                    assert_abs_diff_eq!(z.norm(), 1.0, epsilon = 0.02);
+                    let step = (z * previous.conj()).arg();
+                    assert_abs_diff_eq!(step, core::f64::consts::TAU * frequency, epsilon = 0.03);
+                }
+                previous = z;
+            }
+        }
+    }
+
+    #[test]
+    fn reset_clears_state() {
+        let mut splitter = PhaseSplitter::<f32, 4>::new();
+        splitter.filter(1.0);
+        splitter.filter(2.0);
+
+        let mut splitter = splitter.reset();
+
+        let state = splitter.state_mut();
+        assert!(!state.odd);
+        assert_abs_diff_eq!(state.delayed, 0.0);
+        assert_abs_diff_eq!(state.real[0][0].state_mut().prev_output, 0.0);
+    }
+}
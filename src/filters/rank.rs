@@ -12,14 +12,20 @@
 //!
 //! # When to use which filter
 //!
-//! | Filter                       | Purpose                                                |
-//! | ---------------------------- | ------------------------------------------------------ |
-//! | `bounds::Bounds`             | Tracks minimum and maximum simultaneously              |
-//! | `exp::Exp`                   | Exponential-weighted median (recursive approximation)  |
-//! | `hampel::Hampel`             | Outlier detection and replacement (Hampel identifier)  |
-//! | `max::Max`                   | Sliding-window maximum                                 |
-//! | `median::Median`             | Sliding-window median (canonical rank filter)          |
-//! | `min::Min`                   | Sliding-window minimum                                 |
+//! | Filter                         | Purpose                                                  |
+//! | ------------------------------ | -------------------------------------------------------- |
+//! | `bounds::Bounds`               | Tracks minimum and maximum simultaneously                |
+//! | `exp::Exp`                     | Exponential-weighted median (recursive approximation)    |
+//! | `hampel::Hampel`               | Outlier detection and replacement (Hampel identifier)    |
+//! | `interquartile_range::*`       | Sliding-window interquartile range (robust spread)       |
+//! | `max::Max`                     | Sliding-window maximum                                   |
+//! | `median::Median`               | Sliding-window median (canonical rank filter)            |
+//! | `median_absolute_deviation::*` | Sliding-window median absolute deviation (robust spread) |
+//! | `min::Min`                     | Sliding-window minimum                                   |
+//! | `order_statistic::*`           | Sliding-window k-th order statistic or quantile          |
+//! | `robust_z_score::*`            | Sliding-window z-score from median and MAD               |
+//! | `trimmed_mean::*`              | Sliding-window alpha-trimmed mean                        |
+//! | `winsorized_mean::*`           | Sliding-window winsorized mean                           |
 //!
 //! - **Median** is the canonical rank-order filter: it rejects isolated outliers while
 //!   preserving step edges. Use when you need smoothing that does not blur transitions.
@@ -30,6 +36,15 @@
 //! - **Hampel** detects and replaces outliers by comparing each sample to the local
 //!   median and median absolute deviation (MAD), making it effective for cleaning
 //!   impulsive noise while preserving the underlying signal structure.
+//! - **`OrderStatistic`** generalizes min, median and max to any rank or quantile,
+//!   e.g. for 10th/90th percentile envelopes; **`InterquartileRange`** outputs the
+//!   spread between the 25th and 75th percentiles, a robust alternative to the
+//!   standard deviation.
+//! - **`TrimmedMean`** and **`WinsorizedMean`** sit between the median and the mean:
+//!   they discard or clamp a proportion of the extreme values and average the rest.
+//!   **`MedianAbsoluteDeviation`** is the most robust measure of spread, and
+//!   **`RobustZScore`** uses it to score each sample against the window's median,
+//!   e.g. for outlier flagging. All four reject NaN inputs instead of inserting them.
 //! - **Exp** is a recursive (IIR-style) approximation of the median that uses `O(1)`
 //!   memory per sample rather than `O(window)`. It is lighter weight but less exact.
 //!
@@ -51,6 +66,12 @@
 pub mod bounds;
 pub mod exp;
 pub mod hampel;
+pub mod interquartile_range;
 pub mod max;
 pub mod median;
+pub mod median_absolute_deviation;
 pub mod min;
+pub mod order_statistic;
+pub mod robust_z_score;
+pub mod trimmed_mean;
+pub mod winsorized_mean;
@@ -0,0 +1,238 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Moving interquartile range filters.
+
+use core::ops::Sub;
+
+use crate::storage::AsSlice;
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Filter, Reset, State as StateTrait, StateMut,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::median::{ListNode, Median, MedianArray};
+
+/// The interquartile range filter's state.
+#[derive(Clone)]
+pub struct State<T, B> {
+    /// Median filter maintaining the sorted window.
+    pub median: Median<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for State<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("State")
+            .field("median", &self.median)
+            .finish()
+    }
+}
+
+/// A moving interquartile range (IQR) filter, outputting `Q3 − Q1` of a
+/// sliding window.
+///
+/// The IQR is a robust measure of spread: unlike the standard deviation it
+/// ignores the outer quarters of the window, and with them isolated outliers.
+/// The quartiles are the nearest ranks `round(q·(n − 1))` of the `n` available
+/// values, computed in integer arithmetic, so both are values of the window.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); delegates to the internal `Median` filter (O(N)), then
+///   walks its sorted linked list to both quartiles.
+/// - **Space:** O(N); stores the internal `Median<T, B>` window.
+#[derive(Clone)]
+pub struct InterquartileRange<T, B> {
+    state: State<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for InterquartileRange<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("InterquartileRange")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// An [`InterquartileRange`] filter backed by a fixed-size array.
+pub type InterquartileRangeArray<T, const N: usize> = InterquartileRange<T, [ListNode<T>; N]>;
+
+/// An [`InterquartileRange`] filter backed by a heap-allocated `Vec`.
+#[cfg(feature = "alloc")]
+pub type InterquartileRangeVec<T> = InterquartileRange<T, alloc::vec::Vec<ListNode<T>>>;
+
+/// An [`InterquartileRange`] filter that borrows a caller-owned slice.
+pub type InterquartileRangeRefMut<'a, T> = InterquartileRange<T, &'a mut [ListNode<T>]>;
+
+impl<T, const N: usize> Default for InterquartileRangeArray<T, N> {
+    /// Creates an [`InterquartileRangeArray`] with an empty window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    fn default() -> Self {
+        let median = MedianArray::default();
+        Self {
+            state: State { median },
+        }
+    }
+}
+
+impl<T, B> InterquartileRange<T, B>
+where
+    T: Clone,
+    B: AsSlice<ListNode<T>>,
+{
+    /// Creates an [`InterquartileRange`] from a pre-initialised median filter.
+    ///
+    /// Use [`MedianVec::new`](super::median::MedianVec::new) or
+    /// [`Median::from_parts`] to create the median filter for `Vec` or
+    /// borrowed storage.
+    pub fn from_parts(median: Median<T, B>) -> Self {
+        Self {
+            state: State { median },
+        }
+    }
+
+    /// Returns the window size of the filter.
+    pub fn len(&self) -> usize {
+        self.state.median.len()
+    }
+
+    /// Returns `true` if the filter's window is empty, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.state.median.is_empty()
+    }
+
+    /// Returns the lower and upper quartiles `(Q1, Q3)` of the current
+    /// window, or `None` if the filter has not yet received any values.
+    pub fn quartiles(&self) -> Option<(T, T)> {
+        let median = &self.state.median;
+        let last = median.count().checked_sub(1)?;
+        let lower = median.nth((last + 2) / 4)?;
+        let upper = median.nth((3 * last + 2) / 4)?;
+        Some((lower, upper))
+    }
+}
+
+impl<T, B> StateTrait for InterquartileRange<T, B> {
+    type State = State<T, B>;
+}
+
+impl<T, B> StateMut for InterquartileRange<T, B> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, B> HasGuts for InterquartileRange<T, B> {
+    type Guts = State<T, B>;
+}
+
+impl<T, B> FromGuts for InterquartileRange<T, B> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let state = guts;
+        Self { state }
+    }
+}
+
+impl<T, B> IntoGuts for InterquartileRange<T, B> {
+    fn into_guts(self) -> Self::Guts {
+        self.state
+    }
+}
+
+impl<T, const N: usize> Reset for InterquartileRangeArray<T, N> {
+    fn reset(self) -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for InterquartileRangeArray<T, N> where Self: Reset {}
+
+impl<T, B> Filter<T> for InterquartileRange<T, B>
+where
+    T: Clone + PartialOrd + Sub<Output = T>,
+    B: AsSlice<ListNode<T>>,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        self.state.median.filter(input);
+        let (lower, upper) = self
+            .quartiles()
+            .expect("window must be non-empty after the first filter() call");
+        upper - lower
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::filters::rank::median::{MedianRefMut, MedianVec};
+
+    #[test]
+    fn computes_quartile_spread() {
+        let mut filter: InterquartileRangeArray<i32, 9> = InterquartileRangeArray::default();
+        assert_eq!(filter.quartiles(), None);
+        // The window [0, 10, ..., 80] has quartiles 20 and 60.
+        let mut output = 0;
+        for input in [40, 0, 80, 20, 60, 10, 70, 30, 50] {
+            output = filter.filter(input);
+        }
+        assert_eq!(filter.quartiles(), Some((20, 60)));
+        assert_eq!(output, 40);
+    }
+
+    #[test]
+    fn ignores_outliers() {
+        let mut filter: InterquartileRangeArray<f64, 9> = InterquartileRangeArray::default();
+        let mut output = 0.0;
+        for input in [1.0, 2.0, 1e9, 3.0, 4.0, -1e9, 5.0, 6.0, 7.0] {
+            output = filter.filter(input);
+        }
+        // The sorted window is [−1e9, 1, 2, 3, 4, 5, 6, 7, 1e9].
+        approx::assert_abs_diff_eq!(output, 4.0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_and_ref_mut_storage() {
+        let mut vec = InterquartileRange::from_parts(MedianVec::new(5));
+        let mut buffer: [ListNode<i32>; 5] = core::array::from_fn(|index| ListNode {
+            value: None,
+            previous: (index + 4) % 5,
+            next: (index + 1) % 5,
+        });
+        let mut ref_mut = InterquartileRange::from_parts(MedianRefMut::from_parts(&mut buffer[..]));
+        let mut array: InterquartileRangeArray<_, 5> = InterquartileRangeArray::default();
+        for input in [3, 9, 1, 7, 5, 2, 8] {
+            let expected = array.filter(input);
+            assert_eq!(vec.filter(input), expected);
+            assert_eq!(ref_mut.filter(input), expected);
+        }
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter: InterquartileRangeArray<i32, 3> = InterquartileRangeArray::default();
+        filter.filter(1);
+        filter.filter(5);
+        let mut filter = filter.reset();
+        assert_eq!(filter.filter(3), 0);
+    }
+}
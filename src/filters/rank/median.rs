@@ -37,6 +37,49 @@ where
     }
 }
 
+/// An iterator over a [`Median`] filter's window values in ascending order.
+///
+/// Created by [`Median::sorted_iter`].
+#[derive(Clone, Debug)]
+pub struct SortedIter<'a, T> {
+    buffer: &'a [ListNode<T>],
+    front: usize,
+    back: usize,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for SortedIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = &self.buffer[self.front];
+        self.front = node.next;
+        self.remaining -= 1;
+        node.value.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for SortedIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = &self.buffer[self.back];
+        self.back = node.previous;
+        self.remaining -= 1;
+        node.value.as_ref()
+    }
+}
+
+impl<T> ExactSizeIterator for SortedIter<'_, T> {}
+
 /// The median filter's state.
 ///
 /// `B` is the buffer storage container and must implement [`AsSlice<ListNode<T>>`].
@@ -346,6 +389,42 @@ where
         buf[index].value.clone()
     }
 
+    /// Returns the number of values currently in the window.
+    ///
+    /// This grows with every sample until it reaches the window size.
+    pub fn count(&self) -> usize {
+        self.state.filled
+    }
+
+    /// Returns the `rank`-th smallest value in the window (`0` being the
+    /// minimum), or `None` if the window holds at most `rank` values.
+    pub fn nth(&self, rank: usize) -> Option<T> {
+        if rank >= self.state.filled {
+            return None;
+        }
+        let buffer = self.state.buffer.as_slice();
+        let index = (0..rank).fold(self.state.head, |index, _| buffer[index].next);
+        buffer[index].value.clone()
+    }
+
+    /// Iterates the current window values in ascending order.
+    pub fn sorted_iter(&self) -> SortedIter<'_, T> {
+        let buffer = self.state.buffer.as_slice();
+        let remaining = self.state.filled;
+        let front = self.state.head;
+        // As in `max`, the back of a full window is the head's predecessor. While
+        // the window is filling, the empty nodes trail the values, so step back
+        // over them.
+        let back = (remaining..buffer.len())
+            .fold(buffer[front].previous, |index, _| buffer[index].previous);
+        SortedIter {
+            buffer,
+            front,
+            back,
+            remaining,
+        }
+    }
+
     /// Iterates the current window values.
     /// Returns the populated values in the current window.
     pub fn window_iter(&self) -> impl Iterator<Item = &T> {
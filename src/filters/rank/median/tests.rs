@@ -145,6 +145,44 @@ fn min_max_median() {
     assert_eq!(filter.median(), Some(30));
 }
 
+#[test]
+fn nth_walks_sorted_window() {
+    let mut filter: MedianArray<_, 5> = MedianArray::default();
+    filter.filter(30);
+    filter.filter(10);
+    assert_eq!(filter.count(), 2);
+    assert_eq!(filter.nth(1), Some(30));
+    assert_eq!(filter.nth(2), None);
+    for input in [50, 20, 40, 60] {
+        filter.filter(input);
+    }
+    // Window = [10, 50, 20, 40, 60] after the oldest value dropped out.
+    assert_eq!(filter.count(), 5);
+    let sorted: Vec<_> = (0..5).filter_map(|rank| filter.nth(rank)).collect();
+    assert_eq!(sorted, vec![10, 20, 40, 50, 60]);
+    assert_eq!(filter.nth(0), filter.min());
+    assert_eq!(filter.nth(2), filter.median());
+    assert_eq!(filter.nth(4), filter.max());
+}
+
+#[test]
+fn sorted_iter_walks_sorted_window() {
+    let mut filter: MedianArray<_, 5> = MedianArray::default();
+    for input in [30, 10, 50, 20, 40, 60] {
+        filter.filter(input);
+    }
+    // Window = [10, 50, 20, 40, 60] after the oldest value dropped out.
+    let ascending: Vec<_> = filter.sorted_iter().copied().collect();
+    assert_eq!(ascending, vec![10, 20, 40, 50, 60]);
+    let descending: Vec<_> = filter.sorted_iter().rev().copied().collect();
+    assert_eq!(descending, vec![60, 50, 40, 20, 10]);
+    let mut partial: MedianArray<_, 4> = MedianArray::default();
+    partial.filter(2);
+    partial.filter(1);
+    assert_eq!(partial.sorted_iter().rev().len(), 2);
+    assert_eq!(partial.sorted_iter().next_back(), Some(&2));
+}
+
 #[test]
 #[should_panic(expected = "window size N must be > 0")]
 fn zero_window_panics() {
@@ -0,0 +1,394 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Moving median absolute deviation (MAD) filters.
+
+use num_traits::float::FloatCore;
+
+use crate::storage::AsSlice;
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::median::{ListNode, Median, MedianArray};
+
+/// The MAD's consistency factor for normally distributed data, `1 / Φ⁻¹(3/4)`.
+///
+/// Scaling the MAD by this factor yields an estimate of the standard deviation:
+/// <https://en.wikipedia.org/wiki/Median_absolute_deviation#Relation_to_standard_deviation>
+pub const NORMAL_SCALE: f64 = 1.4826;
+
+/// The median absolute deviation filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The factor applied to the raw MAD (`> 0`).
+    ///
+    /// Use [`NORMAL_SCALE`] to estimate the standard deviation of normally
+    /// distributed data, or `1` for the raw MAD.
+    pub scale: T,
+}
+
+/// The median absolute deviation filter's state.
+#[derive(Clone)]
+pub struct State<T, B> {
+    /// Median filter maintaining the sorted window.
+    pub median: Median<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for State<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("State")
+            .field("median", &self.median)
+            .finish()
+    }
+}
+
+/// A moving median absolute deviation (MAD) filter, outputting
+/// `scale · median(|xᵢ − median(x)|)` of a sliding window.
+///
+/// The MAD is the most robust measure of spread: up to half of the window may
+/// be outliers before it breaks down. Both medians use the lower median of the
+/// `n` available values, i.e. the value of rank `⌊(n − 1) / 2⌋`, so the output
+/// is always the distance between two values of the window.
+///
+/// NaN inputs are rejected: they are not inserted into the window, and the
+/// filter returns the current window's MAD (NaN while the window is empty).
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); delegates to the internal `Median` filter (O(N)), then
+///   merges the deviations below and above the median, which are already sorted
+///   by the window's sorted linked list.
+/// - **Space:** O(N); stores the internal `Median<T, B>` window.
+#[derive(Clone)]
+pub struct MedianAbsoluteDeviation<T, B> {
+    config: Config<T>,
+    state: State<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for MedianAbsoluteDeviation<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("MedianAbsoluteDeviation")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// A [`MedianAbsoluteDeviation`] filter backed by a fixed-size array.
+pub type MedianAbsoluteDeviationArray<T, const N: usize> =
+    MedianAbsoluteDeviation<T, [ListNode<T>; N]>;
+
+/// A [`MedianAbsoluteDeviation`] filter backed by a heap-allocated `Vec`.
+#[cfg(feature = "alloc")]
+pub type MedianAbsoluteDeviationVec<T> = MedianAbsoluteDeviation<T, alloc::vec::Vec<ListNode<T>>>;
+
+/// A [`MedianAbsoluteDeviation`] filter that borrows a caller-owned slice.
+pub type MedianAbsoluteDeviationRefMut<'a, T> = MedianAbsoluteDeviation<T, &'a mut [ListNode<T>]>;
+
+impl<T, B> MedianAbsoluteDeviation<T, B>
+where
+    T: FloatCore,
+    B: AsSlice<ListNode<T>>,
+{
+    /// Creates a [`MedianAbsoluteDeviation`] from pre-initialised parts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not positive.
+    pub fn from_parts(config: Config<T>, median: Median<T, B>) -> Self {
+        assert!(
+            config.scale > T::zero(),
+            "MedianAbsoluteDeviation: scale must be > 0"
+        );
+        Self {
+            config,
+            state: State { median },
+        }
+    }
+
+    /// Returns the window size of the filter.
+    pub fn len(&self) -> usize {
+        self.state.median.len()
+    }
+
+    /// Returns `true` if the filter's window is empty, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.state.median.is_empty()
+    }
+
+    /// Returns the (lower) median of the current window, or `None` if the
+    /// filter has not yet received any (non-NaN) values.
+    pub fn median(&self) -> Option<T> {
+        let count = self.state.median.count();
+        self.state.median.nth(count.checked_sub(1)? / 2)
+    }
+
+    /// Returns the unscaled MAD of the current window, or `None` if the
+    /// filter has not yet received any (non-NaN) values.
+    pub fn deviation(&self) -> Option<T> {
+        let median = &self.state.median;
+        let count = median.count();
+        let rank = count.checked_sub(1)? / 2;
+        let center = median.nth(rank)?;
+
+        // Walking outwards from the median, the deviations on either side
+        // grow monotonically, so merging both sides visits them in order.
+        let mut below = median
+            .sorted_iter()
+            .rev()
+            .skip(count - 1 - rank)
+            .map(|&value| center - value)
+            .peekable();
+        let mut above = median
+            .sorted_iter()
+            .skip(rank + 1)
+            .map(|&value| value - center)
+            .peekable();
+        let mut deviation = None;
+        for _ in 0..=rank {
+            deviation = match (below.peek(), above.peek()) {
+                (Some(lower), Some(upper)) if upper < lower => above.next(),
+                (Some(_), _) => below.next(),
+                (None, _) => above.next(),
+            };
+        }
+        deviation
+    }
+
+    /// Returns the scaled MAD of the current window, or `None` if the
+    /// filter has not yet received any (non-NaN) values.
+    pub fn value(&self) -> Option<T> {
+        self.deviation()
+            .map(|deviation| deviation * self.config.scale)
+    }
+
+    /// Returns the (lower) median and the scaled MAD of the current window,
+    /// or `None` if the filter has not yet received any (non-NaN) values.
+    pub(crate) fn median_and_value(&self) -> Option<(T, T)> {
+        Some((self.median()?, self.value()?))
+    }
+}
+
+impl<T, B> ConfigTrait for MedianAbsoluteDeviation<T, B> {
+    type Config = Config<T>;
+}
+
+impl<T, B> StateTrait for MedianAbsoluteDeviation<T, B> {
+    type State = State<T, B>;
+}
+
+impl<T, const N: usize> WithConfig for MedianAbsoluteDeviationArray<T, N>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `N` is zero or `scale` is not positive.
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(config, MedianArray::default())
+    }
+}
+
+impl<T, B> ConfigRef for MedianAbsoluteDeviation<T, B> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, B> ConfigClone for MedianAbsoluteDeviation<T, B>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, B> StateMut for MedianAbsoluteDeviation<T, B> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, B> HasGuts for MedianAbsoluteDeviation<T, B> {
+    type Guts = (Config<T>, State<T, B>);
+}
+
+impl<T, B> FromGuts for MedianAbsoluteDeviation<T, B> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, B> IntoGuts for MedianAbsoluteDeviation<T, B> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize> Reset for MedianAbsoluteDeviationArray<T, N>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for MedianAbsoluteDeviationArray<T, N> where Self: Reset {}
+
+impl<T, B> Filter<T> for MedianAbsoluteDeviation<T, B>
+where
+    T: FloatCore,
+    B: AsSlice<ListNode<T>>,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        if !input.is_nan() {
+            self.state.median.filter(input);
+        }
+        self.value().unwrap_or_else(T::nan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::filters::rank::median::{MedianRefMut, MedianVec};
+    use crate::util::test_fixtures::quantized_noise;
+
+    fn lower_median(values: &mut [f64]) -> f64 {
+        values.sort_by(f64::total_cmp);
+        values[(values.len() - 1) / 2]
+    }
+
+    fn reference(window: &[f64]) -> f64 {
+        let median = lower_median(&mut window.to_vec());
+        let mut deviations: Vec<_> = window.iter().map(|value| (value - median).abs()).collect();
+        lower_median(&mut deviations)
+    }
+
+    fn filter<const N: usize>(scale: f64) -> MedianAbsoluteDeviationArray<f64, N> {
+        MedianAbsoluteDeviationArray::with_config(Config { scale })
+    }
+
+    #[test]
+    fn matches_reference_implementation() {
+        fn check<const N: usize>() {
+            let mut filter = filter::<N>(1.0);
+            let mut window = Vec::new();
+            for n in 0_u32..200 {
+                let input = quantized_noise(n, 16);
+                window.push(input);
+                if window.len() > N {
+                    window.remove(0);
+                }
+                assert_abs_diff_eq!(filter.filter(input), reference(&window));
+                assert_abs_diff_eq!(
+                    filter.median().expect("window is non-empty"),
+                    lower_median(&mut window.clone())
+                );
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<7>();
+        check::<10>();
+    }
+
+    #[test]
+    fn scales_to_standard_deviation() {
+        let mut filter = filter::<5>(NORMAL_SCALE);
+        let mut output = 0.0;
+        for input in [1.0, 3.0, 5.0, 7.0, 9.0] {
+            output = filter.filter(input);
+        }
+        assert_eq!(filter.deviation(), Some(2.0));
+        assert_abs_diff_eq!(output, 2.0 * NORMAL_SCALE);
+    }
+
+    #[test]
+    fn ignores_outliers() {
+        let mut filter = filter::<7>(1.0);
+        let mut output = 0.0;
+        for input in [1.0, 1e9, 2.0, 3.0, -1e9, 4.0, 5.0] {
+            output = filter.filter(input);
+        }
+        // The median is 3; the sorted deviations are [0, 1, 1, 2, 2, 1e9, 1e9].
+        assert_abs_diff_eq!(output, 2.0);
+    }
+
+    #[test]
+    fn skips_nan() {
+        let mut filter = filter::<3>(1.0);
+        assert!(filter.filter(f64::NAN).is_nan());
+        filter.filter(1.0);
+        filter.filter(5.0);
+        filter.filter(9.0);
+        assert_abs_diff_eq!(filter.filter(f64::NAN), 4.0);
+        // The window is [5, 9, 3], not [9, NaN, 3].
+        assert_abs_diff_eq!(filter.filter(3.0), 2.0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_and_ref_mut_storage() {
+        let config = Config { scale: 1.0 };
+        let mut array = filter::<5>(1.0);
+        let mut vec = MedianAbsoluteDeviation::from_parts(config.clone(), MedianVec::new(5));
+        let mut buffer: [ListNode<f64>; 5] = core::array::from_fn(|index| ListNode {
+            value: None,
+            previous: (index + 4) % 5,
+            next: (index + 1) % 5,
+        });
+        let mut ref_mut =
+            MedianAbsoluteDeviation::from_parts(config, MedianRefMut::from_parts(&mut buffer[..]));
+        for n in 0_u32..50 {
+            let input = quantized_noise(n, 16);
+            let expected = array.filter(input);
+            assert_abs_diff_eq!(vec.filter(input), expected);
+            assert_abs_diff_eq!(ref_mut.filter(input), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "scale must be > 0")]
+    fn zero_scale_panics() {
+        let _ = filter::<3>(0.0);
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter = filter::<3>(1.0);
+        filter.filter(1.0);
+        filter.filter(9.0);
+        let mut filter = filter.reset();
+        assert_eq!(filter.median(), None);
+        assert_abs_diff_eq!(filter.filter(4.0), 0.0);
+    }
+}
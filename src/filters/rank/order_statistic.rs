@@ -0,0 +1,396 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Moving order-statistic (rank and quantile) filters.
+
+use crate::storage::AsSlice;
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::median::{ListNode, Median, MedianArray};
+
+/// The position of the selected value within the sorted window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rank {
+    /// The `k`-th smallest value of a full window (`0` being the minimum and
+    /// `N − 1` the maximum).
+    ///
+    /// While the window is still filling up, the rank is scaled to the
+    /// number of available values, so that the minimum, median and maximum
+    /// stay the minimum, median and maximum.
+    Index(usize),
+    /// The `q`-quantile (`0 ≤ q ≤ 1`), e.g. `0.9` for the 90th percentile.
+    ///
+    /// Resolved to the nearest rank `round(q·(N − 1))` of a full window when
+    /// the filter is built, so the output is always one of the window's values
+    /// and filtering uses integer arithmetic only. While the window is still
+    /// filling up, the rank is scaled like an [`Index`](Self::Index).
+    Quantile(f64),
+}
+
+impl Rank {
+    /// Returns the rank within a full window of size `len`.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn resolve(self, len: usize) -> usize {
+        match self {
+            Self::Index(rank) => rank,
+            Self::Quantile(quantile) => (quantile * len.saturating_sub(1) as f64 + 0.5) as usize,
+        }
+    }
+}
+
+/// Scales a `rank` within a full window of size `len` to a window currently
+/// holding `count` values.
+fn scale(rank: usize, count: usize, len: usize) -> usize {
+    if len > 1 {
+        rank * count.saturating_sub(1) / (len - 1)
+    } else {
+        0
+    }
+}
+
+/// The order-statistic filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The rank of the output value within the sorted window.
+    pub rank: Rank,
+}
+
+/// The order-statistic filter's state.
+#[derive(Clone)]
+pub struct State<T, B> {
+    /// Median filter maintaining the sorted window.
+    pub median: Median<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for State<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("State")
+            .field("median", &self.median)
+            .finish()
+    }
+}
+
+/// A moving order-statistic filter, outputting the value of a given rank or
+/// quantile within a sliding window.
+///
+/// Generalizes [`Median`] (rank `N / 2`), [`Min`](super::min::Min) (rank `0`)
+/// and [`Max`](super::max::Max) (rank `N − 1`) to arbitrary ranks, e.g. for
+/// 10th/90th percentile envelopes. The window is kept sorted by an internal
+/// [`Median`] filter, whose embedded linked list is walked to the selected
+/// rank.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); delegates to the internal `Median` filter (O(N)), then
+///   walks at most N nodes of its sorted linked list to the selected rank.
+/// - **Space:** O(N); stores the internal `Median<T, B>` window.
+#[derive(Clone)]
+pub struct OrderStatistic<T, B> {
+    config: Config,
+    state: State<T, B>,
+    rank: usize,
+}
+
+impl<T, B> core::fmt::Debug for OrderStatistic<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("OrderStatistic")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .field("rank", &self.rank)
+            .finish()
+    }
+}
+
+/// An [`OrderStatistic`] filter backed by a fixed-size array.
+pub type OrderStatisticArray<T, const N: usize> = OrderStatistic<T, [ListNode<T>; N]>;
+
+/// An [`OrderStatistic`] filter backed by a heap-allocated `Vec`.
+#[cfg(feature = "alloc")]
+pub type OrderStatisticVec<T> = OrderStatistic<T, alloc::vec::Vec<ListNode<T>>>;
+
+/// An [`OrderStatistic`] filter that borrows a caller-owned slice.
+pub type OrderStatisticRefMut<'a, T> = OrderStatistic<T, &'a mut [ListNode<T>]>;
+
+impl<T, B> OrderStatistic<T, B>
+where
+    T: Clone,
+    B: AsSlice<ListNode<T>>,
+{
+    /// Creates an [`OrderStatistic`] from pre-initialised parts.
+    ///
+    /// Use [`MedianVec::new`](super::median::MedianVec::new) or
+    /// [`Median::from_parts`] to create the median filter for `Vec` or
+    /// borrowed storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rank index is not less than the window size, or the
+    /// quantile is not within `[0, 1]`.
+    pub fn from_parts(config: Config, median: Median<T, B>) -> Self {
+        match config.rank {
+            Rank::Index(rank) => assert!(
+                rank < median.len(),
+                "OrderStatistic: rank must be < window size"
+            ),
+            Rank::Quantile(quantile) => assert!(
+                (0.0..=1.0).contains(&quantile),
+                "OrderStatistic: quantile must be within [0, 1]"
+            ),
+        }
+        let rank = config.rank.resolve(median.len());
+        Self {
+            config,
+            state: State { median },
+            rank,
+        }
+    }
+
+    /// Returns the window size of the filter.
+    pub fn len(&self) -> usize {
+        self.state.median.len()
+    }
+
+    /// Returns `true` if the filter's window is empty, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.state.median.is_empty()
+    }
+
+    /// Returns the value of the configured rank in the current window, or
+    /// `None` if the filter has not yet received any values.
+    pub fn value(&self) -> Option<T> {
+        self.select(self.rank)
+    }
+
+    /// Returns the value of an arbitrary `rank` in the current window, or
+    /// `None` if the filter has not yet received any values.
+    ///
+    /// Out-of-range ranks are clamped to the window's extremes.
+    pub fn rank(&self, rank: Rank) -> Option<T> {
+        self.select(rank.resolve(self.len()))
+    }
+
+    fn select(&self, rank: usize) -> Option<T> {
+        let median = &self.state.median;
+        let count = median.count();
+        median.nth(scale(rank, count, median.len()).min(count.saturating_sub(1)))
+    }
+}
+
+impl<T, B> ConfigTrait for OrderStatistic<T, B> {
+    type Config = Config;
+}
+
+impl<T, B> StateTrait for OrderStatistic<T, B> {
+    type State = State<T, B>;
+}
+
+impl<T, const N: usize> WithConfig for OrderStatisticArray<T, N>
+where
+    T: Clone,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `N` is zero, the rank index is not less than `N`, or the
+    /// quantile is not within `[0, 1]`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(config, MedianArray::default())
+    }
+}
+
+impl<T, B> ConfigRef for OrderStatistic<T, B> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, B> ConfigClone for OrderStatistic<T, B> {
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, B> StateMut for OrderStatistic<T, B> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, B> HasGuts for OrderStatistic<T, B> {
+    type Guts = (Config, State<T, B>);
+}
+
+impl<T, B> FromGuts for OrderStatistic<T, B>
+where
+    T: Clone,
+    B: AsSlice<ListNode<T>>,
+{
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        let rank = config.rank.resolve(state.median.len());
+        Self {
+            config,
+            state,
+            rank,
+        }
+    }
+}
+
+impl<T, B> IntoGuts for OrderStatistic<T, B> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize> Reset for OrderStatisticArray<T, N>
+where
+    T: Clone,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for OrderStatisticArray<T, N> where Self: Reset {}
+
+impl<T, B> Filter<T> for OrderStatistic<T, B>
+where
+    T: Clone + PartialOrd,
+    B: AsSlice<ListNode<T>>,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        self.state.median.filter(input);
+        self.value()
+            .expect("window must be non-empty after the first filter() call")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::filters::rank::median::{MedianRefMut, MedianVec};
+    use crate::filters::rank::{max::MaxArray, min::MinArray};
+
+    fn filter<const N: usize>(rank: Rank) -> OrderStatisticArray<i32, N> {
+        OrderStatisticArray::with_config(Config { rank })
+    }
+
+    #[test]
+    fn extreme_ranks_match_min_and_max() {
+        let input = [5, 3, 8, 1, 9, 2, 7, 4, 6, 0];
+        let mut lowest = filter::<4>(Rank::Index(0));
+        let mut highest = filter::<4>(Rank::Index(3));
+        let mut min: MinArray<_, 4> = MinArray::default();
+        let mut max: MaxArray<_, 4> = MaxArray::default();
+        for input in input {
+            assert_eq!(lowest.filter(input), min.filter(input));
+            assert_eq!(highest.filter(input), max.filter(input));
+        }
+    }
+
+    #[test]
+    fn middle_rank_matches_median() {
+        let input = [5, 3, 8, 1, 9, 2, 7, 4, 6, 0];
+        let mut middle = filter::<5>(Rank::Index(2));
+        let mut half = filter::<5>(Rank::Quantile(0.5));
+        let mut median: MedianArray<_, 5> = MedianArray::default();
+        for input in input {
+            let expected = median.filter(input);
+            assert_eq!(middle.filter(input), expected);
+            assert_eq!(half.filter(input), expected);
+        }
+    }
+
+    #[test]
+    fn quantiles_select_nearest_rank() {
+        let mut filter = filter::<11>(Rank::Quantile(0.9));
+        let output: Vec<_> = (0..11).map(|input| filter.filter(10 - input)).collect();
+        // The full window holds 0..=10, whose 90th percentile is 9.
+        assert_eq!(output.last(), Some(&9));
+        assert_eq!(filter.rank(Rank::Quantile(0.1)), Some(1));
+        assert_eq!(filter.rank(Rank::Index(10)), Some(10));
+        assert_eq!(filter.rank(Rank::Index(20)), Some(10));
+    }
+
+    #[test]
+    fn quantiles_scale_like_indices_while_filling() {
+        let mut quantile = filter::<11>(Rank::Quantile(0.9));
+        let mut index = filter::<11>(Rank::Index(9));
+        for input in [5, 3, 8, 1, 9, 2, 7, 4, 6, 0, 10, 12] {
+            assert_eq!(quantile.filter(input), index.filter(input));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_and_ref_mut_storage() {
+        let input = [4, 1, 3, 5, 2];
+        let config = Config {
+            rank: Rank::Index(1),
+        };
+        let mut array = OrderStatisticArray::<_, 3>::with_config(config.clone());
+        let mut vec = OrderStatistic::from_parts(config.clone(), MedianVec::new(3));
+        let mut buffer: [ListNode<i32>; 3] = core::array::from_fn(|index| ListNode {
+            value: None,
+            previous: (index + 2) % 3,
+            next: (index + 1) % 3,
+        });
+        let mut ref_mut =
+            OrderStatistic::from_parts(config, MedianRefMut::from_parts(&mut buffer[..]));
+        let output: Vec<_> = input.iter().map(|&input| array.filter(input)).collect();
+        assert_eq!(output, [4, 1, 3, 3, 3]);
+        for input in input {
+            let expected = vec.filter(input);
+            assert_eq!(ref_mut.filter(input), expected);
+        }
+        assert_eq!(vec.value(), array.value());
+    }
+
+    #[test]
+    #[should_panic(expected = "rank must be < window size")]
+    fn rank_out_of_window_panics() {
+        let _ = filter::<3>(Rank::Index(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "quantile must be within [0, 1]")]
+    fn quantile_out_of_range_panics() {
+        let _ = filter::<3>(Rank::Quantile(1.5));
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter = filter::<3>(Rank::Index(2));
+        filter.filter(10);
+        let mut filter = filter.reset();
+        assert_eq!(filter.value(), None);
+        assert_eq!(filter.filter(1), 1);
+    }
+}
@@ -0,0 +1,355 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Moving robust z-score filters.
+
+use num_traits::float::FloatCore;
+
+use crate::storage::AsSlice;
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::{
+    median::{ListNode, Median},
+    median_absolute_deviation::{Config, MedianAbsoluteDeviation, MedianAbsoluteDeviationArray},
+};
+
+/// The robust z-score filter's state.
+#[derive(Clone)]
+pub struct State<T, B> {
+    /// MAD filter maintaining the sorted window.
+    pub deviation: MedianAbsoluteDeviation<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for State<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("State")
+            .field("deviation", &self.deviation)
+            .finish()
+    }
+}
+
+/// A moving robust z-score filter, outputting how many (robust) standard
+/// deviations each sample lies from its window's median:
+///
+/// ```text
+/// z = (x − median) / (scale · MAD)
+/// ```
+///
+/// The median and MAD are those of the [`MedianAbsoluteDeviation`] filter's
+/// window, which includes the current sample. With
+/// [`NORMAL_SCALE`](super::median_absolute_deviation::NORMAL_SCALE), the output
+/// is comparable to a classical z-score for normally distributed data, but is
+/// not inflated by the outliers it is meant to detect; `|z| > 3` is a common
+/// outlier threshold.
+///
+/// A sample equal to the median has a z-score of `0`, even if the MAD is
+/// zero; any other sample in a window with zero MAD has an infinite z-score.
+/// NaN inputs are rejected: they are not inserted into the window, and their
+/// z-score is NaN.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); delegates to the internal `MedianAbsoluteDeviation`
+///   filter (O(N)).
+/// - **Space:** O(N); stores the internal `Median<T, B>` window.
+#[derive(Clone)]
+pub struct RobustZScore<T, B> {
+    state: State<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for RobustZScore<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("RobustZScore")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// A [`RobustZScore`] filter backed by a fixed-size array.
+pub type RobustZScoreArray<T, const N: usize> = RobustZScore<T, [ListNode<T>; N]>;
+
+/// A [`RobustZScore`] filter backed by a heap-allocated `Vec`.
+#[cfg(feature = "alloc")]
+pub type RobustZScoreVec<T> = RobustZScore<T, alloc::vec::Vec<ListNode<T>>>;
+
+/// A [`RobustZScore`] filter that borrows a caller-owned slice.
+pub type RobustZScoreRefMut<'a, T> = RobustZScore<T, &'a mut [ListNode<T>]>;
+
+impl<T, B> RobustZScore<T, B>
+where
+    T: FloatCore,
+    B: AsSlice<ListNode<T>>,
+{
+    /// Creates a [`RobustZScore`] from pre-initialised parts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not positive.
+    pub fn from_parts(config: Config<T>, median: Median<T, B>) -> Self {
+        let deviation = MedianAbsoluteDeviation::from_parts(config, median);
+        Self {
+            state: State { deviation },
+        }
+    }
+
+    /// Returns the window size of the filter.
+    pub fn len(&self) -> usize {
+        self.state.deviation.len()
+    }
+
+    /// Returns `true` if the filter's window is empty, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.state.deviation.is_empty()
+    }
+
+    /// Returns the (lower) median of the current window, or `None` if the
+    /// filter has not yet received any (non-NaN) values.
+    pub fn median(&self) -> Option<T> {
+        self.state.deviation.median()
+    }
+
+    /// Returns the scaled MAD of the current window, or `None` if the
+    /// filter has not yet received any (non-NaN) values.
+    pub fn deviation(&self) -> Option<T> {
+        self.state.deviation.value()
+    }
+
+    /// Returns the z-score of `value` relative to the current window, without
+    /// inserting it, or `None` if the filter has not yet received any
+    /// (non-NaN) values.
+    pub fn score(&self, value: T) -> Option<T> {
+        let (median, deviation) = self.state.deviation.median_and_value()?;
+        if value == median {
+            return Some(T::zero());
+        }
+        Some((value - median) / deviation)
+    }
+}
+
+impl<T, B> ConfigTrait for RobustZScore<T, B> {
+    type Config = Config<T>;
+}
+
+impl<T, B> StateTrait for RobustZScore<T, B> {
+    type State = State<T, B>;
+}
+
+impl<T, const N: usize> WithConfig for RobustZScoreArray<T, N>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `N` is zero or `scale` is not positive.
+    fn with_config(config: Self::Config) -> Self::Output {
+        let deviation = MedianAbsoluteDeviationArray::with_config(config);
+        Self {
+            state: State { deviation },
+        }
+    }
+}
+
+impl<T, B> ConfigRef for RobustZScore<T, B> {
+    fn config_ref(&self) -> &Self::Config {
+        self.state.deviation.config_ref()
+    }
+}
+
+impl<T, B> ConfigClone for RobustZScore<T, B>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.state.deviation.config()
+    }
+}
+
+impl<T, B> StateMut for RobustZScore<T, B> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, B> HasGuts for RobustZScore<T, B> {
+    type Guts = State<T, B>;
+}
+
+impl<T, B> FromGuts for RobustZScore<T, B> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let state = guts;
+        Self { state }
+    }
+}
+
+impl<T, B> IntoGuts for RobustZScore<T, B> {
+    fn into_guts(self) -> Self::Guts {
+        self.state
+    }
+}
+
+impl<T, const N: usize> Reset for RobustZScoreArray<T, N>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config())
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for RobustZScoreArray<T, N> where Self: Reset {}
+
+impl<T, B> Filter<T> for RobustZScore<T, B>
+where
+    T: FloatCore,
+    B: AsSlice<ListNode<T>>,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        if input.is_nan() {
+            return input;
+        }
+        self.state.deviation.filter(input);
+        self.score(input)
+            .expect("window must be non-empty after filtering a non-NaN value")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::filters::rank::median::MedianVec;
+    use crate::filters::rank::median_absolute_deviation::NORMAL_SCALE;
+    use crate::util::test_fixtures::quantized_noise;
+
+    fn lower_median(values: &mut [f64]) -> f64 {
+        values.sort_by(f64::total_cmp);
+        values[(values.len() - 1) / 2]
+    }
+
+    fn reference(window: &[f64], input: f64) -> f64 {
+        let median = lower_median(&mut window.to_vec());
+        let mut deviations: Vec<_> = window.iter().map(|value| (value - median).abs()).collect();
+        let mad = NORMAL_SCALE * lower_median(&mut deviations);
+        if input == median {
+            0.0
+        } else {
+            (input - median) / mad
+        }
+    }
+
+    fn filter<const N: usize>() -> RobustZScoreArray<f64, N> {
+        RobustZScoreArray::with_config(Config {
+            scale: NORMAL_SCALE,
+        })
+    }
+
+    #[test]
+    fn matches_reference_implementation() {
+        const N: usize = 9;
+        let mut filter = filter::<N>();
+        let mut window = Vec::new();
+        for n in 0_u32..200 {
+            let input = quantized_noise(n, 16);
+            window.push(input);
+            if window.len() > N {
+                window.remove(0);
+            }
+            let expected = reference(&window, input);
+            let output = filter.filter(input);
+            if expected.is_infinite() {
+                assert_eq!(output, expected);
+            } else {
+                assert_abs_diff_eq!(output, expected, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn flags_outliers() {
+        let mut filter = filter::<7>();
+        for n in 0_u32..20 {
+            let z = filter.filter(f64::from(n % 3));
+            // Until the window fills up, its MAD may still be zero.
+            if n >= 7 {
+                assert!(z.abs() < 1.0, "z = {z}");
+            }
+        }
+        assert!(filter.filter(50.0) > 3.0);
+        assert!(filter.filter(-50.0) < -3.0);
+    }
+
+    #[test]
+    fn constant_window() {
+        let mut filter = filter::<3>();
+        assert_eq!(filter.filter(2.0), 0.0);
+        assert_eq!(filter.filter(2.0), 0.0);
+        assert_eq!(filter.deviation(), Some(0.0));
+        // The window [2, 2, 5] still has a MAD of zero.
+        assert_eq!(filter.filter(5.0), f64::INFINITY);
+        assert_eq!(filter.score(-1.0), Some(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn skips_nan() {
+        let mut filter = filter::<3>();
+        assert!(filter.filter(f64::NAN).is_nan());
+        assert_eq!(filter.median(), None);
+        filter.filter(1.0);
+        filter.filter(3.0);
+        assert!(filter.filter(f64::NAN).is_nan());
+        assert_eq!(filter.median(), Some(1.0));
+        // The window is [1, 3, 5], not [3, NaN, 5].
+        assert_abs_diff_eq!(filter.filter(5.0), 1.0 / NORMAL_SCALE);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_storage_matches_array() {
+        let config = Config {
+            scale: NORMAL_SCALE,
+        };
+        let mut array = filter::<5>();
+        let mut vec = RobustZScore::from_parts(config, MedianVec::new(5));
+        for n in 0_u32..50 {
+            let input = quantized_noise(n, 16);
+            let expected = array.filter(input);
+            let output = vec.filter(input);
+            assert!(output == expected || (output - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter = filter::<3>();
+        filter.filter(1.0);
+        filter.filter(9.0);
+        let mut filter = filter.reset();
+        assert_eq!(filter.median(), None);
+        assert_eq!(filter.filter(4.0), 0.0);
+    }
+}
@@ -0,0 +1,328 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Moving alpha-trimmed mean filters.
+
+use num_traits::float::FloatCore;
+
+use crate::storage::AsSlice;
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::median::{ListNode, Median, MedianArray};
+
+/// The trimmed mean filter's configuration.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// The proportion `α` of values trimmed from each end of the sorted
+    /// window (`0 ≤ α < 0.5`).
+    ///
+    /// `0` yields the plain mean; values close to `0.5` approach the median.
+    pub trim: T,
+}
+
+/// The trimmed mean filter's state.
+#[derive(Clone)]
+pub struct State<T, B> {
+    /// Median filter maintaining the sorted window.
+    pub median: Median<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for State<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("State")
+            .field("median", &self.median)
+            .finish()
+    }
+}
+
+/// A moving alpha-trimmed mean filter.
+///
+/// Sorts the `n` values of the sliding window, discards the `⌊α·n⌋` smallest
+/// and the `⌊α·n⌋` largest, and outputs the mean of the rest. This combines
+/// the outlier rejection of the median with the noise averaging of the mean.
+///
+/// NaN inputs are rejected: they are not inserted into the window, and the
+/// filter returns the current window's trimmed mean (NaN while the window is
+/// empty). Duplicate values are trimmed like any other value, so the output
+/// does not depend on the order in which ties arrived.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(N); delegates to the internal `Median` filter (O(N)), then
+///   sums the untrimmed part of its sorted linked list.
+/// - **Space:** O(N); stores the internal `Median<T, B>` window.
+#[derive(Clone)]
+pub struct TrimmedMean<T, B> {
+    config: Config<T>,
+    state: State<T, B>,
+}
+
+impl<T, B> core::fmt::Debug for TrimmedMean<T, B>
+where
+    T: core::fmt::Debug,
+    B: AsSlice<ListNode<T>>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("TrimmedMean")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// A [`TrimmedMean`] filter backed by a fixed-size array.
+pub type TrimmedMeanArray<T, const N: usize> = TrimmedMean<T, [ListNode<T>; N]>;
+
+/// A [`TrimmedMean`] filter backed by a heap-allocated `Vec`.
+#[cfg(feature = "alloc")]
+pub type TrimmedMeanVec<T> = TrimmedMean<T, alloc::vec::Vec<ListNode<T>>>;
+
+/// A [`TrimmedMean`] filter that borrows a caller-owned slice.
+pub type TrimmedMeanRefMut<'a, T> = TrimmedMean<T, &'a mut [ListNode<T>]>;
+
+impl<T, B> TrimmedMean<T, B>
+where
+    T: FloatCore,
+    B: AsSlice<ListNode<T>>,
+{
+    /// Creates a [`TrimmedMean`] from pre-initialised parts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `trim` is not within `[0, 0.5)`.
+    pub fn from_parts(config: Config<T>, median: Median<T, B>) -> Self {
+        let half = T::one() / (T::one() + T::one());
+        assert!(
+            config.trim >= T::zero() && config.trim < half,
+            "TrimmedMean: trim must be within [0, 0.5)"
+        );
+        Self {
+            config,
+            state: State { median },
+        }
+    }
+
+    /// Returns the window size of the filter.
+    pub fn len(&self) -> usize {
+        self.state.median.len()
+    }
+
+    /// Returns `true` if the filter's window is empty, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.state.median.is_empty()
+    }
+
+    /// Returns the trimmed mean of the current window, or `None` if the
+    /// filter has not yet received any (non-NaN) values.
+    pub fn value(&self) -> Option<T> {
+        let count = self.state.median.count();
+        if count == 0 {
+            return None;
+        }
+        let trimmed = trimmed_count(self.config.trim, count);
+        let kept = count - 2 * trimmed;
+        let sum = self
+            .state
+            .median
+            .sorted_iter()
+            .skip(trimmed)
+            .take(kept)
+            .fold(T::zero(), |sum, &value| sum + value);
+        Some(sum / T::from(kept).unwrap_or_else(T::nan))
+    }
+}
+
+/// Returns the number `⌊α·n⌋` of values trimmed from each end.
+pub(crate) fn trimmed_count<T: FloatCore>(trim: T, count: usize) -> usize {
+    let count = T::from(count).unwrap_or_else(T::nan);
+    (trim * count).floor().to_usize().unwrap_or(0)
+}
+
+impl<T, B> ConfigTrait for TrimmedMean<T, B> {
+    type Config = Config<T>;
+}
+
+impl<T, B> StateTrait for TrimmedMean<T, B> {
+    type State = State<T, B>;
+}
+
+impl<T, const N: usize> WithConfig for TrimmedMeanArray<T, N>
+where
+    T: FloatCore,
+{
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `N` is zero or `trim` is not within `[0, 0.5)`.
+    fn with_config(config: Self::Config) -> Self::Output {
+        Self::from_parts(config, MedianArray::default())
+    }
+}
+
+impl<T, B> ConfigRef for TrimmedMean<T, B> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, B> ConfigClone for TrimmedMean<T, B>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, B> StateMut for TrimmedMean<T, B> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, B> HasGuts for TrimmedMean<T, B> {
+    type Guts = (Config<T>, State<T, B>);
+}
+
+impl<T, B> FromGuts for TrimmedMean<T, B> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T, B> IntoGuts for TrimmedMean<T, B> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize> Reset for TrimmedMeanArray<T, N>
+where
+    T: FloatCore,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize> ResetMut for TrimmedMeanArray<T, N> where Self: Reset {}
+
+impl<T, B> Filter<T> for TrimmedMean<T, B>
+where
+    T: FloatCore,
+    B: AsSlice<ListNode<T>>,
+{
+    type Output = T;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        if !input.is_nan() {
+            self.state.median.filter(input);
+        }
+        self.value().unwrap_or_else(T::nan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::filters::rank::median::MedianVec;
+    use crate::util::test_fixtures::quantized_noise;
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn reference(window: &[f64], trim: f64) -> f64 {
+        let mut sorted = window.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let trimmed = (trim * sorted.len() as f64).floor() as usize;
+        let kept = &sorted[trimmed..sorted.len() - trimmed];
+        kept.iter().sum::<f64>() / kept.len() as f64
+    }
+
+    #[test]
+    fn matches_reference_implementation() {
+        const N: usize = 10;
+        for trim in [0.0, 0.1, 0.25, 0.45] {
+            let mut filter = TrimmedMeanArray::<f64, N>::with_config(Config { trim });
+            let mut window = Vec::new();
+            for n in 0_u32..200 {
+                let input = quantized_noise(n, 16);
+                window.push(input);
+                if window.len() > N {
+                    window.remove(0);
+                }
+                let output = filter.filter(input);
+                assert_abs_diff_eq!(output, reference(&window, trim), epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let mut filter = TrimmedMeanArray::<f64, 5>::with_config(Config { trim: 0.2 });
+        let mut output = 0.0;
+        for input in [1.0, 1e9, 2.0, 3.0, -1e9] {
+            output = filter.filter(input);
+        }
+        assert_abs_diff_eq!(output, 2.0);
+    }
+
+    #[test]
+    fn skips_nan() {
+        let mut filter = TrimmedMeanArray::<f64, 3>::with_config(Config { trim: 0.0 });
+        assert!(filter.filter(f64::NAN).is_nan());
+        filter.filter(1.0);
+        filter.filter(2.0);
+        assert_abs_diff_eq!(filter.filter(f64::NAN), 1.5);
+        assert_abs_diff_eq!(filter.filter(3.0), 2.0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_storage_matches_array() {
+        let config = Config { trim: 0.2 };
+        let mut array = TrimmedMeanArray::<f64, 7>::with_config(config.clone());
+        let mut vec = TrimmedMean::from_parts(config, MedianVec::new(7));
+        for n in 0_u32..50 {
+            let input = quantized_noise(n, 16);
+            assert_abs_diff_eq!(vec.filter(input), array.filter(input));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "trim must be within [0, 0.5)")]
+    fn half_trim_panics() {
+        let _ = TrimmedMeanArray::<f64, 3>::with_config(Config { trim: 0.5 });
+    }
+
+    #[test]
+    fn reset() {
+        let mut filter = TrimmedMeanArray::<f64, 3>::with_config(Config { trim: 0.0 });
+        filter.filter(10.0);
+        let mut filter = filter.reset();
+        assert_eq!(filter.value(), None);
+        assert_abs_diff_eq!(filter.filter(1.0), 1.0);
+    }
+}
@@ -0,0 +1,129 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Single-bin spectral analysis filters; tone detection without a full FFT.
+//!
+//! These filters evaluate a handful of DFT bins directly from the sample stream.
+//! They are the usual choice for detecting a few known tones (pilot tones, DTMF,
+//! mains hum) on targets where a full FFT would be overkill.
+//!
+//! # When to use which filter
+//!
+//! | Filter                         | Purpose                                                    |
+//! | ------------------------------ | ---------------------------------------------------------- |
+//! | `goertzel::Goertzel`           | One bin per block of `N` samples (block-based)             |
+//! | `sliding_dft::SlidingDft`      | `K` bins over the last `N` samples, updated every sample   |
+//!
+//! - **Goertzel** runs a second-order resonator over a block of `N` samples and emits
+//!   one bin when the block completes. It costs one multiply-add per sample and
+//!   supports arbitrary (non-integer) bin frequencies. Use when a decision per block
+//!   is sufficient, e.g. DTMF decoding.
+//! - **`SlidingDft`** keeps the last `N` samples and updates `K` bins on every sample.
+//!   Use when a detector needs a fresh spectral estimate per sample, e.g. to feed a
+//!   [`Schmitt`](super::classify::schmitt::Schmitt) trigger directly.
+//!
+//! Both filters produce [`Bin`] values. Use [`Bin::power`] to feed
+//! [`classify`](super::classify) filters when building detectors, or (with the
+//! `complex` feature) convert a bin into a `Complex<T>`.
+//!
+//! # See also
+//!
+//! - [`super::iir::biquad`]: band-pass designs for continuous tone extraction in the
+//!   time domain.
+
+use core::ops::{Add, Mul};
+
+#[cfg(any(feature = "libm", feature = "std"))]
+use num_traits::Float;
+
+pub mod goertzel;
+
+pub mod sliding_dft;
+
+/// A single complex DFT bin, stored as rectangular components.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bin<T> {
+    /// Real part.
+    pub re: T,
+    /// Imaginary part.
+    pub im: T,
+}
+
+impl<T> Bin<T> {
+    /// Creates a bin from its real and imaginary parts.
+    pub const fn new(re: T, im: T) -> Self {
+        Self { re, im }
+    }
+
+    /// Returns the bin's power, `re² + im²`.
+    pub fn power(&self) -> T
+    where
+        T: Clone + Add<Output = T> + Mul<Output = T>,
+    {
+        self.re.clone() * self.re.clone() + self.im.clone() * self.im.clone()
+    }
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+impl<T> Bin<T>
+where
+    T: Float,
+{
+    /// Returns the bin's magnitude, `√(re² + im²)`.
+    #[must_use]
+    pub fn magnitude(&self) -> T {
+        self.re.hypot(self.im)
+    }
+
+    /// Returns the bin's phase in radians, in `(−π, π]`.
+    #[must_use]
+    pub fn phase(&self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    /// Returns the unit phasor `cos(θ) + j·sin(θ)`.
+    pub(crate) fn from_angle(theta: T) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        Self { re: cos, im: sin }
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T> From<Bin<T>> for crate::complex::Complex<T> {
+    fn from(bin: Bin<T>) -> Self {
+        Self::new(bin.re, bin.im)
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T> From<crate::complex::Complex<T>> for Bin<T> {
+    fn from(complex: crate::complex::Complex<T>) -> Self {
+        Self {
+            re: complex.re,
+            im: complex.im,
+        }
+    }
+}
+
+/// Multiplies two bins as complex numbers.
+pub(crate) fn complex_mul<T>(lhs: &Bin<T>, rhs: &Bin<T>) -> Bin<T>
+where
+    T: Clone + num_traits::Num,
+{
+    Bin {
+        re: lhs.re.clone() * rhs.re.clone() - lhs.im.clone() * rhs.im.clone(),
+        im: lhs.re.clone() * rhs.im.clone() + lhs.im.clone() * rhs.re.clone(),
+    }
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+fn assert_sample_rate<T>(sample_rate: T)
+where
+    T: Float + core::fmt::Debug,
+{
+    assert!(
+        sample_rate.is_finite() && sample_rate > T::zero(),
+        "sample_rate must be finite and > 0 (got {sample_rate:?})"
+    );
+}
@@ -0,0 +1,439 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Goertzel single-bin DFT detector.
+//!
+//! Evaluates one DFT bin over consecutive, non-overlapping blocks of `N` samples
+//! using a second-order resonator:
+//!
+//! ```text
+//! s[n] = x[n] + 2·cos(ω)·s[n−1] − s[n−2]
+//! X(ω) = e^{−jω(N−1)} · (s[N−1] − e^{−jω}·s[N−2])
+//! ```
+//!
+//! The bin frequency `ω` does not have to be an integer multiple of `2π / N`
+//! (generalized Goertzel).
+
+use num_traits::Num;
+
+#[cfg(any(feature = "libm", feature = "std"))]
+use num_traits::Float;
+
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::Bin;
+
+/// The Goertzel filter's configuration.
+///
+/// The coefficients are derived from the bin frequency and block length.
+/// Prefer [`Config::new`], [`Config::new_hz`] or [`Config::from_bin`] over
+/// setting them by hand.
+#[derive(Clone, Debug)]
+pub struct Config<T> {
+    /// Number of input samples per block (`N`).
+    pub block_len: usize,
+    /// Resonator feedback coefficient `2·cos(ω)`.
+    pub coeff: T,
+    /// Unit phasor `e^{−jω}` combining the two resonator states into a bin.
+    pub twiddle: Bin<T>,
+    /// Unit phasor `e^{−jω(N−1)}` aligning the bin's phase with the DFT definition.
+    pub alignment: Bin<T>,
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+impl<T> Config<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    /// Creates a configuration for a bin at `frequency` cycles per sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_len` is zero, or if `frequency` is not finite or not
+    /// in `[0, 0.5]`.
+    #[must_use]
+    pub fn new(frequency: T, block_len: usize) -> Self {
+        assert!(block_len > 0, "Goertzel: block length must be > 0");
+        assert!(
+            frequency.is_finite()
+                && frequency >= T::zero()
+                && frequency <= T::from(0.5).expect("0.5 is representable"),
+            "Goertzel: frequency must be in [0, 0.5] cycles/sample (got {frequency:?})"
+        );
+
+        let two = T::from(2.0).expect("2 is representable");
+        let pi = T::from(core::f64::consts::PI).expect("π is representable");
+        let omega = two * pi * frequency;
+        let last = T::from(block_len - 1).expect("block length is representable");
+
+        Self {
+            block_len,
+            coeff: two * omega.cos(),
+            twiddle: Bin::from_angle(-omega),
+            alignment: Bin::from_angle(-omega * last),
+        }
+    }
+
+    /// Creates a configuration for a bin at `frequency` Hz given a `sample_rate` in Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_rate` is not finite or not positive, or if
+    /// [`Config::new`] rejects the normalized frequency.
+    #[must_use]
+    pub fn new_hz(sample_rate: T, frequency: T, block_len: usize) -> Self {
+        super::assert_sample_rate(sample_rate);
+        Self::new(frequency / sample_rate, block_len)
+    }
+
+    /// Creates a configuration for DFT bin index `bin` of an `N = block_len` point DFT.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_len` is zero or `bin > block_len / 2`.
+    #[must_use]
+    pub fn from_bin(bin: usize, block_len: usize) -> Self {
+        assert!(block_len > 0, "Goertzel: block length must be > 0");
+        assert!(
+            bin <= block_len / 2,
+            "Goertzel: bin index must be <= block_len / 2 (got {bin})"
+        );
+        let bin_t = T::from(bin).expect("bin index is representable");
+        let len_t = T::from(block_len).expect("block length is representable");
+        Self::new(bin_t / len_t, block_len)
+    }
+}
+
+/// The Goertzel filter's state.
+#[derive(Clone, Debug)]
+pub struct State<T> {
+    /// Resonator state `s[n−1]`.
+    pub s1: T,
+    /// Resonator state `s[n−2]`.
+    pub s2: T,
+    /// Number of samples consumed in the current block.
+    pub count: usize,
+}
+
+/// A Goertzel single-bin DFT filter.
+///
+/// Consumes one sample per call and returns `Some(bin)` once every
+/// `block_len` samples, `None` otherwise. The resonator is cleared after
+/// each block, so consecutive blocks do not overlap.
+///
+/// The bin is not normalized: a sinusoid of amplitude `A` centred on the bin
+/// yields a magnitude of `A·N/2`.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(1); one multiplication and two additions, plus a
+///   few complex multiplications at the end of each block.
+/// - **Space:** O(1); two resonator states and a sample counter.
+#[derive(Clone, Debug)]
+pub struct Goertzel<T> {
+    config: Config<T>,
+    state: State<T>,
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+impl<T> Goertzel<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    /// Creates a Goertzel filter for a bin at `frequency` cycles per sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Config::new`] rejects the parameters.
+    #[must_use]
+    pub fn new(frequency: T, block_len: usize) -> Self {
+        Self::with_config(Config::new(frequency, block_len))
+    }
+
+    /// Creates a Goertzel filter for a bin at `frequency` Hz given a `sample_rate` in Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Config::new_hz`] rejects the parameters.
+    #[must_use]
+    pub fn new_hz(sample_rate: T, frequency: T, block_len: usize) -> Self {
+        Self::with_config(Config::new_hz(sample_rate, frequency, block_len))
+    }
+}
+
+impl<T> ConfigTrait for Goertzel<T> {
+    type Config = Config<T>;
+}
+
+impl<T> StateTrait for Goertzel<T> {
+    type State = State<T>;
+}
+
+impl<T> WithConfig for Goertzel<T>
+where
+    T: num_traits::Zero,
+{
+    type Output = Self;
+
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(config.block_len > 0, "Goertzel: block length must be > 0");
+        let state = {
+            let s1 = T::zero();
+            let s2 = T::zero();
+            let count = 0;
+            State { s1, s2, count }
+        };
+        Self { config, state }
+    }
+}
+
+impl<T> ConfigRef for Goertzel<T> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T> ConfigClone for Goertzel<T>
+where
+    Config<T>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T> StateMut for Goertzel<T> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T> HasGuts for Goertzel<T> {
+    type Guts = (Config<T>, State<T>);
+}
+
+impl<T> FromGuts for Goertzel<T> {
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self { config, state }
+    }
+}
+
+impl<T> IntoGuts for Goertzel<T> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T> Reset for Goertzel<T>
+where
+    T: num_traits::Zero,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T> ResetMut for Goertzel<T> where Self: Reset {}
+
+impl<T> Filter<T> for Goertzel<T>
+where
+    T: Clone + Num,
+{
+    type Output = Option<Bin<T>>;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let s = input + self.config.coeff.clone() * self.state.s1.clone() - self.state.s2.clone();
+        self.state.s2 = core::mem::replace(&mut self.state.s1, s);
+        self.state.count += 1;
+
+        if self.state.count < self.config.block_len {
+            return None;
+        }
+
+        let s1 = core::mem::replace(&mut self.state.s1, T::zero());
+        let s2 = core::mem::replace(&mut self.state.s2, T::zero());
+        self.state.count = 0;
+
+        // y = s[N−1] − e^{−jω}·s[N−2]
+        let twiddle = &self.config.twiddle;
+        let y = Bin {
+            re: s1 - twiddle.re.clone() * s2.clone(),
+            im: T::zero() - twiddle.im.clone() * s2,
+        };
+        Some(super::complex_mul(&self.config.alignment, &y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::filters::classify::threshold::{Config as ThresholdConfig, Threshold};
+
+    use super::*;
+
+    fn dft(input: &[f64], frequency: f64) -> Bin<f64> {
+        let mut bin = Bin::new(0.0, 0.0);
+        for (n, x) in (0_u32..).zip(input) {
+            let theta = -2.0 * core::f64::consts::PI * frequency * f64::from(n);
+            bin.re += x * theta.cos();
+            bin.im += x * theta.sin();
+        }
+        bin
+    }
+
+    fn tone(frequency: f64, len: u32) -> Vec<f64> {
+        (0..len)
+            .map(|n| (2.0 * core::f64::consts::PI * frequency * f64::from(n) + 0.3).sin())
+            .collect()
+    }
+
+    #[test]
+    fn emits_one_bin_per_block() {
+        let mut filter = Goertzel::new(0.1_f64, 8);
+        let outputs: Vec<_> = (0..24).map(|n| filter.filter(f64::from(n))).collect();
+
+        for (n, output) in outputs.iter().enumerate() {
+            assert_eq!(output.is_some(), n % 8 == 7, "unexpected output at {n}");
+        }
+    }
+
+    #[test]
+    fn matches_direct_dft() {
+        let input = crate::util::test_fixtures::collatz();
+        let input: Vec<f64> = input.iter().map(|&x| f64::from(x)).collect();
+
+        for &frequency in &[0.0, 0.05, 0.125, 0.21, 0.5] {
+            let mut filter = Goertzel::new(frequency, input.len());
+            let bin = input
+                .iter()
+                .filter_map(|&x| filter.filter(x))
+                .last()
+                .expect("one full block");
+            let expected = dft(&input, frequency);
+
+            assert_abs_diff_eq!(bin.re, expected.re, epsilon = 1e-9);
+            assert_abs_diff_eq!(bin.im, expected.im, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn from_bin_matches_normalized_frequency() {
+        let a = Config::<f64>::from_bin(3, 32);
+        let b = Config::<f64>::new(3.0 / 32.0, 32);
+
+        assert_abs_diff_eq!(a.coeff, b.coeff, epsilon = 1e-12);
+        assert_abs_diff_eq!(a.alignment.re, b.alignment.re, epsilon = 1e-12);
+        assert_abs_diff_eq!(a.alignment.im, b.alignment.im, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn hz_constructor_detects_tone() {
+        // DTMF "1" row tone at 697 Hz, sampled at 8 kHz, 205-sample blocks.
+        let sample_rate = 8000.0_f64;
+        let input = tone(697.0 / sample_rate, 205);
+
+        let mut on_tone = Goertzel::new_hz(sample_rate, 697.0, 205);
+        let mut off_tone = Goertzel::new_hz(sample_rate, 941.0, 205);
+        let on = input.iter().filter_map(|&x| on_tone.filter(x)).last();
+        let off = input.iter().filter_map(|&x| off_tone.filter(x)).last();
+
+        let on = on.expect("one full block").power();
+        let off = off.expect("one full block").power();
+        assert!(on > 100.0 * off, "on={on}, off={off}");
+    }
+
+    #[test]
+    fn magnitude_of_centred_tone() {
+        let amplitude = 0.5;
+        let input: Vec<_> = tone(4.0 / 64.0, 64).iter().map(|x| x * amplitude).collect();
+        let mut filter = Goertzel::with_config(Config::from_bin(4, 64));
+
+        let bin = input
+            .iter()
+            .filter_map(|&x| filter.filter(x))
+            .last()
+            .expect("one full block");
+
+        assert_abs_diff_eq!(bin.magnitude(), amplitude * 64.0 / 2.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn drives_threshold_detector() {
+        let sample_rate = 8000.0_f32;
+        let mut goertzel = Goertzel::new_hz(sample_rate, 1000.0, 80);
+        let mut threshold = Threshold::with_config(ThresholdConfig {
+            threshold: 100.0,
+            outputs: [false, true],
+        });
+
+        let mut decisions = Vec::new();
+        for n in 0_u16..320 {
+            // Tone present only in the second half.
+            let x = if n >= 160 {
+                (2.0 * core::f32::consts::PI * 1000.0 * f32::from(n) / sample_rate).sin()
+            } else {
+                0.0
+            };
+            if let Some(bin) = goertzel.filter(x) {
+                decisions.push(threshold.filter(bin.power()));
+            }
+        }
+
+        assert_eq!(decisions, [false, false, true, true]);
+    }
+
+    #[test]
+    fn reset_clears_partial_block() {
+        let mut filter = Goertzel::new(0.25_f32, 4);
+        let _ = filter.filter(1.0);
+        let _ = filter.filter(2.0);
+
+        let mut filter = filter.reset();
+
+        assert_eq!(filter.state_mut().count, 0);
+        assert!(filter.filter(1.0).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "block length must be > 0")]
+    fn rejects_empty_block() {
+        let _ = Config::new(0.1_f32, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "frequency must be in [0, 0.5]")]
+    fn rejects_frequency_above_nyquist() {
+        let _ = Config::new(0.6_f32, 8);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn bin_converts_to_complex() {
+        use crate::complex::Complex64;
+
+        let input = tone(0.125, 16);
+        let mut filter = Goertzel::new(0.125, 16);
+        let bin = input
+            .iter()
+            .filter_map(|&x| filter.filter(x))
+            .last()
+            .expect("one full block");
+        let complex = Complex64::from(bin);
+
+        assert_abs_diff_eq!(complex.norm(), bin.magnitude(), epsilon = 1e-12);
+        assert_abs_diff_eq!(complex.arg(), bin.phase(), epsilon = 1e-12);
+    }
+}
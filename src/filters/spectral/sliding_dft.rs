@@ -0,0 +1,549 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Sliding DFT over a set of chosen bins.
+//!
+//! Maintains `K` DFT bins of the most recent `N` samples, updating each bin
+//! recursively on every input sample:
+//!
+//! ```text
+//! X_k[n] = e^{j2πk/N} · (r·X_k[n−1] + x[n] − r^N·x[n−N])
+//! ```
+//!
+//! With `r = 1` this is the exact `N`-point DFT of the current window, with the
+//! oldest sample at time index `0`. Choosing the damping factor `r` slightly
+//! below one (e.g. `0.9999`) keeps the recursion's poles strictly inside the
+//! unit circle so that rounding errors decay instead of accumulating.
+
+use core::marker::PhantomData;
+
+use circular_buffer::FixedCircularBuffer;
+use num_traits::Num;
+
+#[cfg(any(feature = "libm", feature = "std"))]
+use num_traits::Float;
+
+use crate::storage::{zero_filled_fixed_ring, AsSlice, RingBuffer};
+use crate::traits::{
+    guts::{FromGuts, HasGuts, IntoGuts},
+    Config as ConfigTrait, ConfigClone, ConfigRef, Filter, Reset, State as StateTrait, StateMut,
+    WithConfig,
+};
+
+#[cfg(feature = "alloc")]
+use circular_buffer::HeapCircularBuffer;
+
+#[cfg(feature = "derive")]
+use crate::traits::ResetMut;
+
+use super::{complex_mul, Bin};
+
+/// The sliding DFT's configuration.
+///
+/// Holds one rotation phasor per tracked bin in the storage `C`, which must
+/// implement [`AsSlice<Bin<T>>`]. The window length `N` is the capacity of
+/// the tap buffer.
+#[derive(Clone, Debug)]
+pub struct Config<T, C> {
+    /// Per-bin unit phasors `e^{j2πk/N}`, one per tracked bin `k`.
+    pub twiddles: C,
+    /// Damping factor `r` in `(0, 1]`; `1` gives the exact (undamped) DFT.
+    pub damping: T,
+}
+
+/// The sliding DFT's state.
+#[derive(Clone, Debug)]
+pub struct State<R, B> {
+    /// The most recent `N` input samples.
+    pub taps: R,
+    /// The current value of each tracked bin.
+    pub bins: B,
+}
+
+/// A sliding DFT tracking `K` bins over a window of `N` samples.
+///
+/// Each call consumes one sample and returns the updated bins, in the order
+/// of the configured twiddles. Before `N` samples have been consumed, the
+/// window is implicitly zero-padded.
+///
+/// The bins are not normalized: a sinusoid of amplitude `A` centred on bin
+/// `k` yields a magnitude of `A·N/2`.
+///
+/// # Type aliases
+///
+/// Prefer the concrete aliases for common use:
+/// - [`SlidingDftArray<T, N, K>`] — stack-allocated, `no_std`-friendly.
+#[cfg_attr(
+    feature = "alloc",
+    doc = "- [`SlidingDftVec<T>`] — heap-allocated, requires the `alloc` feature."
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = "- `SlidingDftVec<T>` — heap-allocated, requires the `alloc` feature."
+)]
+///
+/// The filter returns a clone of the bin storage `B`, so the `Vec`-backed
+/// variant allocates on every call. Use [`SlidingDft::bins`] to inspect bins
+/// without cloning.
+///
+/// # Complexity
+///
+/// - **Time per sample:** O(K); one complex multiplication per tracked bin.
+/// - **Space:** O(N + K); the sample window plus one phasor and one bin per tracked bin.
+#[derive(Clone, Debug)]
+pub struct SlidingDft<T, C, R, B> {
+    config: Config<T, C>,
+    state: State<R, B>,
+    /// Cached `r^N`, applied to the sample leaving the window.
+    delay_gain: T,
+    _pd: PhantomData<T>,
+}
+
+/// A sliding DFT backed by fixed-size arrays and a [`FixedCircularBuffer`]
+/// window of `N` samples, tracking `K` bins.
+pub type SlidingDftArray<T, const N: usize, const K: usize> =
+    SlidingDft<T, [Bin<T>; K], FixedCircularBuffer<T, N>, [Bin<T>; K]>;
+
+/// A sliding DFT backed by heap-allocated storage.
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub type SlidingDftVec<T> =
+    SlidingDft<T, alloc::vec::Vec<Bin<T>>, HeapCircularBuffer<T>, alloc::vec::Vec<Bin<T>>>;
+
+fn delay_gain<T>(damping: &T, window_len: usize) -> T
+where
+    T: Clone + Num,
+{
+    (0..window_len).fold(T::one(), |gain, _| gain * damping.clone())
+}
+
+impl<T, C, R, B> SlidingDft<T, C, R, B>
+where
+    T: Clone + Num,
+    C: AsSlice<Bin<T>>,
+    R: RingBuffer<T>,
+    B: AsSlice<Bin<T>>,
+{
+    /// Creates a [`SlidingDft`] from an already-constructed `config`, sample
+    /// window and bin storage.
+    ///
+    /// The window and bins are taken as-is with their current contents.
+    ///
+    /// # Expected storage state
+    ///
+    /// For zero-padded cold-start behavior, prefill `taps` with `N` zeros and
+    /// `bins` with zero bins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window capacity is zero or if the number of bins does not
+    /// equal the number of twiddles.
+    pub fn from_parts(config: Config<T, C>, taps: R, bins: B) -> Self {
+        assert!(taps.capacity() > 0, "SlidingDft: window size N must be > 0");
+        assert_eq!(
+            config.twiddles.as_slice().len(),
+            bins.as_slice().len(),
+            "SlidingDft: bins length must equal twiddles length"
+        );
+        Self {
+            delay_gain: delay_gain(&config.damping, taps.capacity()),
+            config,
+            state: State { taps, bins },
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, C, R, B> SlidingDft<T, C, R, B>
+where
+    B: AsSlice<Bin<T>>,
+{
+    /// Returns the current value of each tracked bin.
+    pub fn bins(&self) -> &[Bin<T>] {
+        self.state.bins.as_slice()
+    }
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+fn fill_twiddles<T>(twiddles: &mut [Bin<T>], bins: &[usize], window_len: usize)
+where
+    T: Float,
+{
+    let two_pi = T::from(core::f64::consts::TAU).expect("2π is representable");
+    let len_t = T::from(window_len).expect("window length is representable");
+    for (twiddle, &bin) in twiddles.iter_mut().zip(bins) {
+        assert!(
+            bin < window_len,
+            "SlidingDft: bin index must be < N (got {bin})"
+        );
+        let bin_t = T::from(bin).expect("bin index is representable");
+        *twiddle = Bin::from_angle(two_pi * bin_t / len_t);
+    }
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+fn nearest_bin<T>(sample_rate: T, frequency: T, window_len: usize) -> usize
+where
+    T: Float + core::fmt::Debug,
+{
+    super::assert_sample_rate(sample_rate);
+    assert!(
+        frequency.is_finite()
+            && frequency >= T::zero()
+            && frequency <= sample_rate / T::from(2.0).expect("2 is representable"),
+        "SlidingDft: frequency must be in [0, sample_rate / 2] (got {frequency:?})"
+    );
+    let len_t = T::from(window_len).expect("window length is representable");
+    (frequency * len_t / sample_rate)
+        .round()
+        .to_usize()
+        .expect("bin index fits in usize")
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+impl<T, const N: usize, const K: usize> SlidingDftArray<T, N, K>
+where
+    T: Float + core::fmt::Debug,
+{
+    /// Creates an undamped sliding DFT tracking DFT bin indices `bins` of an
+    /// `N`-point DFT.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero or any bin index is `>= N`.
+    #[must_use]
+    pub fn new(bins: [usize; K]) -> Self {
+        assert!(N > 0, "SlidingDft: window size N must be > 0");
+        let mut twiddles = [Bin::new(T::one(), T::zero()); K];
+        fill_twiddles(&mut twiddles, &bins, N);
+        Self::with_config(Config {
+            twiddles,
+            damping: T::one(),
+        })
+    }
+
+    /// Creates an undamped sliding DFT tracking the bins nearest to
+    /// `frequencies` Hz given a `sample_rate` in Hz.
+    ///
+    /// The bin resolution is `sample_rate / N`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero, if `sample_rate` is not finite or not positive,
+    /// or if any frequency is not finite or not in `[0, sample_rate / 2]`.
+    #[must_use]
+    pub fn new_hz(sample_rate: T, frequencies: [T; K]) -> Self {
+        assert!(N > 0, "SlidingDft: window size N must be > 0");
+        Self::new(frequencies.map(|frequency| nearest_bin(sample_rate, frequency, N)))
+    }
+}
+
+#[cfg(all(feature = "alloc", any(feature = "libm", feature = "std")))]
+impl<T> SlidingDftVec<T>
+where
+    T: Float + core::fmt::Debug,
+{
+    /// Creates an undamped heap-backed sliding DFT tracking DFT bin indices
+    /// `bins` of a `window_len`-point DFT.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_len` is zero or any bin index is `>= window_len`.
+    #[must_use]
+    pub fn new(window_len: usize, bins: &[usize]) -> Self {
+        assert!(window_len > 0, "SlidingDft: window size N must be > 0");
+        let mut twiddles = alloc::vec![Bin::new(T::one(), T::zero()); bins.len()];
+        fill_twiddles(&mut twiddles, bins, window_len);
+        let mut taps = HeapCircularBuffer::with_capacity(window_len);
+        for _ in 0..window_len {
+            let _ = taps.push_back(T::zero());
+        }
+        let state_bins = alloc::vec![Bin::new(T::zero(), T::zero()); bins.len()];
+        Self::from_parts(
+            Config {
+                twiddles,
+                damping: T::one(),
+            },
+            taps,
+            state_bins,
+        )
+    }
+
+    /// Creates an undamped heap-backed sliding DFT tracking the bins nearest
+    /// to `frequencies` Hz given a `sample_rate` in Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_len` is zero, if `sample_rate` is not finite or not
+    /// positive, or if any frequency is not finite or not in
+    /// `[0, sample_rate / 2]`.
+    #[must_use]
+    pub fn new_hz(window_len: usize, sample_rate: T, frequencies: &[T]) -> Self {
+        assert!(window_len > 0, "SlidingDft: window size N must be > 0");
+        let bins: alloc::vec::Vec<usize> = frequencies
+            .iter()
+            .map(|&frequency| nearest_bin(sample_rate, frequency, window_len))
+            .collect();
+        Self::new(window_len, &bins)
+    }
+}
+
+impl<T, C, R, B> ConfigTrait for SlidingDft<T, C, R, B> {
+    type Config = Config<T, C>;
+}
+
+impl<T, C, R, B> StateTrait for SlidingDft<T, C, R, B> {
+    type State = State<R, B>;
+}
+
+impl<T, const N: usize, const K: usize> WithConfig for SlidingDftArray<T, N, K>
+where
+    T: Clone + Num,
+{
+    type Output = Self;
+
+    /// Creates a [`SlidingDftArray`] from a configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    fn with_config(config: Self::Config) -> Self::Output {
+        assert!(N > 0, "SlidingDft: window size N must be > 0");
+        let taps = zero_filled_fixed_ring::<T, N>();
+        let bins = core::array::from_fn(|_| Bin::new(T::zero(), T::zero()));
+        Self::from_parts(config, taps, bins)
+    }
+}
+
+impl<T, C, R, B> ConfigRef for SlidingDft<T, C, R, B> {
+    fn config_ref(&self) -> &Self::Config {
+        &self.config
+    }
+}
+
+impl<T, C, R, B> ConfigClone for SlidingDft<T, C, R, B>
+where
+    Config<T, C>: Clone,
+{
+    fn config(&self) -> Self::Config {
+        self.config.clone()
+    }
+}
+
+impl<T, C, R, B> StateMut for SlidingDft<T, C, R, B> {
+    fn state_mut(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+}
+
+impl<T, C, R, B> HasGuts for SlidingDft<T, C, R, B> {
+    type Guts = (Config<T, C>, State<R, B>);
+}
+
+impl<T, C, R, B> FromGuts for SlidingDft<T, C, R, B>
+where
+    T: Clone + Num,
+    R: RingBuffer<T>,
+{
+    fn from_guts(guts: Self::Guts) -> Self {
+        let (config, state) = guts;
+        Self {
+            delay_gain: delay_gain(&config.damping, state.taps.capacity()),
+            config,
+            state,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, C, R, B> IntoGuts for SlidingDft<T, C, R, B> {
+    fn into_guts(self) -> Self::Guts {
+        (self.config, self.state)
+    }
+}
+
+impl<T, const N: usize, const K: usize> Reset for SlidingDftArray<T, N, K>
+where
+    T: Clone + Num,
+{
+    fn reset(self) -> Self {
+        Self::with_config(self.config)
+    }
+}
+
+#[cfg(feature = "derive")]
+impl<T, const N: usize, const K: usize> ResetMut for SlidingDftArray<T, N, K> where Self: Reset {}
+
+impl<T, C, R, B> Filter<T> for SlidingDft<T, C, R, B>
+where
+    T: Clone + Num,
+    C: AsSlice<Bin<T>>,
+    R: RingBuffer<T>,
+    B: AsSlice<Bin<T>> + Clone,
+{
+    type Output = B;
+
+    fn filter(&mut self, input: T) -> Self::Output {
+        let oldest = self
+            .state
+            .taps
+            .push_back(input.clone())
+            .unwrap_or_else(T::zero);
+        // Comb: x[n] − r^N·x[n−N]
+        let delta = input - self.delay_gain.clone() * oldest;
+
+        let twiddles = self.config.twiddles.as_slice();
+        for (bin, twiddle) in self.state.bins.as_mut_slice().iter_mut().zip(twiddles) {
+            let damped = if self.config.damping.is_one() {
+                Bin::new(bin.re.clone() + delta.clone(), bin.im.clone())
+            } else {
+                Bin::new(
+                    self.config.damping.clone() * bin.re.clone() + delta.clone(),
+                    self.config.damping.clone() * bin.im.clone(),
+                )
+            };
+            *bin = complex_mul(twiddle, &damped);
+        }
+
+        self.state.bins.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::filters::classify::schmitt::{Config as SchmittConfig, Schmitt, Thresholds};
+
+    use super::*;
+
+    fn dft(window: &[f64; 8], bin: u32) -> Bin<f64> {
+        let mut acc = Bin::new(0.0, 0.0);
+        for (n, x) in (0_u32..).zip(window) {
+            let theta = -core::f64::consts::TAU * f64::from(bin) * f64::from(n) / 8.0;
+            acc.re += x * theta.cos();
+            acc.im += x * theta.sin();
+        }
+        acc
+    }
+
+    #[test]
+    fn matches_direct_dft_of_window() {
+        let input: Vec<f64> = crate::util::test_fixtures::collatz()
+            .iter()
+            .map(|&x| f64::from(x))
+            .collect();
+        let mut filter = SlidingDftArray::<f64, 8, 3>::new([0, 1, 3]);
+
+        let mut window = [0.0; 8];
+        for &x in &input {
+            window.rotate_left(1);
+            window[7] = x;
+            let bins = filter.filter(x);
+
+            for (bin, &k) in bins.iter().zip(&[0, 1, 3]) {
+                let expected = dft(&window, k);
+                assert_abs_diff_eq!(bin.re, expected.re, epsilon = 1e-9);
+                assert_abs_diff_eq!(bin.im, expected.im, epsilon = 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn hz_constructor_selects_nearest_bin() {
+        // 8 kHz, 80-sample window: 100 Hz bin spacing.
+        let filter = SlidingDftArray::<f64, 80, 2>::new_hz(8000.0, [1000.0, 1960.0]);
+        let expected = SlidingDftArray::<f64, 80, 2>::new([10, 20]);
+
+        for (a, b) in filter
+            .config_ref()
+            .twiddles
+            .iter()
+            .zip(expected.config_ref().twiddles.iter())
+        {
+            assert_abs_diff_eq!(a.re, b.re, epsilon = 1e-12);
+            assert_abs_diff_eq!(a.im, b.im, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn damped_matches_exponentially_weighted_window() {
+        let damping = 0.9_f64;
+        let mut filter = SlidingDftArray::<f64, 4, 1>::with_config(Config {
+            twiddles: [Bin::new(1.0, 0.0)],
+            damping,
+        });
+
+        let input = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let mut output = 0.0;
+        for &x in &input {
+            output = filter.filter(x)[0].re;
+        }
+
+        // DC bin of the last four samples, weighted by r^age.
+        let expected = 6.0 + damping * 5.0 + damping.powi(2) * 4.0 + damping.powi(3) * 3.0;
+        assert_abs_diff_eq!(output, expected, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn drives_schmitt_detector() {
+        let sample_rate = 8000.0_f32;
+        let mut sdft = SlidingDftArray::<f32, 80, 1>::new_hz(sample_rate, [1000.0]);
+        let mut schmitt = Schmitt::with_config(SchmittConfig {
+            thresholds: Thresholds::new(100.0, 400.0).expect("low <= high"),
+            outputs: [false, true],
+        });
+
+        let mut detected = Vec::new();
+        for n in 0_u16..400 {
+            let x = if (100..300).contains(&n) {
+                (core::f32::consts::TAU * 1000.0 * f32::from(n) / sample_rate).sin()
+            } else {
+                0.0
+            };
+            let [bin] = sdft.filter(x);
+            detected.push(schmitt.filter(bin.power()));
+        }
+
+        assert!(!detected[99]);
+        assert!(detected[200]);
+        assert!(!detected[399]);
+    }
+
+    #[test]
+    fn reset_clears_window_and_bins() {
+        let mut filter = SlidingDftArray::<f32, 4, 1>::new([1]);
+        let _ = filter.filter(1.0);
+
+        let mut filter = filter.reset();
+
+        assert!(filter.bins()[0].power() == 0.0);
+        assert!(filter.state_mut().taps.iter().all(|x| *x == 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "bin index must be < N")]
+    fn rejects_bin_out_of_range() {
+        let _ = SlidingDftArray::<f32, 4, 1>::new([4]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_matches_array() {
+        let mut array = SlidingDftArray::<f64, 16, 2>::new([2, 5]);
+        let mut vec = SlidingDftVec::<f64>::new(16, &[2, 5]);
+
+        for x in crate::util::test_fixtures::collatz() {
+            let a = array.filter(f64::from(x));
+            let v = vec.filter(f64::from(x));
+            for (a, v) in a.iter().zip(v.iter()) {
+                assert_abs_diff_eq!(a.re, v.re, epsilon = 1e-9);
+                assert_abs_diff_eq!(a.im, v.im, epsilon = 1e-9);
+            }
+        }
+    }
+}
@@ -173,7 +173,7 @@ where
 
 impl<T, R> Filter<T> for Delay<T, R>
 where
-    T: Clone + Num,
+    T: Clone,
     R: RingBuffer<T>,
 {
     type Output = T;
@@ -4,6 +4,8 @@
 
 //! Mathematical utility functions for DSP operations.
 
+pub mod matrix;
+
 pub mod phase;
 
 use core::ops::{Add, AddAssign, Sub, SubAssign};
@@ -295,6 +297,34 @@ pub fn safe_normalise_divisor<T: Float + core::fmt::Debug>(den: T, msg: &'static
     den
 }
 
+/// Converts an amplitude ratio to decibels, `20·log₁₀(amplitude)`.
+///
+/// Returns negative infinity for a zero amplitude.
+///
+/// # Panics
+///
+/// Panics if `T::from` conversions from standard f64 literals fail
+/// (impossible for any `Float`-implementing type).
+#[cfg(any(feature = "libm", feature = "std"))]
+#[allow(clippy::unwrap_used)]
+#[must_use]
+pub fn amplitude_to_db<T: Float>(amplitude: T) -> T {
+    T::from(20.0).unwrap() * amplitude.log10()
+}
+
+/// Converts decibels to an amplitude ratio, `10^(db / 20)`.
+///
+/// # Panics
+///
+/// Panics if `T::from` conversions from standard f64 literals fail
+/// (impossible for any `Float`-implementing type).
+#[cfg(any(feature = "libm", feature = "std"))]
+#[allow(clippy::unwrap_used)]
+#[must_use]
+pub fn db_to_amplitude<T: Float>(db: T) -> T {
+    T::from(10.0).unwrap().powf(db / T::from(20.0).unwrap())
+}
+
 /// Error function support for floating-point types.
 #[cfg(feature = "libm")]
 pub trait Erf: Float {
@@ -462,4 +492,14 @@ mod tests {
     fn safe_normalise_divisor_accepts_one() {
         assert_eq!(safe_normalise_divisor(1.0_f32, "test"), 1.0);
     }
+
+    #[cfg(any(feature = "libm", feature = "std"))]
+    #[test]
+    fn decibel_conversions_round_trip() {
+        assert!((amplitude_to_db(10.0_f64) - 20.0).abs() < 1e-12);
+        assert!((db_to_amplitude(-6.0_f64) - 0.501_187_233_627_272_2).abs() < 1e-12);
+        assert!((db_to_amplitude(amplitude_to_db(0.3_f64)) - 0.3).abs() < 1e-12);
+        let silence = amplitude_to_db(0.0_f64);
+        assert!(silence.is_infinite() && silence.is_sign_negative());
+    }
 }
@@ -0,0 +1,372 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Small fixed-size matrices for state-space filters.
+//!
+//! [`Matrix`] stores an `R×C` matrix inline as row-major `[[T; C]; R]`, so it
+//! needs neither an allocator nor a linear-algebra dependency. It provides
+//! just the operations that state estimators need: addition, subtraction,
+//! multiplication, transposition and (for floating-point elements) inversion.
+//!
+//! Everything is `O(R·C)` or `O(R·C·K)` with plain loops; the type is meant for
+//! the handful of dimensions typical of tracking and sensor-fusion filters, not
+//! for large matrices.
+
+use core::ops::{Add, Index, IndexMut, Mul, Sub};
+
+use num_traits::{float::FloatCore, One, Zero};
+
+#[cfg(any(feature = "libm", feature = "std"))]
+use num_traits::Float;
+
+/// An `R×C` matrix, stored inline in row-major order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    rows: [[T; C]; R],
+}
+
+/// An `N`-element column vector.
+pub type Vector<T, const N: usize> = Matrix<T, N, 1>;
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    /// Creates a matrix from its rows.
+    pub const fn from_rows(rows: [[T; C]; R]) -> Self {
+        Self { rows }
+    }
+
+    /// Returns the matrix's rows.
+    pub const fn rows(&self) -> &[[T; C]; R] {
+        &self.rows
+    }
+
+    /// Returns the matrix's rows, consuming the matrix.
+    pub fn into_rows(self) -> [[T; C]; R] {
+        self.rows
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C>
+where
+    T: Copy + Zero,
+{
+    /// Returns the all-zero matrix.
+    #[must_use]
+    pub fn zeros() -> Self {
+        Self {
+            rows: [[T::zero(); C]; R],
+        }
+    }
+
+    /// Returns the transposed `C×R` matrix.
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        let mut transposed = Matrix::zeros();
+        for (r, row) in self.rows.iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                transposed.rows[c][r] = value;
+            }
+        }
+        transposed
+    }
+
+    /// Returns the matrix with every element multiplied by `factor`.
+    #[must_use]
+    pub fn scale(&self, factor: T) -> Self
+    where
+        T: Mul<Output = T>,
+    {
+        let mut scaled = *self;
+        for value in scaled.rows.iter_mut().flatten() {
+            *value = *value * factor;
+        }
+        scaled
+    }
+}
+
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: Copy + Zero + One,
+{
+    /// Returns the `N×N` identity matrix.
+    #[must_use]
+    pub fn identity() -> Self {
+        Self::from_diagonal([T::one(); N])
+    }
+
+    /// Returns the diagonal matrix with the given diagonal.
+    pub fn from_diagonal(diagonal: [T; N]) -> Self {
+        let mut matrix = Self::zeros();
+        for (i, value) in diagonal.into_iter().enumerate() {
+            matrix.rows[i][i] = value;
+        }
+        matrix
+    }
+}
+
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: Copy,
+{
+    /// Returns the matrix's diagonal.
+    pub fn diagonal(&self) -> [T; N] {
+        core::array::from_fn(|i| self.rows[i][i])
+    }
+}
+
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: FloatCore,
+{
+    /// Returns the inverse, or `None` if the matrix is singular.
+    ///
+    /// Uses Gauss-Jordan elimination with partial pivoting.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut matrix = self.rows;
+        let mut inverse = Self::identity().rows;
+        for column in 0..N {
+            let pivot_row = (column..N).max_by(|&a, &b| {
+                matrix[a][column]
+                    .abs()
+                    .partial_cmp(&matrix[b][column].abs())
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })?;
+            let pivot = matrix[pivot_row][column];
+            if pivot == T::zero() || !pivot.is_finite() {
+                return None;
+            }
+            matrix.swap(column, pivot_row);
+            inverse.swap(column, pivot_row);
+
+            let scale = T::one() / pivot;
+            for value in &mut matrix[column] {
+                *value = *value * scale;
+            }
+            for value in &mut inverse[column] {
+                *value = *value * scale;
+            }
+
+            let (pivot_matrix, pivot_inverse) = (matrix[column], inverse[column]);
+            for row in (0..N).filter(|&row| row != column) {
+                let factor = matrix[row][column];
+                if factor == T::zero() {
+                    continue;
+                }
+                for (value, &pivot_value) in matrix[row].iter_mut().zip(&pivot_matrix) {
+                    *value = *value - factor * pivot_value;
+                }
+                for (value, &pivot_value) in inverse[row].iter_mut().zip(&pivot_inverse) {
+                    *value = *value - factor * pivot_value;
+                }
+            }
+        }
+        Some(Self { rows: inverse })
+    }
+}
+
+#[cfg(any(feature = "libm", feature = "std"))]
+impl<T, const N: usize> Matrix<T, N, N>
+where
+    T: Float,
+{
+    /// Returns the lower-triangular Cholesky factor `L` with `L·Lᵀ = A`, or
+    /// `None` if the matrix is not positive definite.
+    ///
+    /// Only the lower triangle of the (symmetric) matrix is read.
+    pub fn cholesky(&self) -> Option<Self> {
+        let mut factor = Self::zeros();
+        for row in 0..N {
+            for column in 0..=row {
+                let dot = (0..column).fold(T::zero(), |sum, k| {
+                    sum + factor.rows[row][k] * factor.rows[column][k]
+                });
+                let value = self.rows[row][column] - dot;
+                factor.rows[row][column] = if row == column {
+                    if value <= T::zero() || !value.is_finite() {
+                        return None;
+                    }
+                    value.sqrt()
+                } else {
+                    value / factor.rows[column][column]
+                };
+            }
+        }
+        Some(factor)
+    }
+}
+
+impl<T, const N: usize> Vector<T, N>
+where
+    T: Copy,
+{
+    /// Creates a column vector from its elements.
+    pub fn from_column(column: [T; N]) -> Self {
+        Self {
+            rows: column.map(|value| [value]),
+        }
+    }
+
+    /// Returns the column vector's elements.
+    pub fn column(&self) -> [T; N] {
+        self.rows.map(|[value]| value)
+    }
+}
+
+impl<T, const R: usize, const C: usize> Default for Matrix<T, R, C>
+where
+    T: Copy + Zero,
+{
+    fn default() -> Self {
+        Self::zeros()
+    }
+}
+
+impl<T, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<T, R, C> {
+    fn from(rows: [[T; C]; R]) -> Self {
+        Self::from_rows(rows)
+    }
+}
+
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Matrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        &self.rows[row][column]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<T, R, C> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        &mut self.rows[row][column]
+    }
+}
+
+impl<T, const R: usize, const C: usize> Add for Matrix<T, R, C>
+where
+    T: Copy + Add<Output = T>,
+{
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        for (lhs, rhs) in self
+            .rows
+            .iter_mut()
+            .flatten()
+            .zip(rhs.rows.iter().flatten())
+        {
+            *lhs = *lhs + *rhs;
+        }
+        self
+    }
+}
+
+impl<T, const R: usize, const C: usize> Sub for Matrix<T, R, C>
+where
+    T: Copy + Sub<Output = T>,
+{
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        for (lhs, rhs) in self
+            .rows
+            .iter_mut()
+            .flatten()
+            .zip(rhs.rows.iter().flatten())
+        {
+            *lhs = *lhs - *rhs;
+        }
+        self
+    }
+}
+
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>> for Matrix<T, R, C>
+where
+    T: Copy + Zero + Mul<Output = T>,
+{
+    type Output = Matrix<T, R, K>;
+
+    fn mul(self, rhs: Matrix<T, C, K>) -> Self::Output {
+        let mut product = Matrix::zeros();
+        for (product_row, lhs_row) in product.rows.iter_mut().zip(&self.rows) {
+            for (&lhs, rhs_row) in lhs_row.iter().zip(&rhs.rows) {
+                for (value, &rhs) in product_row.iter_mut().zip(rhs_row) {
+                    *value = *value + lhs * rhs;
+                }
+            }
+        }
+        product
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn multiplies_rectangular_matrices() {
+        let lhs = Matrix::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let rhs = Matrix::from_rows([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+        let product: Matrix<f64, 2, 2> = lhs * rhs;
+        assert_eq!(product.into_rows(), [[58.0, 64.0], [139.0, 154.0]]);
+    }
+
+    #[test]
+    fn transposes() {
+        let matrix = Matrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(matrix.transpose().into_rows(), [[1, 4], [2, 5], [3, 6]]);
+    }
+
+    #[test]
+    fn adds_subtracts_and_scales() {
+        let lhs = Matrix::from_rows([[1, 2], [3, 4]]);
+        let rhs = Matrix::<i32, 2, 2>::identity();
+        assert_eq!((lhs + rhs).into_rows(), [[2, 2], [3, 5]]);
+        assert_eq!((lhs - rhs).into_rows(), [[0, 2], [3, 3]]);
+        assert_eq!(lhs.scale(2).into_rows(), [[2, 4], [6, 8]]);
+        assert_eq!(lhs.diagonal(), [1, 4]);
+    }
+
+    #[test]
+    fn inverts_with_pivoting() {
+        // The leading zero requires a row swap.
+        let matrix = Matrix::from_rows([[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]]);
+        let inverse = matrix.inverse().expect("matrix is invertible");
+        let product = matrix * inverse;
+        for (r, row) in product.rows().iter().enumerate() {
+            for (c, &value) in row.iter().enumerate() {
+                let expected = if r == c { 1.0 } else { 0.0 };
+                assert_abs_diff_eq!(value, expected, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let matrix = Matrix::from_rows([[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(matrix.inverse(), None);
+    }
+
+    #[cfg(any(feature = "libm", feature = "std"))]
+    #[test]
+    fn cholesky_factors_positive_definite() {
+        let matrix = Matrix::from_rows([[4.0, 2.0, 0.4], [2.0, 5.0, 1.0], [0.4, 1.0, 3.0]]);
+        let factor = matrix.cholesky().expect("matrix is positive definite");
+        assert_eq!(factor[(0, 1)], 0.0);
+        let product = factor * factor.transpose();
+        for (row, expected_row) in product.rows().iter().zip(matrix.rows()) {
+            for (&value, &expected) in row.iter().zip(expected_row) {
+                assert_abs_diff_eq!(value, expected, epsilon = 1e-12);
+            }
+        }
+        let indefinite = Matrix::from_rows([[1.0, 2.0], [2.0, 1.0]]);
+        assert_eq!(indefinite.cholesky(), None);
+    }
+
+    #[test]
+    fn vector_round_trips_column() {
+        let vector = Vector::from_column([1, 2, 3]);
+        assert_eq!(vector[(2, 0)], 3);
+        assert_eq!(vector.column(), [1, 2, 3]);
+    }
+}
@@ -111,6 +111,15 @@ pub trait RingBuffer<T> {
     /// Returns a shared reference to the back (newest) element, or `None` if empty.
     fn back(&self) -> Option<&T>;
 
+    /// Returns a shared reference to the element at `index`, counting from the
+    /// front (oldest), or `None` if `index` is out of bounds.
+    ///
+    /// The default implementation walks [`iter`](Self::iter); implementations
+    /// with random access should override it.
+    fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
     /// Returns an iterator over the elements, from oldest to newest.
     ///
     /// The explicit `'a` lifetime and `T: 'a` bound are required here (but
@@ -168,6 +177,10 @@ impl<T, const N: usize> RingBuffer<T> for FixedCircularBuffer<T, N> {
         (**self).back()
     }
 
+    fn get(&self, index: usize) -> Option<&T> {
+        (**self).get(index)
+    }
+
     fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
     where
         T: 'a,
@@ -221,6 +234,10 @@ impl<T> RingBuffer<T> for HeapCircularBuffer<T> {
         (**self).back()
     }
 
+    fn get(&self, index: usize) -> Option<&T> {
+        (**self).get(index)
+    }
+
     fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
     where
         T: 'a,
@@ -273,6 +290,10 @@ impl<T> RingBuffer<T> for &mut CircularBuffer<T> {
         (**self).back()
     }
 
+    fn get(&self, index: usize) -> Option<&T> {
+        (**self).get(index)
+    }
+
     fn iter<'a>(&'a self) -> impl Iterator<Item = &'a T>
     where
         T: 'a,
@@ -392,6 +413,17 @@ mod tests {
                     assert_eq!(ring.back(), Some(&30));
                 }
 
+                #[test]
+                fn get_counts_from_front() {
+                    let mut ring = make();
+                    ring.push_back(10);
+                    ring.push_back(20);
+                    ring.push_back(30);
+                    assert_eq!(ring.get(0), Some(&10));
+                    assert_eq!(ring.get(2), Some(&30));
+                    assert_eq!(ring.get(3), None);
+                }
+
                 #[test]
                 fn pop_front_removes_oldest() {
                     let mut ring = make();
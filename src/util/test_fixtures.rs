@@ -24,3 +24,37 @@ pub(crate) fn collatz() -> Vec<f32> {
         11.0, 24.0, 24.0,
     ]
 }
+
+/// A deterministic xorshift noise sequence, uniformly spread over `[-1, 1]`.
+///
+/// Different `seed`s give uncorrelated sequences; `seed` must be non-zero.
+pub(crate) fn white_noise(len: usize, seed: u32) -> Vec<f64> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            f64::from(state) / f64::from(u32::MAX) * 2.0 - 1.0
+        })
+        .collect()
+}
+
+/// Deterministic noise at sample `n`, spread over `[-0.5, 0.5]`.
+///
+/// Unlike [`white_noise`], any sample can be computed on its own, which suits
+/// tests that build their input inline.
+pub(crate) fn hash_noise(n: u32) -> f64 {
+    f64::from(hash(n)) / f64::from(u32::MAX) - 0.5
+}
+
+/// Deterministic noise at sample `n`, quantized to the integers `0..levels` so
+/// that values repeat.
+pub(crate) fn quantized_noise(n: u32, levels: u32) -> f64 {
+    f64::from(hash(n) % levels)
+}
+
+/// A multiplicative hash of `n`.
+fn hash(n: u32) -> u32 {
+    n.wrapping_mul(2_654_435_761).rotate_left(13) ^ 0x5bd1_e995
+}